@@ -0,0 +1,4 @@
+fn main() {
+    // The attribute checks read this variable while expanding templates, so rebuild the macros when it changes.
+    println!("cargo:rerun-if-env-changed=PLAIT_ATTRIBUTE_PREFIXES");
+}
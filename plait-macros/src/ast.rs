@@ -1,4 +1,5 @@
 mod attribute;
+mod cache_block;
 mod component_call;
 mod component_definition;
 mod element;
@@ -8,11 +9,13 @@ mod let_binding;
 mod match_expression;
 mod node;
 mod template;
+mod while_loop;
 
 pub use self::{
     attribute::{Attribute, AttributeValue, NameValueAttribute},
-    component_call::{ComponentCall, ComponentCallField},
-    component_definition::{ComponentDefinition, ComponentDefinitionField},
+    cache_block::{CacheBlock, CacheTtl},
+    component_call::{ComponentCall, ComponentCallField, ComponentCallSlot},
+    component_definition::{ComponentDefinition, ComponentDefinitionField, ComponentDefinitions},
     element::Element,
     for_loop::ForLoop,
     if_condition::{ElseBranch, IfCondition},
@@ -20,4 +23,5 @@ pub use self::{
     match_expression::{MatchArm, MatchExpression},
     node::Node,
     template::Template,
+    while_loop::WhileLoop,
 };
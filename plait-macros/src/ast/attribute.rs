@@ -1,5 +1,6 @@
 use syn::{Expr, Ident, LitBool, LitChar, LitFloat, LitInt, LitStr};
 
+#[derive(Clone)]
 pub enum AttributeValue {
     LitStr(LitStr),
     LitChar(LitChar),
@@ -10,13 +11,16 @@ pub enum AttributeValue {
     Raw(Expr),
 }
 
+#[derive(Clone)]
 pub struct NameValueAttribute {
     pub name: LitStr,
     pub is_maybe: bool,
     pub value: Option<AttributeValue>,
 }
 
+#[derive(Clone)]
 pub enum Attribute {
     Spread(Ident),
+    SpreadPairs(Ident, Expr),
     NameValue(NameValueAttribute),
 }
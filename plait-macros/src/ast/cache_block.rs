@@ -0,0 +1,16 @@
+use syn::Expr;
+
+use crate::ast::Node;
+
+pub struct CacheBlock {
+    pub key: Expr,
+    pub ttl: Option<CacheTtl>,
+    pub body: Vec<Node>,
+}
+
+pub enum CacheTtl {
+    /// A duration literal such as `60s`, in milliseconds.
+    Millis(u64),
+    /// An expression evaluating to a `Duration`.
+    Expr(Expr),
+}
@@ -1,4 +1,4 @@
-use syn::{Expr, Ident, Path};
+use syn::{Expr, Ident, Pat, Path};
 
 use crate::ast::{Attribute, Node};
 
@@ -6,6 +6,16 @@ pub struct ComponentCall {
     pub path: Path,
     pub fields: Vec<ComponentCallField>,
     pub attributes: Vec<Attribute>,
+    /// Children outside `slot(name) { ... }` blocks and in `slot(default) { ... }`.
+    pub children: Vec<Node>,
+    pub slots: Vec<ComponentCallSlot>,
+    /// Parameter of `{ |item| ... }` children, which are passed to the component as its `children` render prop.
+    pub render_prop: Option<Pat>,
+}
+
+/// A `slot(name) { ... }` block in the children of a component call.
+pub struct ComponentCallSlot {
+    pub name: Ident,
     pub children: Vec<Node>,
 }
 
@@ -2,9 +2,15 @@ use syn::{Attribute, Generics, Ident, Type, Visibility};
 
 use crate::ast::Node;
 
+pub struct ComponentDefinitions {
+    pub definitions: Vec<ComponentDefinition>,
+}
+
 pub struct ComponentDefinition {
     pub attributes: Vec<Attribute>,
     pub visibility: Visibility,
+    /// Modules the component is generated into (`ui` for `pub fn ui::Button`).
+    pub module: Vec<Ident>,
     pub ident: Ident,
     pub generics: Generics,
     pub fields: Vec<ComponentDefinitionField>,
@@ -12,6 +18,10 @@ pub struct ComponentDefinition {
 }
 
 pub struct ComponentDefinitionField {
+    pub attributes: Vec<Attribute>,
     pub ident: Ident,
+    /// The type of the field, `Option<T>` for optional props.
     pub ty: Type,
+    /// `true` for props declared with `name?: T`, which can be omitted at the call site.
+    pub optional: bool,
 }
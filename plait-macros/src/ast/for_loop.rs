@@ -5,5 +5,8 @@ use crate::ast::Node;
 pub struct ForLoop {
     pub pattern: Pat,
     pub expression: Expr,
+    pub separator: Option<Vec<Node>>,
     pub body: Vec<Node>,
+    /// Rendered when the iterator yields nothing.
+    pub else_branch: Option<Vec<Node>>,
 }
@@ -1,6 +1,10 @@
 use syn::{Expr, Pat};
 
+use crate::ast::Node;
+
 pub struct LetBinding {
     pub pattern: Pat,
     pub expr: Option<Expr>,
+    /// The nodes rendered instead of the rest of the block when the pattern doesn't match, for `let ... else`.
+    pub else_branch: Option<Vec<Node>>,
 }
@@ -1,6 +1,9 @@
 use syn::{Expr, Ident, LitBool, LitChar, LitFloat, LitInt, LitStr};
 
-use crate::ast::{ComponentCall, Element, ForLoop, IfCondition, LetBinding, MatchExpression};
+use crate::ast::{
+    CacheBlock, ComponentCall, Element, ForLoop, IfCondition, LetBinding, MatchExpression,
+    WhileLoop,
+};
 
 pub enum Node {
     Doctype,
@@ -15,8 +18,28 @@ pub enum Node {
     IfCondition(IfCondition),
     MatchExpression(MatchExpression),
     ForLoop(ForLoop),
+    WhileLoop(WhileLoop),
     Element(Element),
     Block(Vec<Node>),
     Children(Ident),
+    /// `#slot(name)`, with the `children` closure the slot is rendered from.
+    Slot {
+        children: Ident,
+        name: Ident,
+    },
+    /// `#children(argument)`, which renders the `children` render prop of the component with an argument.
+    RenderProp {
+        children: Ident,
+        argument: Expr,
+    },
     ComponentCall(ComponentCall),
+    CacheBlock(CacheBlock),
+    /// A node preceded by `#[allow_invalid_nesting]`, whose contents skip the nesting checks.
+    AllowInvalidNesting(Box<Node>),
+    /// A node preceded by `#[deny(unknown_attributes)]` or `#[allow(unknown_attributes)]`, whose contents have the
+    /// attribute checks turned on or off.
+    UnknownAttributes {
+        deny: bool,
+        node: Box<Node>,
+    },
 }
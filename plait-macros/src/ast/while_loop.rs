@@ -0,0 +1,8 @@
+use syn::Expr;
+
+use crate::ast::Node;
+
+pub struct WhileLoop {
+    pub condition: Expr,
+    pub body: Vec<Node>,
+}
@@ -1,20 +1,62 @@
 use std::{
     cmp::max,
     ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Expr, Ident, Lit, LitBool, LitChar, LitFloat, LitInt, LitStr, spanned::Spanned};
+use syn::{
+    Expr, Ident, Lifetime, Lit, LitBool, LitChar, LitFloat, LitInt, LitStr, spanned::Spanned,
+};
 
 use crate::{
     ast::{
-        Attribute, AttributeValue, ComponentCall, Element, ElseBranch, ForLoop, IfCondition,
-        LetBinding, MatchArm, MatchExpression, Node,
+        Attribute, AttributeValue, CacheBlock, CacheTtl, ComponentCall, Element, ElseBranch,
+        ForLoop, IfCondition, LetBinding, MatchArm, MatchExpression, NameValueAttribute, Node,
+        WhileLoop,
+    },
+    known_attributes::attribute_name,
+    utils::{
+        encode_whitespace_to, encodes_attribute_whitespace, escape_html_to, escape_raw_text_to,
+        is_mathml_element, is_url_attribute, is_void_element, mathml_attribute_name,
+        svg_attribute_name, svg_element_name,
     },
-    utils::{escape_html_to, is_void_element},
 };
 
+/// Namespace of the elements a buffer renders.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Namespace {
+    Html,
+    /// The content of an `svg` element.
+    Svg,
+    /// The content of a `math` element.
+    MathMl,
+}
+
+impl Namespace {
+    /// Returns the namespace of the element `tag`, rendered in a buffer of this namespace.
+    fn of_element(self, tag: &str) -> Namespace {
+        match (self, tag) {
+            (_, "svg") | (Namespace::Svg, _) => Namespace::Svg,
+            (_, "math") => Namespace::MathMl,
+            // Elements that aren't MathML break out of MathML content, e.g. in `mtext`
+            (_, tag) if is_mathml_element(tag) => Namespace::MathMl,
+            (_, tag) if svg_element_name(tag).is_some() => Namespace::Svg,
+            _ => Namespace::Html,
+        }
+    }
+
+    /// Returns the namespace URI declared by the root element of this namespace.
+    fn uri(self) -> &'static str {
+        match self {
+            Namespace::Html => "http://www.w3.org/1999/xhtml",
+            Namespace::Svg => "http://www.w3.org/2000/svg",
+            Namespace::MathMl => "http://www.w3.org/1998/Math/MathML",
+        }
+    }
+}
+
 pub struct Buffer {
     pub input_size: usize,
     pub inner: InnerBuffer,
@@ -37,6 +79,11 @@ impl Buffer {
             size_hint,
             token_stream,
             has_dynamic_value,
+            namespace: _,
+            raw_text: _,
+            iter_props: _,
+            child_units: _,
+            attribute_whitespace: _,
         } = self.inner;
 
         let size_hint = if has_dynamic_value {
@@ -57,6 +104,25 @@ impl Buffer {
     }
 }
 
+impl Buffer {
+    pub fn finalize_write(mut self, writer_expr: &Expr) -> TokenStream {
+        self.flush_static_str();
+
+        let InnerBuffer {
+            writer,
+            token_stream,
+            ..
+        } = self.inner;
+
+        quote! {
+            (|#writer: &mut (dyn ::core::fmt::Write + '_)| -> ::core::fmt::Result {
+                #token_stream
+                Ok(())
+            })(&mut *(#writer_expr))
+        }
+    }
+}
+
 impl Deref for Buffer {
     type Target = InnerBuffer;
 
@@ -77,6 +143,18 @@ pub struct InnerBuffer {
     pub size_hint: usize,
     pub token_stream: TokenStream,
     pub has_dynamic_value: bool,
+    /// Namespace of the content the buffer renders.
+    pub namespace: Namespace,
+    /// The raw text element (`script` or `style`) whose text the buffer renders, if any.
+    pub raw_text: Option<&'static str>,
+    /// Component props declared as `impl RenderIter`, which `for` loops iterate with `render_iter()`.
+    pub iter_props: Vec<Ident>,
+    /// Whether the buffer renders the children of a component call, where each node is a separate child that can be
+    /// told apart from the others (see `plait::EachChild`).
+    pub child_units: bool,
+    /// Whether the buffer renders the value of an attribute whose newlines and tabs are written as character
+    /// references (see `plait::spec::WHITESPACE_SENSITIVE_ATTRIBUTES`).
+    pub attribute_whitespace: bool,
 }
 
 impl InnerBuffer {
@@ -87,16 +165,45 @@ impl InnerBuffer {
             size_hint: 0,
             token_stream: TokenStream::new(),
             has_dynamic_value: false,
+            namespace: Namespace::Html,
+            raw_text: None,
+            iter_props: Vec::new(),
+            child_units: false,
+            attribute_whitespace: false,
         }
     }
 
     pub fn push_block(&mut self, block: &[Node]) {
-        for child in block {
+        for (index, child) in block.iter().enumerate() {
+            if let Node::LetBinding(let_binding) = child
+                && let_binding.else_branch.is_some()
+            {
+                self.push_let_else(let_binding, &block[index + 1..]);
+                return;
+            }
+
             self.push_node(child);
         }
     }
 
     fn push_node(&mut self, node: &Node) {
+        if self.child_units {
+            match node {
+                // Control flow is transparent, the nodes it renders are the children
+                Node::LetBinding(_)
+                | Node::IfCondition(_)
+                | Node::MatchExpression(_)
+                | Node::ForLoop(_)
+                | Node::WhileLoop(_)
+                | Node::Block(_) => {}
+                Node::Children(children) => return self.push_forwarded_slot(children, "default"),
+                Node::Slot { children, name } => {
+                    return self.push_forwarded_slot(children, &name.to_string());
+                }
+                _ => return self.push_child_unit(node),
+            }
+        }
+
         match node {
             Node::Doctype => self.push_doctype(),
             Node::LitStr(lit_str) => self.push_lit_str_escaped(lit_str),
@@ -110,10 +217,17 @@ impl InnerBuffer {
             Node::IfCondition(if_condition) => self.push_if_condition(if_condition),
             Node::MatchExpression(match_expression) => self.push_match_expression(match_expression),
             Node::ForLoop(for_loop) => self.push_for_loop(for_loop),
+            Node::WhileLoop(while_loop) => self.push_while_loop(while_loop),
             Node::Element(element) => self.push_element(element),
             Node::Block(block) => self.push_block(block),
-            Node::Children(children) => self.push_children(children),
+            Node::Children(children) => self.push_slot(children, "default"),
+            Node::Slot { children, name } => self.push_slot(children, &name.to_string()),
+            Node::RenderProp { children, argument } => self.push_render_prop(children, argument),
             Node::ComponentCall(component_call) => self.push_component_call(component_call),
+            Node::CacheBlock(cache_block) => self.push_cache_block(cache_block),
+            Node::AllowInvalidNesting(node) | Node::UnknownAttributes { node, .. } => {
+                self.push_node(node)
+            }
         }
     }
 
@@ -122,7 +236,20 @@ impl InnerBuffer {
     }
 
     fn push_lit_str_escaped(&mut self, lit_str: &LitStr) {
-        escape_html_to(&mut self.static_str, &lit_str.value());
+        self.push_text_escaped(&lit_str.value());
+    }
+
+    /// Pushes static text, escaped for the element it is rendered in.
+    fn push_text_escaped(&mut self, text: &str) {
+        match self.raw_text {
+            Some(tag) => escape_raw_text_to(&mut self.static_str, text, tag),
+            None if self.attribute_whitespace => {
+                let mut escaped = String::new();
+                escape_html_to(&mut escaped, text);
+                encode_whitespace_to(&mut self.static_str, &escaped);
+            }
+            None => escape_html_to(&mut self.static_str, text),
+        }
     }
 
     fn push_lit_str_raw(&mut self, lit_str: &LitStr) {
@@ -130,7 +257,7 @@ impl InnerBuffer {
     }
 
     fn push_lit_char_escaped(&mut self, lit_char: &LitChar) {
-        escape_html_to(&mut self.static_str, &lit_char.value().to_string());
+        self.push_text_escaped(&lit_char.value().to_string());
     }
 
     fn push_lit_char_raw(&mut self, lit_char: &LitChar) {
@@ -142,7 +269,22 @@ impl InnerBuffer {
     }
 
     fn push_lit_float(&mut self, lit_float: &LitFloat) {
-        self.static_str.push_str(lit_float.base10_digits());
+        let digits = lit_float.base10_digits();
+
+        // Write exponents out (`1e-7` as `0.0000001`), which is how values computed at runtime are rendered too
+        if digits.contains(['e', 'E']) {
+            let plain = match lit_float.suffix() {
+                "f32" => digits.parse::<f32>().map(|value| value.to_string()),
+                _ => digits.parse::<f64>().map(|value| value.to_string()),
+            };
+
+            if let Ok(plain) = plain {
+                self.static_str.push_str(&plain);
+                return;
+            }
+        }
+
+        self.static_str.push_str(digits);
     }
 
     fn push_lit_bool(&mut self, lit_bool: &LitBool) {
@@ -181,7 +323,7 @@ impl InnerBuffer {
     fn push_let_binding(&mut self, let_binding: &LetBinding) {
         self.flush_static_str();
 
-        let LetBinding { pattern, expr } = let_binding;
+        let LetBinding { pattern, expr, .. } = let_binding;
 
         match expr {
             Some(expr) => self.token_stream.extend(quote! {
@@ -193,6 +335,53 @@ impl InnerBuffer {
         }
     }
 
+    /// Renders a `let ... else` binding followed by the `rest` of its block. When the pattern doesn't match, the else
+    /// branch is rendered instead and the rest of the block is skipped, by breaking out of a labeled block around it.
+    fn push_let_else(&mut self, let_binding: &LetBinding, rest: &[Node]) {
+        static LABELS: AtomicUsize = AtomicUsize::new(0);
+
+        self.flush_static_str();
+
+        let LetBinding {
+            pattern,
+            expr,
+            else_branch,
+        } = let_binding;
+
+        let label = Lifetime::new(
+            &format!(
+                "'__plait_let_else_{}",
+                LABELS.fetch_add(1, Ordering::Relaxed)
+            ),
+            Span::mixed_site(),
+        );
+
+        let mut else_buffer = self.create_inner();
+        else_buffer.push_block(else_branch.as_deref().unwrap_or_default());
+        else_buffer.flush_static_str();
+
+        let mut rest_buffer = self.create_inner();
+        rest_buffer.push_block(rest);
+        rest_buffer.flush_static_str();
+
+        let else_token_stream = else_buffer.token_stream;
+        let rest_token_stream = rest_buffer.token_stream;
+
+        self.token_stream.extend(quote! {
+            #label: {
+                let #pattern = #expr else {
+                    #else_token_stream
+                    break #label;
+                };
+
+                #rest_token_stream
+            }
+        });
+
+        self.has_dynamic_value = true;
+        self.size_hint += max(else_buffer.size_hint, rest_buffer.size_hint);
+    }
+
     fn push_if_condition(&mut self, if_condition: &IfCondition) {
         self.flush_static_str();
 
@@ -323,7 +512,9 @@ impl InnerBuffer {
         let ForLoop {
             pattern,
             expression,
+            separator,
             body,
+            else_branch,
         } = for_loop;
 
         let mut body_buffer = self.create_inner();
@@ -332,8 +523,92 @@ impl InnerBuffer {
 
         let body_token_stream = body_buffer.token_stream;
 
+        let expression = match expression {
+            Expr::Path(path)
+                if path.qself.is_none()
+                    && path
+                        .path
+                        .get_ident()
+                        .is_some_and(|ident| self.iter_props.contains(ident)) =>
+            {
+                quote! { ::plait::RenderIter::render_iter(#expression) }
+            }
+            _ => quote! { #expression },
+        };
+
+        if separator.is_none() && else_branch.is_none() {
+            self.token_stream.extend(quote! {
+                for #pattern in #expression {
+                    #body_token_stream
+                }
+            });
+        } else {
+            // The separator is written before every item but the first, so the length of the iterator doesn't need
+            // to be known up front. The same flag tells whether the iterator was empty.
+            let separator_token_stream = separator.as_ref().map(|separator| {
+                let mut separator_buffer = self.create_inner();
+                separator_buffer.child_units = false;
+                separator_buffer.push_block(separator);
+                separator_buffer.flush_static_str();
+
+                self.size_hint += separator_buffer.size_hint;
+
+                let separator_token_stream = separator_buffer.token_stream;
+
+                quote! {
+                    if !__plait_is_first {
+                        #separator_token_stream
+                    }
+                }
+            });
+
+            let else_token_stream = else_branch.as_ref().map(|else_branch| {
+                let mut else_buffer = self.create_inner();
+                else_buffer.push_block(else_branch);
+                else_buffer.flush_static_str();
+
+                self.size_hint += else_buffer.size_hint;
+
+                let else_token_stream = else_buffer.token_stream;
+
+                quote! {
+                    if __plait_is_first {
+                        #else_token_stream
+                    }
+                }
+            });
+
+            self.token_stream.extend(quote! {
+                {
+                    let mut __plait_is_first = true;
+                    for #pattern in #expression {
+                        #separator_token_stream
+                        __plait_is_first = false;
+
+                        #body_token_stream
+                    }
+                    #else_token_stream
+                }
+            });
+        }
+
+        self.has_dynamic_value = true;
+        self.size_hint += body_buffer.size_hint;
+    }
+
+    fn push_while_loop(&mut self, while_loop: &WhileLoop) {
+        self.flush_static_str();
+
+        let WhileLoop { condition, body } = while_loop;
+
+        let mut body_buffer = self.create_inner();
+        body_buffer.push_block(body);
+        body_buffer.flush_static_str();
+
+        let body_token_stream = body_buffer.token_stream;
+
         self.token_stream.extend(quote! {
-            for #pattern in #expression {
+            while #condition {
                 #body_token_stream
             }
         });
@@ -351,12 +626,32 @@ impl InnerBuffer {
 
         let tag_str = tag.value();
 
+        let namespace = self.namespace.of_element(&tag_str);
+
+        if namespace != Namespace::Html {
+            self.push_foreign_element(element, namespace);
+            return;
+        }
+
+        if let Some(no_xmlns) = attributes.iter().find(|attribute| is_no_xmlns(attribute)) {
+            self.push_compile_error(
+                no_xmlns,
+                "`no_xmlns` can only be used on `svg` and `math` elements",
+            );
+            return;
+        }
+
         if tag_str == "html" && !self.static_str.ends_with("<!DOCTYPE html>") {
             self.static_str.push_str("<!DOCTYPE html>");
         }
 
         self.static_str.push_str(&format!("<{}", tag_str));
 
+        if let Some(auto_id) = attributes.iter().find(|attribute| is_auto_id(attribute)) {
+            self.push_auto_id_element(element, auto_id);
+            return;
+        }
+
         for attribute in attributes {
             self.push_attribute(attribute);
         }
@@ -364,18 +659,283 @@ impl InnerBuffer {
         self.static_str.push('>');
 
         if !is_void_element(&tag_str) {
+            let raw_text = match tag_str.as_str() {
+                "script" => Some("script"),
+                "style" => Some("style"),
+                _ => self.raw_text,
+            };
+            let raw_text = std::mem::replace(&mut self.raw_text, raw_text);
+
             self.push_block(children);
             self.static_str.push_str(&format!("</{}>", tag_str));
+
+            self.raw_text = raw_text;
+        }
+    }
+
+    /// Renders an SVG or MathML element. Foreign content follows XML rules: element and attribute names keep their
+    /// case, elements without children are self-closing, and the root `svg` or `math` element declares its namespace.
+    fn push_foreign_element(&mut self, element: &Element, namespace: Namespace) {
+        let Element {
+            tag,
+            attributes,
+            children,
+        } = element;
+
+        let tag_str = tag.value();
+
+        if let Some(auto_id) = attributes.iter().find(|attribute| is_auto_id(attribute)) {
+            self.push_compile_error(
+                auto_id,
+                "`auto_id` cannot be used on SVG or MathML elements",
+            );
+            return;
+        }
+
+        let is_root = matches!(tag_str.as_str(), "svg" | "math") && self.namespace != namespace;
+        let no_xmlns = attributes.iter().find(|attribute| is_no_xmlns(attribute));
+
+        if let Some(no_xmlns) = no_xmlns
+            && !is_root
+        {
+            self.push_compile_error(
+                no_xmlns,
+                "`no_xmlns` can only be used on root `svg` and `math` elements",
+            );
+            return;
         }
+
+        let name = match namespace {
+            Namespace::Svg => svg_element_name(&tag_str).unwrap_or(&tag_str),
+            _ => &tag_str,
+        };
+
+        self.static_str.push_str(&format!("<{}", name));
+
+        let has_xmlns = attributes.iter().any(|attribute| {
+            matches!(attribute, Attribute::NameValue(attribute) if attribute.name.value() == "xmlns")
+        });
+
+        if is_root && no_xmlns.is_none() && !has_xmlns {
+            self.static_str
+                .push_str(&format!(" xmlns=\"{}\"", namespace.uri()));
+        }
+
+        for attribute in attributes
+            .iter()
+            .filter(|attribute| !is_no_xmlns(attribute))
+        {
+            let Attribute::NameValue(name_value_attribute) = attribute else {
+                self.push_attribute(attribute);
+                continue;
+            };
+
+            let foreign_name = match namespace {
+                Namespace::Svg => svg_attribute_name(&name_value_attribute.name.value()),
+                _ => mathml_attribute_name(&name_value_attribute.name.value()),
+            };
+
+            match foreign_name {
+                Some(foreign_name) => {
+                    let mut name_value_attribute = name_value_attribute.clone();
+                    name_value_attribute.name =
+                        LitStr::new(foreign_name, name_value_attribute.name.span());
+
+                    self.push_attribute(&Attribute::NameValue(name_value_attribute));
+                }
+                None => self.push_attribute(attribute),
+            }
+        }
+
+        if children.is_empty() {
+            self.static_str.push_str("/>");
+        } else {
+            // The content of `foreignObject` is HTML again
+            let children_namespace = if tag_str == "foreignobject" {
+                Namespace::Html
+            } else {
+                namespace
+            };
+            let namespace = std::mem::replace(&mut self.namespace, children_namespace);
+
+            self.static_str.push('>');
+            self.push_block(children);
+            self.static_str.push_str(&format!("</{}>", name));
+
+            self.namespace = namespace;
+        }
+    }
+
+    /// Renders the children of an element with an `auto_id` marker into a temporary buffer first, so that the `id` can be
+    /// derived from their text content before the start tag is completed.
+    fn push_auto_id_element(&mut self, element: &Element, auto_id: &Attribute) {
+        static CONTENTS: AtomicUsize = AtomicUsize::new(0);
+
+        let Element {
+            tag,
+            attributes,
+            children,
+        } = element;
+
+        let tag_str = tag.value();
+
+        if is_void_element(&tag_str) {
+            self.push_compile_error(auto_id, "`auto_id` cannot be used on void elements");
+            return;
+        }
+
+        if let Some(id) = attributes.iter().find(|attribute| {
+            matches!(attribute, Attribute::NameValue(attribute) if attribute.name.value() == "id")
+        }) {
+            self.push_compile_error(id, "`id` cannot be used together with `auto_id`");
+            return;
+        }
+
+        self.flush_static_str();
+
+        // Nested `auto_id` elements are expanded inside this one, so each expansion gets its own names.
+        let index = CONTENTS.fetch_add(1, Ordering::Relaxed);
+        let content = Ident::new(
+            &format!("__plait_auto_id_content_{index}"),
+            Span::mixed_site(),
+        );
+        let content_writer = Ident::new(&format!("__plait_auto_id_writer_{index}"), tag.span());
+
+        let mut children_buffer = self.create_inner_with(content_writer.clone());
+        children_buffer.push_block(children);
+        children_buffer.flush_static_str();
+
+        let children_token_stream = children_buffer.token_stream;
+        let writer = self.writer.clone();
+
+        self.token_stream.extend(quote! {
+            let #content = {
+                let mut #content = ::std::string::String::new();
+                let #content_writer: &mut (dyn ::core::fmt::Write + '_) = &mut #content;
+                #children_token_stream
+                #content
+            };
+            ::plait::write_auto_id(#writer, &#content)?;
+        });
+
+        for attribute in attributes.iter().filter(|attribute| !is_auto_id(attribute)) {
+            self.push_attribute(attribute);
+        }
+
+        self.static_str.push('>');
+        self.flush_static_str();
+        self.token_stream.extend(quote! {
+            ::core::fmt::Write::write_str(#writer, &#content)?;
+        });
+        self.static_str.push_str(&format!("</{}>", tag_str));
+
+        self.has_dynamic_value = true;
+        self.size_hint += children_buffer.size_hint;
+    }
+
+    fn push_cache_block(&mut self, cache_block: &CacheBlock) {
+        self.flush_static_str();
+
+        let CacheBlock { key, ttl, body } = cache_block;
+
+        let body_writer = Ident::new("__plait_cache_writer", self.writer.span());
+
+        let mut body_buffer = self.create_inner_with(body_writer.clone());
+        body_buffer.push_block(body);
+        body_buffer.flush_static_str();
+
+        let body_token_stream = body_buffer.token_stream;
+        let writer = &self.writer;
+
+        let ttl = match ttl {
+            Some(CacheTtl::Millis(millis)) => quote! {
+                ::core::option::Option::Some(::core::time::Duration::from_millis(#millis))
+            },
+            Some(CacheTtl::Expr(expr)) => quote! {
+                ::core::option::Option::Some(#expr)
+            },
+            None => quote! { ::core::option::Option::None },
+        };
+
+        self.token_stream.extend(quote! {
+            ::plait::cache::render_cached(
+                #writer,
+                &(#key),
+                #ttl,
+                |#body_writer: &mut (dyn ::core::fmt::Write + '_)| -> ::core::fmt::Result {
+                    #body_token_stream
+                    Ok(())
+                },
+            )?;
+        });
+
+        self.has_dynamic_value = true;
+        self.size_hint += body_buffer.size_hint;
+    }
+
+    fn push_compile_error(&mut self, attribute: &Attribute, message: &str) {
+        let span = match attribute {
+            Attribute::Spread(ident) | Attribute::SpreadPairs(ident, _) => ident.span(),
+            Attribute::NameValue(attribute) => attribute.name.span(),
+        };
+
+        self.flush_static_str();
+        self.token_stream
+            .extend(syn::Error::new(span, message).to_compile_error());
+    }
+
+    fn push_slot(&mut self, children: &Ident, name: &str) {
+        self.flush_static_str();
+
+        let writer = &self.writer;
+
+        self.token_stream.extend(quote! {
+            ::plait::render_slot(&#children, #name, #writer)?;
+        });
+    }
+
+    fn push_render_prop(&mut self, children: &Ident, argument: &Expr) {
+        self.flush_static_str();
+
+        let writer = &self.writer;
+
+        self.token_stream.extend(quote! {
+            ::plait::render_prop(#children, #argument, #writer)?;
+        });
+
+        self.has_dynamic_value = true;
+    }
+
+    /// Pushes `node` as a child of a component call, marking where it starts for `plait::EachChild`.
+    fn push_child_unit(&mut self, node: &Node) {
+        self.flush_static_str();
+
+        let mut unit_buffer = self.create_inner();
+        unit_buffer.child_units = false;
+        unit_buffer.push_node(node);
+        unit_buffer.flush_static_str();
+
+        let unit_token_stream = unit_buffer.token_stream;
+
+        self.token_stream.extend(quote! {
+            ::plait::start_child();
+            {
+                #unit_token_stream
+            }
+        });
+
+        self.size_hint += unit_buffer.size_hint;
+        self.has_dynamic_value = true;
     }
 
-    fn push_children(&mut self, children: &Ident) {
+    /// Pushes a slot forwarded to the children of a component call, keeping its children separate.
+    fn push_forwarded_slot(&mut self, children: &Ident, name: &str) {
         self.flush_static_str();
 
         let writer = &self.writer;
 
         self.token_stream.extend(quote! {
-            #children(#writer)?;
+            ::plait::forward_slot(&#children, #name, #writer)?;
         });
     }
 
@@ -387,9 +947,11 @@ impl InnerBuffer {
             fields,
             attributes,
             children,
+            slots,
+            render_prop,
         } = component_call;
 
-        let mut field_statements = Vec::with_capacity(fields.len());
+        let mut field_statements = Vec::with_capacity(fields.len() + 1);
 
         for field in fields {
             let ident = &field.ident;
@@ -397,18 +959,44 @@ impl InnerBuffer {
 
             match value {
                 Some(value) => field_statements.push(quote! {
-                    #ident : #value
+                    .#ident(#value)
                 }),
                 None => field_statements.push(quote! {
-                    #ident
+                    .#ident(#ident)
                 }),
             }
         }
 
-        let component_statement = quote! {
-            &#path {
-                #(#field_statements),*
+        let mut children_size_hint = 0;
+
+        // `{ |item| ... }` children are the `children` render prop instead of the default slot
+        let children: &[Node] = match render_prop {
+            Some(pattern) => {
+                let mut render_prop_buffer = self.create_inner();
+                render_prop_buffer.push_block(children);
+                render_prop_buffer.flush_static_str();
+
+                let writer = &self.writer;
+                let render_prop_token_stream = render_prop_buffer.token_stream;
+
+                field_statements.push(quote! {
+                    .children(|#pattern, #writer: &mut (dyn ::core::fmt::Write + '_)| -> ::core::fmt::Result {
+                        #render_prop_token_stream
+                        Ok(())
+                    })
+                });
+                children_size_hint += render_prop_buffer.size_hint;
+
+                &[]
             }
+            None => children,
+        };
+
+        // Errors about missing props point at the component
+        let build = Ident::new("__plait_build", path.span());
+
+        let component_statement = quote! {
+            &#path::__plait_props() #(#field_statements)* .#build()
         };
 
         let mut attributes_buffer = self.create_inner();
@@ -419,19 +1007,41 @@ impl InnerBuffer {
 
         let attributes_token_stream = attributes_buffer.token_stream;
 
-        let mut children_buffer = self.create_inner();
-        children_buffer.push_block(children);
-        children_buffer.flush_static_str();
+        let mut children_token_stream = TokenStream::new();
 
-        let children_token_stream = children_buffer.token_stream;
+        let slots = std::iter::once(("default".to_owned(), children)).chain(
+            slots
+                .iter()
+                .map(|slot| (slot.name.to_string(), slot.children.as_slice())),
+        );
+
+        for (name, children) in slots {
+            let mut slot_buffer = self.create_inner();
+            slot_buffer.child_units = true;
+            slot_buffer.push_block(children);
+            slot_buffer.flush_static_str();
+
+            let slot_token_stream = slot_buffer.token_stream;
 
-        self.size_hint += attributes_buffer.size_hint + children_buffer.size_hint;
+            if slot_token_stream.is_empty() {
+                continue;
+            }
+
+            children_token_stream.extend(quote! {
+                if ::plait::is_slot(#name) {
+                    #slot_token_stream
+                }
+            });
+            children_size_hint += slot_buffer.size_hint;
+        }
+
+        self.size_hint += attributes_buffer.size_hint + children_size_hint;
         self.has_dynamic_value = true;
 
         let writer = &self.writer;
 
         self.token_stream.extend(quote! {
-            ::plait::Component::render_component(
+            ::plait::render_component(
                 #component_statement,
                 #writer,
                 |#writer: &mut (dyn ::core::fmt::Write + '_)| -> ::core::fmt::Result {
@@ -449,14 +1059,32 @@ impl InnerBuffer {
     fn push_dynamic_expr_escaped(&mut self, expr: &Expr) {
         self.flush_static_str();
 
-        let writer = &self.writer;
-        self.token_stream.extend(quote! {
-            ::plait::RenderEscaped::render_escaped(&#expr, #writer)?;
-        });
+        let writer = self.value_writer();
+
+        match self.raw_text {
+            Some(tag) => self.token_stream.extend(quote! {
+                ::plait::render_raw_text(&#expr, #tag, #writer)?;
+            }),
+            None => self.token_stream.extend(quote! {
+                ::plait::RenderEscaped::render_escaped(&#expr, #writer)?;
+            }),
+        }
 
         self.has_dynamic_value = true;
     }
 
+    /// Returns the writer that escaped values are rendered into, which encodes whitespace in the values of whitespace
+    /// sensitive attributes.
+    fn value_writer(&self) -> TokenStream {
+        let writer = &self.writer;
+
+        if self.attribute_whitespace {
+            quote! { &mut ::plait::AttributeWhitespace(&mut *#writer) }
+        } else {
+            quote! { #writer }
+        }
+    }
+
     fn push_dynamic_expr_raw(&mut self, expr: &Expr) {
         self.flush_static_str();
 
@@ -479,217 +1107,249 @@ impl InnerBuffer {
                     #attrs(#writer)?;
                 });
             }
+            Attribute::SpreadPairs(_, expr) => {
+                self.flush_static_str();
+
+                let writer = &self.writer;
+
+                self.token_stream.extend(quote! {
+                    ::plait::render_attribute_pairs(#expr, #writer)?;
+                });
+
+                self.has_dynamic_value = true;
+            }
             Attribute::NameValue(name_value_attribute) => {
-                match (name_value_attribute.is_maybe, &name_value_attribute.value) {
-                    (false, None) => {
-                        self.static_str
-                            .push_str(&format!(" {}", name_value_attribute.name.value()));
+                let name = name_value_attribute.name.value().to_ascii_lowercase();
+                self.attribute_whitespace = encodes_attribute_whitespace(&name);
+
+                match dynamic_escaped_value(name_value_attribute) {
+                    Some(expr) if is_url_attribute(&name) => {
+                        self.push_url_attribute(name_value_attribute, expr)
                     }
-                    (false, Some(value)) => {
-                        self.static_str
-                            .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
+                    _ => self.push_name_value_attribute(name_value_attribute),
+                }
 
-                        match value {
-                            AttributeValue::LitStr(lit_str) => self.push_lit_str_escaped(lit_str),
-                            AttributeValue::LitChar(lit_char) => {
-                                self.push_lit_char_escaped(lit_char)
-                            }
-                            AttributeValue::LitInt(lit_int) => self.push_lit_int(lit_int),
-                            AttributeValue::LitFloat(lit_float) => self.push_lit_float(lit_float),
-                            AttributeValue::LitBool(lit_bool) => self.push_lit_bool(lit_bool),
-                            AttributeValue::Escaped(expr) => match &expr {
-                                Expr::Lit(expr_lit) => match &expr_lit.lit {
-                                    Lit::Str(lit_str) => self.push_lit_str_escaped(lit_str),
-                                    Lit::Char(lit_char) => self.push_lit_char_escaped(lit_char),
-                                    Lit::Int(lit_int) => self.push_lit_int(lit_int),
-                                    Lit::Float(lit_float) => self.push_lit_float(lit_float),
-                                    Lit::Bool(lit_bool) => self.push_lit_bool(lit_bool),
-                                    _ => self.push_dynamic_expr_escaped(expr),
-                                },
-                                _ => self.push_dynamic_expr_escaped(expr),
-                            },
-                            AttributeValue::Raw(expr) => match &expr {
-                                Expr::Lit(expr_lit) => match &expr_lit.lit {
-                                    Lit::Str(lit_str) => self.push_lit_str_raw(lit_str),
-                                    Lit::Char(lit_char) => self.push_lit_char_raw(lit_char),
-                                    Lit::Int(lit_int) => self.push_lit_int(lit_int),
-                                    Lit::Float(lit_float) => self.push_lit_float(lit_float),
-                                    Lit::Bool(lit_bool) => self.push_lit_bool(lit_bool),
-                                    _ => self.push_dynamic_expr_raw(expr),
-                                },
-                                _ => self.push_dynamic_expr_raw(expr),
-                            },
-                        }
+                self.attribute_whitespace = false;
+            }
+        }
+    }
+
+    /// Pushes a URL attribute with a dynamic escaped value, which is checked against the `UrlRules` in the render
+    /// context at runtime.
+    fn push_url_attribute(&mut self, name_value_attribute: &NameValueAttribute, expr: &Expr) {
+        self.flush_static_str();
+
+        let writer = self.value_writer();
+        let name = attribute_name(&name_value_attribute.name);
+
+        let value = if name_value_attribute.is_maybe {
+            quote! { ::plait::url_policy::MaybeUrlValue(&#expr) }
+        } else {
+            quote! { ::plait::url_policy::UrlValue(&#expr) }
+        };
+
+        self.token_stream.extend(quote! {
+            ::plait::RenderMaybeAttributeEscaped::render_maybe_attribute_escaped(&#value, #name, #writer)?;
+        });
 
-                        self.static_str.push('"');
+        self.has_dynamic_value = true;
+    }
+
+    fn push_name_value_attribute(&mut self, name_value_attribute: &NameValueAttribute) {
+        match (name_value_attribute.is_maybe, &name_value_attribute.value) {
+            (false, None) => {
+                self.static_str
+                    .push_str(&format!(" {}", name_value_attribute.name.value()));
+            }
+            (false, Some(value)) => {
+                self.static_str
+                    .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
+
+                match value {
+                    AttributeValue::LitStr(lit_str) => self.push_lit_str_escaped(lit_str),
+                    AttributeValue::LitChar(lit_char) => self.push_lit_char_escaped(lit_char),
+                    AttributeValue::LitInt(lit_int) => self.push_lit_int(lit_int),
+                    AttributeValue::LitFloat(lit_float) => self.push_lit_float(lit_float),
+                    AttributeValue::LitBool(lit_bool) => self.push_lit_bool(lit_bool),
+                    AttributeValue::Escaped(expr) => match &expr {
+                        Expr::Lit(expr_lit) => match &expr_lit.lit {
+                            Lit::Str(lit_str) => self.push_lit_str_escaped(lit_str),
+                            Lit::Char(lit_char) => self.push_lit_char_escaped(lit_char),
+                            Lit::Int(lit_int) => self.push_lit_int(lit_int),
+                            Lit::Float(lit_float) => self.push_lit_float(lit_float),
+                            Lit::Bool(lit_bool) => self.push_lit_bool(lit_bool),
+                            _ => self.push_dynamic_expr_escaped(expr),
+                        },
+                        _ => self.push_dynamic_expr_escaped(expr),
+                    },
+                    AttributeValue::Raw(expr) => match &expr {
+                        Expr::Lit(expr_lit) => match &expr_lit.lit {
+                            Lit::Str(lit_str) => self.push_lit_str_raw(lit_str),
+                            Lit::Char(lit_char) => self.push_lit_char_raw(lit_char),
+                            Lit::Int(lit_int) => self.push_lit_int(lit_int),
+                            Lit::Float(lit_float) => self.push_lit_float(lit_float),
+                            Lit::Bool(lit_bool) => self.push_lit_bool(lit_bool),
+                            _ => self.push_dynamic_expr_raw(expr),
+                        },
+                        _ => self.push_dynamic_expr_raw(expr),
+                    },
+                }
+
+                self.static_str.push('"');
+            }
+            (true, None) => {}
+            (true, Some(value)) => match value {
+                AttributeValue::LitStr(lit_str) => {
+                    self.static_str
+                        .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
+                    self.push_lit_str_escaped(lit_str);
+                    self.static_str.push('"');
+                }
+                AttributeValue::LitChar(lit_char) => {
+                    self.static_str
+                        .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
+                    self.push_lit_char_escaped(lit_char);
+                    self.static_str.push('"');
+                }
+                AttributeValue::LitInt(lit_int) => {
+                    self.static_str
+                        .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
+                    self.push_lit_int(lit_int);
+                    self.static_str.push('"');
+                }
+                AttributeValue::LitFloat(lit_float) => {
+                    self.static_str
+                        .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
+                    self.push_lit_float(lit_float);
+                    self.static_str.push('"');
+                }
+                AttributeValue::LitBool(lit_bool) => {
+                    if lit_bool.value {
+                        self.static_str
+                            .push_str(&format!(" {}", name_value_attribute.name.value()));
                     }
-                    (true, None) => {}
-                    (true, Some(value)) => match value {
-                        AttributeValue::LitStr(lit_str) => {
+                }
+                AttributeValue::Escaped(expr) => match &expr {
+                    Expr::Lit(expr_lit) => match &expr_lit.lit {
+                        Lit::Str(lit_str) => {
                             self.static_str
                                 .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
                             self.push_lit_str_escaped(lit_str);
                             self.static_str.push('"');
                         }
-                        AttributeValue::LitChar(lit_char) => {
+                        Lit::Char(lit_char) => {
                             self.static_str
                                 .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
                             self.push_lit_char_escaped(lit_char);
                             self.static_str.push('"');
                         }
-                        AttributeValue::LitInt(lit_int) => {
+                        Lit::Int(lit_int) => {
                             self.static_str
                                 .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
                             self.push_lit_int(lit_int);
                             self.static_str.push('"');
                         }
-                        AttributeValue::LitFloat(lit_float) => {
+                        Lit::Float(lit_float) => {
                             self.static_str
                                 .push_str(&format!(" {}=\"", name_value_attribute.name.value()));
                             self.push_lit_float(lit_float);
                             self.static_str.push('"');
                         }
-                        AttributeValue::LitBool(lit_bool) => {
+                        Lit::Bool(lit_bool) => {
                             if lit_bool.value {
                                 self.static_str
                                     .push_str(&format!(" {}", name_value_attribute.name.value()));
                             }
                         }
-                        AttributeValue::Escaped(expr) => match &expr {
-                            Expr::Lit(expr_lit) => match &expr_lit.lit {
-                                Lit::Str(lit_str) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"",
-                                        name_value_attribute.name.value()
-                                    ));
-                                    self.push_lit_str_escaped(lit_str);
-                                    self.static_str.push('"');
-                                }
-                                Lit::Char(lit_char) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"",
-                                        name_value_attribute.name.value()
-                                    ));
-                                    self.push_lit_char_escaped(lit_char);
-                                    self.static_str.push('"');
-                                }
-                                Lit::Int(lit_int) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"",
-                                        name_value_attribute.name.value()
-                                    ));
-                                    self.push_lit_int(lit_int);
-                                    self.static_str.push('"');
-                                }
-                                Lit::Float(lit_float) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"",
-                                        name_value_attribute.name.value()
-                                    ));
-                                    self.push_lit_float(lit_float);
-                                    self.static_str.push('"');
-                                }
-                                Lit::Bool(lit_bool) => {
-                                    if lit_bool.value {
-                                        self.static_str.push_str(&format!(
-                                            " {}",
-                                            name_value_attribute.name.value()
-                                        ));
-                                    }
-                                }
-                                _ => {
-                                    self.flush_static_str();
-
-                                    let writer = &self.writer;
-                                    let name = &name_value_attribute.name;
-
-                                    self.token_stream.extend(quote! {
-                                        ::plait::RenderMaybeAttributeEscaped::render_maybe_attribute_escaped(&#expr, #name, #writer)?;
-                                    });
-
-                                    self.has_dynamic_value = true;
-                                }
-                            },
-                            _ => {
-                                self.flush_static_str();
-
-                                let writer = &self.writer;
-                                let name = &name_value_attribute.name;
-
-                                self.token_stream.extend(quote! {
-                                    ::plait::RenderMaybeAttributeEscaped::render_maybe_attribute_escaped(&#expr, #name, #writer)?;
-                                });
-
-                                self.has_dynamic_value = true;
-                            }
-                        },
-                        AttributeValue::Raw(expr) => match &expr {
-                            Expr::Lit(expr_lit) => match &expr_lit.lit {
-                                Lit::Str(lit_str) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"{}\"",
-                                        name_value_attribute.name.value(),
-                                        lit_str.value()
-                                    ));
-                                }
-                                Lit::Char(lit_char) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"{}\"",
-                                        name_value_attribute.name.value(),
-                                        lit_char.value()
-                                    ));
-                                }
-                                Lit::Int(lit_int) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"{}\"",
-                                        name_value_attribute.name.value(),
-                                        lit_int.base10_digits()
-                                    ));
-                                }
-                                Lit::Float(lit_float) => {
-                                    self.static_str.push_str(&format!(
-                                        " {}=\"{}\"",
-                                        name_value_attribute.name.value(),
-                                        lit_float.base10_digits()
-                                    ));
-                                }
-                                Lit::Bool(lit_bool) => {
-                                    if lit_bool.value {
-                                        self.static_str.push_str(&format!(
-                                            " {}",
-                                            name_value_attribute.name.value()
-                                        ));
-                                    }
-                                }
-                                _ => {
-                                    self.flush_static_str();
-
-                                    let writer = &self.writer;
-                                    let name = &name_value_attribute.name;
-
-                                    self.token_stream.extend(quote! {
-                                        ::plait::RenderMaybeAttributeRaw::render_maybe_attribute_raw(&#expr, #name, #writer)?;
-                                    });
-
-                                    self.has_dynamic_value = true;
-                                }
-                            },
-                            _ => {
-                                self.flush_static_str();
-
-                                let writer = &self.writer;
-                                let name = &name_value_attribute.name;
-
-                                self.token_stream.extend(quote! {
-                                    ::plait::RenderMaybeAttributeRaw::render_maybe_attribute_raw(&#expr, #name, #writer)?;
-                                });
-
-                                self.has_dynamic_value = true;
+                        _ => {
+                            self.flush_static_str();
+
+                            let writer = self.value_writer();
+                            let name = attribute_name(&name_value_attribute.name);
+
+                            self.token_stream.extend(quote! {
+                                ::plait::RenderMaybeAttributeEscaped::render_maybe_attribute_escaped(&#expr, #name, #writer)?;
+                            });
+
+                            self.has_dynamic_value = true;
+                        }
+                    },
+                    _ => {
+                        self.flush_static_str();
+
+                        let writer = self.value_writer();
+                        let name = attribute_name(&name_value_attribute.name);
+
+                        self.token_stream.extend(quote! {
+                            ::plait::RenderMaybeAttributeEscaped::render_maybe_attribute_escaped(&#expr, #name, #writer)?;
+                        });
+
+                        self.has_dynamic_value = true;
+                    }
+                },
+                AttributeValue::Raw(expr) => match &expr {
+                    Expr::Lit(expr_lit) => match &expr_lit.lit {
+                        Lit::Str(lit_str) => {
+                            self.static_str.push_str(&format!(
+                                " {}=\"{}\"",
+                                name_value_attribute.name.value(),
+                                lit_str.value()
+                            ));
+                        }
+                        Lit::Char(lit_char) => {
+                            self.static_str.push_str(&format!(
+                                " {}=\"{}\"",
+                                name_value_attribute.name.value(),
+                                lit_char.value()
+                            ));
+                        }
+                        Lit::Int(lit_int) => {
+                            self.static_str.push_str(&format!(
+                                " {}=\"{}\"",
+                                name_value_attribute.name.value(),
+                                lit_int.base10_digits()
+                            ));
+                        }
+                        Lit::Float(lit_float) => {
+                            self.static_str.push_str(&format!(
+                                " {}=\"{}\"",
+                                name_value_attribute.name.value(),
+                                lit_float.base10_digits()
+                            ));
+                        }
+                        Lit::Bool(lit_bool) => {
+                            if lit_bool.value {
+                                self.static_str
+                                    .push_str(&format!(" {}", name_value_attribute.name.value()));
                             }
-                        },
+                        }
+                        _ => {
+                            self.flush_static_str();
+
+                            let writer = &self.writer;
+                            let name = attribute_name(&name_value_attribute.name);
+
+                            self.token_stream.extend(quote! {
+                                ::plait::RenderMaybeAttributeRaw::render_maybe_attribute_raw(&#expr, #name, #writer)?;
+                            });
+
+                            self.has_dynamic_value = true;
+                        }
                     },
-                }
-            }
+                    _ => {
+                        self.flush_static_str();
+
+                        let writer = &self.writer;
+                        let name = attribute_name(&name_value_attribute.name);
+
+                        self.token_stream.extend(quote! {
+                            ::plait::RenderMaybeAttributeRaw::render_maybe_attribute_raw(&#expr, #name, #writer)?;
+                        });
+
+                        self.has_dynamic_value = true;
+                    }
+                },
+            },
         }
     }
 
@@ -700,14 +1360,63 @@ impl InnerBuffer {
 
         let ident = &self.writer;
         let static_str = &self.static_str;
-        self.token_stream.extend(quote! {
-            ::core::fmt::Write::write_str(#ident, #static_str)?;
-        });
+
+        if static_str.is_ascii() {
+            self.token_stream.extend(quote! {
+                ::core::fmt::Write::write_str(#ident, #static_str)?;
+            });
+        } else {
+            // Non-ASCII text depends on the charset of the render
+            self.token_stream.extend(quote! {
+                ::plait::charset::write_static(#ident, #static_str)?;
+            });
+        }
+
         self.size_hint += static_str.len();
         self.static_str.clear();
     }
 
     fn create_inner(&self) -> Self {
-        Self::new(self.writer.clone())
+        self.create_inner_with(self.writer.clone())
+    }
+
+    /// Creates a buffer writing into `writer`, rendering in the same context as this one.
+    fn create_inner_with(&self, writer: Ident) -> Self {
+        let mut inner = Self::new(writer);
+        inner.namespace = self.namespace;
+        inner.raw_text = self.raw_text;
+        inner.iter_props = self.iter_props.clone();
+        inner.child_units = self.child_units;
+        inner
+    }
+}
+
+fn is_no_xmlns(attribute: &Attribute) -> bool {
+    matches!(
+        attribute,
+        Attribute::NameValue(attribute)
+            if !attribute.is_maybe && attribute.value.is_none() && attribute.name.value() == "no-xmlns"
+    )
+}
+
+fn is_auto_id(attribute: &Attribute) -> bool {
+    matches!(
+        attribute,
+        Attribute::NameValue(attribute)
+            if !attribute.is_maybe && attribute.value.is_none() && attribute.name.value() == "auto-id"
+    )
+}
+
+/// Returns the expression of an escaped attribute value that is only known at runtime.
+fn dynamic_escaped_value(name_value_attribute: &NameValueAttribute) -> Option<&Expr> {
+    match &name_value_attribute.value {
+        Some(AttributeValue::Escaped(expr)) => match expr {
+            Expr::Lit(expr_lit) => match &expr_lit.lit {
+                Lit::Str(_) | Lit::Char(_) | Lit::Int(_) | Lit::Float(_) | Lit::Bool(_) => None,
+                _ => Some(expr),
+            },
+            _ => Some(expr),
+        },
+        _ => None,
     }
 }
@@ -1,5 +1,24 @@
+mod asset;
+mod async_component;
 mod component;
+mod component_alias;
+mod component_example;
+mod component_test;
 mod desugar;
+mod form_model;
 mod html;
+mod options;
+mod url;
 
-pub use self::{component::component_impl, html::html_impl};
+pub use self::{
+    asset::asset_impl,
+    async_component::async_component_impl,
+    component::component_impl,
+    component_alias::component_alias_impl,
+    component_example::component_example_impl,
+    component_test::component_test_impl,
+    form_model::form_model_impl,
+    html::{html_impl, html_to_impl},
+    options::options_impl,
+    url::url_impl,
+};
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+pub fn asset_impl(input: TokenStream) -> TokenStream {
+    let path: LitStr = match syn::parse2(input) {
+        Ok(path) => path,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let relative_path = path.value();
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = PathBuf::from(manifest_dir).join(&relative_path);
+
+    let content = match std::fs::read_to_string(&full_path) {
+        Ok(content) => content,
+        Err(e) => {
+            return syn::Error::new(
+                path.span(),
+                format!("failed to read asset `{}`: {e}", full_path.display()),
+            )
+            .to_compile_error();
+        }
+    };
+
+    let hash = format!("{:016x}", fnv1a_64(content.as_bytes()));
+    let full_path = full_path.to_string_lossy();
+
+    quote! {
+        ::plait::Asset::new_unchecked(
+            #relative_path,
+            ::core::include_str!(#full_path),
+            #hash,
+        )
+    }
+}
+
+/// 64-bit FNV-1a hash, used to derive cache-busting content hashes for assets.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a_64() {
+        assert_eq!(fnv1a_64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x85944171f73967e8);
+    }
+}
@@ -0,0 +1,155 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+use crate::{
+    ast::{ComponentDefinition, ComponentDefinitions, LetBinding, Node},
+    buffer::InnerBuffer,
+    codegen::{
+        component::{component_struct, component_struct_deconstruct, iter_props},
+        desugar::desugar_fields,
+    },
+    known_attributes::check_attributes,
+    nesting::check_nesting,
+};
+
+pub fn async_component_impl(input: TokenStream) -> TokenStream {
+    let ComponentDefinitions { definitions } = match syn::parse2(input) {
+        Ok(a) => a,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let mut items = TokenStream::new();
+
+    for mut definition in definitions {
+        if let Some(module) = definition.module.first() {
+            return syn::Error::new(
+                module.span(),
+                "async components can't be generated into modules",
+            )
+            .to_compile_error();
+        }
+
+        if let Err(e) = check_nesting(&definition.body) {
+            return e.to_compile_error();
+        }
+
+        if let Err(e) = check_attributes(&definition.body) {
+            return e.to_compile_error();
+        }
+
+        let iter_props = iter_props(&definition);
+        let bound_params = desugar_fields(&mut definition.fields, &mut definition.generics);
+
+        items.extend(component_struct(&definition, &bound_params));
+        items.extend(render_async_impl(&definition, &bound_params, iter_props));
+    }
+
+    items
+}
+
+/// Returns the `let` bindings at the start of the body, which are evaluated in the generated `async` block so that they
+/// can `.await`, and the nodes rendered after them.
+fn split_loads(body: &[Node]) -> (Vec<&LetBinding>, &[Node]) {
+    let loads: Vec<&LetBinding> = body
+        .iter()
+        .map_while(|node| match node {
+            Node::LetBinding(let_binding)
+                if let_binding.expr.is_some() && let_binding.else_branch.is_none() =>
+            {
+                Some(let_binding)
+            }
+            _ => None,
+        })
+        .collect();
+
+    let rest = &body[loads.len()..];
+    (loads, rest)
+}
+
+fn render_async_impl(
+    component: &ComponentDefinition,
+    bound_params: &[Ident],
+    iter_props: Vec<Ident>,
+) -> TokenStream {
+    let ident = &component.ident;
+    let (impl_generics, type_generics, where_clause) = component.generics.split_for_impl();
+
+    let deconstruct = component_struct_deconstruct(component, bound_params);
+
+    let (loads, rest) = split_loads(&component.body);
+    let loads = loads.iter().map(|load| {
+        let pattern = &load.pattern;
+        let expr = &load.expr;
+        quote! { let #pattern = #expr; }
+    });
+
+    let writer = Ident::new("__plait_component", component.ident.span());
+
+    let mut buffer = InnerBuffer::new(writer.clone());
+    buffer.iter_props = iter_props;
+    buffer.push_block(rest);
+    buffer.flush_static_str();
+
+    let statements = buffer.token_stream;
+
+    quote! {
+        #[allow(deprecated)]
+        impl #impl_generics ::plait::RenderAsync for #ident #type_generics #where_clause {
+            fn render_async(&self) -> impl ::core::future::Future<Output = ::plait::Html> {
+                ::plait::ids::scope(async move {
+                    #deconstruct
+                    #(#loads)*
+
+                    ::plait::ToHtml::to_html(&::plait::HtmlFragment::new(
+                        |#writer: &mut (dyn ::core::fmt::Write + '_)| {
+                            #statements
+
+                            Ok(())
+                        },
+                        0,
+                    ))
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn test_split_loads() {
+        let ComponentDefinitions { definitions } = syn::parse2(quote! {
+            fn Profile(id: u64) {
+                let user = load_user(*id).await;
+                let posts = load_posts(*id).await;
+                h1 { (user.name) }
+                let count = posts.len();
+                p { (count) }
+            }
+        })
+        .unwrap();
+
+        let (loads, rest) = split_loads(&definitions[0].body);
+
+        assert_eq!(loads.len(), 2);
+        assert_eq!(rest.len(), 3);
+    }
+
+    #[test]
+    fn test_modules_are_rejected() {
+        let output = async_component_impl(quote! {
+            pub fn ui::Profile(id: u64) { "profile" }
+        });
+
+        assert!(
+            output
+                .to_string()
+                .contains("async components can't be generated into modules")
+        );
+    }
+}
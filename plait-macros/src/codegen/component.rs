@@ -1,30 +1,232 @@
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Ident;
+use quote::{format_ident, quote};
+use syn::{GenericParam, Generics, Ident, Type, TypeParamBound, Visibility, parse_quote};
 
-use crate::{ast::ComponentDefinition, buffer::InnerBuffer, codegen::desugar::desugar_fields};
+use crate::{
+    ast::{ComponentDefinition, ComponentDefinitions},
+    buffer::InnerBuffer,
+    codegen::desugar::desugar_fields,
+    known_attributes::check_attributes,
+    nesting::check_nesting,
+};
 
 pub fn component_impl(input: TokenStream) -> TokenStream {
-    let mut component_definition: ComponentDefinition = match syn::parse2(input) {
+    let ComponentDefinitions { definitions } = match syn::parse2(input) {
         Ok(a) => a,
         Err(e) => return e.to_compile_error(),
     };
 
-    desugar_fields(
-        &mut component_definition.fields,
-        &mut component_definition.generics,
-    );
+    if let Err(e) = check_duplicates(&definitions) {
+        return e.to_compile_error();
+    }
 
-    let component_struct = component_struct(&component_definition);
-    let component_component_impl = component_component_impl(&component_definition);
+    for definition in &definitions {
+        if let Err(e) = check_nesting(&definition.body) {
+            return e.to_compile_error();
+        }
 
-    quote! {
-        #component_struct
-        #component_component_impl
+        if let Err(e) = check_attributes(&definition.body) {
+            return e.to_compile_error();
+        }
+    }
+
+    let mut root = Module::default();
+
+    for mut component_definition in definitions {
+        let visibility = component_definition.visibility.clone();
+        component_definition.visibility =
+            nested_visibility(&visibility, component_definition.module.len());
+
+        let iter_props = iter_props(&component_definition);
+
+        let bound_params = desugar_fields(
+            &mut component_definition.fields,
+            &mut component_definition.generics,
+        );
+
+        let component_struct = component_struct(&component_definition, &bound_params);
+        let component_props_builder = component_props_builder(&component_definition, &bound_params);
+        let component_component_impl =
+            component_component_impl(&component_definition, &bound_params, iter_props);
+
+        root.module(&component_definition.module, &visibility)
+            .items
+            .extend(quote! {
+                #component_struct
+                #component_props_builder
+                #component_component_impl
+            });
+    }
+
+    root.into_items(0)
+}
+
+/// Adjusts the visibility of an item generated `depth` modules below the `component!` invocation, so that private and
+/// `pub(super)` items stay visible where they would be without the modules.
+fn nested_visibility(visibility: &Visibility, depth: usize) -> Visibility {
+    if depth == 0 {
+        return visibility.clone();
+    }
+
+    let depth = match visibility {
+        Visibility::Inherited => depth,
+        Visibility::Restricted(restricted) if restricted.in_token.is_none() => {
+            if restricted.path.is_ident("self") {
+                depth
+            } else if restricted.path.is_ident("super") {
+                depth + 1
+            } else {
+                return visibility.clone();
+            }
+        }
+        _ => return visibility.clone(),
+    };
+
+    let supers = std::iter::repeat_n(quote! { super }, depth);
+    syn::parse_quote! { pub(in #(#supers)::*) }
+}
+
+/// Reports components that are defined more than once in the same `component!` invocation, instead of leaving it to
+/// the duplicate definition errors of the generated code.
+fn check_duplicates(definitions: &[ComponentDefinition]) -> syn::Result<()> {
+    for (index, definition) in definitions.iter().enumerate() {
+        let previous = definitions[..index].iter().find(|previous| {
+            previous.ident == definition.ident && previous.module == definition.module
+        });
+
+        if let Some(previous) = previous {
+            let mut error = syn::Error::new(
+                definition.ident.span(),
+                format!(
+                    "component `{}` is defined multiple times",
+                    component_path(definition)
+                ),
+            );
+            error.combine(syn::Error::new(
+                previous.ident.span(),
+                format!(
+                    "previous definition of component `{}` here",
+                    component_path(previous)
+                ),
+            ));
+
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+fn component_path(definition: &ComponentDefinition) -> String {
+    definition
+        .module
+        .iter()
+        .chain(std::iter::once(&definition.ident))
+        .map(Ident::to_string)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Generated items, grouped by the module they are generated into.
+///
+/// Each module is declared as a new `mod` by the invocation, since a macro can't add items to a module declared
+/// elsewhere. Two invocations generating into the same module therefore conflict.
+#[derive(Default)]
+struct Module {
+    visibility: Option<Visibility>,
+    items: TokenStream,
+    modules: Vec<(Ident, Module)>,
+}
+
+impl Module {
+    /// Returns the (nested) submodule at `path`, creating it if it doesn't exist yet.
+    ///
+    /// A module gets the visibility of the first component generated into it, or `pub` if any of its components is
+    /// `pub`.
+    fn module(&mut self, path: &[Ident], visibility: &Visibility) -> &mut Module {
+        let Some((ident, rest)) = path.split_first() else {
+            return self;
+        };
+
+        let index = match self.modules.iter().position(|(name, _)| name == ident) {
+            Some(index) => index,
+            None => {
+                self.modules.push((ident.clone(), Module::default()));
+                self.modules.len() - 1
+            }
+        };
+
+        let module = &mut self.modules[index].1;
+
+        if module.visibility.is_none() || matches!(visibility, Visibility::Public(_)) {
+            module.visibility = Some(visibility.clone());
+        }
+
+        module.module(rest, visibility)
+    }
+
+    /// Returns the items of a module `depth` modules below the `component!` invocation, followed by its submodules.
+    fn into_items(self, depth: usize) -> TokenStream {
+        let items = self.items;
+        let modules = self.modules.into_iter().map(|(ident, module)| {
+            let visibility = module
+                .visibility
+                .as_ref()
+                .map(|visibility| nested_visibility(visibility, depth));
+            let items = module.into_items(depth + 1);
+
+            quote! {
+                #visibility mod #ident {
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    #items
+                }
+            }
+        });
+
+        quote! {
+            #items
+            #(#modules)*
+        }
     }
 }
 
-fn component_struct(component: &ComponentDefinition) -> TokenStream {
+/// Returns the props declared as `impl RenderIter<..>`, which `for` loops in the body iterate with `render_iter()`.
+pub(super) fn iter_props(component: &ComponentDefinition) -> Vec<Ident> {
+    component
+        .fields
+        .iter()
+        .filter(|field| match &field.ty {
+            Type::ImplTrait(impl_trait) => impl_trait.bounds.iter().any(|bound| {
+                matches!(
+                    bound,
+                    TypeParamBound::Trait(bound)
+                        if bound.path.segments.last().is_some_and(|segment| segment.ident == "RenderIter")
+                )
+            }),
+            _ => false,
+        })
+        .map(|field| field.ident.clone())
+        .collect()
+}
+
+/// Returns the field marking the type parameters only used in the bounds of other parameters (`P1` in
+/// `render_row: impl Fn(&Row) -> impl RenderEscaped`) as used, if there are any.
+fn bound_params_field(bound_params: &[Ident]) -> Option<TokenStream> {
+    if bound_params.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        __plait_bounds: ::core::marker::PhantomData<fn() -> (#(#bound_params,)*)>
+    })
+}
+
+pub(super) fn component_struct(
+    component: &ComponentDefinition,
+    bound_params: &[Ident],
+) -> TokenStream {
     let attributes = &component.attributes;
     let visibility = &component.visibility;
     let name = &component.ident;
@@ -32,14 +234,18 @@ fn component_struct(component: &ComponentDefinition) -> TokenStream {
     let mut field_statements = Vec::new();
 
     for field in component.fields.iter() {
+        let field_attributes = &field.attributes;
         let field_name = &field.ident;
         let field_type = &field.ty;
 
         field_statements.push(quote! {
+            #(#field_attributes)*
             pub #field_name: #field_type
         });
     }
 
+    field_statements.extend(bound_params_field(bound_params));
+
     let generics = &component.generics;
     let where_clause = &generics.where_clause;
 
@@ -53,21 +259,195 @@ fn component_struct(component: &ComponentDefinition) -> TokenStream {
     out
 }
 
-fn component_component_impl(component: &ComponentDefinition) -> TokenStream {
+/// Returns the arguments naming the parameters of `generics`, e.g. `'plait_0, P0` for `<'plait_0, P0: Class>`.
+fn generic_arguments(generics: &Generics) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => {
+                let lifetime = &param.lifetime;
+                quote! { #lifetime }
+            }
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                quote! { #ident }
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                quote! { #ident }
+            }
+        })
+        .collect()
+}
+
+/// Generates the builder component calls set props through (`__ButtonProps` for `Button`), and the
+/// `Button::__plait_props()` function creating it.
+///
+/// Whether each required prop is set is tracked in a type parameter of the builder, starting as
+/// `::plait::MissingProp`, so that `__plait_build()` only compiles once all of them are set. Optional
+/// props start as `None`.
+fn component_props_builder(component: &ComponentDefinition, bound_params: &[Ident]) -> TokenStream {
+    let visibility = &component.visibility;
+    let ident = &component.ident;
+    let builder = format_ident!("__{}Props", ident);
+
+    let (component_impl_generics, component_type_generics, component_where_clause) =
+        component.generics.split_for_impl();
+    let component_arguments = generic_arguments(&component.generics);
+
+    let mut states = Vec::new();
+    let mut builder_generics = component.generics.clone();
+
+    for field in component.fields.iter().filter(|field| !field.optional) {
+        let state = format_ident!("PlaitProp{}", states.len());
+        builder_generics.params.push(parse_quote! { #state });
+        states.push((field, state));
+    }
+
+    let (impl_generics, type_generics, where_clause) = builder_generics.split_for_impl();
+
+    let mut builder_fields = Vec::new();
+    let mut initial_fields = Vec::new();
+    let mut setters = Vec::new();
+    let mut build_fields = Vec::new();
+    let mut build_bounds = Vec::new();
+
+    for field in &component.fields {
+        let field_name = &field.ident;
+        let field_type = &field.ty;
+
+        let deprecations = field
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.path().is_ident("deprecated"));
+
+        if field.optional {
+            builder_fields.push(quote! { #field_name: #field_type });
+            initial_fields.push(quote! { #field_name: ::core::option::Option::None });
+            build_fields.push(quote! { #field_name: self.#field_name });
+
+            setters.push(quote! {
+                #(#deprecations)*
+                pub fn #field_name(mut self, #field_name: #field_type) -> Self {
+                    self.#field_name = #field_name;
+                    self
+                }
+            });
+
+            continue;
+        }
+
+        let Some((_, state)) = states
+            .iter()
+            .find(|(required, _)| required.ident == *field_name)
+        else {
+            unreachable!("every required prop has a state");
+        };
+
+        let set_states = states.iter().map(|(_, other)| {
+            if other == state {
+                quote! { ::plait::SetProp<#field_type> }
+            } else {
+                quote! { #other }
+            }
+        });
+
+        let other_fields = component
+            .fields
+            .iter()
+            .filter(|other| other.ident != *field_name)
+            .map(|other| {
+                let other_name = &other.ident;
+                quote! { #other_name: self.#other_name }
+            });
+
+        builder_fields.push(quote! { #field_name: #state });
+        initial_fields.push(quote! { #field_name: ::plait::MissingProp });
+        build_fields.push(quote! {
+            #field_name: ::plait::RequiredProp::into_prop(self.#field_name)
+        });
+        build_bounds.push(quote! { #state: ::plait::RequiredProp<#field_type> });
+
+        setters.push(quote! {
+            #(#deprecations)*
+            pub fn #field_name(
+                self,
+                #field_name: #field_type,
+            ) -> #builder<#(#component_arguments,)* #(#set_states),*> {
+                #builder {
+                    #field_name: ::plait::SetProp(#field_name),
+                    #(#other_fields,)*
+                    __plait_component: ::core::marker::PhantomData,
+                }
+            }
+        });
+    }
+
+    if bound_params_field(bound_params).is_some() {
+        build_fields.push(quote! { __plait_bounds: ::core::marker::PhantomData });
+    }
+
+    let missing_states = states.iter().map(|_| quote! { ::plait::MissingProp });
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(deprecated)]
+        #visibility struct #builder #impl_generics #where_clause {
+            #(#builder_fields,)*
+            __plait_component: ::core::marker::PhantomData<fn() -> #ident #component_type_generics>,
+        }
+
+        #[allow(deprecated)]
+        impl #component_impl_generics #ident #component_type_generics #component_where_clause {
+            #[doc(hidden)]
+            pub fn __plait_props() -> #builder<#(#component_arguments,)* #(#missing_states),*> {
+                #builder {
+                    #(#initial_fields,)*
+                    __plait_component: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        #[allow(deprecated)]
+        impl #impl_generics #builder #type_generics #where_clause {
+            #(#setters)*
+
+            #[doc(hidden)]
+            pub fn __plait_build(self) -> #ident #component_type_generics
+            where
+                #(#build_bounds),*
+            {
+                #ident {
+                    #(#build_fields),*
+                }
+            }
+        }
+    }
+}
+
+fn component_component_impl(
+    component: &ComponentDefinition,
+    bound_params: &[Ident],
+    iter_props: Vec<Ident>,
+) -> TokenStream {
     let ident = &component.ident;
     let (impl_generics, type_generics, where_clause) = component.generics.split_for_impl();
 
-    let deconstruct = component_struct_deconstruct(component);
+    let deconstruct = component_struct_deconstruct(component, bound_params);
 
     let writer = Ident::new("__plait_component", component.ident.span());
 
     let mut buffer = InnerBuffer::new(writer.clone());
+    buffer.iter_props = iter_props;
     buffer.push_block(&component.body);
     buffer.flush_static_str();
 
     let statements = buffer.token_stream;
 
+    // Deprecation warnings belong at call sites, not in the generated implementation
     quote! {
+        #[allow(deprecated)]
         impl #impl_generics ::plait::Component for #ident #type_generics #where_clause {
             fn render_component(
                 &self,
@@ -84,7 +464,10 @@ fn component_component_impl(component: &ComponentDefinition) -> TokenStream {
     }
 }
 
-fn component_struct_deconstruct(component: &ComponentDefinition) -> TokenStream {
+pub(super) fn component_struct_deconstruct(
+    component: &ComponentDefinition,
+    bound_params: &[Ident],
+) -> TokenStream {
     if component.fields.is_empty() {
         return quote! {};
     }
@@ -98,9 +481,80 @@ fn component_struct_deconstruct(component: &ComponentDefinition) -> TokenStream
         });
     }
 
+    if !bound_params.is_empty() {
+        fields.push(quote! { .. });
+    }
+
     let ident = &component.ident;
 
     quote! {
         let #ident { #(#fields),* } = self;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use quote::{ToTokens, quote};
+
+    use super::*;
+
+    fn definitions(input: TokenStream) -> Vec<ComponentDefinition> {
+        syn::parse2::<ComponentDefinitions>(input)
+            .unwrap()
+            .definitions
+    }
+
+    #[test]
+    fn test_duplicate_components() {
+        let error = check_duplicates(&definitions(quote! {
+            pub fn Button() { "a" }
+            pub fn ui::Button() { "b" }
+            pub fn Button() { "c" }
+        }))
+        .unwrap_err();
+
+        let messages: Vec<String> = error.into_iter().map(|error| error.to_string()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "component `Button` is defined multiple times",
+                "previous definition of component `Button` here",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_name_in_different_modules() {
+        assert!(
+            check_duplicates(&definitions(quote! {
+                pub fn Button() { "a" }
+                pub fn ui::Button() { "b" }
+                pub fn ui::forms::Button() { "c" }
+            }))
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_nested_visibility() {
+        let visibility = |visibility: TokenStream, depth| {
+            nested_visibility(&syn::parse2(visibility).unwrap(), depth)
+                .to_token_stream()
+                .to_string()
+        };
+
+        assert_eq!(visibility(quote! {}, 0), "");
+        assert_eq!(visibility(quote! {}, 1), "pub (in super)");
+        assert_eq!(
+            visibility(quote! { pub(self) }, 2),
+            "pub (in super :: super)"
+        );
+        assert_eq!(
+            visibility(quote! { pub(super) }, 1),
+            "pub (in super :: super)"
+        );
+        assert_eq!(visibility(quote! { pub(crate) }, 1), "pub (crate)");
+        assert_eq!(visibility(quote! { pub }, 3), "pub");
+    }
+}
@@ -0,0 +1,162 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{
+    Attribute, GenericParam, Generics, Ident, Path, Visibility,
+    parse::{Parse, ParseStream},
+    token::{Eq, Semi},
+};
+
+struct ComponentAlias {
+    attributes: Vec<Attribute>,
+    visibility: Visibility,
+    ident: Ident,
+    generics: Generics,
+    target: Path,
+}
+
+impl Parse for ComponentAlias {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+        let visibility = input.parse()?;
+        let ident = input.parse()?;
+        let generics: Generics = input.parse()?;
+
+        if let Some(where_clause) = &generics.where_clause {
+            return Err(syn::Error::new_spanned(
+                where_clause,
+                "component aliases can't have a where clause",
+            ));
+        }
+
+        let _: Eq = input.parse()?;
+        let target: Path = input.parse()?;
+
+        if input.peek(Semi) {
+            let _: Semi = input.parse()?;
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("expected `OldName = NewName`"));
+        }
+
+        if target
+            .segments
+            .iter()
+            .any(|segment| !segment.arguments.is_empty())
+        {
+            return Err(syn::Error::new_spanned(
+                &target,
+                "the generic parameters of a component alias are written after its name, e.g. `OldCard<'a> = Card`",
+            ));
+        }
+
+        Ok(Self {
+            attributes,
+            visibility,
+            ident,
+            generics,
+            target,
+        })
+    }
+}
+
+pub fn component_alias_impl(input: TokenStream) -> TokenStream {
+    let alias: ComponentAlias = match syn::parse2(input) {
+        Ok(alias) => alias,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let ComponentAlias {
+        attributes,
+        visibility,
+        ident,
+        generics,
+        target,
+    } = alias;
+
+    let deprecation = if attributes
+        .iter()
+        .any(|attribute| attribute.path().is_ident("deprecated"))
+    {
+        None
+    } else {
+        let target_name = target
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
+        let note = format!("`{ident}` has been renamed to `{target_name}`");
+
+        Some(quote! { #[deprecated(note = #note)] })
+    };
+
+    // Bounds aren't enforced on type aliases, the target component checks them
+    let params = generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+        GenericParam::Type(param) => param.ident.to_token_stream(),
+        GenericParam::Const(param) => {
+            let ident = &param.ident;
+            let ty = &param.ty;
+            quote! { const #ident: #ty }
+        }
+    });
+
+    let arguments = generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+        GenericParam::Type(param) => param.ident.to_token_stream(),
+        GenericParam::Const(param) => param.ident.to_token_stream(),
+    });
+
+    let (params, arguments) = if generics.params.is_empty() {
+        (None, None)
+    } else {
+        (
+            Some(quote! { <#(#params),*> }),
+            Some(quote! { <#(#arguments),*> }),
+        )
+    };
+
+    quote! {
+        #(#attributes)*
+        #deprecation
+        #visibility type #ident #params = #target #arguments;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn test_component_alias_generics() {
+        let output = component_alias_impl(quote! { pub OldCard<'a, T: Class> = ui::Card });
+
+        assert_eq!(
+            output.to_string(),
+            quote! {
+                #[deprecated(note = "`OldCard` has been renamed to `Card`")]
+                pub type OldCard<'a, T> = ui::Card<'a, T>;
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_component_alias_keeps_deprecation() {
+        let output = component_alias_impl(quote! {
+            #[deprecated(note = "use `Card` instead")]
+            OldCard = Card
+        });
+
+        assert_eq!(
+            output.to_string(),
+            quote! {
+                #[deprecated(note = "use `Card` instead")]
+                type OldCard = Card;
+            }
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ItemFn, LitStr, Path,
+    parse::{Parse, ParseStream},
+    token::Comma,
+};
+
+struct ComponentExampleArgs {
+    component: Path,
+    name: Option<LitStr>,
+}
+
+impl Parse for ComponentExampleArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let component = input.parse()?;
+
+        let name = if input.peek(Comma) {
+            let _ = input.parse::<Comma>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        if !input.is_empty() {
+            return Err(input.error("expected `Component` or `Component, \"example name\"`"));
+        }
+
+        Ok(Self { component, name })
+    }
+}
+
+pub fn component_example_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args: ComponentExampleArgs = match syn::parse2(args) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let item: ItemFn = match syn::parse2(input) {
+        Ok(item) => item,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    if !item.sig.inputs.is_empty() || !item.sig.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &item.sig,
+            "component examples must be functions without arguments or generics",
+        )
+        .to_compile_error();
+    }
+
+    let fn_ident = &item.sig.ident;
+
+    let component = args
+        .component
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default();
+
+    let name = match args.name {
+        Some(name) => name.value(),
+        None => fn_ident.to_string(),
+    };
+
+    quote! {
+        #item
+
+        ::plait::gallery::inventory::submit! {
+            ::plait::gallery::Example::new(#component, #name, || {
+                ::plait::ToHtml::to_html(&#fn_ident())
+            })
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ItemFn, LitStr, ReturnType};
+
+pub fn component_test_impl(args: TokenStream, input: TokenStream) -> TokenStream {
+    let snapshot: Option<LitStr> = if args.is_empty() {
+        None
+    } else {
+        match syn::parse2(args) {
+            Ok(snapshot) => Some(snapshot),
+            Err(e) => {
+                return syn::Error::new(e.span(), "expected an accessible name snapshot string")
+                    .to_compile_error();
+            }
+        }
+    };
+
+    let item: ItemFn = match syn::parse2(input) {
+        Ok(item) => item,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    if !item.sig.inputs.is_empty()
+        || !item.sig.generics.params.is_empty()
+        || item.sig.asyncness.is_some()
+        || matches!(item.sig.output, ReturnType::Default)
+    {
+        return syn::Error::new_spanned(
+            &item.sig,
+            "component tests must be functions without arguments or generics returning `impl ToHtml`",
+        )
+        .to_compile_error();
+    }
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item;
+
+    let fn_ident = &sig.ident;
+    let output = &sig.output;
+
+    let assert_snapshot = snapshot.map(|snapshot| {
+        quote! {
+            ::plait::testing::assert_accessible_names(&html, #snapshot);
+        }
+    });
+
+    quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #fn_ident() {
+            fn example() #output #block
+
+            let html = ::plait::ToHtml::to_html(&example());
+
+            ::plait::testing::assert_valid_html(&html);
+            #assert_snapshot
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn test_component_test_rejects_arguments() {
+        let output = component_test_impl(
+            quote! {},
+            quote! {
+                fn button(label: &str) -> impl ToHtml {
+                    html! { button { (label) } }
+                }
+            },
+        );
+
+        assert!(output.to_string().contains("compile_error"));
+    }
+
+    #[test]
+    fn test_component_test_rejects_invalid_snapshot() {
+        let output = component_test_impl(
+            quote! { Button },
+            quote! {
+                fn button() -> impl ToHtml {
+                    html! { button { "Save" } }
+                }
+            },
+        );
+
+        assert!(
+            output
+                .to_string()
+                .contains("expected an accessible name snapshot string")
+        );
+    }
+}
@@ -2,6 +2,7 @@ use proc_macro2::Span;
 use syn::{
     GenericParam, Generics, Ident, Lifetime, LifetimeParam, Type, TypeImplTrait, TypeParam,
     TypePath,
+    visit::{self, Visit},
     visit_mut::{self, VisitMut},
 };
 
@@ -14,7 +15,13 @@ use crate::ast::ComponentDefinitionField;
 /// - Every `&str` or `&'_ str` in field types has been replaced with `&'plait_N str`
 /// - Every `impl Trait` in field types has been replaced with a type parameter `P_N`
 /// - The corresponding lifetime and type parameters have been added to `generics`
-pub fn desugar_fields(fields: &mut [ComponentDefinitionField], generics: &mut Generics) {
+///
+/// Returns the parameters of `impl Trait` nested in the bounds of another one (`P1` for
+/// `impl Fn(&Row) -> impl RenderEscaped`), which no field type mentions directly.
+pub fn desugar_fields(
+    fields: &mut [ComponentDefinitionField],
+    generics: &mut Generics,
+) -> Vec<Ident> {
     let mut lifetimes = CollectLifetimes::new();
     let mut impl_traits = CollectImplTraits::new();
 
@@ -41,6 +48,8 @@ pub fn desugar_fields(fields: &mut [ComponentDefinitionField], generics: &mut Ge
     for type_param in impl_traits.type_params {
         generics.params.push(GenericParam::Type(type_param));
     }
+
+    impl_traits.nested
 }
 
 /// Walks field types and replaces anonymous/elided lifetimes with named ones.
@@ -80,6 +89,19 @@ impl CollectLifetimes {
 }
 
 impl VisitMut for CollectLifetimes {
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        arguments: &mut syn::ParenthesizedGenericArguments,
+    ) {
+        // Elided lifetimes in the arguments of `Fn(&T) -> U` are higher-ranked, like in a function
+        // signature, so that the component can call it with borrows of its own
+        if arguments.inputs.iter().any(has_elided_lifetime) {
+            return;
+        }
+
+        visit_mut::visit_parenthesized_generic_arguments_mut(self, arguments);
+    }
+
     fn visit_type_reference_mut(&mut self, ty: &mut syn::TypeReference) {
         self.visit_opt_lifetime(ty.and_token.span, &mut ty.lifetime);
         visit_mut::visit_type_reference_mut(self, ty);
@@ -93,19 +115,45 @@ impl VisitMut for CollectLifetimes {
     }
 }
 
+/// Returns `true` if `ty` contains an anonymous or elided lifetime.
+fn has_elided_lifetime(ty: &Type) -> bool {
+    struct FindElided(bool);
+
+    impl<'ast> Visit<'ast> for FindElided {
+        fn visit_type_reference(&mut self, ty: &'ast syn::TypeReference) {
+            self.0 |= ty.lifetime.is_none();
+            visit::visit_type_reference(self, ty);
+        }
+
+        fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+            self.0 |= lifetime.ident == "_";
+        }
+    }
+
+    let mut find = FindElided(false);
+    find.visit_type(ty);
+    find.0
+}
+
 /// Walks field types and replaces `impl Trait` with named type parameters.
 ///
 /// - `impl ClassPart` becomes `P0` (with `P0: ClassPart` added to generics)
 /// - `impl Display + Debug` becomes `P0` (with `P0: Display + Debug`)
 /// - Each `impl Trait` occurrence gets its own parameter
+/// - `impl Fn(&Row) -> impl Display` becomes `P0` (with `P0: Fn(&Row) -> P1, P1: Display`)
 struct CollectImplTraits {
     type_params: Vec<TypeParam>,
+    /// Parameters of `impl Trait` nested in the bounds of another `impl Trait`.
+    nested: Vec<Ident>,
+    depth: usize,
 }
 
 impl CollectImplTraits {
     fn new() -> Self {
         Self {
             type_params: Vec::new(),
+            nested: Vec::new(),
+            depth: 0,
         }
     }
 
@@ -113,10 +161,21 @@ impl CollectImplTraits {
         let index = self.type_params.len();
         let ident = Ident::new(&format!("P{index}"), impl_trait.impl_token.span);
 
-        let mut type_param = TypeParam::from(ident.clone());
-        type_param.bounds = impl_trait.bounds.clone();
+        self.type_params.push(TypeParam::from(ident.clone()));
 
-        self.type_params.push(type_param);
+        if self.depth > 0 {
+            self.nested.push(ident.clone());
+        }
+
+        let mut bounds = impl_trait.bounds.clone();
+
+        self.depth += 1;
+        for bound in bounds.iter_mut() {
+            self.visit_type_param_bound_mut(bound);
+        }
+        self.depth -= 1;
+
+        self.type_params[index].bounds = bounds;
 
         Type::Path(TypePath {
             qself: None,
@@ -129,7 +188,7 @@ impl VisitMut for CollectImplTraits {
     fn visit_type_mut(&mut self, ty: &mut Type) {
         if let Type::ImplTrait(impl_trait) = ty {
             *ty = self.next_type_param(impl_trait);
-            // No need to recurse into the replacement (it's a simple path)
+            // The bounds are visited by `next_type_param`, and the replacement is a simple path
             return;
         }
 
@@ -161,8 +220,10 @@ mod tests {
                     panic!("expected ident pattern");
                 };
                 fields.push(ComponentDefinitionField {
+                    attributes: Vec::new(),
                     ident,
                     ty: *pat_type.ty,
+                    optional: false,
                 });
             }
         }
@@ -283,8 +344,10 @@ mod tests {
                     panic!("expected ident pattern");
                 };
                 fields.push(ComponentDefinitionField {
+                    attributes: Vec::new(),
                     ident,
                     ty: *pat_type.ty,
+                    optional: false,
                 });
             }
         }
@@ -307,6 +370,35 @@ mod tests {
         assert_eq!(generics_to_string(&generics), "< 'plait_0 >");
     }
 
+    #[test]
+    fn test_nested_impl_trait() {
+        let (fields, generics) =
+            desugar(quote! { render_row: impl Fn(&Row) -> impl RenderEscaped });
+
+        assert_eq!(type_to_string(&fields[0].ty), "P0");
+        assert_eq!(
+            generics_to_string(&generics),
+            "< P0 : Fn (& Row) -> P1 , P1 : RenderEscaped >"
+        );
+    }
+
+    #[test]
+    fn test_fn_lifetimes() {
+        let (fields, generics) = desugar(quote! {
+            a: impl Fn(&str) -> &str,
+            b: impl Fn() -> &str,
+            c: impl Fn(&'a Row) -> impl Display,
+        });
+
+        assert_eq!(type_to_string(&fields[0].ty), "P0");
+        assert_eq!(type_to_string(&fields[1].ty), "P1");
+        assert_eq!(type_to_string(&fields[2].ty), "P2");
+        assert_eq!(
+            generics_to_string(&generics),
+            "< 'plait_0 , P0 : Fn (& str) -> & str , P1 : Fn () -> & 'plait_0 str , P2 : Fn (& 'a Row) -> P3 , P3 : Display >"
+        );
+    }
+
     #[test]
     fn test_no_desugaring_needed() {
         let (fields, generics) = desugar(quote! { x: String, y: u32 });
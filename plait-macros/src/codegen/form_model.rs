@@ -0,0 +1,97 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, ext::IdentExt};
+
+pub fn form_model_impl(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = match syn::parse2(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input.ident,
+                    "`FormModel` can only be derived for structs with named fields",
+                )
+                .to_compile_error();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "`FormModel` can only be derived for structs",
+            )
+            .to_compile_error();
+        }
+    };
+
+    let vis = &input.vis;
+    let mut arms = Vec::with_capacity(fields.len());
+    let mut constants = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+
+        let mut name = LitStr::new(&ident.to_string(), ident.span());
+        let mut skip = false;
+
+        for attribute in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("form"))
+        {
+            let result = attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip` or `rename = \"...\"`"))
+                }
+            });
+
+            if let Err(e) = result {
+                return e.to_compile_error();
+            }
+        }
+
+        if !skip {
+            let constant = Ident::new(&ident.unraw().to_string().to_uppercase(), ident.span());
+
+            arms.push(quote! {
+                #name => ::plait::form::FormValue::form_value(&self.#ident),
+            });
+            constants.push(quote! {
+                #vis const #constant: ::plait::form::Field<Self> = ::plait::form::Field::new(#name);
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #ident #type_generics #where_clause {
+            #(#constants)*
+        }
+
+        impl #impl_generics ::plait::form::FormModel for #ident #type_generics #where_clause {
+            fn field_value(
+                &self,
+                field: &str,
+            ) -> ::core::option::Option<::std::borrow::Cow<'_, str>> {
+                match field {
+                    #(#arms)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,233 @@
 use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Expr, braced,
+    parse::{Parse, ParseStream},
+    token::Comma,
+};
 
-use crate::{ast::Template, buffer::Buffer};
+use crate::{
+    ast::Template, buffer::Buffer, known_attributes::check_attributes, nesting::check_nesting,
+};
+
+struct HtmlToInput {
+    writer: Expr,
+    template: Template,
+}
+
+impl Parse for HtmlToInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let writer = input.parse()?;
+        let _: Comma = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let template = content.parse()?;
+
+        if !input.is_empty() {
+            let _: Comma = input.parse()?;
+        }
+
+        Ok(Self { writer, template })
+    }
+}
+
+/// Returns the errors as a single expression: templates expand to expressions, in which a sequence of `compile_error!`
+/// invocations isn't valid.
+fn compile_errors(error: syn::Error) -> TokenStream {
+    let errors = error.to_compile_error();
+    quote! { { #errors } }
+}
 
 pub fn html_impl(input: TokenStream) -> TokenStream {
     let mut buffer = Buffer::new(&input);
 
     let html_input: Template = match syn::parse2(input) {
         Ok(a) => a,
-        Err(e) => return e.to_compile_error(),
+        Err(e) => return compile_errors(e),
     };
 
+    if let Err(e) = check_nesting(&html_input.nodes) {
+        return compile_errors(e);
+    }
+
+    if let Err(e) = check_attributes(&html_input.nodes) {
+        return compile_errors(e);
+    }
+
     buffer.push_block(&html_input.nodes);
     buffer.finalize_html()
 }
+
+pub fn html_to_impl(input: TokenStream) -> TokenStream {
+    let mut buffer = Buffer::new(&input);
+
+    let HtmlToInput { writer, template } = match syn::parse2(input) {
+        Ok(a) => a,
+        Err(e) => return compile_errors(e),
+    };
+
+    if let Err(e) = check_nesting(&template.nodes) {
+        return compile_errors(e);
+    }
+
+    if let Err(e) = check_attributes(&template.nodes) {
+        return compile_errors(e);
+    }
+
+    buffer.push_block(&template.nodes);
+    buffer.finalize_write(&writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    fn write_calls(output: &TokenStream) -> Vec<String> {
+        let output = output.to_string();
+
+        output
+            .match_indices("write_str (__plait_html , ")
+            .map(|(index, _)| {
+                let rest = &output[index + "write_str (__plait_html , ".len()..];
+                rest[..rest.find(")").unwrap()].trim().to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_adjacent_static_parts_are_coalesced() {
+        let output = html_impl(quote! {
+            div(class: "card", hidden) {
+                h1 { "Title" }
+                p { "Body " 42 }
+            }
+        });
+
+        assert_eq!(
+            write_calls(&output),
+            [r#""<div class=\"card\" hidden><h1>Title</h1><p>Body 42</p></div>""#]
+        );
+    }
+
+    #[test]
+    fn test_static_parts_around_dynamic_values() {
+        let output = html_impl(quote! {
+            ul(class: "list") {
+                li { (first) }
+                li(class: "last") { "static" }
+            }
+        });
+
+        assert_eq!(
+            write_calls(&output),
+            [
+                r#""<ul class=\"list\"><li>""#,
+                r#""</li><li class=\"last\">static</li></ul>""#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literals_are_escaped_at_compile_time() {
+        let output = html_impl(quote! {
+            p(title: "Fish & \"chips\"") { "1 < 2 & 'a'" }
+        });
+
+        assert_eq!(
+            write_calls(&output),
+            [r#""<p title=\"Fish &amp; &quot;chips&quot;\">1 &lt; 2 &amp; &#39;a&#39;</p>""#]
+        );
+        assert!(!output.to_string().contains("render_escaped"));
+    }
+
+    #[test]
+    fn test_dynamic_attribute_names_are_shared() {
+        let output = html_impl(quote! {
+            a(href: (url), class?: active, hx_get?: href) { "Home" }
+        })
+        .to_string();
+
+        assert!(
+            output.contains(":: plait :: attribute_names :: HREF"),
+            "{output}"
+        );
+        assert!(
+            output.contains(":: plait :: attribute_names :: CLASS"),
+            "{output}"
+        );
+        assert!(output.contains("\"hx-get\""), "{output}");
+        assert!(!output.contains("\"class\""), "{output}");
+    }
+
+    #[test]
+    fn test_text_interpolation() {
+        let output = html_impl(quote! {
+            p { f!("Hi {user.name}, {{x}} {pair.0}") "{not_interpolated}" }
+            script { f!("let a = {b};") }
+        });
+
+        assert_eq!(
+            write_calls(&output),
+            [
+                r#""<p>Hi ""#,
+                r#"", {x} ""#,
+                r#""{not_interpolated}</p><script>let a = ""#,
+                r#"";</script>""#
+            ]
+        );
+        assert!(output.to_string().contains("user . name"));
+        assert!(output.to_string().contains("pair . 0"));
+    }
+
+    #[test]
+    fn test_multiple_errors_are_one_expression() {
+        let output = html_impl(quote! {
+            #[deny(unknown_attributes)]
+            div(href: "/", src: "/a.png") {}
+        });
+
+        let output = output.to_string();
+        assert!(output.starts_with('{') && output.ends_with('}'), "{output}");
+        assert_eq!(output.matches("compile_error").count(), 2);
+    }
+
+    #[test]
+    fn test_text_interpolation_errors() {
+        for (template, message) in [
+            (quote! { f!("{name") }, "unclosed `{`"),
+            (quote! { f!("name}") }, "unmatched `}`"),
+            (quote! { f!("{}") }, "empty placeholder"),
+            (quote! { f!("{ your cart }") }, "invalid placeholder"),
+        ] {
+            let output = html_impl(template).to_string();
+            assert!(output.contains("compile_error"), "{output}");
+            assert!(output.contains(message), "{output}");
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "i18n"))]
+    fn test_translation_requires_feature() {
+        let output = html_impl(quote! { p { t!("greeting", name = name) } });
+
+        assert!(
+            output
+                .to_string()
+                .contains("`t!` requires the `i18n` feature of plait")
+        );
+    }
+
+    #[test]
+    fn test_render_prop_takes_a_single_parameter() {
+        let output = html_impl(quote! { @List(items: &rows) { |row, index| tr {} } });
+
+        assert!(
+            output
+                .to_string()
+                .contains("render props take a single parameter")
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+pub fn options_impl(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = match syn::parse2(input) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let Data::Enum(data) = &input.data else {
+        return syn::Error::new_spanned(&input.ident, "`Options` can only be derived for enums")
+            .to_compile_error();
+    };
+
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input.generics,
+            "`Options` cannot be derived for generic enums",
+        )
+        .to_compile_error();
+    }
+
+    let mut variants = Vec::with_capacity(data.variants.len());
+    let mut value_arms = Vec::with_capacity(data.variants.len());
+    let mut label_arms = Vec::with_capacity(data.variants.len());
+
+    for variant in data.variants.iter() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return syn::Error::new_spanned(
+                variant,
+                "`Options` can only be derived for enums without fields",
+            )
+            .to_compile_error();
+        }
+
+        let ident = &variant.ident;
+        let mut value = LitStr::new(&ident.to_string(), ident.span());
+        let mut label = value.clone();
+
+        for attribute in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("option"))
+        {
+            let result = attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("value") {
+                    value = meta.value()?.parse()?;
+                    Ok(())
+                } else if meta.path.is_ident("label") {
+                    label = meta.value()?.parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `value = \"...\"` or `label = \"...\"`"))
+                }
+            });
+
+            if let Err(e) = result {
+                return e.to_compile_error();
+            }
+        }
+
+        variants.push(quote! { Self::#ident });
+        value_arms.push(quote! { Self::#ident => #value, });
+        label_arms.push(quote! { Self::#ident => #label, });
+    }
+
+    let ident = &input.ident;
+
+    quote! {
+        impl ::plait::form::Options for #ident {
+            fn options() -> &'static [Self] {
+                &[#(#variants),*]
+            }
+
+            fn value(&self) -> &'static str {
+                match self {
+                    #(#value_arms)*
+                }
+            }
+
+            fn label(&self) -> &'static str {
+                match self {
+                    #(#label_arms)*
+                }
+            }
+        }
+
+        impl ::plait::form::SelectOption for #ident {
+            fn option_value(&self) -> &str {
+                ::plait::form::Options::value(self)
+            }
+
+            fn option_label(&self) -> &str {
+                ::plait::form::Options::label(self)
+            }
+        }
+
+        impl ::plait::form::FormValue for #ident {
+            fn form_value(&self) -> ::core::option::Option<::std::borrow::Cow<'_, str>> {
+                ::core::option::Option::Some(::std::borrow::Cow::Borrowed(
+                    ::plait::form::Options::value(self),
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, LitStr};
+
+/// Part of a URL template in which a placeholder appears. It determines how the placeholder value is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UrlPart {
+    Path,
+    Query,
+}
+
+enum Segment {
+    Static(String),
+    Placeholder(Expr, UrlPart),
+}
+
+pub fn url_impl(input: TokenStream) -> TokenStream {
+    let template: LitStr = match syn::parse2(input) {
+        Ok(template) => template,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let segments = match parse_template(&template) {
+        Ok(segments) => segments,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let buffer = quote! { __plait_url };
+    let mut size_hint = 0;
+    let mut statements = Vec::with_capacity(segments.len());
+
+    for segment in segments {
+        match segment {
+            Segment::Static(value) => {
+                size_hint += value.len();
+                statements.push(quote! {
+                    #buffer.push_str(#value);
+                });
+            }
+            Segment::Placeholder(expr, UrlPart::Path) => statements.push(quote! {
+                ::plait::push_url_path_segment(&mut #buffer, &(#expr));
+            }),
+            Segment::Placeholder(expr, UrlPart::Query) => statements.push(quote! {
+                ::plait::push_url_query_component(&mut #buffer, &(#expr));
+            }),
+        }
+    }
+
+    quote! {
+        {
+            let mut #buffer = ::std::string::String::with_capacity(#size_hint);
+            #(#statements)*
+            #buffer
+        }
+    }
+}
+
+fn parse_template(template: &LitStr) -> syn::Result<Vec<Segment>> {
+    let value = template.value();
+    let mut segments = Vec::new();
+    let mut static_part = String::new();
+    let mut part = UrlPart::Path;
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                static_part.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                static_part.push('}');
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            return Err(syn::Error::new(
+                                template.span(),
+                                "unclosed `{` in URL template, use `{{` for a literal `{`",
+                            ));
+                        }
+                    }
+                }
+
+                let placeholder = placeholder.trim();
+                if placeholder.is_empty() {
+                    return Err(syn::Error::new(
+                        template.span(),
+                        "empty placeholder `{}` in URL template",
+                    ));
+                }
+
+                let expr = syn::parse_str::<Expr>(placeholder).map_err(|_| {
+                    syn::Error::new(
+                        template.span(),
+                        format!("invalid placeholder `{{{placeholder}}}` in URL template"),
+                    )
+                })?;
+
+                if !static_part.is_empty() {
+                    segments.push(Segment::Static(std::mem::take(&mut static_part)));
+                }
+                segments.push(Segment::Placeholder(expr, part));
+            }
+            '}' => {
+                return Err(syn::Error::new(
+                    template.span(),
+                    "unmatched `}` in URL template, use `}}` for a literal `}`",
+                ));
+            }
+            c if c.is_whitespace() || matches!(c, '"' | '<' | '>' | '`') => {
+                return Err(syn::Error::new(
+                    template.span(),
+                    format!("invalid character {c:?} in URL template"),
+                ));
+            }
+            '?' | '#' => {
+                part = UrlPart::Query;
+                static_part.push(c);
+            }
+            c => static_part.push(c),
+        }
+    }
+
+    if !static_part.is_empty() {
+        segments.push(Segment::Static(static_part));
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    fn parse(template: &str) -> syn::Result<Vec<String>> {
+        let segments = parse_template(&LitStr::new(template, proc_macro2::Span::call_site()))?;
+
+        Ok(segments
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Static(value) => value,
+                Segment::Placeholder(expr, part) => {
+                    format!("{part:?}({})", expr.to_token_stream())
+                }
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_static_template() {
+        assert_eq!(parse("/about").unwrap(), vec!["/about"]);
+    }
+
+    #[test]
+    fn test_path_and_query_placeholders() {
+        assert_eq!(
+            parse("/users/{id}/posts?page={page}").unwrap(),
+            vec!["/users/", "Path(id)", "/posts?page=", "Query(page)"]
+        );
+    }
+
+    #[test]
+    fn test_field_access_placeholder() {
+        assert_eq!(
+            parse("/users/{ user.id }").unwrap(),
+            vec!["/users/", "Path(user . id)"]
+        );
+    }
+
+    #[test]
+    fn test_fragment_placeholder() {
+        assert_eq!(
+            parse("/docs#{anchor}").unwrap(),
+            vec!["/docs#", "Query(anchor)"]
+        );
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        assert_eq!(parse("/a{{b}}").unwrap(), vec!["/a{b}"]);
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(parse("/users/{id").is_err());
+        assert!(parse("/users/id}").is_err());
+        assert!(parse("/users/{}").is_err());
+        assert!(parse("/users/{1 +}").is_err());
+        assert!(parse("/users/a b").is_err());
+    }
+}
@@ -0,0 +1,757 @@
+//! Compile-time checks that attributes are valid for the HTML elements they are written on.
+//!
+//! The checks are off by default. `#[deny(unknown_attributes)]` turns them on for the node it precedes, and
+//! `#[allow(unknown_attributes)]` turns them off again for a node inside it. Global attributes, event handlers (`on*`),
+//! `data-*` and `aria-*` attributes are valid on every element, as is hyperscript's `_`, and more prefixes (such as
+//! `hx-` for htmx) can be allowed with a comma-separated list in the `PLAIT_ATTRIBUTE_PREFIXES` environment variable at
+//! build time (see `build.rs`, which rebuilds the macros when it changes). Custom elements, unknown elements and the
+//! contents of `svg` and `math` are not checked.
+//!
+//! The same tables back `plait::attribute_names`: one `static` per known attribute name, which generated code passes
+//! for dynamic attributes instead of a fresh string literal, so every template in every crate shares one copy of
+//! each name.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Ident, LitStr};
+
+use crate::ast::{Attribute, ComponentCall, Element, ElseBranch, IfCondition, Node};
+
+/// Environment variable with the extra attribute prefixes allowed on every element.
+const PREFIXES_VAR: &str = "PLAIT_ATTRIBUTE_PREFIXES";
+
+/// Attributes of scripting libraries allowed on every element: `_` for hyperscript.
+const LIBRARY_ATTRIBUTES: &[&str] = &["_"];
+
+/// Attribute prefixes allowed on every element.
+const ALLOWED_PREFIXES: &[&str] = &["data-", "aria-", "xml:", "xmlns"];
+
+/// Attributes valid on every HTML element, sorted.
+const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "accesskey",
+    "autocapitalize",
+    "autocorrect",
+    "autofocus",
+    "class",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "enterkeyhint",
+    "exportparts",
+    "hidden",
+    "id",
+    "inert",
+    "inputmode",
+    "is",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "lang",
+    "nonce",
+    "part",
+    "popover",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+    "writingsuggestions",
+];
+
+/// HTML elements with the attributes they accept besides the global ones, both sorted.
+const ELEMENT_ATTRIBUTES: &[(&str, &[&str])] = &[
+    (
+        "a",
+        &[
+            "download",
+            "href",
+            "hreflang",
+            "ping",
+            "referrerpolicy",
+            "rel",
+            "target",
+            "type",
+        ],
+    ),
+    ("abbr", &[]),
+    ("address", &[]),
+    (
+        "area",
+        &[
+            "alt",
+            "coords",
+            "download",
+            "href",
+            "ping",
+            "referrerpolicy",
+            "rel",
+            "shape",
+            "target",
+        ],
+    ),
+    ("article", &[]),
+    ("aside", &[]),
+    (
+        "audio",
+        &[
+            "autoplay",
+            "controls",
+            "crossorigin",
+            "disableremoteplayback",
+            "loop",
+            "muted",
+            "preload",
+            "src",
+        ],
+    ),
+    ("b", &[]),
+    ("base", &["href", "target"]),
+    ("bdi", &[]),
+    ("bdo", &[]),
+    ("blockquote", &["cite"]),
+    ("body", &[]),
+    ("br", &[]),
+    (
+        "button",
+        &[
+            "command",
+            "commandfor",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "name",
+            "popovertarget",
+            "popovertargetaction",
+            "type",
+            "value",
+        ],
+    ),
+    ("canvas", &["height", "width"]),
+    ("caption", &[]),
+    ("cite", &[]),
+    ("code", &[]),
+    ("col", &["span"]),
+    ("colgroup", &["span"]),
+    ("data", &["value"]),
+    ("datalist", &[]),
+    ("dd", &[]),
+    ("del", &["cite", "datetime"]),
+    ("details", &["name", "open"]),
+    ("dfn", &[]),
+    ("dialog", &["closedby", "open"]),
+    ("div", &[]),
+    ("dl", &[]),
+    ("dt", &[]),
+    ("em", &[]),
+    ("embed", &["height", "src", "type", "width"]),
+    ("fieldset", &["disabled", "form", "name"]),
+    ("figcaption", &[]),
+    ("figure", &[]),
+    ("footer", &[]),
+    (
+        "form",
+        &[
+            "accept-charset",
+            "action",
+            "autocomplete",
+            "enctype",
+            "method",
+            "name",
+            "novalidate",
+            "rel",
+            "target",
+        ],
+    ),
+    ("h1", &[]),
+    ("h2", &[]),
+    ("h3", &[]),
+    ("h4", &[]),
+    ("h5", &[]),
+    ("h6", &[]),
+    ("head", &[]),
+    ("header", &[]),
+    ("hgroup", &[]),
+    ("hr", &[]),
+    ("html", &[]),
+    ("i", &[]),
+    (
+        "iframe",
+        &[
+            "allow",
+            "allowfullscreen",
+            "height",
+            "loading",
+            "name",
+            "referrerpolicy",
+            "sandbox",
+            "src",
+            "srcdoc",
+            "width",
+        ],
+    ),
+    (
+        "img",
+        &[
+            "alt",
+            "crossorigin",
+            "decoding",
+            "fetchpriority",
+            "height",
+            "ismap",
+            "loading",
+            "referrerpolicy",
+            "sizes",
+            "src",
+            "srcset",
+            "usemap",
+            "width",
+        ],
+    ),
+    (
+        "input",
+        &[
+            "accept",
+            "alpha",
+            "alt",
+            "autocomplete",
+            "capture",
+            "checked",
+            "colorspace",
+            "dirname",
+            "disabled",
+            "form",
+            "formaction",
+            "formenctype",
+            "formmethod",
+            "formnovalidate",
+            "formtarget",
+            "height",
+            "list",
+            "max",
+            "maxlength",
+            "min",
+            "minlength",
+            "multiple",
+            "name",
+            "pattern",
+            "placeholder",
+            "popovertarget",
+            "popovertargetaction",
+            "readonly",
+            "required",
+            "size",
+            "src",
+            "step",
+            "type",
+            "value",
+            "width",
+        ],
+    ),
+    ("ins", &["cite", "datetime"]),
+    ("kbd", &[]),
+    ("label", &["for"]),
+    ("legend", &[]),
+    ("li", &["value"]),
+    (
+        "link",
+        &[
+            "as",
+            "blocking",
+            "color",
+            "crossorigin",
+            "disabled",
+            "fetchpriority",
+            "href",
+            "hreflang",
+            "imagesizes",
+            "imagesrcset",
+            "integrity",
+            "media",
+            "referrerpolicy",
+            "rel",
+            "sizes",
+            "type",
+        ],
+    ),
+    ("main", &[]),
+    ("map", &["name"]),
+    ("mark", &[]),
+    ("menu", &[]),
+    (
+        "meta",
+        &[
+            "charset",
+            "content",
+            "http-equiv",
+            "media",
+            "name",
+            "property",
+        ],
+    ),
+    ("meter", &["high", "low", "max", "min", "optimum", "value"]),
+    ("nav", &[]),
+    ("noscript", &[]),
+    (
+        "object",
+        &["data", "form", "height", "name", "type", "width"],
+    ),
+    ("ol", &["reversed", "start", "type"]),
+    ("optgroup", &["disabled", "label"]),
+    ("option", &["disabled", "label", "selected", "value"]),
+    ("output", &["for", "form", "name"]),
+    ("p", &[]),
+    ("picture", &[]),
+    ("pre", &[]),
+    ("progress", &["max", "value"]),
+    ("q", &["cite"]),
+    ("rp", &[]),
+    ("rt", &[]),
+    ("ruby", &[]),
+    ("s", &[]),
+    ("samp", &[]),
+    (
+        "script",
+        &[
+            "async",
+            "blocking",
+            "crossorigin",
+            "defer",
+            "fetchpriority",
+            "integrity",
+            "nomodule",
+            "referrerpolicy",
+            "src",
+            "type",
+        ],
+    ),
+    ("search", &[]),
+    ("section", &[]),
+    (
+        "select",
+        &[
+            "autocomplete",
+            "disabled",
+            "form",
+            "multiple",
+            "name",
+            "required",
+            "size",
+        ],
+    ),
+    ("slot", &["name"]),
+    ("small", &[]),
+    (
+        "source",
+        &["height", "media", "sizes", "src", "srcset", "type", "width"],
+    ),
+    ("span", &[]),
+    ("strong", &[]),
+    ("style", &["blocking", "media"]),
+    ("sub", &[]),
+    ("summary", &[]),
+    ("sup", &[]),
+    ("table", &[]),
+    ("tbody", &[]),
+    ("td", &["colspan", "headers", "rowspan"]),
+    (
+        "template",
+        &[
+            "shadowrootclonable",
+            "shadowrootcustomelementregistry",
+            "shadowrootdelegatesfocus",
+            "shadowrootmode",
+            "shadowrootserializable",
+        ],
+    ),
+    (
+        "textarea",
+        &[
+            "autocomplete",
+            "cols",
+            "dirname",
+            "disabled",
+            "form",
+            "maxlength",
+            "minlength",
+            "name",
+            "placeholder",
+            "readonly",
+            "required",
+            "rows",
+            "wrap",
+        ],
+    ),
+    ("tfoot", &[]),
+    ("th", &["abbr", "colspan", "headers", "rowspan", "scope"]),
+    ("thead", &[]),
+    ("time", &["datetime"]),
+    ("title", &[]),
+    ("tr", &[]),
+    ("track", &["default", "kind", "label", "src", "srclang"]),
+    ("u", &[]),
+    ("ul", &[]),
+    ("var", &[]),
+    (
+        "video",
+        &[
+            "autoplay",
+            "controls",
+            "crossorigin",
+            "disableremoteplayback",
+            "height",
+            "loop",
+            "muted",
+            "playsinline",
+            "poster",
+            "preload",
+            "src",
+            "width",
+        ],
+    ),
+    ("wbr", &[]),
+];
+
+/// Returns the prefixes listed in `PLAIT_ATTRIBUTE_PREFIXES`.
+fn configured_prefixes() -> Vec<String> {
+    std::env::var(PREFIXES_VAR)
+        .map(|prefixes| {
+            prefixes
+                .split(',')
+                .map(str::trim)
+                .filter(|prefix| !prefix.is_empty())
+                .map(|prefix| prefix.to_ascii_lowercase())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns whether `name` is a valid attribute of the element `tag`, or `None` if the element isn't checked.
+fn is_known_attribute(tag: &str, name: &str, prefixes: &[String]) -> Option<bool> {
+    let index = ELEMENT_ATTRIBUTES
+        .binary_search_by_key(&tag, |(element, _)| element)
+        .ok()?;
+    let (_, attributes) = ELEMENT_ATTRIBUTES[index];
+
+    let is_event_handler = name.len() > 2
+        && name.starts_with("on")
+        && name.bytes().all(|byte| byte.is_ascii_lowercase());
+
+    Some(
+        attributes.binary_search(&name).is_ok()
+            || GLOBAL_ATTRIBUTES.binary_search(&name).is_ok()
+            || LIBRARY_ATTRIBUTES.contains(&name)
+            || is_event_handler
+            || ALLOWED_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            || prefixes
+                .iter()
+                .any(|prefix| name.starts_with(prefix.as_str())),
+    )
+}
+
+/// Returns every attribute name in the tables, sorted and without duplicates.
+fn attribute_names() -> Vec<&'static str> {
+    let mut names: Vec<_> = GLOBAL_ATTRIBUTES
+        .iter()
+        .chain(
+            ELEMENT_ATTRIBUTES
+                .iter()
+                .flat_map(|(_, attributes)| attributes.iter()),
+        )
+        .copied()
+        .collect();
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Returns the identifier of the `static` holding the attribute name `name` (`accept-charset` becomes
+/// `ACCEPT_CHARSET`).
+fn name_static(name: &str) -> Ident {
+    Ident::new(
+        &name.to_ascii_uppercase().replace('-', "_"),
+        Span::call_site(),
+    )
+}
+
+/// Expands to the contents of `plait::attribute_names`: one `static` per known attribute name.
+pub fn attribute_name_statics() -> TokenStream {
+    let statics = attribute_names().into_iter().map(|name| {
+        let ident = name_static(name);
+
+        quote! {
+            pub static #ident: &str = #name;
+        }
+    });
+
+    quote! { #(#statics)* }
+}
+
+/// Returns the expression generated code passes as the attribute name `name`: the shared `static` for known names, or
+/// the literal itself for any other name.
+pub fn attribute_name(name: &LitStr) -> TokenStream {
+    let value = name.value();
+    let is_known = GLOBAL_ATTRIBUTES.binary_search(&value.as_str()).is_ok()
+        || ELEMENT_ATTRIBUTES
+            .iter()
+            .any(|(_, attributes)| attributes.binary_search(&value.as_str()).is_ok());
+
+    if is_known {
+        let ident = name_static(&value);
+        quote! { ::plait::attribute_names::#ident }
+    } else {
+        quote! { #name }
+    }
+}
+
+/// Checks the attributes of the elements of a template, returning one error per unknown attribute.
+pub fn check_attributes(nodes: &[Node]) -> syn::Result<()> {
+    let mut checker = Checker::new(false, configured_prefixes());
+    checker.check_nodes(nodes);
+
+    let mut errors = checker.errors.into_iter();
+
+    match errors.next() {
+        Some(mut error) => {
+            error.extend(errors);
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+struct Checker {
+    deny: bool,
+    prefixes: Vec<String>,
+    errors: Vec<syn::Error>,
+}
+
+impl Checker {
+    fn new(deny: bool, prefixes: Vec<String>) -> Self {
+        Self {
+            deny,
+            prefixes,
+            errors: Vec::new(),
+        }
+    }
+
+    fn check_nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            self.check_node(node);
+        }
+    }
+
+    fn check_node(&mut self, node: &Node) {
+        match node {
+            Node::Element(element) => self.check_element(element),
+            Node::Block(nodes) => self.check_nodes(nodes),
+            Node::IfCondition(if_condition) => self.check_if_condition(if_condition),
+            Node::MatchExpression(match_expression) => {
+                for arm in &match_expression.arms {
+                    self.check_nodes(&arm.body);
+                }
+            }
+            Node::ForLoop(for_loop) => {
+                self.check_nodes(&for_loop.body);
+
+                if let Some(separator) = &for_loop.separator {
+                    self.check_nodes(separator);
+                }
+
+                if let Some(else_branch) = &for_loop.else_branch {
+                    self.check_nodes(else_branch);
+                }
+            }
+            Node::WhileLoop(while_loop) => self.check_nodes(&while_loop.body),
+            Node::LetBinding(let_binding) => {
+                if let Some(else_branch) = &let_binding.else_branch {
+                    self.check_nodes(else_branch);
+                }
+            }
+            Node::CacheBlock(cache_block) => self.check_nodes(&cache_block.body),
+            Node::ComponentCall(component_call) => self.check_component_call(component_call),
+            Node::AllowInvalidNesting(node) => self.check_node(node),
+            Node::UnknownAttributes { deny, node } => {
+                let outer = std::mem::replace(&mut self.deny, *deny);
+                self.check_node(node);
+                self.deny = outer;
+            }
+            Node::Doctype
+            | Node::LitStr(_)
+            | Node::LitChar(_)
+            | Node::LitInt(_)
+            | Node::LitFloat(_)
+            | Node::LitBool(_)
+            | Node::Escaped(_)
+            | Node::Raw(_)
+            | Node::Children(_)
+            | Node::Slot { .. }
+            | Node::RenderProp { .. } => {}
+        }
+    }
+
+    fn check_element(&mut self, element: &Element) {
+        let tag = element.tag.value().to_ascii_lowercase();
+
+        // Foreign content has its own attributes
+        if matches!(tag.as_str(), "svg" | "math") {
+            return;
+        }
+
+        if self.deny {
+            for attribute in &element.attributes {
+                if let Attribute::NameValue(attribute) = attribute {
+                    self.check_attribute(&tag, &attribute.name);
+                }
+            }
+        }
+
+        self.check_nodes(&element.children);
+    }
+
+    fn check_attribute(&mut self, tag: &str, name: &LitStr) {
+        let name_str = name.value().to_ascii_lowercase();
+
+        // Markers handled by the macro itself
+        if name_str == "auto-id" {
+            return;
+        }
+
+        if is_known_attribute(tag, &name_str, &self.prefixes) == Some(false) {
+            self.errors.push(syn::Error::new(
+                name.span(),
+                format!(
+                    "`{name_str}` is not a valid attribute of `<{tag}>` (use `#[allow(unknown_attributes)]` to allow it)"
+                ),
+            ));
+        }
+    }
+
+    fn check_if_condition(&mut self, if_condition: &IfCondition) {
+        self.check_nodes(&if_condition.then_branch);
+
+        match &if_condition.else_branch {
+            Some(ElseBranch::If(if_condition)) => self.check_if_condition(if_condition),
+            Some(ElseBranch::Else(nodes)) => self.check_nodes(nodes),
+            None => {}
+        }
+    }
+
+    fn check_component_call(&mut self, component_call: &ComponentCall) {
+        self.check_nodes(&component_call.children);
+
+        for slot in &component_call.slots {
+            self.check_nodes(&slot.children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    use super::*;
+    use crate::ast::Template;
+
+    fn errors(input: TokenStream, deny: bool) -> Vec<String> {
+        let template: Template = syn::parse2(input).unwrap();
+
+        let mut checker = Checker::new(deny, vec!["hx-".to_owned()]);
+        checker.check_nodes(&template.nodes);
+
+        checker
+            .errors
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_known_attributes() {
+        assert!(
+            errors(
+                quote! {
+                    a(href: "/", class: "link", data_id: 1, aria_label: "Home", onclick: "go()") {}
+                    input(type: "checkbox", checked, hx_post: "/toggle");
+                    div(aria_hidden: "true", hidden, css_vars: {gap: "1rem"}) {}
+                    my_widget(href: "/") {}
+                    svg(viewBox: "0 0 10 10") { a(href: "/") {} }
+                    section(auto_id) { h2 { "Title" } }
+                    button(_: "on click toggle .open") {}
+                    input(type: "file", capture: "user");
+                },
+                true
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_unknown_attributes() {
+        assert_eq!(
+            errors(
+                quote! {
+                    div(href: "/") { span(src: "/a.png") {} }
+                    #[allow(unknown_attributes)] p(value: 1) {}
+                    @Card { img(source: "/b.png"); }
+                },
+                true
+            ),
+            [
+                "`href` is not a valid attribute of `<div>` (use `#[allow(unknown_attributes)]` to allow it)",
+                "`src` is not a valid attribute of `<span>` (use `#[allow(unknown_attributes)]` to allow it)",
+                "`source` is not a valid attribute of `<img>` (use `#[allow(unknown_attributes)]` to allow it)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deny_unknown_attributes() {
+        assert_eq!(
+            errors(
+                quote! {
+                    div(href: "/") {}
+                    #[deny(unknown_attributes)] ul { li(href: "/") {} }
+                },
+                false
+            ),
+            [
+                "`href` is not a valid attribute of `<li>` (use `#[allow(unknown_attributes)]` to allow it)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tables_are_sorted() {
+        assert!(GLOBAL_ATTRIBUTES.is_sorted());
+        assert!(ELEMENT_ATTRIBUTES.is_sorted_by_key(|(element, _)| *element));
+        assert!(
+            ELEMENT_ATTRIBUTES
+                .iter()
+                .all(|(_, attributes)| attributes.is_sorted())
+        );
+    }
+
+    #[test]
+    fn test_attribute_name() {
+        let name = |value: &str| attribute_name(&LitStr::new(value, Span::call_site())).to_string();
+
+        assert_eq!(name("class"), ":: plait :: attribute_names :: CLASS");
+        assert_eq!(
+            name("http-equiv"),
+            ":: plait :: attribute_names :: HTTP_EQUIV"
+        );
+        assert_eq!(name("hx-post"), "\"hx-post\"");
+    }
+}
@@ -1,11 +1,15 @@
 //! Procedural macros for the [`plait`](https://docs.rs/plait) HTML templating library.
 //!
-//! This crate provides the [`html!`] and [`component!`] macros. You should depend on the `plait` crate directly -
-//! these macros are re-exported from there with full documentation.
+//! This crate provides the [`html!`], [`html_to!`], [`component!`], [`async_component!`], [`component_alias!`],
+//! [`asset!`] and [`url!`] macros, the [`macro@component_example`] and [`macro@component_test`] attributes and the
+//! [`FormModel`] and [`Options`] derives. You should depend on the `plait` crate directly - these macros are
+//! re-exported from there with full documentation.
 
 mod ast;
 mod buffer;
 mod codegen;
+mod known_attributes;
+mod nesting;
 mod parse;
 mod utils;
 
@@ -32,6 +36,26 @@ pub fn html(input: TokenStream) -> TokenStream {
     codegen::html_impl(input.into()).into()
 }
 
+/// See [`plait::html_to!`](https://docs.rs/plait/latest/plait/macro.html_to.html) for full documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::html_to;
+///
+/// let mut output = String::new();
+/// html_to!(&mut output, {
+///     p { "Hello" }
+/// })
+/// .unwrap();
+///
+/// assert_eq!(output, "<p>Hello</p>");
+/// ```
+#[proc_macro]
+pub fn html_to(input: TokenStream) -> TokenStream {
+    codegen::html_to_impl(input.into()).into()
+}
+
 /// See [`plait::component!`](https://docs.rs/plait/latest/plait/macro.component.html) for full documentation.
 ///
 /// # Example
@@ -51,3 +75,152 @@ pub fn html(input: TokenStream) -> TokenStream {
 pub fn component(input: TokenStream) -> TokenStream {
     codegen::component_impl(input.into()).into()
 }
+
+/// See [`plait::async_component!`](https://docs.rs/plait/latest/plait/macro.async_component.html) for full
+/// documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::async_component;
+///
+/// async_component! {
+///     pub fn Profile<'a>(db: &'a Db, id: u64) {
+///         let user = db.find(*id).await;
+///         h1 { (user.name) }
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn async_component(input: TokenStream) -> TokenStream {
+    codegen::async_component_impl(input.into()).into()
+}
+
+/// See [`plait::component_alias!`](https://docs.rs/plait/latest/plait/macro.component_alias.html) for full
+/// documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::component_alias;
+///
+/// component_alias!(pub OldButton = Button);
+/// ```
+#[proc_macro]
+pub fn component_alias(input: TokenStream) -> TokenStream {
+    codegen::component_alias_impl(input.into()).into()
+}
+
+/// See [`plait::asset!`](https://docs.rs/plait/latest/plait/macro.asset.html) for full documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::{asset, Asset};
+///
+/// const APP_CSS: Asset = asset!("assets/app.css");
+/// ```
+#[proc_macro]
+pub fn asset(input: TokenStream) -> TokenStream {
+    codegen::asset_impl(input.into()).into()
+}
+
+/// See [`plait::component_example`](https://docs.rs/plait/latest/plait/attr.component_example.html) for full
+/// documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::{component_example, html, ToHtml};
+///
+/// #[component_example(Button, "Primary")]
+/// fn button_primary() -> impl ToHtml {
+///     html! { @Button(class: "primary") { "Click" } }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn component_example(args: TokenStream, input: TokenStream) -> TokenStream {
+    codegen::component_example_impl(args.into(), input.into()).into()
+}
+
+/// See [`plait::component_test`](https://docs.rs/plait/latest/plait/attr.component_test.html) for full documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::{component_test, html, ToHtml};
+///
+/// #[component_test(r#"button "Save""#)]
+/// fn button_primary() -> impl ToHtml {
+///     html! { @Button(class: "primary") { "Save" } }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn component_test(args: TokenStream, input: TokenStream) -> TokenStream {
+    codegen::component_test_impl(args.into(), input.into()).into()
+}
+
+/// See [`plait::url!`](https://docs.rs/plait/latest/plait/macro.url.html) for full documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::url;
+///
+/// let id = 42;
+/// let page = 2;
+///
+/// assert_eq!(url!("/users/{id}/posts?page={page}"), "/users/42/posts?page=2");
+/// ```
+#[proc_macro]
+pub fn url(input: TokenStream) -> TokenStream {
+    codegen::url_impl(input.into()).into()
+}
+
+/// Expands to one `static` per known attribute name; used once, to define `plait::attribute_names`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn attribute_names(_input: TokenStream) -> TokenStream {
+    known_attributes::attribute_name_statics().into()
+}
+
+/// See [`plait::form::FormModel`](https://docs.rs/plait/latest/plait/form/trait.FormModel.html) for full
+/// documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::form::FormModel;
+///
+/// #[derive(FormModel)]
+/// struct Signup {
+///     email: String,
+///     #[form(rename = "display-name")]
+///     name: String,
+///     #[form(skip)]
+///     password: String,
+/// }
+/// ```
+#[proc_macro_derive(FormModel, attributes(form))]
+pub fn form_model(input: TokenStream) -> TokenStream {
+    codegen::form_model_impl(input.into()).into()
+}
+
+/// See [`plait::form::Options`](https://docs.rs/plait/latest/plait/form/trait.Options.html) for full documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::form::Options;
+///
+/// #[derive(Options)]
+/// enum Color {
+///     Red,
+///     #[option(value = "dark-blue", label = "Dark blue")]
+///     DarkBlue,
+/// }
+/// ```
+#[proc_macro_derive(Options, attributes(option))]
+pub fn options(input: TokenStream) -> TokenStream {
+    codegen::options_impl(input.into()).into()
+}
@@ -0,0 +1,337 @@
+//! Compile-time checks of basic HTML content-model rules.
+//!
+//! Only relationships visible in one template are checked: the parent of an element at the top level of a template,
+//! or of an element passed as children to a component, is unknown. Control flow (`if`, `for`, `match`, ...) is
+//! transparent. Elements inside `svg`, `math` and `template` are not checked, and `#[allow_invalid_nesting]` turns
+//! the checks off for the node it precedes.
+
+use syn::LitStr;
+
+use crate::ast::{ComponentCall, ElseBranch, IfCondition, Node};
+
+/// Elements that close an open `p` element, so they can't be its descendants.
+const P_CLOSING_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "search",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Elements that end the scope in which an open `p` element is closed.
+const P_SCOPE_BOUNDARIES: &[&str] = &[
+    "button", "caption", "html", "marquee", "object", "table", "td", "template", "th",
+];
+
+/// Elements that may only be children of the listed parents.
+const REQUIRED_PARENTS: &[(&str, &[&str])] = &[
+    ("caption", &["table"]),
+    ("col", &["colgroup", "table"]),
+    ("colgroup", &["table"]),
+    ("dd", &["dl", "div"]),
+    ("dt", &["dl", "div"]),
+    ("figcaption", &["figure"]),
+    ("legend", &["fieldset"]),
+    ("li", &["ul", "ol", "menu"]),
+    ("optgroup", &["select"]),
+    ("option", &["select", "datalist", "optgroup"]),
+    ("summary", &["details"]),
+    ("tbody", &["table"]),
+    ("td", &["tr"]),
+    ("tfoot", &["table"]),
+    ("th", &["tr"]),
+    ("thead", &["table"]),
+    ("tr", &["table", "thead", "tbody", "tfoot"]),
+];
+
+/// Elements that may only contain the listed children (besides `script` and `template`).
+const ALLOWED_CHILDREN: &[(&str, &[&str])] = &[
+    ("dl", &["dt", "dd", "div"]),
+    ("menu", &["li"]),
+    ("ol", &["li"]),
+    ("select", &["option", "optgroup", "hr"]),
+    (
+        "table",
+        &[
+            "caption", "colgroup", "thead", "tbody", "tfoot", "tr", "style",
+        ],
+    ),
+    ("tbody", &["tr"]),
+    ("tfoot", &["tr"]),
+    ("thead", &["tr"]),
+    ("tr", &["td", "th"]),
+    ("ul", &["li"]),
+];
+
+/// Interactive elements that can't contain each other.
+const INTERACTIVE_ELEMENTS: &[&str] = &["a", "button"];
+
+/// Returns `items` as "`<a>`, `<b>` or `<c>`".
+fn tag_list(items: &[&str], conjunction: &str) -> String {
+    let tags: Vec<String> = items.iter().map(|item| format!("`<{item}>`")).collect();
+
+    match tags.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} {conjunction} {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Returns the rule broken by an element `tag` inside `ancestors` (innermost last), if any.
+fn broken_rule(tag: &str, ancestors: &[String]) -> Option<String> {
+    if let Some(parent) = ancestors.last().map(String::as_str) {
+        if let Some((_, parents)) = REQUIRED_PARENTS.iter().find(|(name, _)| *name == tag)
+            && !parents.contains(&parent)
+        {
+            return Some(format!(
+                "`<{tag}>` must be a child of {}",
+                tag_list(parents, "or")
+            ));
+        }
+
+        if let Some((_, children)) = ALLOWED_CHILDREN.iter().find(|(name, _)| *name == parent)
+            && !children.contains(&tag)
+            && tag != "script"
+            && tag != "template"
+        {
+            let mut allowed = children.to_vec();
+            allowed.extend(["script", "template"]);
+
+            return Some(format!(
+                "`<{parent}>` can only contain {}",
+                tag_list(&allowed, "and")
+            ));
+        }
+    }
+
+    if P_CLOSING_ELEMENTS.contains(&tag) {
+        let in_p = ancestors
+            .iter()
+            .rev()
+            .take_while(|ancestor| !P_SCOPE_BOUNDARIES.contains(&ancestor.as_str()))
+            .any(|ancestor| ancestor == "p");
+
+        if in_p {
+            return Some(format!(
+                "`<{tag}>` can't be inside `<p>`, browsers close the paragraph before it"
+            ));
+        }
+    }
+
+    if INTERACTIVE_ELEMENTS.contains(&tag)
+        && let Some(ancestor) = ancestors
+            .iter()
+            .find(|ancestor| INTERACTIVE_ELEMENTS.contains(&ancestor.as_str()))
+    {
+        return Some(format!("`<{tag}>` can't be inside `<{ancestor}>`"));
+    }
+
+    if tag == "form" && ancestors.iter().any(|ancestor| ancestor == "form") {
+        return Some("`<form>` can't be inside another `<form>`".to_owned());
+    }
+
+    None
+}
+
+/// Checks the nodes of a template, returning one error per offending element.
+pub fn check_nesting(nodes: &[Node]) -> syn::Result<()> {
+    let mut checker = Checker::default();
+    checker.check_nodes(nodes);
+
+    let mut errors = checker.errors.into_iter();
+
+    match errors.next() {
+        Some(mut error) => {
+            error.extend(errors);
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+#[derive(Default)]
+struct Checker {
+    ancestors: Vec<String>,
+    errors: Vec<syn::Error>,
+}
+
+impl Checker {
+    fn check_nodes(&mut self, nodes: &[Node]) {
+        for node in nodes {
+            self.check_node(node);
+        }
+    }
+
+    fn check_node(&mut self, node: &Node) {
+        match node {
+            Node::Element(element) => self.check_element(&element.tag, &element.children),
+            Node::Block(nodes) => self.check_nodes(nodes),
+            Node::IfCondition(if_condition) => self.check_if_condition(if_condition),
+            Node::MatchExpression(match_expression) => {
+                for arm in &match_expression.arms {
+                    self.check_nodes(&arm.body);
+                }
+            }
+            Node::ForLoop(for_loop) => {
+                self.check_nodes(&for_loop.body);
+
+                if let Some(separator) = &for_loop.separator {
+                    self.check_nodes(separator);
+                }
+
+                if let Some(else_branch) = &for_loop.else_branch {
+                    self.check_nodes(else_branch);
+                }
+            }
+            Node::WhileLoop(while_loop) => self.check_nodes(&while_loop.body),
+            Node::LetBinding(let_binding) => {
+                if let Some(else_branch) = &let_binding.else_branch {
+                    self.check_nodes(else_branch);
+                }
+            }
+            Node::CacheBlock(cache_block) => self.check_nodes(&cache_block.body),
+            Node::ComponentCall(component_call) => self.check_component_call(component_call),
+            Node::UnknownAttributes { node, .. } => self.check_node(node),
+            Node::AllowInvalidNesting(_)
+            | Node::Doctype
+            | Node::LitStr(_)
+            | Node::LitChar(_)
+            | Node::LitInt(_)
+            | Node::LitFloat(_)
+            | Node::LitBool(_)
+            | Node::Escaped(_)
+            | Node::Raw(_)
+            | Node::Children(_)
+            | Node::Slot { .. }
+            | Node::RenderProp { .. } => {}
+        }
+    }
+
+    fn check_element(&mut self, tag: &LitStr, children: &[Node]) {
+        let name = tag.value().to_ascii_lowercase();
+
+        // Foreign and template content follows other rules
+        if matches!(name.as_str(), "svg" | "math" | "template") {
+            return;
+        }
+
+        if let Some(message) = broken_rule(&name, &self.ancestors) {
+            self.errors.push(syn::Error::new(
+                tag.span(),
+                format!("{message} (use `#[allow_invalid_nesting]` to allow it)"),
+            ));
+        }
+
+        self.ancestors.push(name);
+        self.check_nodes(children);
+        self.ancestors.pop();
+    }
+
+    fn check_if_condition(&mut self, if_condition: &IfCondition) {
+        self.check_nodes(&if_condition.then_branch);
+
+        match &if_condition.else_branch {
+            Some(ElseBranch::If(if_condition)) => self.check_if_condition(if_condition),
+            Some(ElseBranch::Else(nodes)) => self.check_nodes(nodes),
+            None => {}
+        }
+    }
+
+    /// Checks the children of a component call, whose parent element is only known to the component.
+    fn check_component_call(&mut self, component_call: &ComponentCall) {
+        let ancestors = std::mem::take(&mut self.ancestors);
+
+        self.check_nodes(&component_call.children);
+
+        for slot in &component_call.slots {
+            self.check_nodes(&slot.children);
+        }
+
+        self.ancestors = ancestors;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream;
+    use quote::quote;
+
+    use super::*;
+    use crate::ast::Template;
+
+    fn errors(input: TokenStream) -> Vec<String> {
+        let template: Template = syn::parse2(input).unwrap();
+
+        match check_nesting(&template.nodes) {
+            Ok(()) => Vec::new(),
+            Err(error) => error.into_iter().map(|error| error.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_valid_nesting() {
+        assert!(
+            errors(quote! {
+                li { "top level" }
+                ul { li { a(href: "/") { "Home" } } if true { li {} } for x in xs { li { (x) } } }
+                table { thead { tr { th { "a" } } } tr { td { p { "b" } } } }
+                p { button { div {} } }
+                dl { div { dt { "k" } dd { "v" } } }
+                select { optgroup { option { "x" } } }
+                svg { a { a {} } }
+                ul { @Item { li {} } }
+                #[allow_invalid_nesting] p { div {} }
+            })
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_invalid_nesting() {
+        assert_eq!(
+            errors(quote! {
+                p { span { div {} } }
+                div { li {} }
+                ul { div {} }
+                table { td {} }
+                a { span { button {} } }
+                form { div { form {} } }
+            }),
+            [
+                "`<div>` can't be inside `<p>`, browsers close the paragraph before it (use `#[allow_invalid_nesting]` to allow it)",
+                "`<li>` must be a child of `<ul>`, `<ol>` or `<menu>` (use `#[allow_invalid_nesting]` to allow it)",
+                "`<ul>` can only contain `<li>`, `<script>` and `<template>` (use `#[allow_invalid_nesting]` to allow it)",
+                "`<td>` must be a child of `<tr>` (use `#[allow_invalid_nesting]` to allow it)",
+                "`<button>` can't be inside `<a>` (use `#[allow_invalid_nesting]` to allow it)",
+                "`<form>` can't be inside another `<form>` (use `#[allow_invalid_nesting]` to allow it)",
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod attribute;
+mod cache_block;
 mod component_call;
 mod component_definition;
 mod element;
@@ -8,3 +9,4 @@ mod let_binding;
 mod match_expression;
 mod node;
 mod template;
+mod while_loop;
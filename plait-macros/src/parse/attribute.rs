@@ -1,13 +1,17 @@
 use convert_case::{Boundary, Case, Casing};
+use quote::quote;
 use syn::{
-    Ident, LitBool, LitChar, LitFloat, LitInt, LitStr,
+    Expr, Ident, LitBool, LitChar, LitFloat, LitInt, LitStr, braced,
     ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream},
-    token::{Colon, Comma, Paren, Pound, Question},
+    token::{Brace, Colon, Comma, Paren, Pound, Question},
 };
 
-use crate::ast::{Attribute, AttributeValue, NameValueAttribute};
+use crate::{
+    ast::{Attribute, AttributeValue, NameValueAttribute},
+    utils::is_valid_attribute_name,
+};
 
 impl Parse for AttributeValue {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
@@ -42,13 +46,29 @@ impl Parse for AttributeValue {
 impl Parse for NameValueAttribute {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let name = if input.peek(LitStr) {
-            input.parse()?
+            let name: LitStr = input.parse()?;
+
+            if !is_valid_attribute_name(&name.value()) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "invalid attribute name, names can't be empty or contain whitespace, control characters, `/`, `>`, `=`, `\"`, `'` or `<`",
+                ));
+            }
+
+            name
         } else {
             let name_ident = input.call(Ident::parse_any)?;
-            let name_string = name_ident
-                .to_string()
-                .set_boundaries(&[Boundary::Underscore])
-                .to_case(Case::Kebab);
+            let name_string = name_ident.to_string();
+
+            // Raw identifiers (`r#ng_model`) and names made only of underscores (hyperscript's `_`) keep their
+            // underscores
+            let name_string = match name_string.strip_prefix("r#") {
+                Some(raw_name) => raw_name.to_owned(),
+                None if name_string.chars().all(|c| c == '_') => name_string,
+                None => name_string
+                    .set_boundaries(&[Boundary::Underscore])
+                    .to_case(Case::Kebab),
+            };
             LitStr::new(&name_string, name_ident.span())
         };
 
@@ -67,6 +87,14 @@ impl Parse for NameValueAttribute {
 
         let _ = input.parse::<Colon>()?;
 
+        if name.value() == "css-vars" && input.peek(Brace) {
+            return Ok(Self {
+                name: LitStr::new("style", name.span()),
+                is_maybe,
+                value: Some(parse_css_vars(input)?),
+            });
+        }
+
         let value = Some(input.parse()?);
 
         Ok(Self {
@@ -77,13 +105,52 @@ impl Parse for NameValueAttribute {
     }
 }
 
+/// Parses the `{name: value, ...}` of `css_vars: {...}` into a [`Style`](plait::style::Style) setting the custom
+/// property `--name` to each value.
+fn parse_css_vars(input: ParseStream<'_>) -> syn::Result<AttributeValue> {
+    let content;
+    let _ = braced!(content in input);
+
+    let mut style = quote! { ::plait::style::Style::new() };
+
+    while !content.is_empty() {
+        let ident = content.call(Ident::parse_any)?;
+        let property = format!(
+            "--{}",
+            ident
+                .to_string()
+                .trim_start_matches("r#")
+                .set_boundaries(&[Boundary::Underscore])
+                .to_case(Case::Kebab)
+        );
+
+        let _ = content.parse::<Colon>()?;
+        let value: Expr = content.parse()?;
+
+        style.extend(quote! { .prop(#property, &(#value)) });
+
+        if content.peek(Comma) {
+            let _ = content.parse::<Comma>()?;
+        } else if !content.is_empty() {
+            return Err(content.error("expected ',' after a CSS variable"));
+        }
+    }
+
+    Ok(AttributeValue::Escaped(syn::parse2(style)?))
+}
+
 impl Parse for Attribute {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         if input.peek(Pound) {
             let _ = input.parse::<Pound>()?;
             let ident = input.parse::<Ident>()?;
 
-            if ident == "attrs" {
+            if ident == "attrs" && input.peek(Paren) {
+                let content;
+                let _ = parenthesized!(content in input);
+
+                Ok(Self::SpreadPairs(ident, content.parse()?))
+            } else if ident == "attrs" {
                 Ok(Self::Spread(ident))
             } else {
                 Err(syn::Error::new(
@@ -92,7 +159,63 @@ impl Parse for Attribute {
                 ))
             }
         } else {
-            Ok(Self::NameValue(input.parse()?))
+            let attribute: NameValueAttribute = input.parse()?;
+
+            // `data: data!(...)` spreads the `data-*` attributes of the group
+            if let (false, Some(AttributeValue::Escaped(Expr::Macro(expr_macro)))) =
+                (attribute.is_maybe, &attribute.value)
+                && attribute.name.value() == "data"
+                && expr_macro
+                    .mac
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "data")
+            {
+                return Ok(Self::SpreadPairs(
+                    Ident::new("data", attribute.name.span()),
+                    Expr::Macro(expr_macro.clone()),
+                ));
+            }
+
+            Ok(Self::NameValue(attribute))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn test_literal_attribute_names() {
+        for name in ["@click", ":class", "x-on:submit.prevent", "data-id"] {
+            assert!(syn::parse2::<NameValueAttribute>(quote! { #name: "x" }).is_ok());
+        }
+
+        for name in ["", "a b", "a/b", "a>b", "a=b", "a\"b", "a'b", "a<b", "a\tb"] {
+            let error = syn::parse2::<NameValueAttribute>(quote! { #name: "x" })
+                .err()
+                .unwrap();
+
+            assert!(error.to_string().starts_with("invalid attribute name"));
         }
     }
+
+    #[test]
+    fn test_underscore_attribute_names() {
+        let name = |tokens| {
+            syn::parse2::<NameValueAttribute>(tokens)
+                .unwrap()
+                .name
+                .value()
+        };
+
+        assert_eq!(name(quote! { _: "on click" }), "_");
+        assert_eq!(name(quote! { __: "x" }), "__");
+        assert_eq!(name(quote! { x: "1" }), "x");
+        assert_eq!(name(quote! { hx_get: "/" }), "hx-get");
+    }
 }
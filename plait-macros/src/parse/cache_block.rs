@@ -0,0 +1,89 @@
+use syn::{
+    Expr, Ident, LitInt, braced, parenthesized,
+    parse::{Parse, ParseStream},
+    token::{Comma, Eq, Not},
+};
+
+use crate::ast::{CacheBlock, CacheTtl};
+
+fn parse_ttl(input: ParseStream<'_>) -> syn::Result<CacheTtl> {
+    if input.peek(LitInt) {
+        let lit: LitInt = input.parse()?;
+        let value: u64 = lit.base10_parse()?;
+
+        let millis = match lit.suffix() {
+            "ms" => Some(value),
+            "s" => value.checked_mul(1_000),
+            "m" => value.checked_mul(60_000),
+            "h" => value.checked_mul(3_600_000),
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected a duration with a unit, e.g. `500ms`, `60s`, `5m` or `1h`",
+                ));
+            }
+        };
+
+        millis
+            .map(CacheTtl::Millis)
+            .ok_or_else(|| syn::Error::new(lit.span(), "duration is too long"))
+    } else {
+        Ok(CacheTtl::Expr(input.parse()?))
+    }
+}
+
+impl Parse for CacheBlock {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let cache: Ident = input.parse()?;
+        let _: Not = input.parse()?;
+
+        let args;
+        parenthesized!(args in input);
+
+        let mut key = None;
+        let mut ttl = None;
+
+        while !args.is_empty() {
+            let name: Ident = args.parse()?;
+            let _: Eq = args.parse()?;
+
+            if name == "key" && key.is_none() {
+                key = Some(args.parse::<Expr>()?);
+            } else if name == "ttl" && ttl.is_none() {
+                ttl = Some(parse_ttl(&args)?);
+            } else if name == "key" || name == "ttl" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("duplicate `{name}` argument"),
+                ));
+            } else {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "unknown argument, expected `key` or `ttl`",
+                ));
+            }
+
+            if !args.is_empty() {
+                let _: Comma = args.parse()?;
+            }
+        }
+
+        let Some(key) = key else {
+            return Err(syn::Error::new(
+                cache.span(),
+                "missing `key` argument, e.g. `cache!(key = (user.id, page)) { ... }`",
+            ));
+        };
+
+        let content;
+        braced!(content in input);
+
+        let mut body = Vec::new();
+
+        while !content.is_empty() {
+            body.push(content.parse()?);
+        }
+
+        Ok(Self { key, ttl, body })
+    }
+}
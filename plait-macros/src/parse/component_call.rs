@@ -1,10 +1,40 @@
 use syn::{
-    Ident, braced, parenthesized,
+    Expr, Ident, Pat, braced,
+    ext::IdentExt,
+    parenthesized,
     parse::{Parse, ParseStream},
-    token::{At, Colon, Comma, Paren, Semi},
+    parse_quote,
+    token::{At, Brace, Colon, Comma, Or, Paren, Semi},
 };
 
-use crate::ast::{ComponentCall, ComponentCallField};
+use super::node::parse_nodes;
+use crate::ast::{ComponentCall, ComponentCallField, ComponentCallSlot};
+
+/// Returns `true` if `input` starts with `slot(name) {`. A `slot` element with a single boolean attribute would look
+/// the same, but isn't meaningful HTML.
+fn is_slot_block(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+    parse_slot_name(&fork).is_ok() && fork.peek(Brace)
+}
+
+/// Parses `slot(name)`, returning the name.
+fn parse_slot_name(input: ParseStream<'_>) -> syn::Result<Ident> {
+    let ident: Ident = input.parse()?;
+
+    if ident != "slot" {
+        return Err(syn::Error::new(ident.span(), "expected `slot`"));
+    }
+
+    let content;
+    let _ = parenthesized!(content in input);
+    let name = content.call(Ident::parse_any)?;
+
+    if !content.is_empty() {
+        return Err(content.error("expected a slot name, e.g. `slot(header)`"));
+    }
+
+    Ok(name)
+}
 
 impl Parse for ComponentCall {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
@@ -22,7 +52,19 @@ impl Parse for ComponentCall {
                 let _ = content.parse::<Semi>()?;
             } else {
                 while !content.is_empty() {
-                    fields.push(content.parse()?);
+                    let field: ComponentCallField = content.parse()?;
+
+                    if fields
+                        .iter()
+                        .any(|previous: &ComponentCallField| previous.ident == field.ident)
+                    {
+                        return Err(syn::Error::new(
+                            field.ident.span(),
+                            format!("prop `{}` is set more than once", field.ident),
+                        ));
+                    }
+
+                    fields.push(field);
 
                     if content.peek(Comma) {
                         let _ = content.parse::<Comma>()?;
@@ -53,10 +95,53 @@ impl Parse for ComponentCall {
         let content;
         let _ = braced!(content in input);
 
+        if content.peek(Or) {
+            let _ = content.parse::<Or>()?;
+            let render_prop = Pat::parse_single(&content)?;
+
+            if !content.peek(Or) {
+                return Err(content.error("render props take a single parameter, e.g. `|item|`"));
+            }
+            let _ = content.parse::<Or>()?;
+
+            return Ok(Self {
+                path,
+                fields,
+                attributes,
+                children: parse_nodes(&content)?,
+                slots: Vec::new(),
+                render_prop: Some(render_prop),
+            });
+        }
+
         let mut children = Vec::new();
+        let mut slots: Vec<ComponentCallSlot> = Vec::new();
 
         while !content.is_empty() {
-            children.push(content.parse()?);
+            if !is_slot_block(&content) {
+                children.push(content.parse()?);
+                continue;
+            }
+
+            let name = parse_slot_name(&content)?;
+
+            let slot_content;
+            let _ = braced!(slot_content in content);
+            let slot_children = parse_nodes(&slot_content)?;
+
+            if name == "default" {
+                children.extend(slot_children);
+            } else if slots.iter().any(|slot| slot.name == name) {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("slot `{name}` is filled more than once"),
+                ));
+            } else {
+                slots.push(ComponentCallSlot {
+                    name,
+                    children: slot_children,
+                });
+            }
         }
 
         Ok(Self {
@@ -64,6 +149,8 @@ impl Parse for ComponentCall {
             fields,
             attributes,
             children,
+            slots,
+            render_prop: None,
         })
     }
 }
@@ -74,7 +161,12 @@ impl Parse for ComponentCallField {
 
         let value = if input.peek(Colon) {
             let _ = input.parse::<Colon>()?;
-            input.parse().map(Some)
+
+            if input.peek(At) {
+                parse_lazy_value(input).map(Some)
+            } else {
+                input.parse().map(Some)
+            }
         } else {
             Ok(None)
         }?;
@@ -82,3 +174,27 @@ impl Parse for ComponentCallField {
         Ok(Self { ident, value })
     }
 }
+
+/// Parses `@lazy(expr)`, which passes the closure `|| expr` so that `expr` is only evaluated if the component calls it.
+fn parse_lazy_value(input: ParseStream<'_>) -> syn::Result<Expr> {
+    let _ = input.parse::<At>()?;
+    let ident: Ident = input.parse()?;
+
+    if ident != "lazy" {
+        return Err(syn::Error::new(
+            ident.span(),
+            "expected `@lazy(expr)` or an expression",
+        ));
+    }
+
+    let content;
+    let _ = parenthesized!(content in input);
+    let value: Expr = content.parse()?;
+
+    if !content.is_empty() {
+        return Err(content.error("expected a single expression in `@lazy(..)`"));
+    }
+
+    // The closure isn't written by the user, so lints about it (e.g. `redundant_closure`) don't apply
+    Ok(parse_quote! { || #value })
+}
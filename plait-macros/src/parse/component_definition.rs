@@ -1,17 +1,39 @@
 use syn::{
-    Attribute, Generics, braced, parenthesized,
+    Attribute, Generics, Type, braced, parenthesized,
     parse::{Parse, ParseStream},
-    token::{Colon, Comma, Fn, Paren},
+    parse_quote,
+    token::{Colon, Comma, Fn, Paren, PathSep, Question},
 };
 
-use crate::ast::{ComponentDefinition, ComponentDefinitionField};
+use crate::ast::{ComponentDefinition, ComponentDefinitionField, ComponentDefinitions};
+
+impl Parse for ComponentDefinitions {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut definitions = Vec::new();
+
+        while !input.is_empty() {
+            definitions.push(input.parse()?);
+        }
+
+        Ok(Self { definitions })
+    }
+}
 
 impl Parse for ComponentDefinition {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let attributes = input.call(Attribute::parse_outer)?;
         let visibility = input.parse()?;
         let _ = input.parse::<Fn>()?;
-        let ident = input.parse()?;
+
+        let mut module = Vec::new();
+        let mut ident = input.parse()?;
+
+        while input.peek(PathSep) {
+            let _ = input.parse::<PathSep>()?;
+            module.push(ident);
+            ident = input.parse()?;
+        }
+
         let mut generics = input.parse::<Generics>()?;
 
         let fields = if input.peek(Paren) {
@@ -49,6 +71,7 @@ impl Parse for ComponentDefinition {
         Ok(Self {
             attributes,
             visibility,
+            module,
             ident,
             generics,
             fields,
@@ -59,9 +82,21 @@ impl Parse for ComponentDefinition {
 
 impl Parse for ComponentDefinitionField {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
         let ident = input.parse()?;
+        let optional = input.parse::<Option<Question>>()?.is_some();
         let _ = input.parse::<Colon>()?;
-        let ty = input.parse()?;
-        Ok(Self { ident, ty })
+        let mut ty: Type = input.parse()?;
+
+        if optional {
+            ty = parse_quote! { ::core::option::Option<#ty> };
+        }
+
+        Ok(Self {
+            attributes,
+            ident,
+            ty,
+            optional,
+        })
     }
 }
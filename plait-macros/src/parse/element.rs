@@ -7,7 +7,10 @@ use syn::{
     token::{Brace, Comma, Paren, Semi},
 };
 
-use crate::{ast::Element, utils::is_void_element};
+use crate::{
+    ast::Element,
+    utils::{is_mathml_element, is_void_element, svg_element_name},
+};
 
 impl Parse for Element {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
@@ -45,7 +48,10 @@ impl Parse for Element {
             Vec::new()
         };
 
-        if is_void {
+        let is_foreign =
+            svg_element_name(&name_string).is_some() || is_mathml_element(&name_string);
+
+        if is_void || (is_foreign && input.peek(Semi)) {
             if !input.peek(Semi) {
                 return Err(syn::Error::new(
                     name_ident.span(),
@@ -1,31 +1,97 @@
 use syn::{
-    Expr, Pat, braced,
+    Expr, Ident, Pat, braced,
+    ext::IdentExt,
+    parenthesized,
     parse::{Parse, ParseStream},
-    token::{For, In},
+    parse_quote,
+    token::{Comma, Else, For, In, Pound},
 };
 
-use crate::ast::ForLoop;
+use crate::ast::{ForLoop, Node};
+
+mod kw {
+    syn::custom_keyword!(sep);
+}
+
+fn parse_block(input: ParseStream<'_>) -> syn::Result<Vec<Node>> {
+    let content;
+    let _ = braced!(content in input);
+
+    let mut nodes = Vec::new();
+
+    while !content.is_empty() {
+        nodes.push(content.parse()?);
+    }
+
+    Ok(nodes)
+}
+
+/// Parses the `#children` or `#slot(name)` of `for child in #children`, as an iterator over the children.
+fn parse_each_child(input: ParseStream<'_>) -> syn::Result<Expr> {
+    let _: Pound = input.parse()?;
+    let ident = input.call(Ident::parse_any)?;
+
+    let name = if ident == "children" {
+        "default".to_owned()
+    } else if ident == "slot" {
+        let content;
+        parenthesized!(content in input);
+
+        let name = content.call(Ident::parse_any)?;
+
+        if !content.is_empty() {
+            return Err(content.error("expected a slot name, e.g. `#slot(header)`"));
+        }
+
+        name.to_string()
+    } else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "expected `#children` or `#slot(name)`",
+        ));
+    };
+
+    let children = Ident::new("children", ident.span());
+
+    Ok(parse_quote! { ::plait::each_child(&#children, #name) })
+}
 
 impl Parse for ForLoop {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let _: For = input.parse()?;
         let pattern = Pat::parse_multi_with_leading_vert(input)?;
         let _: In = input.parse()?;
-        let expression = input.call(Expr::parse_without_eager_brace)?;
+        let expression = if input.peek(Pound) {
+            parse_each_child(input)?
+        } else {
+            input.call(Expr::parse_without_eager_brace)?
+        };
 
-        let content;
-        let _ = braced!(content in input);
+        let separator = if input.peek(Comma) {
+            let _: Comma = input.parse()?;
+            let _: kw::sep = input.parse()?;
 
-        let mut body = Vec::new();
+            Some(parse_block(input)?)
+        } else {
+            None
+        };
 
-        while !content.is_empty() {
-            body.push(content.parse()?);
-        }
+        let body = parse_block(input)?;
+
+        let else_branch = if input.peek(Else) {
+            let _: Else = input.parse()?;
+
+            Some(parse_block(input)?)
+        } else {
+            None
+        };
 
         Ok(Self {
             pattern,
             expression,
+            separator,
             body,
+            else_branch,
         })
     }
 }
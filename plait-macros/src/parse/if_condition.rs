@@ -1,7 +1,9 @@
+use quote::quote_spanned;
 use syn::{
-    Expr, braced,
+    Expr, Ident, braced, parenthesized,
     parse::{Parse, ParseStream},
-    token::{Else, If},
+    spanned::Spanned,
+    token::{At, Else, If},
 };
 
 use crate::ast::{ElseBranch, IfCondition};
@@ -54,3 +56,72 @@ impl Parse for ElseBranch {
         }
     }
 }
+
+/// Parses `@can(permission) { ... } @else { ... }` into a condition checking the permission with
+/// `plait::permission::can`.
+pub(super) fn parse_can_block(input: ParseStream<'_>) -> syn::Result<IfCondition> {
+    let _: At = input.parse()?;
+    let _: Ident = input.parse()?;
+
+    let args;
+    let _ = parenthesized!(args in input);
+    let permission: Expr = args.parse()?;
+
+    let condition = syn::parse2(quote_spanned! {permission.span()=>
+        ::plait::permission::can(&(#permission))
+    })?;
+
+    let content;
+    let _ = braced!(content in input);
+
+    let mut then_branch = Vec::new();
+
+    while !content.is_empty() {
+        then_branch.push(content.parse()?);
+    }
+
+    let else_branch = if input.peek(At) && input.peek2(Else) {
+        let _: At = input.parse()?;
+        let _: Else = input.parse()?;
+
+        if input.peek(At) {
+            Some(ElseBranch::If(Box::new(parse_can_block(input)?)))
+        } else {
+            Some(input.parse()?)
+        }
+    } else {
+        None
+    };
+
+    Ok(IfCondition {
+        condition,
+        then_branch,
+        else_branch,
+    })
+}
+
+/// Parses `@debug { ... }` into a condition that only holds in debug builds, unless debug content has been enabled
+/// with `plait::debug::set_enabled`.
+pub(super) fn parse_debug_block(input: ParseStream<'_>) -> syn::Result<IfCondition> {
+    let _: At = input.parse()?;
+    let _: Ident = input.parse()?;
+
+    let condition = syn::parse_quote! {
+        ::core::cfg!(debug_assertions) || ::plait::debug::is_enabled()
+    };
+
+    let content;
+    let _ = braced!(content in input);
+
+    let mut then_branch = Vec::new();
+
+    while !content.is_empty() {
+        then_branch.push(content.parse()?);
+    }
+
+    Ok(IfCondition {
+        condition,
+        then_branch,
+        else_branch: None,
+    })
+}
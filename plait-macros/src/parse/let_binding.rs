@@ -1,9 +1,10 @@
 use syn::{
-    Pat, PatType, Type,
+    Pat, PatType, Type, braced,
     parse::{Parse, ParseStream},
-    token::{Colon, Eq, Let, Semi},
+    token::{Colon, Else, Eq, Let, Semi},
 };
 
+use super::node::parse_nodes;
 use crate::ast::LetBinding;
 
 impl Parse for LetBinding {
@@ -29,8 +30,23 @@ impl Parse for LetBinding {
             None
         };
 
+        let else_branch = if expr.is_some() && input.peek(Else) {
+            let _: Else = input.parse()?;
+
+            let content;
+            let _ = braced!(content in input);
+
+            Some(parse_nodes(&content)?)
+        } else {
+            None
+        };
+
         let _: Semi = input.parse()?;
 
-        Ok(Self { pattern, expr })
+        Ok(Self {
+            pattern,
+            expr,
+            else_branch,
+        })
     }
 }
@@ -1,13 +1,170 @@
+use proc_macro2::TokenStream;
 use syn::{
-    Ident, LitBool, LitChar, LitFloat, LitInt, LitStr, braced,
+    Expr, Ident, LitBool, LitChar, LitFloat, LitInt, LitStr, braced, bracketed,
     ext::IdentExt,
     parenthesized,
     parse::{Parse, ParseStream},
-    token::{At, Brace, For, If, Let, Match, Paren, Pound},
+    parse_quote, parse_quote_spanned,
+    token::{At, Brace, Bracket, For, If, Let, Match, Not, Paren, Pound, While},
 };
 
+use super::if_condition::{parse_can_block, parse_debug_block};
 use crate::ast::{Element, Node};
 
+pub(super) fn parse_nodes(input: ParseStream<'_>) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        nodes.push(input.parse()?);
+    }
+
+    Ok(nodes)
+}
+
+/// Returns `true` if the input starts with `@can(`.
+fn is_can_block(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+
+    fork.parse::<At>().is_ok()
+        && fork.parse::<Ident>().is_ok_and(|ident| ident == "can")
+        && fork.peek(Paren)
+}
+
+/// Returns `true` if the input starts with `@debug {`.
+fn is_debug_block(input: ParseStream<'_>) -> bool {
+    let fork = input.fork();
+
+    fork.parse::<At>().is_ok()
+        && fork.parse::<Ident>().is_ok_and(|ident| ident == "debug")
+        && fork.peek(Brace)
+}
+
+/// Parses a nested `html! { ... }` (or `html!(...)`, `html![...]`), which is inlined into the enclosing template.
+fn parse_nested_html(input: ParseStream<'_>) -> syn::Result<Vec<Node>> {
+    let _: Ident = input.parse()?;
+    let _: Not = input.parse()?;
+
+    let content;
+    if input.peek(Paren) {
+        parenthesized!(content in input);
+    } else if input.peek(Bracket) {
+        bracketed!(content in input);
+    } else {
+        braced!(content in input);
+    }
+
+    parse_nodes(&content)
+}
+
+/// Parses an `f!("Hello, {name}!")` text into its static text and the escaped values of its placeholders, like
+/// `format!`: `{{` and `}}` are literal braces.
+fn parse_interpolated_text(input: ParseStream<'_>) -> syn::Result<Vec<Node>> {
+    let _: Ident = input.parse()?;
+    let _: Not = input.parse()?;
+
+    let content;
+    parenthesized!(content in input);
+    let text: LitStr = content.parse()?;
+    if !content.is_empty() {
+        return Err(content.error("expected a single string literal in `f!`"));
+    }
+
+    let value = text.value();
+    let mut nodes = Vec::new();
+    let mut static_part = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                static_part.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                static_part.push('}');
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            return Err(syn::Error::new(
+                                text.span(),
+                                "unclosed `{` in `f!` text, use `{{` for a literal `{`",
+                            ));
+                        }
+                    }
+                }
+
+                let placeholder = placeholder.trim();
+                if placeholder.is_empty() {
+                    return Err(syn::Error::new(
+                        text.span(),
+                        "empty placeholder `{}` in `f!` text",
+                    ));
+                }
+
+                let expr = syn::parse_str::<Expr>(placeholder).map_err(|_| {
+                    syn::Error::new(
+                        text.span(),
+                        format!("invalid placeholder `{{{placeholder}}}` in `f!` text"),
+                    )
+                })?;
+
+                if !static_part.is_empty() {
+                    let static_part = std::mem::take(&mut static_part);
+                    nodes.push(Node::LitStr(LitStr::new(&static_part, text.span())));
+                }
+                // Parentheses keep an expression like `count + 1` together when it is rendered by reference.
+                let expr = match expr {
+                    Expr::Call(_)
+                    | Expr::Field(_)
+                    | Expr::Index(_)
+                    | Expr::Lit(_)
+                    | Expr::MethodCall(_)
+                    | Expr::Path(_) => expr,
+                    expr => parse_quote!((#expr)),
+                };
+                nodes.push(Node::Escaped(expr));
+            }
+            '}' => {
+                return Err(syn::Error::new(
+                    text.span(),
+                    "unmatched `}` in `f!` text, use `}}` for a literal `}`",
+                ));
+            }
+            c => static_part.push(c),
+        }
+    }
+
+    if !static_part.is_empty() {
+        nodes.push(Node::LitStr(LitStr::new(&static_part, text.span())));
+    }
+
+    Ok(nodes)
+}
+
+/// Parses a `t!("key", name = value)` translation into a call to `plait::t!`, whose message is rendered escaped.
+fn parse_translation(input: ParseStream<'_>) -> syn::Result<Expr> {
+    let ident: Ident = input.parse()?;
+    let _: Not = input.parse()?;
+
+    if !cfg!(feature = "i18n") {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`t!` requires the `i18n` feature of plait",
+        ));
+    }
+
+    let content;
+    parenthesized!(content in input);
+    let arguments: TokenStream = content.parse()?;
+
+    Ok(parse_quote_spanned! {ident.span()=> ::plait::t!(#arguments) })
+}
+
 impl Parse for Node {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         if input.peek(LitStr) {
@@ -24,12 +181,7 @@ impl Parse for Node {
             let content;
             braced!(content in input);
 
-            let mut nodes = Vec::new();
-            while !content.is_empty() {
-                nodes.push(content.parse()?);
-            }
-
-            Ok(Node::Block(nodes))
+            Ok(Node::Block(parse_nodes(&content)?))
         } else if input.peek(Let) {
             Ok(Node::LetBinding(input.parse()?))
         } else if input.peek(If) {
@@ -38,6 +190,8 @@ impl Parse for Node {
             Ok(Node::MatchExpression(input.parse()?))
         } else if input.peek(For) {
             Ok(Node::ForLoop(input.parse()?))
+        } else if input.peek(While) {
+            Ok(Node::WhileLoop(input.parse()?))
         } else if input.peek(Paren) {
             let content;
             parenthesized!(content in input);
@@ -51,13 +205,64 @@ impl Parse for Node {
                 parenthesized!(content in input);
 
                 Ok(Node::Raw(content.parse()?))
+            } else if input.peek(Bracket) {
+                let content;
+                bracketed!(content in input);
+
+                let ident = content.call(Ident::parse_any)?;
+
+                if ident == "allow_invalid_nesting" && content.is_empty() {
+                    return Ok(Node::AllowInvalidNesting(Box::new(input.parse()?)));
+                }
+
+                if (ident == "deny" || ident == "allow") && content.peek(Paren) {
+                    let lint;
+                    parenthesized!(lint in content);
+
+                    let lint_name = lint.call(Ident::parse_any)?;
+
+                    if lint_name == "unknown_attributes" && lint.is_empty() && content.is_empty() {
+                        return Ok(Node::UnknownAttributes {
+                            deny: ident == "deny",
+                            node: Box::new(input.parse()?),
+                        });
+                    }
+                }
+
+                Err(syn::Error::new(
+                    ident.span(),
+                    "unknown attribute, expected `#[allow_invalid_nesting]`, `#[deny(unknown_attributes)]` or \
+                     `#[allow(unknown_attributes)]`",
+                ))
             } else if input.peek(Ident::peek_any) {
                 let ident: Ident = input.parse()?;
 
                 if ident == "doctype" {
                     Ok(Node::Doctype)
+                } else if ident == "children" && input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in input);
+
+                    Ok(Node::RenderProp {
+                        children: ident,
+                        argument: content.parse()?,
+                    })
                 } else if ident == "children" {
                     Ok(Node::Children(ident))
+                } else if ident == "slot" && input.peek(Paren) {
+                    let content;
+                    parenthesized!(content in input);
+
+                    let name = content.call(Ident::parse_any)?;
+
+                    if !content.is_empty() {
+                        return Err(content.error("expected a slot name, e.g. `#slot(header)`"));
+                    }
+
+                    Ok(Node::Slot {
+                        children: Ident::new("children", ident.span()),
+                        name,
+                    })
                 } else {
                     Err(syn::Error::new(
                         ident.span(),
@@ -67,8 +272,36 @@ impl Parse for Node {
             } else {
                 Err(input.error("unexpected token in html node"))
             }
+        } else if input.peek(At) && input.peek2(Brace) {
+            let _: At = input.parse()?;
+
+            let content;
+            braced!(content in input);
+
+            Ok(Node::Block(parse_nodes(&content)?))
+        } else if input.peek(At) && is_can_block(input) {
+            Ok(Node::IfCondition(parse_can_block(input)?))
+        } else if input.peek(At) && is_debug_block(input) {
+            Ok(Node::IfCondition(parse_debug_block(input)?))
         } else if input.peek(At) {
             Ok(Node::ComponentCall(input.parse()?))
+        } else if input.peek(Ident) && input.peek2(Not) {
+            let ident: Ident = input.fork().parse()?;
+
+            if ident == "cache" {
+                Ok(Node::CacheBlock(input.parse()?))
+            } else if ident == "html" {
+                Ok(Node::Block(parse_nested_html(input)?))
+            } else if ident == "f" {
+                Ok(Node::Block(parse_interpolated_text(input)?))
+            } else if ident == "t" {
+                Ok(Node::Escaped(parse_translation(input)?))
+            } else {
+                Err(syn::Error::new(
+                    ident.span(),
+                    "unknown macro in html template, expected `cache!`, `f!`, `html!` or `t!`",
+                ))
+            }
         } else if input.peek(Ident::peek_any) {
             Ok(Node::Element(Element::parse(input)?))
         } else {
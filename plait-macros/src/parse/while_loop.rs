@@ -0,0 +1,26 @@
+use syn::{
+    Expr, braced,
+    parse::{Parse, ParseStream},
+    token::While,
+};
+
+use crate::ast::WhileLoop;
+
+impl Parse for WhileLoop {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let _: While = input.parse()?;
+        // `while let` conditions are parsed as `Expr::Let`, like `if let`
+        let condition = input.call(Expr::parse_without_eager_brace)?;
+
+        let content;
+        let _ = braced!(content in input);
+
+        let mut body = Vec::new();
+
+        while !content.is_empty() {
+            body.push(content.parse()?);
+        }
+
+        Ok(Self { condition, body })
+    }
+}
@@ -1,5 +1,5 @@
 /// Returns true if the given element name is a void element.
-/// Expects the name to be in ASCII lowercase.
+/// Expects the name to be in ASCII lowercase. Keep in sync with `plait::spec::VOID_ELEMENTS`.
 pub fn is_void_element(tag: &str) -> bool {
     matches!(
         tag,
@@ -20,6 +20,244 @@ pub fn is_void_element(tag: &str) -> bool {
     )
 }
 
+/// Returns true if the value of the given attribute is a URL.
+/// Expects the name to be in ASCII lowercase. Keep in sync with `plait::spec::URL_ATTRIBUTES`.
+pub fn is_url_attribute(name: &str) -> bool {
+    matches!(
+        name,
+        "action"
+            | "cite"
+            | "data"
+            | "formaction"
+            | "href"
+            | "manifest"
+            | "ping"
+            | "poster"
+            | "src"
+            | "srcset"
+    )
+}
+
+/// Returns true if newlines and tabs in the value of the given attribute are written as character references: for
+/// every attribute with the `encode-attribute-whitespace` feature, and for whitespace sensitive attributes otherwise.
+/// Expects the name to be in ASCII lowercase. Keep in sync with `plait::spec::WHITESPACE_SENSITIVE_ATTRIBUTES`.
+pub fn encodes_attribute_whitespace(name: &str) -> bool {
+    cfg!(feature = "encode-attribute-whitespace")
+        || matches!(
+            name,
+            "content" | "imagesizes" | "imagesrcset" | "sizes" | "srcset"
+        )
+}
+
+/// Elements of SVG that don't share their name with an HTML element, as written in SVG. Names are sorted by their ASCII
+/// lowercase form.
+const SVG_ELEMENTS: &[&str] = &[
+    "animate",
+    "animateMotion",
+    "animateTransform",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "foreignObject",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "mpath",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "set",
+    "stop",
+    "svg",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "tspan",
+    "use",
+    "view",
+];
+
+/// Attributes of SVG elements whose name isn't lowercase, as written in SVG. Names are sorted by their ASCII lowercase
+/// form.
+const SVG_ATTRIBUTES: &[&str] = &[
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseProfile",
+    "calcMode",
+    "clipPathUnits",
+    "diffuseConstant",
+    "edgeMode",
+    "filterUnits",
+    "glyphRef",
+    "gradientTransform",
+    "gradientUnits",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "limitingConeAngle",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "maskContentUnits",
+    "maskUnits",
+    "numOctaves",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "requiredExtensions",
+    "requiredFeatures",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "textLength",
+    "viewBox",
+    "viewTarget",
+    "xChannelSelector",
+    "yChannelSelector",
+    "zoomAndPan",
+];
+
+/// Looks up `name` in a table sorted by ASCII lowercase form, ignoring ASCII case.
+fn find_ignore_case(table: &[&'static str], name: &str) -> Option<&'static str> {
+    table
+        .binary_search_by(|entry| {
+            entry
+                .bytes()
+                .map(|byte| byte.to_ascii_lowercase())
+                .cmp(name.bytes().map(|byte| byte.to_ascii_lowercase()))
+        })
+        .ok()
+        .map(|index| table[index])
+}
+
+/// Returns the name of the given element as written in SVG, if it is an SVG element that doesn't share its name with
+/// an HTML element (`a`, `script`, `style` and `title` are shared).
+pub fn svg_element_name(tag: &str) -> Option<&'static str> {
+    find_ignore_case(SVG_ELEMENTS, tag)
+}
+
+/// Returns the name of the given SVG attribute as written in SVG, if it isn't lowercase (e.g. `viewBox`).
+pub fn svg_attribute_name(name: &str) -> Option<&'static str> {
+    find_ignore_case(SVG_ATTRIBUTES, name)
+}
+
+/// Elements of MathML. Keep sorted.
+const MATHML_ELEMENTS: &[&str] = &[
+    "annotation",
+    "annotation-xml",
+    "maction",
+    "math",
+    "menclose",
+    "merror",
+    "mfenced",
+    "mfrac",
+    "mi",
+    "mmultiscripts",
+    "mn",
+    "mo",
+    "mover",
+    "mpadded",
+    "mphantom",
+    "mprescripts",
+    "mroot",
+    "mrow",
+    "ms",
+    "mspace",
+    "msqrt",
+    "mstyle",
+    "msub",
+    "msubsup",
+    "msup",
+    "mtable",
+    "mtd",
+    "mtext",
+    "mtr",
+    "munder",
+    "munderover",
+    "none",
+    "semantics",
+];
+
+/// Returns true if the given element name is a MathML element.
+/// Expects the name to be in ASCII lowercase.
+pub fn is_mathml_element(tag: &str) -> bool {
+    MATHML_ELEMENTS.binary_search(&tag).is_ok()
+}
+
+/// Returns the name of the given MathML attribute as written in MathML, if it isn't lowercase (`definitionURL`).
+pub fn mathml_attribute_name(name: &str) -> Option<&'static str> {
+    name.eq_ignore_ascii_case("definitionurl")
+        .then_some("definitionURL")
+}
+
+/// Returns true if the given name can be written as an attribute name without ending it early.
+/// Keep in sync with `plait::spec::is_valid_attribute_name`.
+pub fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.contains(|c: char| {
+            c.is_ascii_control() || matches!(c, ' ' | '/' | '>' | '=' | '"' | '\'' | '<')
+        })
+}
+
 /// Escapes a HTML string into a writer.
 pub fn escape_html_to(writer: &mut String, input: &str) {
     // Fast path for strings without special characters
@@ -61,3 +299,45 @@ pub fn escape_html_to(writer: &mut String, input: &str) {
         writer.push_str(&input[last..]);
     }
 }
+
+/// Writes an escaped attribute value into a writer, with newlines, carriage returns and tabs as numeric character
+/// references. Keep in sync with `plait::AttributeWhitespace`.
+pub fn encode_whitespace_to(writer: &mut String, input: &str) {
+    for c in input.chars() {
+        match c {
+            '\n' => writer.push_str("&#10;"),
+            '\r' => writer.push_str("&#13;"),
+            '\t' => writer.push_str("&#9;"),
+            c => writer.push(c),
+        }
+    }
+}
+
+/// Writes the text of the raw text element `tag` (`script` or `style`) into a writer: as it is, except that the `<` of
+/// `</script` and `<!--` is written as `\u003c` in `script` and `</style` as `<\/style` in `style`, so that the text
+/// can't end the element early.
+/// Keep in sync with `plait::utils::escape_raw_text_to`.
+pub fn escape_raw_text_to(writer: &mut String, input: &str, tag: &str) {
+    let mut last = 0;
+
+    for (index, _) in input.match_indices('<') {
+        let rest = &input.as_bytes()[index + 1..];
+
+        let ends_element = rest.len() > tag.len()
+            && rest[0] == b'/'
+            && rest[1..=tag.len()].eq_ignore_ascii_case(tag.as_bytes());
+        let starts_comment = tag == "script" && rest.starts_with(b"!--");
+
+        if tag == "script" && (ends_element || starts_comment) {
+            writer.push_str(&input[last..index]);
+            writer.push_str("\\u003c");
+            last = index + 1;
+        } else if ends_element {
+            writer.push_str(&input[last..=index]);
+            writer.push('\\');
+            last = index + 1;
+        }
+    }
+
+    writer.push_str(&input[last..]);
+}
@@ -0,0 +1,111 @@
+//! Renders a 10,000-row table with long class strings with `html!`, and with every tag and attribute part written
+//! separately, and prints the median time of each.
+//!
+//! Run with `cargo bench --bench table`.
+
+use std::{
+    fmt::{self, Write},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use plait::{RenderEscaped, ToHtml, html};
+
+const ROWS: usize = 10_000;
+const RUNS: usize = 51;
+
+const ROW_CLASS: &str =
+    "table-row align-middle border-bottom border-light-subtle bg-body hover-shadow-sm";
+const NUM_CLASS: &str =
+    "table-cell text-end font-monospace text-body-secondary px-3 py-2 fw-semibold";
+const NAME_CLASS: &str =
+    "table-cell text-start text-truncate text-body-emphasis px-3 py-2 fw-normal";
+
+fn rows() -> Vec<(usize, String)> {
+    (0..ROWS).map(|id| (id, format!("Row <{id}>"))).collect()
+}
+
+fn render_html_macro(rows: &[(usize, String)]) -> String {
+    let table = html! {
+        tbody {
+            for (id, name) in rows.iter() {
+                tr(class: "table-row align-middle border-bottom border-light-subtle bg-body hover-shadow-sm") {
+                    td(class: "table-cell text-end font-monospace text-body-secondary px-3 py-2 fw-semibold") {
+                        (id)
+                    }
+                    td(class: "table-cell text-start text-truncate text-body-emphasis px-3 py-2 fw-normal") {
+                        (name)
+                    }
+                }
+            }
+        }
+    };
+
+    table.to_html().into()
+}
+
+fn render_separate_writes(rows: &[(usize, String)]) -> String {
+    fn element(f: &mut String, tag: &str, class: &str) -> fmt::Result {
+        f.write_str("<")?;
+        f.write_str(tag)?;
+        f.write_str(" ")?;
+        f.write_str("class")?;
+        f.write_str("=\"")?;
+        f.write_str(class)?;
+        f.write_str("\"")?;
+        f.write_str(">")
+    }
+
+    fn close(f: &mut String, tag: &str) -> fmt::Result {
+        f.write_str("</")?;
+        f.write_str(tag)?;
+        f.write_str(">")
+    }
+
+    let mut f = String::new();
+    let mut render = || -> fmt::Result {
+        f.write_str("<tbody>")?;
+        for (id, name) in rows {
+            element(&mut f, "tr", ROW_CLASS)?;
+            element(&mut f, "td", NUM_CLASS)?;
+            id.render_escaped(&mut f)?;
+            close(&mut f, "td")?;
+            element(&mut f, "td", NAME_CLASS)?;
+            name.render_escaped(&mut f)?;
+            close(&mut f, "td")?;
+            close(&mut f, "tr")?;
+        }
+        f.write_str("</tbody>")
+    };
+    render().expect("writing to a string never fails");
+
+    f
+}
+
+/// Returns the median time of `RUNS` calls of `render`.
+fn median(render: impl Fn() -> String) -> Duration {
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(render());
+            start.elapsed()
+        })
+        .collect();
+
+    times.sort();
+    times[RUNS / 2]
+}
+
+fn main() {
+    let rows = rows();
+
+    let output = render_html_macro(&rows);
+    assert_eq!(output, render_separate_writes(&rows));
+    println!("{ROWS} rows, {} bytes of output", output.len());
+
+    let html_macro = median(|| render_html_macro(black_box(&rows)));
+    let separate_writes = median(|| render_separate_writes(black_box(&rows)));
+
+    println!("html!:           {html_macro:?}");
+    println!("separate writes: {separate_writes:?}");
+}
@@ -0,0 +1,193 @@
+//! Headless components for common [WAI-ARIA Authoring Practices](https://www.w3.org/WAI/ARIA/apg/patterns/) patterns.
+//!
+//! The components render only markup: roles, ids and the `aria-*` relationships between the parts of each pattern.
+//! Behavior (toggling `aria-expanded`/`hidden`, keyboard navigation, focus management) is left to the client, e.g.
+//! htmx or Alpine. Every component takes an `id` from which the ids of its parts are derived, so the relationships
+//! stay consistent without manual bookkeeping.
+//!
+//! | Component      | Pattern                                                              |
+//! |----------------|----------------------------------------------------------------------|
+//! | [`Disclosure`] | [Disclosure](https://www.w3.org/WAI/ARIA/apg/patterns/disclosure/)   |
+//! | [`Dialog`]     | [Dialog (modal)](https://www.w3.org/WAI/ARIA/apg/patterns/dialog-modal/) |
+//! | [`Tabs`]       | [Tabs](https://www.w3.org/WAI/ARIA/apg/patterns/tabs/)               |
+//! | [`Menu`]       | [Menu button](https://www.w3.org/WAI/ARIA/apg/patterns/menu-button/) |
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, aria::Disclosure};
+//!
+//! let frag = html! {
+//!     @Disclosure(id: "faq-1", label: "What is plait?", open: false) {
+//!         p { "An HTML templating library." }
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<button type=\"button\" aria-expanded=\"false\" aria-controls=\"faq-1\">What is plait?</button>\
+//!     <div id=\"faq-1\" hidden><p>An HTML templating library.</p></div>"
+//! );
+//! ```
+use std::fmt;
+
+use crate::{RenderEscaped, component};
+
+fn bool_str(value: bool) -> &'static str {
+    if value { "true" } else { "false" }
+}
+
+/// Trait for items rendered as a tab and its panel by [`Tabs`].
+///
+/// Implemented for `(label, panel)` pairs of [`RenderEscaped`] values and for references.
+pub trait TabItem {
+    /// Renders the label of the tab.
+    fn render_label(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result;
+
+    /// Renders the content of the tab panel.
+    fn render_panel(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result;
+}
+
+impl<T> TabItem for &T
+where
+    T: TabItem + ?Sized,
+{
+    fn render_label(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        (**self).render_label(f)
+    }
+
+    fn render_panel(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        (**self).render_panel(f)
+    }
+}
+
+impl<L, P> TabItem for (L, P)
+where
+    L: RenderEscaped,
+    P: RenderEscaped,
+{
+    fn render_label(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.0.render_escaped(f)
+    }
+
+    fn render_panel(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.1.render_escaped(f)
+    }
+}
+
+struct TabLabel<'a, T>(&'a T);
+
+impl<T> RenderEscaped for TabLabel<'_, T>
+where
+    T: TabItem,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.0.render_label(f)
+    }
+}
+
+struct TabPanel<'a, T>(&'a T);
+
+impl<T> RenderEscaped for TabPanel<'_, T>
+where
+    T: TabItem,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.0.render_panel(f)
+    }
+}
+
+component! {
+    /// A button that shows and hides a section of content.
+    ///
+    /// Renders a `button` with `aria-expanded` and `aria-controls` pointing at a `<div id="{id}">` containing the
+    /// children, which is `hidden` unless `open` is `true`. Extra attributes are forwarded to the button.
+    pub fn Disclosure(id: &str, label: impl RenderEscaped, open: bool) {
+        button(type: "button", aria_expanded: (bool_str(*open)), aria_controls: id, #attrs) { (label) }
+        div(id: id, hidden?: (!*open)) { #children }
+    }
+
+    /// A modal dialog.
+    ///
+    /// Renders a `<div role="dialog" aria-modal="true" id="{id}">` labelled by an `<h2 id="{id}-title">` containing
+    /// `title`, followed by the children. Extra attributes are forwarded to the dialog element.
+    pub fn Dialog(id: &str, title: impl RenderEscaped) {
+        let title_id = format!("{id}-title");
+
+        div(role: "dialog", id: id, aria_modal: "true", aria_labelledby: title_id, #attrs) {
+            h2(id: title_id) { (title) }
+            #children
+        }
+    }
+
+    /// A set of tabs with one panel per tab.
+    ///
+    /// Renders a `<div role="tablist">` with a `<button role="tab" id="{id}-tab-{n}">` per item, each controlling a
+    /// `<div role="tabpanel" id="{id}-panel-{n}">` that is labelled by its tab (`n` starts at `0`). The tab at index
+    /// `selected` has `aria-selected="true"` and its panel is shown; the other tabs are removed from the tab sequence
+    /// and their panels are `hidden`. Everything is wrapped in a `<div id="{id}">` which receives any extra
+    /// attributes.
+    pub fn Tabs<I>(id: &str, tabs: I, selected: usize) where I: IntoIterator + Clone, I::Item: TabItem {
+        div(id: id, #attrs) {
+            div(role: "tablist") {
+                for (index, tab) in tabs.clone().into_iter().enumerate() {
+                    let is_selected = index == *selected;
+
+                    button(
+                        type: "button",
+                        role: "tab",
+                        id: (format!("{id}-tab-{index}")),
+                        aria_selected: (bool_str(is_selected)),
+                        aria_controls: (format!("{id}-panel-{index}")),
+                        tabindex?: ((!is_selected).then_some("-1"))
+                    ) {
+                        (TabLabel(&tab))
+                    }
+                }
+            }
+
+            for (index, tab) in tabs.clone().into_iter().enumerate() {
+                let is_selected = index == *selected;
+
+                div(
+                    role: "tabpanel",
+                    id: (format!("{id}-panel-{index}")),
+                    aria_labelledby: (format!("{id}-tab-{index}")),
+                    tabindex: "0",
+                    hidden?: (!is_selected)
+                ) {
+                    (TabPanel(&tab))
+                }
+            }
+        }
+    }
+
+    /// A menu button and its menu.
+    ///
+    /// Renders a `<button id="{id}-button" aria-haspopup="menu">` with `aria-expanded` and `aria-controls` pointing at
+    /// a `<ul role="menu" id="{id}">` labelled by the button, which is `hidden` unless `open` is `true`. Children
+    /// should be [`MenuItem`]s. Extra attributes are forwarded to the button.
+    pub fn Menu(id: &str, label: impl RenderEscaped, open: bool) {
+        let button_id = format!("{id}-button");
+
+        button(
+            type: "button",
+            id: button_id,
+            aria_haspopup: "menu",
+            aria_expanded: (bool_str(*open)),
+            aria_controls: id,
+            #attrs
+        ) {
+            (label)
+        }
+        ul(role: "menu", id: id, aria_labelledby: button_id, hidden?: (!*open)) { #children }
+    }
+
+    /// An item of a [`Menu`].
+    ///
+    /// Renders a `<li role="menuitem" tabindex="-1">` containing the children. Extra attributes are forwarded to the
+    /// item.
+    pub fn MenuItem() {
+        li(role: "menuitem", tabindex: "-1", #attrs) { #children }
+    }
+}
@@ -0,0 +1,185 @@
+use std::{fmt, sync::Arc};
+
+use crate::{RenderEscaped, context, utils::escape_html_to};
+
+/// A static asset embedded into the binary at compile time by the [`asset!`](crate::asset) macro.
+///
+/// An `Asset` carries the asset's path (relative to the crate's `Cargo.toml`), its content, and a content hash that
+/// changes whenever the file changes. The hash is used to build cache-busting URLs with [`url()`](Asset::url).
+///
+/// When rendered inside an [`html!`](crate::html) template, stylesheets (`.css`) and scripts (`.js`) render the
+/// appropriate tag: small assets (at most [`inline_threshold`](Asset::inline_threshold) bytes) are inlined into a
+/// `<style>` or `<script>` element, larger ones are referenced with `<link rel="stylesheet">` or `<script src>`.
+/// Other assets render their URL.
+///
+/// By default an asset is served from its path (`/assets/app.css?v=...`). Provide an [`AssetUrls`] resolver in the
+/// render [`context`](crate::context) to serve assets from somewhere else, such as a CDN or a directory with another
+/// name; [`url()`](Asset::url) and the rendered `<link>` and `<script src>` tags use it.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::{asset, html, Asset, ToHtml};
+///
+/// const APP_CSS: Asset = asset!("assets/app.css");
+///
+/// let page = html! {
+///     head {
+///         (APP_CSS)                                 // <style>...</style> or <link rel="stylesheet" href="...">
+///         (APP_CSS.inline_threshold(0))             // always <link rel="stylesheet" href="/assets/app.css?v=...">
+///         link(rel: "preload", href: (APP_CSS.url()));
+///     }
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Asset {
+    path: &'static str,
+    content: &'static str,
+    hash: &'static str,
+    inline_threshold: usize,
+}
+
+impl Asset {
+    /// Assets with at most this many bytes are inlined by default.
+    pub const DEFAULT_INLINE_THRESHOLD: usize = 1024;
+
+    #[doc(hidden)]
+    /// Creates a new `Asset`. This is used internally by the `asset!` macro.
+    pub const fn new_unchecked(
+        path: &'static str,
+        content: &'static str,
+        hash: &'static str,
+    ) -> Self {
+        Self {
+            path,
+            content,
+            hash,
+            inline_threshold: Self::DEFAULT_INLINE_THRESHOLD,
+        }
+    }
+
+    /// Returns the asset's path relative to the crate's `Cargo.toml`.
+    pub const fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// Returns the embedded content of the asset.
+    pub const fn content(&self) -> &'static str {
+        self.content
+    }
+
+    /// Returns the hex-encoded content hash of the asset.
+    pub const fn hash(&self) -> &'static str {
+        self.hash
+    }
+
+    /// Returns a copy of this asset that is inlined when its content is at most `threshold` bytes.
+    ///
+    /// Use `0` to always reference the asset by URL and `usize::MAX` to always inline it.
+    pub const fn inline_threshold(mut self, threshold: usize) -> Self {
+        self.inline_threshold = threshold;
+        self
+    }
+
+    /// Returns the cache-busting URL of the asset, resolved by the [`AssetUrls`] in the render context, or
+    /// [`default_url()`](Asset::default_url) without one.
+    pub fn url(&self) -> String {
+        match context::get::<AssetUrls>() {
+            Some(urls) => urls.resolve(self),
+            None => self.default_url(),
+        }
+    }
+
+    /// Returns the URL of the asset when it is served from its path: `/{path}?v={hash}`.
+    pub fn default_url(&self) -> String {
+        format!("/{}?v={}", self.path.trim_start_matches('/'), self.hash)
+    }
+
+    fn is_inline(&self) -> bool {
+        self.content.len() <= self.inline_threshold
+    }
+}
+
+impl RenderEscaped for Asset {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        let url = self.url();
+
+        if self.path.ends_with(".css") {
+            if self.is_inline() {
+                f.write_str("<style>")?;
+                f.write_str(self.content)?;
+                f.write_str("</style>")
+            } else {
+                f.write_str("<link rel=\"stylesheet\" href=\"")?;
+                escape_html_to(f, &url)?;
+                f.write_str("\">")
+            }
+        } else if self.path.ends_with(".js") {
+            if self.is_inline() {
+                f.write_str("<script>")?;
+                f.write_str(self.content)?;
+                f.write_str("</script>")
+            } else {
+                f.write_str("<script src=\"")?;
+                escape_html_to(f, &url)?;
+                f.write_str("\"></script>")
+            }
+        } else {
+            escape_html_to(f, &url)
+        }
+    }
+}
+
+/// Resolves the public URLs of [`Asset`]s, for assets that aren't served from their path.
+///
+/// Provide it in the render [`context`](crate::context) while rendering; [`Asset::url`] and the `<link>` and
+/// `<script src>` tags of rendered assets then use it. Cloning it is cheap.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::{asset, context, html, Asset, AssetUrls, ToHtml};
+///
+/// const APP_CSS: Asset = asset!("assets/app.css");
+///
+/// let page = html! { head { (APP_CSS.inline_threshold(0)) } };
+///
+/// // <link rel="stylesheet" href="https://cdn.example.com/assets/app.css?v=...">
+/// context::provide(AssetUrls::prefix("https://cdn.example.com"), || page.to_html());
+///
+/// // <link rel="stylesheet" href="/static/app.css?v=...">
+/// let urls = AssetUrls::new(|asset| {
+///     let name = asset.path().trim_start_matches("assets/");
+///     format!("/static/{name}?v={}", asset.hash())
+/// });
+/// context::provide(urls, || page.to_html());
+/// ```
+#[derive(Clone)]
+pub struct AssetUrls(Arc<dyn Fn(&Asset) -> String + Send + Sync>);
+
+impl AssetUrls {
+    /// Creates a resolver returning the URL of an asset with `resolve`.
+    pub fn new(resolve: impl Fn(&Asset) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(resolve))
+    }
+
+    /// Creates a resolver serving assets from their path under `prefix`, such as the URL of a CDN: the URL of
+    /// `assets/app.css` is `{prefix}/assets/app.css?v={hash}`.
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        let prefix = prefix.trim_end_matches('/').to_owned();
+
+        Self::new(move |asset| format!("{prefix}{}", asset.default_url()))
+    }
+
+    /// Returns the URL of `asset`.
+    pub fn resolve(&self, asset: &Asset) -> String {
+        (self.0)(asset)
+    }
+}
+
+impl fmt::Debug for AssetUrls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AssetUrls").finish_non_exhaustive()
+    }
+}
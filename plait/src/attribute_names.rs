@@ -0,0 +1,6 @@
+//! Known attribute names, one `static` each.
+//!
+//! Generated code passes these for dynamic attributes (`class?: ..`, `href: (url)`) instead of a string literal per
+//! use, so every template in every crate shares a single copy of each name. The statics are generated from the same
+//! tables as the `#[deny(unknown_attributes)]` checks.
+plait_macros::attribute_names!();
@@ -0,0 +1,151 @@
+use std::{iter, slice, vec};
+
+use crate::{Html, RenderEscaped, spec};
+
+/// A reusable group of attributes, usually built with the [`attrs!`](crate::attrs) macro.
+///
+/// Underscores in names are converted to hyphens like attribute names in [`html!`](crate::html), and names that aren't
+/// [valid attribute names](spec::is_valid_attribute_name) are skipped. Values are escaped when they are added, so a
+/// group is rendered without evaluating its values again.
+///
+/// A group is spread onto an element with `#attrs(&group)`, which can be repeated on as many elements as needed. Like
+/// other `#attrs(expr)` attributes, the attributes are written in the order they were added, and the values of URL
+/// attributes are checked against the [`UrlRules`](crate::url_policy::UrlRules) in the render context.
+///
+/// # Example
+///
+/// ```
+/// use plait::{Attrs, attrs, html, ToHtml};
+///
+/// let frag = html! {
+///     let common = attrs!(class: "card", data_module: "feed");
+///     article(#attrs(&common)) { "One" }
+///     article(#attrs(&common), id: "two") { "Two" }
+/// };
+///
+/// assert_eq!(
+///     frag.to_html(),
+///     r#"<article class="card" data-module="feed">One</article><article class="card" data-module="feed" id="two">Two</article>"#
+/// );
+///
+/// let link = Attrs::new().set("rel", "noopener").set("target", "_blank");
+/// let frag = html! { a(href: "https://example.com", #attrs(&link)) { "Example" } };
+///
+/// assert_eq!(frag.to_html(), r#"<a href="https://example.com" rel="noopener" target="_blank">Example</a>"#);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Attrs {
+    attrs: Vec<(String, Html)>,
+}
+
+impl Attrs {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the attribute `name` with `value`.
+    pub fn set(mut self, name: &str, value: impl RenderEscaped) -> Self {
+        let name = name.trim_start_matches("r#").replace('_', "-");
+
+        if !spec::is_valid_attribute_name(&name) {
+            return self;
+        }
+
+        let mut rendered = String::new();
+        value
+            .render_escaped(&mut rendered)
+            .expect("writing to a string never fails");
+
+        self.attrs.push((name, Html::new_unchecked(rendered)));
+        self
+    }
+
+    /// Adds the attributes of `other` after the attributes of the group.
+    pub fn extend(mut self, other: impl IntoIterator<Item = (String, Html)>) -> Self {
+        self.attrs.extend(other);
+        self
+    }
+
+    /// Returns `true` if the group has no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+
+    /// Returns the number of attributes in the group.
+    pub fn len(&self) -> usize {
+        self.attrs.len()
+    }
+
+    /// Returns an iterator over the names and escaped values of the attributes.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Html)> {
+        self.into_iter()
+    }
+}
+
+impl IntoIterator for Attrs {
+    type Item = (String, Html);
+    type IntoIter = vec::IntoIter<(String, Html)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.attrs.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Attrs {
+    type Item = (&'a str, &'a Html);
+    type IntoIter =
+        iter::Map<slice::Iter<'a, (String, Html)>, fn(&(String, Html)) -> (&str, &Html)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.attrs.iter().map(as_pair)
+    }
+}
+
+fn as_pair((name, value): &(String, Html)) -> (&str, &Html) {
+    (name, value)
+}
+
+/// Builds an [`Attrs`] group from `name: value` pairs, e.g. `attrs!(class: "card", data_id: id)` for
+/// `class="card" data-id="..."`.
+///
+/// Each value must implement [`RenderEscaped`].
+#[macro_export]
+macro_rules! attrs {
+    ($($name:ident : $value:expr),* $(,)?) => {
+        $crate::Attrs::new()$(.set(::core::stringify!($name), $value))*
+    };
+}
+
+/// Attribute pairs spread sorted by name with `#attrs(Sorted(expr))`, for unordered collections such as a `HashMap`
+/// whose iteration order changes between runs.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use plait::{Sorted, html, ToHtml};
+///
+/// let config = HashMap::from([("title", "Widget"), ("data-theme", "dark"), ("role", "region")]);
+/// let frag = html! { div(#attrs(Sorted(&config))) {} };
+///
+/// assert_eq!(frag.to_html(), r#"<div data-theme="dark" role="region" title="Widget"></div>"#);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Sorted<I>(pub I);
+
+impl<I, K, V> IntoIterator for Sorted<I>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+{
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut pairs: Vec<_> = self.0.into_iter().collect();
+        pairs.sort_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+        pairs.into_iter()
+    }
+}
@@ -0,0 +1,590 @@
+//! Caching of rendered fragments across requests.
+//!
+//! The `cache!` node of the [`html!`](crate::html) macro renders its children once and reuses the output for as long
+//! as the cached copy is valid:
+//!
+//! ```text
+//! cache!(key = (user.id, page), ttl = 60s) { ... }
+//! ```
+//!
+//! - `key` is any [`Hash`] value, such as a tuple of the values the fragment is rendered from. Keys are shared by all
+//!   `cache!` nodes, so include a name in the key (`("sidebar", user.id)`) when several regions are cached by the same
+//!   values. Keys are compared in full rather than by their hash (see [`CacheKey`]), so two keys only share a fragment
+//!   when they are equal.
+//! - `ttl` is optional and is either a literal with a unit (`500ms`, `60s`, `5m`, `1h`) or an expression evaluating to
+//!   a [`Duration`]. Without it, entries only leave the cache when they are evicted or invalidated.
+//!
+//! Fragments are cached in the [`RenderCache`] in the render [`context`](crate::context). Without one, `cache!` simply
+//! renders its children. The storage is pluggable through the [`CacheStore`] trait; [`LruCache`] is an in-memory
+//! least-recently-used cache, used by [`RenderCache::default()`]. Entries can be dropped before they expire with
+//! [`RenderCache::invalidate`], e.g. when the data they were rendered from changes. To cache a value embedded with
+//! `(expr)` rather than a part of the template, wrap it in [`Cached`].
+//!
+//! # Stale-while-revalidate
+//!
+//! For expensive regions that don't need to be perfectly fresh, [`stale_while_revalidate`] keeps serving a fragment
+//! for a while after its `ttl` has passed and re-renders it in the background, using the executor set with
+//! [`RenderCache::with_executor`]. Because the re-render happens after the current request has moved on, the render
+//! function must own all its data (`Send + 'static`); it runs outside the render context of the request.
+//!
+//! ```
+//! use std::{sync::Arc, time::Duration};
+//!
+//! use plait::{context, html, ToHtml, cache::{self, LruCache, RenderCache}};
+//!
+//! fn dashboard(visits: Arc<u64>) -> plait::Html {
+//!     html! { p { "Visits: " (*visits) } }.to_html()
+//! }
+//!
+//! let visits = Arc::new(1024);
+//!
+//! let page = html! {
+//!     main {
+//!         (cache::stale_while_revalidate(
+//!             "dashboard",
+//!             Duration::from_secs(10),
+//!             Duration::from_secs(300),
+//!             {
+//!                 let visits = visits.clone();
+//!                 move || dashboard(visits)
+//!             },
+//!         ))
+//!     }
+//! };
+//!
+//! // Run background re-renders on their own thread.
+//! let cache = RenderCache::new(LruCache::new(100)).with_executor(|job| {
+//!     std::thread::spawn(job);
+//! });
+//!
+//! assert_eq!(
+//!     context::provide(cache, || page.to_html()),
+//!     "<main><p>Visits: 1024</p></main>"
+//! );
+//! ```
+//!
+//! # What not to cache
+//!
+//! Cached output is reused as-is for every request that uses the same key, so the key must include everything the
+//! fragment depends on. A fragment showing per-user data (a dashboard, a greeting) must have the user in its key, or
+//! one user is served another user's HTML. Output that depends on the render context rather than on the key must not
+//! be cached at all: a [`CsrfToken`](crate::form::CsrfToken) field, [flash messages](crate::flash) and `@can`
+//! [permission](crate::permission) checks would be rendered for the first request and then served to everyone else.
+//!
+//! Likewise, ids generated with `auto_id` or [`ids::next`](crate::ids::next) inside a cached fragment aren't checked
+//! against the rest of the page when the fragment comes from the cache.
+//!
+//! # Example
+//!
+//! ```
+//! use std::cell::Cell;
+//!
+//! use plait::{context, html, ToHtml, cache::{LruCache, RenderCache}};
+//!
+//! let renders = &Cell::new(0);
+//! let user_id = 7;
+//!
+//! let page = html! {
+//!     main {
+//!         cache!(key = ("dashboard", user_id), ttl = 60s) {
+//!             ({ renders.set(renders.get() + 1); "Expensive dashboard" })
+//!         }
+//!     }
+//! };
+//!
+//! let cache = RenderCache::new(LruCache::new(100));
+//!
+//! let first = context::provide(cache.clone(), || page.to_html());
+//! let second = context::provide(cache.clone(), || page.to_html());
+//!
+//! assert_eq!(first, "<main>Expensive dashboard</main>");
+//! assert_eq!(second, first);
+//! assert_eq!(renders.get(), 1);
+//!
+//! cache.invalidate(&("dashboard", user_id));
+//! context::provide(cache, || page.to_html());
+//! assert_eq!(renders.get(), 2);
+//! ```
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{Html, RenderEscaped, context};
+
+/// The key of a cached fragment: the data a key value feeds to a [`Hasher`] when it is hashed.
+///
+/// Keys are compared by this data rather than by a hash of it, so two different key values can't collide and serve
+/// each other's fragments. Values whose [`Hash`] implementation skips some of their fields are equal keys when the
+/// other fields are equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CacheKey(Arc<[u8]>);
+
+impl CacheKey {
+    /// Derives the cache key for `key`.
+    pub fn new<K>(key: &K) -> Self
+    where
+        K: Hash + ?Sized,
+    {
+        let mut recorder = KeyRecorder(Vec::new());
+        key.hash(&mut recorder);
+        Self(recorder.0.into())
+    }
+
+    /// Returns the data of the key, e.g. to derive a key for an external store.
+    ///
+    /// The data is the same on every run of a build, but it depends on the [`Hash`] implementations of the key and on
+    /// the platform, which can change between Rust releases. An external store shared by different builds of an
+    /// application should include the application's version in its keys.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A [`Hasher`] recording the data written to it, used to build [`CacheKey`]s.
+struct KeyRecorder(Vec<u8>);
+
+impl Hasher for KeyRecorder {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        // Only called by `Hash` implementations that mix an intermediate hash into their data
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&self.0);
+        hasher.finish()
+    }
+}
+
+/// A cached fragment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheEntry {
+    /// The rendered fragment.
+    pub html: Html,
+    /// When the fragment should be re-rendered, for entries cached with [`stale_while_revalidate`]. The entry is still
+    /// served until it expires.
+    pub stale_at: Option<Instant>,
+}
+
+/// Storage for cached fragments.
+///
+/// Implementations must be safe to share between threads, since a single store is usually shared by all requests.
+pub trait CacheStore: Send + Sync {
+    /// Returns the entry cached under `key`, if any and it hasn't expired.
+    fn get(&self, key: &CacheKey) -> Option<CacheEntry>;
+
+    /// Caches `entry` under `key`, replacing any previous entry. The entry expires after `ttl`, if given.
+    fn insert(&self, key: CacheKey, entry: CacheEntry, ttl: Option<Duration>);
+
+    /// Removes the entry cached under `key`.
+    fn remove(&self, key: &CacheKey);
+
+    /// Removes all entries.
+    fn clear(&self);
+}
+
+/// Number of fragments held by the store of a [default](RenderCache::default) [`RenderCache`].
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// A job re-rendering a stale fragment, passed to the executor of a [`RenderCache`].
+pub type RevalidateJob = Box<dyn FnOnce() + Send + 'static>;
+
+type Executor = Arc<dyn Fn(RevalidateJob) + Send + Sync>;
+
+/// Handle to the [`CacheStore`] used by `cache!` nodes and [`stale_while_revalidate`].
+///
+/// Provide this in the render [`context`](crate::context) while rendering. Cloning it is cheap and the clones share
+/// the same store.
+#[derive(Clone)]
+pub struct RenderCache {
+    store: Arc<dyn CacheStore>,
+    executor: Option<Executor>,
+    revalidating: Arc<Mutex<HashSet<CacheKey>>>,
+}
+
+impl RenderCache {
+    /// Creates a handle to `store`.
+    pub fn new(store: impl CacheStore + 'static) -> Self {
+        Self::from_arc(Arc::new(store))
+    }
+
+    /// Creates a handle to a store that is already shared.
+    pub fn from_arc(store: Arc<dyn CacheStore>) -> Self {
+        Self {
+            store,
+            executor: None,
+            revalidating: Arc::default(),
+        }
+    }
+
+    /// Sets the executor running the background re-renders of [`stale_while_revalidate`], e.g. a thread pool or an
+    /// async runtime's `spawn_blocking`.
+    ///
+    /// Without an executor, stale fragments are re-rendered while the page is being rendered.
+    pub fn with_executor(
+        mut self,
+        executor: impl Fn(RevalidateJob) + Send + Sync + 'static,
+    ) -> Self {
+        self.executor = Some(Arc::new(executor));
+        self
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &Arc<dyn CacheStore> {
+        &self.store
+    }
+
+    /// Removes the fragment cached under `key`, so that it is rendered again the next time it is needed.
+    pub fn invalidate<K>(&self, key: &K)
+    where
+        K: Hash + ?Sized,
+    {
+        self.store.remove(&CacheKey::new(key));
+    }
+
+    /// Removes all cached fragments.
+    pub fn clear(&self) {
+        self.store.clear();
+    }
+
+    /// Returns the fragment cached under `key` and, if it is stale, re-renders it in the background with the
+    /// executor. Fragments that aren't cached yet (or have expired) are rendered right away.
+    ///
+    /// See [`stale_while_revalidate`] for details.
+    pub fn stale_while_revalidate<K, F>(
+        &self,
+        key: &K,
+        ttl: Duration,
+        stale: Duration,
+        render: F,
+    ) -> Html
+    where
+        K: Hash + ?Sized,
+        F: FnOnce() -> Html + Send + 'static,
+    {
+        let key = CacheKey::new(key);
+
+        let Some(entry) = self.store.get(&key) else {
+            return render_and_store(&*self.store, key, ttl, stale, render);
+        };
+
+        if entry
+            .stale_at
+            .is_none_or(|stale_at| Instant::now() < stale_at)
+        {
+            return entry.html;
+        }
+
+        let Some(executor) = &self.executor else {
+            return render_and_store(&*self.store, key, ttl, stale, render);
+        };
+
+        if !lock(&self.revalidating).insert(key.clone()) {
+            // Another request is already re-rendering the fragment
+            return entry.html;
+        }
+
+        let store = self.store.clone();
+        let revalidating = self.revalidating.clone();
+
+        executor(Box::new(move || {
+            struct Guard(Arc<Mutex<HashSet<CacheKey>>>, CacheKey);
+
+            impl Drop for Guard {
+                fn drop(&mut self) {
+                    lock(&self.0).remove(&self.1);
+                }
+            }
+
+            let _guard = Guard(revalidating, key.clone());
+            render_and_store(&*store, key, ttl, stale, render);
+        }));
+
+        entry.html
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    // A panic while holding the lock can't leave the data in an inconsistent state
+    mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+fn render_and_store(
+    store: &dyn CacheStore,
+    key: CacheKey,
+    ttl: Duration,
+    stale: Duration,
+    render: impl FnOnce() -> Html,
+) -> Html {
+    let html = render();
+    let entry = CacheEntry {
+        html: html.clone(),
+        stale_at: Instant::now().checked_add(ttl),
+    };
+
+    store.insert(key, entry, ttl.checked_add(stale));
+    html
+}
+
+impl Default for RenderCache {
+    /// Creates a handle to an [`LruCache`] holding up to [`DEFAULT_CAPACITY`] fragments.
+    fn default() -> Self {
+        Self::new(LruCache::new(DEFAULT_CAPACITY))
+    }
+}
+
+impl fmt::Debug for RenderCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderCache").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    entry: CacheEntry,
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+impl LruEntry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    entries: HashMap<CacheKey, LruEntry>,
+    clock: u64,
+}
+
+impl LruState {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+/// An in-memory [`CacheStore`] holding up to a fixed number of fragments.
+///
+/// When the cache is full, expired entries are dropped first, then the least recently used one.
+#[derive(Debug)]
+pub struct LruCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl LruCache {
+    /// Creates a cache holding up to `capacity` fragments. A capacity of `0` disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Returns the maximum number of cached fragments.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of cached fragments, including expired ones that haven't been dropped yet.
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    /// Returns `true` if no fragments are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LruState> {
+        lock(&self.state)
+    }
+}
+
+impl CacheStore for LruCache {
+    fn get(&self, key: &CacheKey) -> Option<CacheEntry> {
+        let mut state = self.state();
+        let now = Instant::now();
+
+        if state.entries.get(key)?.is_expired(now) {
+            state.entries.remove(key);
+            return None;
+        }
+
+        let tick = state.tick();
+        let entry = state.entries.get_mut(key)?;
+        entry.last_used = tick;
+
+        Some(entry.entry.clone())
+    }
+
+    fn insert(&self, key: CacheKey, entry: CacheEntry, ttl: Option<Duration>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state();
+        let now = Instant::now();
+
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.capacity {
+            state.entries.retain(|_, entry| !entry.is_expired(now));
+
+            if state.entries.len() >= self.capacity
+                && let Some(oldest) = state
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone())
+            {
+                state.entries.remove(&oldest);
+            }
+        }
+
+        let last_used = state.tick();
+
+        state.entries.insert(
+            key,
+            LruEntry {
+                entry,
+                expires_at: ttl.and_then(|ttl| now.checked_add(ttl)),
+                last_used,
+            },
+        );
+    }
+
+    fn remove(&self, key: &CacheKey) {
+        self.state().entries.remove(key);
+    }
+
+    fn clear(&self) {
+        self.state().entries.clear();
+    }
+}
+
+#[doc(hidden)]
+/// Writes the fragment cached under `key`, or renders it with `render` and caches the output. This is used internally
+/// by the `cache!` node of the `html!` macro.
+pub fn render_cached<K>(
+    f: &mut (dyn fmt::Write + '_),
+    key: &K,
+    ttl: Option<Duration>,
+    render: impl Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+) -> fmt::Result
+where
+    K: Hash + ?Sized,
+{
+    let Some(cache) = context::get::<RenderCache>() else {
+        return render(f);
+    };
+
+    let key = CacheKey::new(key);
+
+    if let Some(entry) = cache.store.get(&key) {
+        return f.write_str(&entry.html);
+    }
+
+    let mut html = String::new();
+    render(&mut html)?;
+    f.write_str(&html)?;
+
+    let entry = CacheEntry {
+        html: Html::new_unchecked(html),
+        stale_at: None,
+    };
+
+    cache.store.insert(key, entry, ttl);
+
+    Ok(())
+}
+
+/// A value rendered through the [`RenderCache`] of the render context, the expression form of a `cache!` node.
+///
+/// Use it where a template isn't written inline, e.g. to cache the output of a helper function or component that is
+/// embedded with `(expr)`. The key is shared with `cache!` nodes, so include a name in it. Without a [`RenderCache`]
+/// in the context, the value is rendered every time.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use plait::{context, html, ToHtml, cache::{Cached, RenderCache}, RenderEscaped};
+///
+/// fn nav(user_id: u64) -> impl RenderEscaped {
+///     html! { nav { a(href: #(format!("/users/{user_id}"))) { "Profile" } } }
+/// }
+///
+/// let user_id = 7;
+/// let page = html! {
+///     (Cached::new(("nav", user_id), nav(user_id)).ttl(Duration::from_secs(60)))
+///     main { "Content" }
+/// };
+///
+/// assert_eq!(
+///     context::provide(RenderCache::default(), || page.to_html()),
+///     "<nav><a href=\"/users/7\">Profile</a></nav><main>Content</main>"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cached<K, T> {
+    key: K,
+    ttl: Option<Duration>,
+    value: T,
+}
+
+impl<K, T> Cached<K, T>
+where
+    K: Hash,
+    T: RenderEscaped,
+{
+    /// Creates a wrapper rendering `value` through the cache under `key`, without expiry.
+    pub fn new(key: K, value: T) -> Self {
+        Self {
+            key,
+            ttl: None,
+            value,
+        }
+    }
+
+    /// Sets how long the rendered value stays cached.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+impl<K, T> RenderEscaped for Cached<K, T>
+where
+    K: Hash,
+    T: RenderEscaped,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        render_cached(f, &self.key, self.ttl, |f| self.value.render_escaped(f))
+    }
+}
+
+/// Returns the fragment cached under `key` in the [`RenderCache`] of the render context, rendering it with `render`
+/// if needed.
+///
+/// A fragment is fresh for `ttl` after it has been rendered. For another `stale` it is still returned, but the first
+/// request that sees it stale also hands `render` to the [executor](RenderCache::with_executor), which re-renders the
+/// fragment in the background and updates the cache. Only one re-render per key runs at a time. After `ttl + stale`
+/// the entry expires and the fragment is rendered again right away.
+///
+/// Without a [`RenderCache`] in the context the fragment is rendered on every call.
+pub fn stale_while_revalidate<K, F>(key: &K, ttl: Duration, stale: Duration, render: F) -> Html
+where
+    K: Hash + ?Sized,
+    F: FnOnce() -> Html + Send + 'static,
+{
+    match context::get::<RenderCache>() {
+        Some(cache) => cache.stale_while_revalidate(key, ttl, stale, render),
+        None => render(),
+    }
+}
@@ -0,0 +1,82 @@
+//! Character set of the rendered output, for consumers that can't read UTF-8.
+//!
+//! Output is UTF-8 by default. [Provide](crate::context::provide) [`Charset::Ascii`] in the render context to write
+//! every non-ASCII character of escaped content as a numeric character reference (`…` as `&#x2026;`), which some email
+//! clients and legacy systems need. This applies to the static text of templates and to every escaped value (`(expr)`,
+//! `attr: (expr)`, ...). Raw values computed at runtime (`#(markup)`) are written as they are.
+//!
+//! Character references are not decoded inside `script` and `style` elements, so keep their contents ASCII when
+//! rendering with [`Charset::Ascii`].
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{charset::Charset, context, html, ToHtml};
+//!
+//! let name = "Zoë";
+//! let page = html! {
+//!     p(title: (name)) { "Café, " (name) "…" }
+//! };
+//!
+//! assert_eq!(
+//!     context::provide(Charset::Ascii, || page.to_html()),
+//!     "<p title=\"Zo&#xeb;\">Caf&#xe9;, Zo&#xeb;&#x2026;</p>"
+//! );
+//!
+//! assert_eq!(page.to_html(), "<p title=\"Zoë\">Café, Zoë…</p>");
+//! ```
+use std::fmt;
+
+use crate::context;
+
+/// Character set of the rendered output. See the [module documentation](self).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Charset {
+    /// Write non-ASCII characters as they are.
+    #[default]
+    Utf8,
+    /// Write non-ASCII characters as numeric character references.
+    Ascii,
+}
+
+impl Charset {
+    /// Returns the charset in the render context, or [`Charset::Utf8`] if none is provided.
+    pub fn current() -> Self {
+        context::get::<Charset>().map_or(Charset::Utf8, |charset| *charset)
+    }
+
+    /// Returns the name of the charset, as used in `<meta charset>`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Charset::Utf8 => "utf-8",
+            Charset::Ascii => "us-ascii",
+        }
+    }
+}
+
+/// Writes `input`, with its non-ASCII characters as numeric character references.
+pub(crate) fn write_ascii(writer: &mut (impl fmt::Write + ?Sized), input: &str) -> fmt::Result {
+    let mut last = 0;
+
+    for (index, c) in input.char_indices() {
+        if c.is_ascii() {
+            continue;
+        }
+
+        writer.write_str(&input[last..index])?;
+        write!(writer, "&#x{:x};", u32::from(c))?;
+        last = index + c.len_utf8();
+    }
+
+    writer.write_str(&input[last..])
+}
+
+#[doc(hidden)]
+/// Writes markup that is already escaped, encoding its non-ASCII characters if the render context asks for
+/// [`Charset::Ascii`]. This is used internally by the [`html!`](crate::html) macro for static text that isn't ASCII.
+pub fn write_static(writer: &mut (dyn fmt::Write + '_), markup: &str) -> fmt::Result {
+    match Charset::current() {
+        Charset::Utf8 => writer.write_str(markup),
+        Charset::Ascii => write_ascii(writer, markup),
+    }
+}
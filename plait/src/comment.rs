@@ -0,0 +1,230 @@
+//! HTML comments that are validated to end where they should.
+//!
+//! A comment ends at the first `-->` (or `--!>`) in its text, so emitting unchecked text as a comment can leak the rest
+//! of it into the page as markup. The types in this module check their text against the
+//! [comment syntax](https://html.spec.whatwg.org/multipage/syntax.html#comments) when they are created, and render as
+//! markup with both `(expr)` and `#(expr)`:
+//!
+//! - [`Comment`] is a plain comment, including search engine directives like `<!--googleoff: index-->`.
+//! - [`ConditionalComment`] is a downlevel-hidden or downlevel-revealed conditional comment for old versions of
+//!   Internet Explorer.
+//!
+//! These comments carry meaning, so minifiers that strip comments should leave them in place.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, comment::{Comment, ConditionalComment, GoogleOff}};
+//!
+//! let frag = html! {
+//!     (Comment::googleoff(GoogleOff::Index))
+//!     nav { "Menu" }
+//!     (Comment::googleon(GoogleOff::Index))
+//!     (ConditionalComment::revealed("!IE", html! { p { "Modern browser" } }))
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<!--googleoff: index--><nav>Menu</nav><!--googleon: index-->\
+//!     <!--[if !IE]><!--><p>Modern browser</p><!--<![endif]-->"
+//! );
+//!
+//! assert!(Comment::try_new("ends --> early").is_none());
+//! ```
+use std::{borrow::Cow, fmt};
+
+use crate::{RenderEscaped, RenderRaw};
+
+/// Returns `true` if `text` can be the text of a comment: it doesn't start with `>` or `->`, doesn't contain `<!--`,
+/// `-->` or `--!>` and doesn't end with `<!-`.
+fn is_valid_comment_text(text: &str) -> bool {
+    !text.starts_with('>')
+        && !text.starts_with("->")
+        && !text.contains("<!--")
+        && !text.contains("-->")
+        && !text.contains("--!>")
+        && !text.ends_with("<!-")
+}
+
+/// Returns `true` if `condition` only contains characters used in conditional comment expressions.
+fn is_valid_condition(condition: &str) -> bool {
+    !condition.is_empty()
+        && condition
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b" !()&|.".contains(&byte))
+}
+
+/// A validated HTML comment.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Comment {
+    text: Cow<'static, str>,
+}
+
+impl Comment {
+    /// Creates a comment with the given text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `text` would end the comment early or is otherwise not allowed in a comment (see
+    /// [`try_new()`](Comment::try_new)).
+    pub fn new(text: impl Into<Cow<'static, str>>) -> Self {
+        match Self::try_new(text) {
+            Some(comment) => comment,
+            None => panic!("invalid comment text"),
+        }
+    }
+
+    /// Creates a comment with the given text, or returns `None` if the text starts with `>` or `->`, contains `<!--`,
+    /// `-->` or `--!>`, or ends with `<!-`.
+    pub fn try_new(text: impl Into<Cow<'static, str>>) -> Option<Self> {
+        let text = text.into();
+        is_valid_comment_text(&text).then_some(Self { text })
+    }
+
+    /// Returns the `<!--googleoff: ...-->` directive, which excludes the following content from Google Search
+    /// Appliance indexing until the matching [`googleon()`](Comment::googleon).
+    pub fn googleoff(scope: GoogleOff) -> Self {
+        Self {
+            text: Cow::Owned(format!("googleoff: {}", scope.as_str())),
+        }
+    }
+
+    /// Returns the `<!--googleon: ...-->` directive, ending a [`googleoff()`](Comment::googleoff) section.
+    pub fn googleon(scope: GoogleOff) -> Self {
+        Self {
+            text: Cow::Owned(format!("googleon: {}", scope.as_str())),
+        }
+    }
+
+    /// Returns the text of the comment, without `<!--` and `-->`.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl RenderEscaped for Comment {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str("<!--")?;
+        f.write_str(&self.text)?;
+        f.write_str("-->")
+    }
+}
+
+impl RenderRaw for Comment {
+    fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.render_escaped(f)
+    }
+}
+
+/// What a [`googleoff`](Comment::googleoff) section excludes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GoogleOff {
+    /// Words in the section are not indexed.
+    Index,
+    /// Links in the section are not followed.
+    Anchor,
+    /// The section is not used in result snippets.
+    Snippet,
+    /// All of the above.
+    All,
+}
+
+impl GoogleOff {
+    /// Returns the name used in the directive.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GoogleOff::Index => "index",
+            GoogleOff::Anchor => "anchor",
+            GoogleOff::Snippet => "snippet",
+            GoogleOff::All => "all",
+        }
+    }
+}
+
+/// An Internet Explorer conditional comment.
+///
+/// The condition is an expression like `IE`, `lt IE 9` or `!IE`, and may only contain ASCII letters, digits, spaces
+/// and `!()&|.`. The content is rendered (escaped, like `(expr)`) when the conditional comment is created.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, ToHtml, comment::ConditionalComment};
+///
+/// let shim = ConditionalComment::hidden("lt IE 9", html! { script(src: "/html5shiv.js") {} });
+///
+/// assert_eq!(
+///     html! { head { (shim) } }.to_html(),
+///     "<head><!--[if lt IE 9]><script src=\"/html5shiv.js\"></script><![endif]--></head>"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConditionalComment {
+    markup: String,
+}
+
+impl ConditionalComment {
+    /// Creates a downlevel-hidden conditional comment: `content` is inside the comment, so only browsers matching
+    /// `condition` render it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `condition` is invalid or the rendered `content` would end the comment early (see
+    /// [`try_hidden()`](ConditionalComment::try_hidden)).
+    pub fn hidden(condition: &str, content: impl RenderEscaped) -> Self {
+        match Self::try_hidden(condition, content) {
+            Some(comment) => comment,
+            None => panic!("invalid conditional comment: {condition:?}"),
+        }
+    }
+
+    /// Creates a downlevel-hidden conditional comment, or returns `None` if `condition` is invalid or the rendered
+    /// `content` contains `<!--`, `-->` or `--!>`.
+    pub fn try_hidden(condition: &str, content: impl RenderEscaped) -> Option<Self> {
+        if !is_valid_condition(condition) {
+            return None;
+        }
+
+        let mut markup = format!("<!--[if {condition}]>");
+        content.render_escaped(&mut markup).ok()?;
+        markup.push_str("<![endif]-->");
+
+        is_valid_comment_text(&markup[4..markup.len() - 3]).then_some(Self { markup })
+    }
+
+    /// Creates a downlevel-revealed conditional comment: `content` is outside the comment, so every browser except
+    /// old versions of Internet Explorer not matching `condition` renders it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `condition` is invalid.
+    pub fn revealed(condition: &str, content: impl RenderEscaped) -> Self {
+        assert!(
+            is_valid_condition(condition),
+            "invalid conditional comment: {condition:?}"
+        );
+
+        let mut markup = format!("<!--[if {condition}]><!-->");
+        let _ = content.render_escaped(&mut markup);
+        markup.push_str("<!--<![endif]-->");
+
+        Self { markup }
+    }
+
+    /// Returns the rendered conditional comment.
+    pub fn as_str(&self) -> &str {
+        &self.markup
+    }
+}
+
+impl RenderEscaped for ConditionalComment {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(&self.markup)
+    }
+}
+
+impl RenderRaw for ConditionalComment {
+    fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.render_escaped(f)
+    }
+}
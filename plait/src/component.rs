@@ -1,4 +1,6 @@
-use std::fmt;
+use std::{cell::Cell, fmt, rc::Rc};
+
+use crate::{Html, RenderEscaped};
 
 /// Trait for reusable HTML components.
 ///
@@ -11,6 +13,10 @@ use std::fmt;
 /// The `attrs` closure writes any extra HTML attributes passed at the call site (those appearing after the `;` in
 /// `@Component(props; attrs)`). The `children` closure writes the child content placed inside the component's braces.
 ///
+/// Components with several insertion points render the named slots of their children with `#slot(name)`, and the
+/// content outside `slot(name) { ... }` blocks with `#children` (or `#slot(default)`). Calling `children` directly
+/// renders the default slot.
+///
 /// # Example
 ///
 /// ```
@@ -58,3 +64,351 @@ where
         (**self).render_component(f, attrs, children)
     }
 }
+
+/// Trait for components that load data asynchronously before rendering.
+///
+/// You normally don't implement this trait by hand - use the [`async_component!`](crate::async_component) macro
+/// instead, which generates a struct and this trait implementation for you. The rendered [`Html`] can be embedded in
+/// other templates, including the bodies of other async components.
+pub trait RenderAsync {
+    /// Loads the data of the component and renders it.
+    fn render_async(&self) -> impl Future<Output = Html>;
+}
+
+impl<T> RenderAsync for &T
+where
+    T: RenderAsync,
+{
+    fn render_async(&self) -> impl Future<Output = Html> {
+        (**self).render_async()
+    }
+}
+
+/// Children that are rendered with an argument, so that a list or table component can own the iteration while its
+/// caller controls the markup of each item.
+///
+/// Declare a `children: impl RenderProp<T>` prop and render it with `#children(value)` in the body. The children of
+/// the call site are then written `{ |item| ... }`: the closure parameter receives each value, as in the `children`
+/// closure of a [`component!`](crate::component). Any `Fn(T, &mut dyn fmt::Write) -> fmt::Result` is a render prop.
+///
+/// # Example
+///
+/// ```
+/// use plait::{component, html, RenderProp, ToHtml};
+///
+/// pub struct Row {
+///     name: &'static str,
+/// }
+///
+/// component! {
+///     pub fn Table<'a>(rows: &'a [Row], children: impl RenderProp<(usize, &'a Row)>) {
+///         table {
+///             for (index, row) in rows.iter().enumerate() {
+///                 #children((index, row))
+///             }
+///         }
+///     }
+/// }
+///
+/// let rows = [Row { name: "apples" }, Row { name: "pears" }];
+/// let page = html! {
+///     @Table(rows: &rows) { |(index, row)| tr { td { (index) } td { (row.name) } } }
+/// };
+///
+/// assert_eq!(
+///     page.to_html(),
+///     "<table><tr><td>0</td><td>apples</td></tr><tr><td>1</td><td>pears</td></tr></table>"
+/// );
+/// ```
+pub trait RenderProp<T>: Fn(T, &mut (dyn fmt::Write + '_)) -> fmt::Result {}
+
+impl<T, F> RenderProp<T> for F where F: Fn(T, &mut (dyn fmt::Write + '_)) -> fmt::Result {}
+
+#[doc(hidden)]
+/// Renders the `children` render prop of a component with `argument`. This is used internally by `#children(expr)`.
+pub fn render_prop<T>(
+    children: &impl RenderProp<T>,
+    argument: T,
+    f: &mut (dyn fmt::Write + '_),
+) -> fmt::Result {
+    children(argument, f)
+}
+
+// Component calls generated by `html!` go through this function so that development tooling (e.g. the `profile` and
+// `tracing` features) can observe each render.
+#[doc(hidden)]
+#[inline]
+pub fn render_component<C>(
+    component: &C,
+    f: &mut (dyn fmt::Write + '_),
+    attrs: impl Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+    children: impl Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+) -> fmt::Result
+where
+    C: Component,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::trace_span!(
+        "component",
+        name = component_name(std::any::type_name::<C>())
+    )
+    .entered();
+
+    #[cfg(feature = "profile")]
+    {
+        crate::profile::record(std::any::type_name::<C>(), f, |f| {
+            component.render_component(f, attrs, children)
+        })
+    }
+
+    #[cfg(not(feature = "profile"))]
+    {
+        component.render_component(f, attrs, children)
+    }
+}
+
+/// Returns the name of the component with the type name `type_name`, without its path and generics.
+#[cfg(any(feature = "profile", feature = "tracing"))]
+pub(crate) fn component_name(type_name: &'static str) -> &'static str {
+    let without_generics = type_name.split('<').next().unwrap_or(type_name);
+    without_generics
+        .rsplit("::")
+        .next()
+        .unwrap_or(without_generics)
+}
+
+thread_local! {
+    static SLOT: Cell<&'static str> = const { Cell::new("default") };
+    /// The number of children seen so far while collecting the children of a component for [`EachChild`], and whether
+    /// the children being rendered count towards them.
+    static CHILD: Cell<Option<(usize, bool)>> = const { Cell::new(None) };
+}
+
+/// Restores the previously rendered slot and child when dropped.
+struct SlotGuard(&'static str, Option<(usize, bool)>);
+
+impl Drop for SlotGuard {
+    fn drop(&mut self) {
+        SLOT.set(self.0);
+        CHILD.set(self.1);
+    }
+}
+
+#[doc(hidden)]
+/// Renders the slot `name` of the `children` of a component. This is used internally by `#children` and
+/// `#slot(name)`.
+pub fn render_slot(
+    children: &impl Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+    name: &'static str,
+    f: &mut (dyn fmt::Write + '_),
+) -> fmt::Result {
+    // The children of a component rendered in a child (and their children) aren't children of the component call
+    let counted = CHILD.get().map(|(seen, _)| (seen, false));
+    let _guard = SlotGuard(SLOT.replace(name), CHILD.replace(counted));
+    children(f)
+}
+
+#[doc(hidden)]
+/// Renders the slot `name` of the `children` of a component as children of a component call, so that each of them is
+/// still a separate [`Child`]. This is used internally by `#children` and `#slot(name)` in the children of a
+/// component call.
+pub fn forward_slot(
+    children: &impl Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+    name: &'static str,
+    f: &mut (dyn fmt::Write + '_),
+) -> fmt::Result {
+    let mut guard = SlotGuard(SLOT.replace(name), None);
+    let result = children(f);
+
+    // The children of the slot count towards the children of the component call
+    guard.1 = CHILD.get();
+    result
+}
+
+#[doc(hidden)]
+/// Returns `true` if the slot `name` is being rendered. This is used internally by component calls to select the
+/// content of a slot.
+pub fn is_slot(name: &str) -> bool {
+    SLOT.get() == name
+}
+
+#[doc(hidden)]
+/// Marks the start of the next child of a component call. This is used internally by component calls so that
+/// [`EachChild`] can tell their children apart.
+pub fn start_child() {
+    if let Some((seen, true)) = CHILD.get() {
+        CHILD.set(Some((seen + 1, true)));
+    }
+}
+
+/// The children of a component, rendered once, with the offset at which each child starts.
+struct Collected {
+    output: String,
+    starts: Vec<usize>,
+    result: fmt::Result,
+}
+
+impl Collected {
+    /// Records the start of the children seen since the last write.
+    fn mark(&mut self) {
+        let seen = CHILD.get().map_or(0, |(seen, _)| seen);
+
+        while self.starts.len() < seen {
+            self.starts.push(self.output.len());
+        }
+    }
+}
+
+impl fmt::Write for Collected {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.mark();
+        self.output.push_str(s);
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+/// Returns an iterator over the children in the slot `name` of the `children` of a component. This is used internally
+/// by `for child in #children` and `for child in #slot(name)`.
+pub fn each_child<F>(children: &F, name: &'static str) -> EachChild
+where
+    F: Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+{
+    let mut collected = Collected {
+        output: String::new(),
+        starts: Vec::new(),
+        result: Ok(()),
+    };
+
+    {
+        let _guard = SlotGuard(SLOT.replace(name), CHILD.replace(Some((0, true))));
+        collected.result = children(&mut collected);
+        collected.mark();
+    }
+
+    EachChild {
+        collected: Rc::new(collected),
+        next: 0,
+    }
+}
+
+/// Iterator over the children of a component, created by `for child in #children` in a component body.
+///
+/// The children are the nodes in the braces of a component call, with every iteration of a `for` or `while` loop and
+/// every node in the taken branch of an `if` or `match` being a separate child. `let` bindings aren't children, and
+/// `#children` forwarded to another component stays a list of separate children. The children are rendered once, when
+/// the iterator is created, and each [`Child`] writes its part of the output.
+///
+/// # Example
+///
+/// ```
+/// use plait::{component, html, ToHtml};
+///
+/// component! {
+///     pub fn List() {
+///         ul(#attrs) {
+///             for child in #children {
+///                 li { (child) }
+///             }
+///         }
+///     }
+/// }
+///
+/// let items = ["Two", "Three"];
+/// let html = html! {
+///     @List(; class: "steps") {
+///         strong { "One" }
+///         for item in items {
+///             (item)
+///         }
+///     }
+/// };
+///
+/// assert_eq!(
+///     html.to_html(),
+///     r#"<ul class="steps"><li><strong>One</strong></li><li>Two</li><li>Three</li></ul>"#
+/// );
+/// ```
+pub struct EachChild {
+    collected: Rc<Collected>,
+    next: usize,
+}
+
+impl Iterator for EachChild {
+    type Item = Child;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.collected.starts.len() {
+            return None;
+        }
+
+        let child = Child {
+            collected: Rc::clone(&self.collected),
+            index: self.next,
+        };
+        self.next += 1;
+
+        Some(child)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.collected.starts.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for EachChild {}
+
+/// A single child of a component, rendered with `(child)`. See [`EachChild`].
+pub struct Child {
+    collected: Rc<Collected>,
+    index: usize,
+}
+
+impl Child {
+    /// Returns the position of the child, starting at 0.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl RenderEscaped for Child {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        let Collected {
+            output,
+            starts,
+            result,
+        } = &*self.collected;
+        (*result)?;
+
+        let end = starts.get(self.index + 1).copied().unwrap_or(output.len());
+        f.write_str(&output[starts[self.index]..end])
+    }
+}
+
+#[doc(hidden)]
+/// The state of a required prop that isn't set yet in a component call.
+pub struct MissingProp;
+
+#[doc(hidden)]
+/// The state of a required prop set in a component call.
+pub struct SetProp<T>(pub T);
+
+#[doc(hidden)]
+/// Implemented by the state of a required prop once it is set. Component calls only compile if every required prop of
+/// the component implements it.
+#[diagnostic::on_unimplemented(
+    message = "a required prop of type `{T}` is missing in this component call",
+    label = "missing prop",
+    note = "only props declared with `name?: Type` can be omitted"
+)]
+pub trait RequiredProp<T> {
+    /// Returns the value of the prop.
+    fn into_prop(self) -> T;
+}
+
+impl<T> RequiredProp<T> for SetProp<T> {
+    fn into_prop(self) -> T {
+        self.0
+    }
+}
@@ -0,0 +1,73 @@
+//! Values made available to components for the duration of a render.
+//!
+//! Templates are rendered lazily, so request-scoped data (CSRF tokens, flash messages, the current user, ...) can't
+//! always be threaded through every component as props. Instead, wrap the call that renders the page in
+//! [`provide`] and read the value from any component with [`get`].
+//!
+//! Context is stored per thread and values are looked up by type. Nested [`provide`] calls shadow outer values of the
+//! same type until they return.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{component, context, html, ToHtml};
+//!
+//! struct CurrentUser(String);
+//!
+//! component! {
+//!     pub fn UserName() {
+//!         if let Some(user) = context::get::<CurrentUser>() {
+//!             span { (user.0) }
+//!         }
+//!     }
+//! }
+//!
+//! let page = html! {
+//!     @UserName() {}
+//! };
+//!
+//! let html = context::provide(CurrentUser("Ada".to_owned()), || page.to_html());
+//! assert_eq!(html, "<span>Ada</span>");
+//!
+//! assert_eq!(page.to_html(), "");
+//! ```
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+thread_local! {
+    static CONTEXT: RefCell<Vec<Rc<dyn Any>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Makes `value` available to [`get`] while `f` runs on the current thread, and returns the result of `f`.
+///
+/// The value is removed when `f` returns (or panics).
+pub fn provide<T, R>(value: T, f: impl FnOnce() -> R) -> R
+where
+    T: 'static,
+{
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            CONTEXT.with(|context| context.borrow_mut().pop());
+        }
+    }
+
+    CONTEXT.with(|context| context.borrow_mut().push(Rc::new(value)));
+    let _guard = Guard;
+
+    f()
+}
+
+/// Returns the innermost value of type `T` provided with [`provide`] on the current thread, if any.
+pub fn get<T>() -> Option<Rc<T>>
+where
+    T: 'static,
+{
+    CONTEXT.with(|context| {
+        context
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|value| Rc::clone(value).downcast::<T>().ok())
+    })
+}
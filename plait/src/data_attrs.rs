@@ -0,0 +1,80 @@
+use std::ops::Deref;
+
+use crate::{Attrs, RenderEscaped};
+
+/// A group of `data-*` attributes, usually built with the [`data!`](crate::data) macro.
+///
+/// It is an [`Attrs`] group whose names are given without the `data-` prefix, and dereferences to it. `data: data!(...)`
+/// in a template renders the attributes of the group, and a group stored in a variable is rendered with
+/// `#attrs(expr)`.
+///
+/// # Example
+///
+/// ```
+/// use plait::{DataAttrs, data, html, ToHtml};
+///
+/// let frag = html! {
+///     button(data: data!(user_id: 42, role: "admin")) { "Edit" }
+/// };
+/// assert_eq!(frag.to_html(), r#"<button data-user-id="42" data-role="admin">Edit</button>"#);
+///
+/// let attrs = DataAttrs::new().set("controller", "dropdown");
+/// let frag = html! { div(#attrs(&attrs)) {} };
+/// assert_eq!(frag.to_html(), r#"<div data-controller="dropdown"></div>"#);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DataAttrs(Attrs);
+
+impl DataAttrs {
+    /// Creates an empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the attribute `data-{name}` with `value`.
+    pub fn set(self, name: &str, value: impl RenderEscaped) -> Self {
+        let name = format!("data-{}", name.trim_start_matches("r#"));
+        Self(self.0.set(&name, value))
+    }
+}
+
+impl Deref for DataAttrs {
+    type Target = Attrs;
+
+    fn deref(&self) -> &Attrs {
+        &self.0
+    }
+}
+
+impl From<DataAttrs> for Attrs {
+    fn from(data: DataAttrs) -> Self {
+        data.0
+    }
+}
+
+impl IntoIterator for DataAttrs {
+    type Item = <Attrs as IntoIterator>::Item;
+    type IntoIter = <Attrs as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a DataAttrs {
+    type Item = <&'a Attrs as IntoIterator>::Item;
+    type IntoIter = <&'a Attrs as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.0).into_iter()
+    }
+}
+
+/// Builds a [`DataAttrs`] group from `name: value` pairs, e.g. `data!(id: user.id, role: "admin")` for
+/// `data-id="..." data-role="admin"`.
+#[macro_export]
+macro_rules! data {
+    ($($name:ident : $value:expr),* $(,)?) => {
+        $crate::DataAttrs::new()$(.set(::core::stringify!($name), $value))*
+    };
+}
@@ -0,0 +1,75 @@
+//! Debug-only content.
+//!
+//! Content inside `@debug { ... }` is rendered in debug builds (with `debug_assertions` enabled in the crate containing
+//! the template) and skipped in release builds, where it costs a single flag check. Use it for debug panels or for
+//! dumping state into the page with [`dump`]:
+//!
+//! ```
+//! use plait::{html, ToHtml, debug};
+//!
+//! let items = vec!["a", "b"];
+//!
+//! let frag = html! {
+//!     ul {
+//!         for item in &items {
+//!             li { (item) }
+//!         }
+//!     }
+//!     @debug {
+//!         (debug::dump(&items))
+//!     }
+//! };
+//!
+//! # if cfg!(debug_assertions) {
+//! assert_eq!(frag.to_html(), "<ul><li>a</li><li>b</li></ul><!-- [\"a\", \"b\"] -->");
+//! # }
+//! ```
+//!
+//! [`set_enabled`] turns debug content on in release builds too, e.g. for a staging deployment.
+use std::{
+    fmt::{self, Write},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::comment::Comment;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Renders `@debug` content in release builds as well when `enabled` is `true`.
+///
+/// Debug builds always render `@debug` content.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if `@debug` content has been enabled with [`set_enabled`].
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Returns a comment containing the [`Debug`](fmt::Debug) output of `value`.
+///
+/// A space is inserted between consecutive hyphens, so the output can't end the comment early.
+pub fn dump(value: &(impl fmt::Debug + ?Sized)) -> Comment {
+    struct Writer(String);
+
+    impl Write for Writer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for c in s.chars() {
+                if c == '-' && self.0.ends_with('-') {
+                    self.0.push(' ');
+                }
+
+                self.0.push(c);
+            }
+
+            Ok(())
+        }
+    }
+
+    let mut writer = Writer(String::from(" "));
+    let _ = write!(writer, "{value:?}");
+    writer.0.push(' ');
+
+    Comment::new(writer.0)
+}
@@ -0,0 +1,176 @@
+//! Flash messages: one-off notifications shown on the next rendered page.
+//!
+//! Handlers collect messages in a [`Flash`] and [`provide`](crate::context::provide) it in the render context while
+//! the page is rendered. The [`FlashMessages`] component renders the collected messages in an accessible
+//! `role="alert"` region. Messages can also be added while rendering with [`push`].
+//!
+//! How messages are carried across a redirect (session, cookie, ...) is up to the application: load them into a
+//! [`Flash`] at the start of the request and clear the store once the page has been rendered.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{context, html, ToHtml, flash::{Flash, FlashMessages}};
+//!
+//! let page = html! {
+//!     main {
+//!         @FlashMessages() {}
+//!         h1 { "Posts" }
+//!     }
+//! };
+//!
+//! let flash = Flash::new();
+//! flash.success("Post created");
+//!
+//! let html = context::provide(flash, || page.to_html());
+//!
+//! assert_eq!(
+//!     html,
+//!     "<main><div class=\"flash-messages\" role=\"alert\">\
+//!     <p class=\"flash flash-success\">Post created</p></div>\
+//!     <h1>Posts</h1></main>"
+//! );
+//! ```
+use std::cell::RefCell;
+
+use crate::{classes, component, context};
+
+/// Severity of a [`FlashMessage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Level {
+    /// Informational message.
+    Info,
+    /// The requested action succeeded.
+    Success,
+    /// Something needs the user's attention.
+    Warning,
+    /// The requested action failed.
+    Error,
+}
+
+impl Level {
+    /// Returns the lower-case name of the level.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Success => "success",
+            Level::Warning => "warning",
+            Level::Error => "error",
+        }
+    }
+
+    /// Returns the `flash-{level}` class of messages with this level.
+    pub const fn class(&self) -> &'static str {
+        match self {
+            Level::Info => "flash-info",
+            Level::Success => "flash-success",
+            Level::Warning => "flash-warning",
+            Level::Error => "flash-error",
+        }
+    }
+}
+
+/// A single flash message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlashMessage {
+    /// Severity of the message.
+    pub level: Level,
+    /// Text of the message.
+    pub text: String,
+}
+
+/// A queue of flash messages for the page being rendered.
+#[derive(Debug, Default)]
+pub struct Flash {
+    messages: RefCell<Vec<FlashMessage>>,
+}
+
+impl Flash {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a message with the given level.
+    pub fn push(&self, level: Level, text: impl Into<String>) {
+        self.messages.borrow_mut().push(FlashMessage {
+            level,
+            text: text.into(),
+        });
+    }
+
+    /// Adds an [`Info`](Level::Info) message.
+    pub fn info(&self, text: impl Into<String>) {
+        self.push(Level::Info, text)
+    }
+
+    /// Adds a [`Success`](Level::Success) message.
+    pub fn success(&self, text: impl Into<String>) {
+        self.push(Level::Success, text)
+    }
+
+    /// Adds a [`Warning`](Level::Warning) message.
+    pub fn warning(&self, text: impl Into<String>) {
+        self.push(Level::Warning, text)
+    }
+
+    /// Adds an [`Error`](Level::Error) message.
+    pub fn error(&self, text: impl Into<String>) {
+        self.push(Level::Error, text)
+    }
+
+    /// Returns a copy of the queued messages, in the order they were added.
+    pub fn messages(&self) -> Vec<FlashMessage> {
+        self.messages.borrow().clone()
+    }
+
+    /// Returns `true` if no messages are queued.
+    pub fn is_empty(&self) -> bool {
+        self.messages.borrow().is_empty()
+    }
+
+    /// Removes and returns all queued messages.
+    pub fn take(&self) -> Vec<FlashMessage> {
+        std::mem::take(&mut *self.messages.borrow_mut())
+    }
+}
+
+impl FromIterator<FlashMessage> for Flash {
+    fn from_iter<I: IntoIterator<Item = FlashMessage>>(iter: I) -> Self {
+        Self {
+            messages: RefCell::new(iter.into_iter().collect()),
+        }
+    }
+}
+
+/// Adds a message to the [`Flash`] in the render context.
+///
+/// Returns `false` (and drops the message) if no [`Flash`] has been provided.
+pub fn push(level: Level, text: impl Into<String>) -> bool {
+    match context::get::<Flash>() {
+        Some(flash) => {
+            flash.push(level, text);
+            true
+        }
+        None => false,
+    }
+}
+
+component! {
+    /// Renders the messages of the [`Flash`] in the render context.
+    ///
+    /// Messages are rendered as `<p class="flash flash-{level}">` inside a `<div class="flash-messages" role="alert">`
+    /// region. Nothing is rendered if there is no [`Flash`] in the context or it is empty. Extra attributes are
+    /// forwarded to the region.
+    pub fn FlashMessages() {
+        let messages = context::get::<Flash>().map(|flash| flash.messages()).unwrap_or_default();
+
+        if !messages.is_empty() {
+            div(class: "flash-messages", role: "alert", #attrs) {
+                for message in messages.iter() {
+                    p(class: classes!("flash", message.level.class())) { (message.text) }
+                }
+            }
+        }
+    }
+}
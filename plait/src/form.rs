@@ -0,0 +1,627 @@
+//! Helpers for rendering HTML forms.
+//!
+//! # Model binding
+//!
+//! Derive [`FormModel`] on the struct backing a form and render its inputs with [`FieldFor`]. Each input gets its
+//! `name`, `id` and current `value` from the model, and is marked `aria-invalid` and linked to its error message when
+//! the [`ValidationErrors`] contain errors for the field.
+//!
+//! The derive adds a [`Field`] constant to the struct for every field, named after the field in upper case (`email`
+//! becomes `Signup::EMAIL`). [`FieldFor`] takes these constants rather than strings, so a misspelled field name or a
+//! field of a different model is a compile error instead of an empty input.
+//!
+//! ```
+//! use plait::{html, ToHtml, form::{FieldFor, FormModel, ValidationErrors}};
+//!
+//! #[derive(FormModel)]
+//! struct Signup {
+//!     email: String,
+//!     age: Option<u32>,
+//! }
+//!
+//! let signup = Signup { email: "not-an-email".to_owned(), age: None };
+//!
+//! let mut errors = ValidationErrors::new();
+//! errors.add("email", "Enter a valid email address");
+//!
+//! let frag = html! {
+//!     @FieldFor(model: &signup, field: Signup::EMAIL, errors: &errors; type: "email") {}
+//!     @FieldFor(model: &signup, field: Signup::AGE, errors: &errors; type: "number") {}
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<input name=\"email\" id=\"email\" value=\"not-an-email\" aria-invalid=\"true\" \
+//!     aria-describedby=\"email-error\" type=\"email\">\
+//!     <span id=\"email-error\" class=\"field-error\">Enter a valid email address</span>\
+//!     <input name=\"age\" id=\"age\" type=\"number\">"
+//! );
+//! ```
+//!
+//! Fields are checked against the model:
+//!
+//! ```compile_fail,E0599
+//! use plait::{html, form::{FieldFor, FormModel, ValidationErrors}};
+//!
+//! #[derive(FormModel)]
+//! struct Signup {
+//!     email: String,
+//! }
+//!
+//! let signup = Signup { email: String::new() };
+//!
+//! let frag = html! {
+//!     @FieldFor(model: &signup, field: Signup::EMIAL, errors: &ValidationErrors::new()) {}
+//! };
+//! ```
+//!
+//! # Select options
+//!
+//! [`SelectFrom`] renders a `select` element with one `option` per item, marking the option whose value matches
+//! `selected`. Items can be `(value, label)` pairs or values of an enum deriving [`Options`]:
+//!
+//! ```
+//! use plait::{html, ToHtml, form::{Options, SelectFrom}};
+//!
+//! #[derive(Options)]
+//! enum Size {
+//!     #[option(value = "s", label = "Small")]
+//!     Small,
+//!     #[option(value = "l", label = "Large")]
+//!     Large,
+//! }
+//!
+//! let frag = html! {
+//!     @SelectFrom(options: Size::options(), selected: Some("l"); name: "size") {}
+//!     @SelectFrom(options: [("a", "A & B")], selected: None) {
+//!         option(value: "") { "Choose one" }
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<select name=\"size\"><option value=\"s\">Small</option><option value=\"l\" selected>Large</option></select>\
+//!     <select><option value=\"\">Choose one</option><option value=\"a\">A &amp; B</option></select>"
+//! );
+//! ```
+//!
+//! # Labels
+//!
+//! [`Labeled`] renders a `label` for the first form control among its children and wires the two together. The
+//! control keeps its `id` if it has one; otherwise it gets a unique one generated with [`ids::next`](crate::ids::next)
+//! from its `name`:
+//!
+//! ```
+//! use plait::{html, ToHtml, form::Labeled};
+//!
+//! let frag = html! {
+//!     @Labeled(label: "Email") {
+//!         input(type: "email", name: "email");
+//!     }
+//!     @Labeled(label: "Email again"; class: "secondary") {
+//!         input(type: "email", name: "email");
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<label for=\"email\">Email</label><input id=\"email\" type=\"email\" name=\"email\">\
+//!     <label for=\"email-1\" class=\"secondary\">Email again</label>\
+//!     <input id=\"email-1\" type=\"email\" name=\"email\">"
+//! );
+//! ```
+//!
+//! # Method spoofing and CSRF protection
+//!
+//! HTML forms can only be submitted with `GET` or `POST`. [`FormWith`] renders other verbs the way most backend
+//! frameworks expect: as a `POST` form with a hidden `_method` input carrying the real verb. The name of that input
+//! can be changed by providing a [`MethodField`] in the render [`context`](crate::context).
+//!
+//! When a [`CsrfToken`] is provided in the render context, every non-`GET` form also gets a hidden input with the
+//! token:
+//!
+//! ```
+//! use plait::{context, html, ToHtml, form::{CsrfToken, FormWith, Method}};
+//!
+//! let frag = html! {
+//!     @FormWith(method: Method::Delete, action: "/posts/1") {
+//!         button { "Delete" }
+//!     }
+//! };
+//!
+//! let html = context::provide(CsrfToken::new("authenticity_token", "s3cr3t"), || frag.to_html());
+//!
+//! assert_eq!(
+//!     html,
+//!     "<form method=\"post\" action=\"/posts/1\">\
+//!     <input type=\"hidden\" name=\"_method\" value=\"DELETE\">\
+//!     <input type=\"hidden\" name=\"authenticity_token\" value=\"s3cr3t\">\
+//!     <button>Delete</button></form>"
+//! );
+//! ```
+use std::{borrow::Cow, collections::BTreeMap, fmt, marker::PhantomData};
+
+use crate::{
+    RenderEscaped, component, context, ids,
+    text::text_content,
+    tokenizer::{self, Token, Tokenizer},
+    utils::escape_html_to,
+};
+
+/// Derives [`FormModel`](trait@FormModel) for a struct with named fields.
+///
+/// Every field must implement [`FormValue`]. Fields can be customized with the `#[form(...)]` attribute:
+///
+/// - `#[form(rename = "name")]` — use `name` as the field name instead of the Rust identifier.
+/// - `#[form(skip)]` — exclude the field (e.g. passwords, which should not be rendered back into the form).
+///
+/// For every field that isn't skipped, the derive also adds an associated [`Field`] constant named after the field in
+/// upper case (`display_name` becomes `DISPLAY_NAME`), carrying the (possibly renamed) field name.
+pub use plait_macros::FormModel;
+
+/// Derives [`Options`](trait@Options) for an enum without fields.
+///
+/// The value and label of each variant default to the variant name. They can be customized with the
+/// `#[option(value = "...", label = "...")]` attribute. The derive also implements [`SelectOption`] and
+/// [`FormValue`] (using the variant's value), so enum fields can be used with [`SelectFrom`] and [`FieldFor`].
+pub use plait_macros::Options;
+
+/// Trait for structs whose fields can be bound to form inputs by name.
+///
+/// You normally derive this trait with [`#[derive(FormModel)]`](macro@FormModel).
+pub trait FormModel {
+    /// Returns the current value of `field`, or `None` if the field doesn't exist or has no value.
+    fn field_value(&self, field: &str) -> Option<Cow<'_, str>>;
+}
+
+impl<T> FormModel for &T
+where
+    T: FormModel + ?Sized,
+{
+    fn field_value(&self, field: &str) -> Option<Cow<'_, str>> {
+        (**self).field_value(field)
+    }
+}
+
+/// A field of the form model `M`.
+///
+/// Values are created by [`#[derive(FormModel)]`](macro@FormModel), which adds one associated constant per field to
+/// the model, so only fields that exist on `M` can be passed to [`FieldFor`].
+pub struct Field<M: ?Sized> {
+    name: &'static str,
+    model: PhantomData<fn() -> M>,
+}
+
+impl<M: ?Sized> Field<M> {
+    #[doc(hidden)]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            model: PhantomData,
+        }
+    }
+
+    /// Returns the field name used for the input's `name` and `id` and to look up errors.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<M: ?Sized> Clone for Field<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: ?Sized> Copy for Field<M> {}
+
+impl<M: ?Sized> fmt::Debug for Field<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Field").field(&self.name).finish()
+    }
+}
+
+/// Trait for values that can be used as the value of a form input.
+///
+/// # Built-in implementations
+///
+/// | Type                                                  | Behavior                                  |
+/// |-------------------------------------------------------|-------------------------------------------|
+/// | `&str`, `String`, `Cow<'_, str>`                      | Used as-is                                |
+/// | `bool`, `char`, integer and float types               | Formatted with [`ToString`]               |
+/// | `Option<T: FormValue>`                                | Delegates to inner value, or no value     |
+/// | `&T` where `T: FormValue`                             | Delegates to inner value                  |
+pub trait FormValue {
+    /// Returns the value to render into the input, or `None` for no value.
+    fn form_value(&self) -> Option<Cow<'_, str>>;
+}
+
+impl<T> FormValue for &T
+where
+    T: FormValue + ?Sized,
+{
+    fn form_value(&self) -> Option<Cow<'_, str>> {
+        (**self).form_value()
+    }
+}
+
+impl<T> FormValue for Option<T>
+where
+    T: FormValue,
+{
+    fn form_value(&self) -> Option<Cow<'_, str>> {
+        self.as_ref().and_then(FormValue::form_value)
+    }
+}
+
+impl FormValue for str {
+    fn form_value(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl FormValue for String {
+    fn form_value(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl FormValue for Cow<'_, str> {
+    fn form_value(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+macro_rules! impl_form_value_to_string {
+    ($($ty:ty),+) => {
+        $(
+            impl FormValue for $ty {
+                fn form_value(&self) -> Option<Cow<'_, str>> {
+                    Some(Cow::Owned(self.to_string()))
+                }
+            }
+        )+
+    };
+}
+
+impl_form_value_to_string!(
+    bool, char, usize, isize, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64
+);
+
+/// Validation error messages keyed by field name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: BTreeMap<String, Vec<String>>,
+}
+
+impl ValidationErrors {
+    /// Creates an empty set of validation errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error message for `field`.
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors
+            .entry(field.into())
+            .or_default()
+            .push(message.into());
+    }
+
+    /// Returns the error messages for `field` (empty if the field is valid).
+    pub fn get(&self, field: &str) -> &[String] {
+        self.errors.get(field).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns `true` if `field` has at least one error.
+    pub fn has(&self, field: &str) -> bool {
+        !self.get(field).is_empty()
+    }
+
+    /// Returns `true` if there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+component! {
+    /// Renders an `input` bound to a field of a [`FormModel`].
+    ///
+    /// The input gets `name` and `id` set to the field name and `value` set to the field's current value. When
+    /// `errors` contains messages for the field, the input is marked `aria-invalid="true"` and described by a
+    /// `<span id="{field}-error" class="field-error">` containing the messages, rendered right after the input. Extra
+    /// attributes (e.g. `type`) are forwarded to the input.
+    pub fn FieldFor<M>(model: &M, field: Field<M>, errors: &ValidationErrors) where M: FormModel + ?Sized {
+        let field = field.name();
+        let value = model.field_value(field);
+        let messages = errors.get(field);
+        let error_id = format!("{field}-error");
+        let is_invalid = !messages.is_empty();
+
+        input(
+            name: field,
+            id: field,
+            value?: value,
+            aria_invalid?: is_invalid.then_some("true"),
+            aria_describedby?: is_invalid.then_some(&error_id),
+            #attrs
+        );
+
+        if is_invalid {
+            span(id: error_id, class: "field-error") {
+                for (index, message) in messages.iter().enumerate() {
+                    if index > 0 { " " }
+                    (message)
+                }
+            }
+        }
+    }
+}
+
+/// Trait for enums whose variants are the options of a `select` element.
+///
+/// You normally derive this trait with [`#[derive(Options)]`](macro@Options).
+pub trait Options: Sized + 'static {
+    /// Returns all variants, in declaration order.
+    fn options() -> &'static [Self];
+
+    /// Returns the value submitted with the form when this variant is selected.
+    fn value(&self) -> &'static str;
+
+    /// Returns the human-readable label of this variant.
+    fn label(&self) -> &'static str;
+}
+
+/// Trait for items that can be rendered as an `option` by [`SelectFrom`].
+///
+/// Implemented for `(value, label)` pairs of string-like types, for references and for enums deriving
+/// [`Options`](macro@Options).
+pub trait SelectOption {
+    /// Returns the value of the option.
+    fn option_value(&self) -> &str;
+
+    /// Returns the label of the option.
+    fn option_label(&self) -> &str;
+}
+
+impl<T> SelectOption for &T
+where
+    T: SelectOption + ?Sized,
+{
+    fn option_value(&self) -> &str {
+        (**self).option_value()
+    }
+
+    fn option_label(&self) -> &str {
+        (**self).option_label()
+    }
+}
+
+impl<V, L> SelectOption for (V, L)
+where
+    V: AsRef<str>,
+    L: AsRef<str>,
+{
+    fn option_value(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    fn option_label(&self) -> &str {
+        self.1.as_ref()
+    }
+}
+
+component! {
+    /// Renders a `select` element with an `option` for each item of `options`.
+    ///
+    /// The option whose value equals `selected` gets the `selected` attribute. Values and labels are escaped. Extra
+    /// attributes (e.g. `name`) are forwarded to the `select` element and children are rendered before the options,
+    /// which is useful for a placeholder option.
+    pub fn SelectFrom<I>(options: I, selected: Option<&str>) where I: IntoIterator + Clone, I::Item: SelectOption {
+        select(#attrs) {
+            #children
+
+            for option in options.clone() {
+                let value = option.option_value();
+                let is_selected = *selected == Some(value);
+
+                option(value: value, selected?: is_selected) {
+                    (option.option_label())
+                }
+            }
+        }
+    }
+}
+
+/// HTTP method of a form rendered with [`FormWith`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// `GET`
+    Get,
+    /// `POST`
+    Post,
+    /// `PUT`
+    Put,
+    /// `PATCH`
+    Patch,
+    /// `DELETE`
+    Delete,
+}
+
+impl Method {
+    /// Returns the upper-case name of the method (e.g. `"DELETE"`).
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+        }
+    }
+
+    /// Returns the value of the form's `method` attribute: `"get"` for [`Method::Get`] and `"post"` otherwise.
+    pub const fn form_method(&self) -> &'static str {
+        match self {
+            Method::Get => "get",
+            _ => "post",
+        }
+    }
+
+    /// Returns the method to send in the hidden method input, or `None` if the form's `method` attribute is enough.
+    pub const fn spoofed(&self) -> Option<&'static str> {
+        match self {
+            Method::Get | Method::Post => None,
+            _ => Some(self.as_str()),
+        }
+    }
+}
+
+/// Name of the hidden input used by [`FormWith`] to send the real method of the form.
+///
+/// Provide this in the render [`context`](crate::context) to override the default name, `_method`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MethodField(pub &'static str);
+
+impl MethodField {
+    /// The default name of the hidden method input.
+    pub const DEFAULT: &'static str = "_method";
+}
+
+impl Default for MethodField {
+    fn default() -> Self {
+        Self(Self::DEFAULT)
+    }
+}
+
+/// A CSRF token rendered by [`FormWith`] as a hidden input in every non-`GET` form.
+///
+/// Provide this in the render [`context`](crate::context) for the request being rendered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrfToken {
+    field: Cow<'static, str>,
+    token: String,
+}
+
+impl CsrfToken {
+    /// Creates a CSRF token sent in the input named `field`.
+    pub fn new(field: impl Into<Cow<'static, str>>, token: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            token: token.into(),
+        }
+    }
+
+    /// Returns the name of the input carrying the token.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the token.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+component! {
+    /// Renders a `form` element submitted with `method`.
+    ///
+    /// Methods other than `GET` and `POST` are sent as `POST` with a hidden input (named by the [`MethodField`] in the
+    /// render context, `_method` by default) containing the real method. Non-`GET` forms also get a hidden input with
+    /// the [`CsrfToken`] from the render context, if any. Extra attributes are forwarded to the `form` element and
+    /// children are rendered after the hidden inputs.
+    pub fn FormWith(method: Method, action: &str) {
+        let method_field = context::get::<MethodField>().map_or(MethodField::DEFAULT, |field| field.0);
+        let csrf_token = match method {
+            Method::Get => None,
+            _ => context::get::<CsrfToken>(),
+        };
+
+        form(method: method.form_method(), action: action, #attrs) {
+            if let Some(spoofed) = method.spoofed() {
+                input(type: "hidden", name: method_field, value: spoofed);
+            }
+
+            if let Some(csrf_token) = csrf_token {
+                input(type: "hidden", name: csrf_token.field(), value: csrf_token.token());
+            }
+
+            #children
+        }
+    }
+}
+
+/// Elements that can be associated with a `label`.
+const LABELABLE_ELEMENTS: &[&str] = &[
+    "button", "input", "meter", "output", "progress", "select", "textarea",
+];
+
+/// Renders `children` and finds the first labelable control in them, giving it an id if it doesn't have one.
+///
+/// Returns the id of the control, if any, and the rendered children.
+fn wire_control(
+    children: &impl Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+) -> Result<(Option<String>, String), fmt::Error> {
+    let mut html = String::new();
+    children(&mut html)?;
+
+    let mut offset = 0;
+    let mut control = None;
+
+    for token in Tokenizer::new(&html) {
+        if let Token::StartTag { name, source, .. } = token {
+            let is_hidden_input = name.eq_ignore_ascii_case("input")
+                && tokenizer::attribute(source, "type")
+                    .is_some_and(|kind| kind.eq_ignore_ascii_case("hidden"));
+
+            if !is_hidden_input
+                && LABELABLE_ELEMENTS
+                    .iter()
+                    .any(|element| name.eq_ignore_ascii_case(element))
+            {
+                let id = tokenizer::attribute(source, "id").map(text_content);
+                let name_attribute = tokenizer::attribute(source, "name").map(text_content);
+                control = Some((offset + 1 + name.len(), id, name_attribute));
+                break;
+            }
+        }
+
+        offset += token.source().len();
+    }
+
+    let Some((insert_at, id, name)) = control else {
+        return Ok((None, html));
+    };
+
+    if let Some(id) = id {
+        return Ok((Some(id), html));
+    }
+
+    let id = ids::next(
+        name.as_deref()
+            .filter(|name| !name.is_empty())
+            .unwrap_or("field"),
+    );
+
+    let mut attribute = String::from(" id=\"");
+    escape_html_to(&mut attribute, &id)?;
+    attribute.push('"');
+    html.insert_str(insert_at, &attribute);
+
+    Ok((Some(id), html))
+}
+
+component! {
+    /// Renders a `label` containing `label` followed by the children, with the label's `for` pointing at the first
+    /// form control (`input`, `select`, `textarea`, ...) among the children.
+    ///
+    /// The control keeps its `id` if it has one. Otherwise it gets one generated with [`ids::next`], using the
+    /// control's `name` (or `field`) as prefix. Hidden inputs are skipped. Extra attributes are forwarded to the
+    /// `label` element.
+    pub fn Labeled(label: impl RenderEscaped) {
+        let (id, control) = wire_control(&children)?;
+
+        label(for?: id, #attrs) { (label) }
+        #(control)
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, io};
 
 use crate::{Html, RenderEscaped, ToHtml};
 
@@ -43,6 +43,176 @@ where
     pub fn new(f: F, size_hint: usize) -> Self {
         HtmlFragment { f, size_hint }
     }
+
+    /// Returns the estimated length of the rendered HTML, in bytes.
+    ///
+    /// The estimate is computed when the `html!` macro expands: it is the length of the static parts of the template
+    /// (the longer branch of each `if` or `match`), and at least the length of the template source if it has dynamic
+    /// values. Rendering reserves this much space up front, and [`render_with_capacity()`] can reserve more for
+    /// templates with a lot of dynamic content.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plait::{html, ToHtml};
+    ///
+    /// let frag = html! { p(class: "note") { "Hello" } };
+    ///
+    /// assert_eq!(frag.size_hint(), frag.to_html().len());
+    /// ```
+    pub fn size_hint(&self) -> usize {
+        self.size_hint
+    }
+
+    /// Renders the fragment, appending the HTML to `buffer`.
+    ///
+    /// Unlike [`to_html()`](ToHtml::to_html), this doesn't allocate a new string for every render, so a buffer can be
+    /// [cleared](String::clear) and reused across renders in a loop or a [pool](crate::pool). Like `to_html()`, each
+    /// call is a separate render: ids generated with [`ids::next`](crate::ids::next) start over.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plait::html;
+    ///
+    /// let mut buffer = String::new();
+    ///
+    /// for name in ["Alice", "Bob"] {
+    ///     buffer.clear();
+    ///     html! { p { "Hello, " (name) } }.render_into(&mut buffer);
+    ///
+    ///     assert!(buffer.starts_with("<p>Hello, "));
+    /// }
+    ///
+    /// assert_eq!(buffer, "<p>Hello, Bob</p>");
+    /// ```
+    pub fn render_into(&self, buffer: &mut String) {
+        buffer.reserve(self.size_hint);
+        crate::scope::enter(|| (self.f)(buffer)).unwrap();
+    }
+
+    /// Renders the fragment into the byte sink `writer`, such as a file or a socket.
+    ///
+    /// The HTML is written as it is rendered, without collecting the whole page in a string first. The template writes
+    /// many small pieces, so unbuffered sinks should be wrapped in a [`BufWriter`](io::BufWriter). The first I/O error
+    /// stops the render and is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plait::html;
+    ///
+    /// let mut output = Vec::new();
+    /// html! { p { "Hello" } }.write_io(&mut output).unwrap();
+    ///
+    /// assert_eq!(output, b"<p>Hello</p>");
+    /// ```
+    pub fn write_io(&self, writer: &mut impl io::Write) -> io::Result<()> {
+        let mut adapter = IoWriter {
+            writer,
+            error: None,
+        };
+
+        match crate::scope::enter(|| (self.f)(&mut adapter)) {
+            Ok(()) => Ok(()),
+            Err(fmt::Error) => Err(adapter
+                .error
+                .unwrap_or_else(|| io::Error::other("formatting a value failed"))),
+        }
+    }
+
+    /// Renders the fragment into an owned [`Html`] value.
+    ///
+    /// The closure of a fragment borrows or captures the values of its template, so a fragment usually can't be stored
+    /// in a struct, cached or sent to another thread. The returned `Html` is a plain string: it is `Clone`, `Send` and
+    /// `'static`, and renders as-is when embedded in a template with `(html)`, or passed to a [`PartialHtml`] prop.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use plait::{html, Html, ToHtml};
+    ///
+    /// fn nav(items: &[&str]) -> Html {
+    ///     html! {
+    ///         nav { for item in items.iter() { a { (item) } } }
+    ///     }
+    ///     .into_owned()
+    /// }
+    ///
+    /// let cached = nav(&["Home", "About"]);
+    /// let page = std::thread::spawn(move || html! { (cached) main { "Body" } }.to_html())
+    ///     .join()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(page, "<nav><a>Home</a><a>About</a></nav><main>Body</main>");
+    /// ```
+    pub fn into_owned(self) -> Html {
+        self.to_html()
+    }
+}
+
+/// Adapts an [`io::Write`] sink to [`fmt::Write`], keeping the first I/O error.
+struct IoWriter<'a, W: ?Sized> {
+    writer: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W> fmt::Write for IoWriter<'_, W>
+where
+    W: io::Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Renders `fragment` into a new `String`.
+///
+/// This is the same as `fragment.to_html().into()`, for code that works with plain strings.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, render};
+///
+/// let output: String = render(&html! { p { "Hello" } });
+///
+/// assert_eq!(output, "<p>Hello</p>");
+/// ```
+pub fn render<F>(fragment: &HtmlFragment<F>) -> String
+where
+    F: Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+{
+    render_with_capacity(fragment, 0)
+}
+
+/// Renders `fragment` into a new `String` with room for at least `capacity` bytes.
+///
+/// The `html!` macro only knows the size of the static parts of a template, so pages with a lot of dynamic content grow
+/// the string several times while rendering. Passing the typical size of the output (e.g. measured from earlier
+/// renders) allocates it once instead.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, render_with_capacity};
+///
+/// let rows = ["Alice", "Bob", "Carol"];
+/// let output = render_with_capacity(&html! { ul { for row in rows { li { (row) } } } }, 4096);
+///
+/// assert_eq!(output, "<ul><li>Alice</li><li>Bob</li><li>Carol</li></ul>");
+/// assert!(output.capacity() >= 4096);
+/// ```
+pub fn render_with_capacity<F>(fragment: &HtmlFragment<F>, capacity: usize) -> String
+where
+    F: Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+{
+    let mut buffer = String::with_capacity(capacity);
+    fragment.render_into(&mut buffer);
+    buffer
 }
 
 impl<F> RenderEscaped for HtmlFragment<F>
@@ -59,8 +229,8 @@ where
     F: Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
 {
     fn to_html(&self) -> Html {
-        let mut buffer = String::with_capacity(self.size_hint);
-        (self.f)(&mut buffer).unwrap();
+        let mut buffer = String::new();
+        self.render_into(&mut buffer);
 
         Html::new_unchecked(buffer)
     }
@@ -156,3 +326,5 @@ mod rocket {
 pub trait PartialHtml: RenderEscaped {}
 
 impl<F> PartialHtml for HtmlFragment<F> where F: Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result {}
+
+impl PartialHtml for Html {}
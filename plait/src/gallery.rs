@@ -0,0 +1,133 @@
+//! A static gallery of component examples for design review and visual regression testing.
+//!
+//! Functions annotated with [`#[component_example]`](crate::component_example) are registered automatically at
+//! link time. [`render()`] produces a standalone HTML page containing every registered example, grouped by component,
+//! with both the rendered output and its HTML source. Point a screenshot or snapshot tool at this page to catch visual
+//! regressions without a separate storybook tool.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{component, component_example, html, ToHtml};
+//!
+//! component! {
+//!     pub fn Badge(label: &str) {
+//!         span(class: "badge") { (label) }
+//!     }
+//! }
+//!
+//! #[component_example(Badge, "New")]
+//! fn badge_new() -> impl ToHtml {
+//!     html! { @Badge(label: "New") {} }
+//! }
+//!
+//! let example = plait::gallery::examples()
+//!     .into_iter()
+//!     .find(|example| example.component() == "Badge")
+//!     .unwrap();
+//!
+//! assert_eq!(example.name(), "New");
+//! assert_eq!(example.render(), r#"<span class="badge">New</span>"#);
+//!
+//! let page = plait::gallery::render();
+//! assert!(page.contains(r#"<section id="component-Badge">"#));
+//! ```
+use crate::{Html, ToHtml, html};
+
+#[doc(hidden)]
+pub use inventory;
+
+/// A registered component example.
+///
+/// Examples are created by the [`#[component_example]`](crate::component_example) attribute.
+#[derive(Debug)]
+pub struct Example {
+    component: &'static str,
+    name: &'static str,
+    render: fn() -> Html,
+}
+
+impl Example {
+    #[doc(hidden)]
+    /// Creates a new `Example`. This is used internally by the `#[component_example]` attribute.
+    pub const fn new(component: &'static str, name: &'static str, render: fn() -> Html) -> Self {
+        Self {
+            component,
+            name,
+            render,
+        }
+    }
+
+    /// Returns the name of the component this example demonstrates.
+    pub fn component(&self) -> &'static str {
+        self.component
+    }
+
+    /// Returns the name of the example.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Renders the example.
+    pub fn render(&self) -> Html {
+        (self.render)()
+    }
+}
+
+inventory::collect!(Example);
+
+/// Returns all registered examples, sorted by component name and then by example name.
+pub fn examples() -> Vec<&'static Example> {
+    let mut examples: Vec<&'static Example> = inventory::iter::<Example>.into_iter().collect();
+    examples.sort_by_key(|example| (example.component, example.name));
+    examples
+}
+
+/// Renders a standalone HTML page containing every registered example.
+///
+/// Each component gets a `<section id="component-{name}">` with one `<article class="plait-example">` per example,
+/// containing the rendered output and its escaped HTML source.
+pub fn render() -> Html {
+    let mut groups: Vec<(&'static str, Vec<&'static Example>)> = Vec::new();
+
+    for example in examples() {
+        match groups.last_mut() {
+            Some((component, group)) if *component == example.component => group.push(example),
+            _ => groups.push((example.component, vec![example])),
+        }
+    }
+
+    html! {
+        html(lang: "en") {
+            head {
+                meta(charset: "utf-8");
+                title { "Component gallery" }
+            }
+            body {
+                h1 { "Component gallery" }
+                nav {
+                    ul {
+                        for (component, _) in groups.iter() {
+                            li { a(href: #(format!("#component-{component}"))) { (component) } }
+                        }
+                    }
+                }
+                for (component, group) in groups.iter() {
+                    section(id: #(format!("component-{component}"))) {
+                        h2 { (component) }
+                        for example in group.iter() {
+                            let output = example.render();
+
+                            article(class: "plait-example") {
+                                h3 { (example.name) }
+                                div(class: "plait-example-output") { (output) }
+                                pre { code { (&*output) } }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    .to_html()
+}
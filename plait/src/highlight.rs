@@ -0,0 +1,150 @@
+//! Syntax-highlighted code blocks.
+//!
+//! [`CodeBlock`] highlights code with [`syntect`](https://docs.rs/syntect) using the bundled Sublime Text syntaxes and
+//! renders it as `<pre class="code-block"><code class="language-{lang}">`. All code is escaped; languages that aren't
+//! recognized are rendered as plain text.
+//!
+//! By default tokens are wrapped in `<span>`s with `hl-` prefixed classes, to be styled with a stylesheet generated by
+//! [`theme_css`]. To use inline `style` attributes instead (e.g. for emails or feeds), provide
+//! [`Highlighting::InlineStyles`] in the render [`context`](crate::context).
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{context, html, ToHtml, highlight::{CodeBlock, Highlighting}};
+//!
+//! let frag = html! {
+//!     @CodeBlock(lang: "rust", code: "let x = 1;") {}
+//! };
+//!
+//! let classed = frag.to_html();
+//! assert!(classed.starts_with(
+//!     "<pre class=\"code-block\"><code class=\"language-rust\"><span class=\"hl-source hl-rust\">"
+//! ));
+//!
+//! let inline = context::provide(Highlighting::InlineStyles { theme: "InspiredGitHub" }, || frag.to_html());
+//! assert!(inline.starts_with("<pre class=\"code-block\" style=\"background-color:#ffffff;\">"));
+//! ```
+use std::sync::LazyLock;
+
+use syntect::{
+    easy::HighlightLines,
+    highlighting::ThemeSet,
+    html::{
+        ClassStyle, ClassedHTMLGenerator, IncludeBackground, css_for_theme_with_class_style,
+        styled_line_to_highlighted_html,
+    },
+    parsing::{SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+use crate::{component, context, utils::escape_html_to};
+
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+/// How [`CodeBlock`] styles highlighted tokens.
+///
+/// Provide this in the render [`context`](crate::context) to override the default, [`Highlighting::Classes`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Highlighting {
+    /// Tokens get `hl-` prefixed classes. Style them with the stylesheet returned by [`theme_css`].
+    #[default]
+    Classes,
+    /// Tokens get inline `style` attributes using the named bundled theme (see [`themes`]). Falls back to
+    /// [`Highlighting::Classes`] if the theme doesn't exist.
+    InlineStyles {
+        /// Name of the theme.
+        theme: &'static str,
+    },
+}
+
+/// Returns the names of the bundled themes.
+pub fn themes() -> impl Iterator<Item = &'static str> {
+    THEME_SET.themes.keys().map(String::as_str)
+}
+
+/// Returns a stylesheet for code highlighted with [`Highlighting::Classes`] using the named bundled theme, or `None` if
+/// the theme doesn't exist.
+pub fn theme_css(theme: &str) -> Option<String> {
+    let theme = THEME_SET.themes.get(theme)?;
+    css_for_theme_with_class_style(theme, CLASS_STYLE).ok()
+}
+
+fn find_syntax(lang: &str) -> &'static SyntaxReference {
+    SYNTAX_SET
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text())
+}
+
+fn highlight_classed(lang: &str, code: &str) -> Option<String> {
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(find_syntax(lang), &SYNTAX_SET, CLASS_STYLE);
+
+    for line in LinesWithEndings::from(code) {
+        generator
+            .parse_html_for_line_which_includes_newline(line)
+            .ok()?;
+    }
+
+    Some(generator.finalize())
+}
+
+fn highlight_inline(lang: &str, code: &str, theme: &str) -> Option<(String, String)> {
+    let theme = THEME_SET.themes.get(theme)?;
+    let mut highlighter = HighlightLines::new(find_syntax(lang), theme);
+    let mut html = String::with_capacity(code.len() * 2);
+
+    for line in LinesWithEndings::from(code) {
+        let ranges = highlighter.highlight_line(line, &SYNTAX_SET).ok()?;
+        html.push_str(&styled_line_to_highlighted_html(&ranges, IncludeBackground::No).ok()?);
+    }
+
+    let background = theme.settings.background.map(|color| {
+        format!(
+            "background-color:#{:02x}{:02x}{:02x};",
+            color.r, color.g, color.b
+        )
+    });
+
+    Some((html, background.unwrap_or_default()))
+}
+
+fn escape(code: &str) -> String {
+    let mut escaped = String::with_capacity(code.len());
+    let _ = escape_html_to(&mut escaped, code);
+    escaped
+}
+
+/// Highlights `code` and returns the inner HTML of the `code` element, along with the `style` of the `pre` element.
+fn highlight(lang: &str, code: &str) -> (String, Option<String>) {
+    let highlighting = context::get::<Highlighting>()
+        .map(|highlighting| *highlighting)
+        .unwrap_or_default();
+
+    if let Highlighting::InlineStyles { theme } = highlighting
+        && let Some((html, style)) = highlight_inline(lang, code, theme)
+    {
+        return (html, Some(style).filter(|style| !style.is_empty()));
+    }
+
+    let html = highlight_classed(lang, code).unwrap_or_else(|| escape(code));
+    (html, None)
+}
+
+component! {
+    /// Renders `code` as a syntax-highlighted `<pre class="code-block"><code class="language-{lang}">` block.
+    ///
+    /// `lang` is a language name or file extension (e.g. `"rust"`, `"rs"`, `"html"`). Extra attributes are forwarded
+    /// to the `pre` element.
+    pub fn CodeBlock(lang: &str, code: &str) {
+        let (highlighted, style) = highlight(lang, code);
+        let language = format!("language-{lang}");
+
+        pre(class: "code-block", style?: style, #attrs) {
+            code(class: language) { #(highlighted) }
+        }
+    }
+}
@@ -146,3 +146,9 @@ pub trait ToHtml {
     /// Renders `self` into an [`Html`] value.
     fn to_html(&self) -> Html;
 }
+
+impl ToHtml for Html {
+    fn to_html(&self) -> Html {
+        self.clone()
+    }
+}
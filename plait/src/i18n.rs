@@ -0,0 +1,191 @@
+//! Translation of template text through a catalog chosen at runtime.
+//!
+//! Wrap a [`Translator`] in [`Translations`] and [`provide`](crate::context::provide) it in the render context while
+//! the page is rendered. Templates then translate text with `t!("key", name = value)`, which looks up the message for
+//! `key` and replaces each `{name}` placeholder in it with the escaped value of the argument of the same name:
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use plait::{context, html, ToHtml, i18n::Translations};
+//!
+//! let user = "<Ada>";
+//! let count = 3;
+//! let page = html! {
+//!     h1 { t!("greeting", name = user) }
+//!     p { t!("You have {count} items", count = count) }
+//! };
+//!
+//! let french = Translations::new(HashMap::from([
+//!     ("greeting", "Bonjour, {name} !"),
+//!     ("You have {count} items", "Vous avez {count} articles"),
+//! ]));
+//! assert_eq!(
+//!     context::provide(french, || page.to_html()),
+//!     "<h1>Bonjour, &lt;Ada&gt; !</h1><p>Vous avez 3 articles</p>"
+//! );
+//!
+//! // Without `Translations` in the context the key is the message
+//! assert_eq!(page.to_html(), "<h1>greeting</h1><p>You have 3 items</p>");
+//! ```
+//!
+//! Keys the translator doesn't know are rendered as the message too, so keys can be written in the source language
+//! as with gettext. Messages use the same placeholders as text literals: `{{` and `}}` are literal braces, and a
+//! placeholder without an argument is rendered as it is. The message and the arguments are escaped like any other
+//! text, so translations can't inject markup.
+//!
+//! Outside of `html!` templates, e.g. in attribute values or Rust code, import the [`t!`](crate::t) macro, which
+//! returns a [`Message`] implementing [`RenderEscaped`]. [`translate`] looks up a message without arguments.
+use std::{borrow::Cow, collections::HashMap, fmt, rc::Rc};
+
+use crate::{RenderEscaped, context, utils::escape_html_to};
+
+/// Looks up the messages of the language of the current render.
+pub trait Translator {
+    /// Returns the message for `key`, or `None` if it isn't translated.
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>>;
+}
+
+impl<F> Translator for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        self(key).map(Cow::Owned)
+    }
+}
+
+impl Translator for HashMap<String, String> {
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.get(key).map(|message| Cow::Borrowed(message.as_str()))
+    }
+}
+
+impl Translator for HashMap<&'static str, &'static str> {
+    fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.get(key).map(|message| Cow::Borrowed(*message))
+    }
+}
+
+/// The [`Translator`] of the current render, provided with [`provide`](crate::context::provide).
+#[derive(Clone)]
+pub struct Translations {
+    translator: Rc<dyn Translator>,
+}
+
+impl Translations {
+    /// Creates translations looked up by `translator`.
+    pub fn new(translator: impl Translator + 'static) -> Self {
+        Self {
+            translator: Rc::new(translator),
+        }
+    }
+
+    /// Returns the message for `key`, or `None` if it isn't translated.
+    pub fn translate(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.translator.translate(key)
+    }
+}
+
+impl fmt::Debug for Translations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Translations").finish_non_exhaustive()
+    }
+}
+
+/// Returns the message for `key` from the [`Translations`] in the render context, or `key` itself if it isn't
+/// translated or no [`Translations`] have been provided.
+pub fn translate(key: &str) -> String {
+    context::get::<Translations>()
+        .and_then(|translations| translations.translate(key).map(Cow::into_owned))
+        .unwrap_or_else(|| key.to_owned())
+}
+
+/// A translated message with its arguments, created by the [`t!`](crate::t) macro.
+///
+/// The message is looked up when the value is rendered, and its `{name}` placeholders are replaced with the escaped
+/// arguments.
+pub struct Message<'a> {
+    key: &'a str,
+    args: &'a [(&'a str, &'a dyn RenderEscaped)],
+}
+
+impl<'a> Message<'a> {
+    /// Creates the message for `key` with named arguments.
+    pub fn new(key: &'a str, args: &'a [(&'a str, &'a dyn RenderEscaped)]) -> Self {
+        Self { key, args }
+    }
+}
+
+impl fmt::Debug for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.args.iter().map(|(name, _)| *name).collect();
+
+        f.debug_struct("Message")
+            .field("key", &self.key)
+            .field("args", &names)
+            .finish()
+    }
+}
+
+impl RenderEscaped for Message<'_> {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        let message = translate(self.key);
+        let mut rest = message.as_str();
+
+        while let Some(index) = rest.find(['{', '}']) {
+            let brace = &rest[index..index + 1];
+            escape_html_to(f, &rest[..index])?;
+            rest = &rest[index + 1..];
+
+            if rest.starts_with(brace) {
+                escape_html_to(f, brace)?;
+                rest = &rest[1..];
+                continue;
+            }
+
+            let arg = (brace == "{")
+                .then(|| rest.split_once('}'))
+                .flatten()
+                .and_then(|(name, after)| {
+                    let (_, value) = self.args.iter().find(|(arg, _)| *arg == name)?;
+                    Some((*value, after))
+                });
+
+            match arg {
+                Some((value, after)) => {
+                    value.render_escaped(f)?;
+                    rest = after;
+                }
+                None => escape_html_to(f, brace)?,
+            }
+        }
+
+        escape_html_to(f, rest)
+    }
+}
+
+/// Creates a translated [`Message`] from a key and named arguments, e.g. `t!("greeting", name = user)`.
+///
+/// Inside [`html!`](crate::html) templates `t!` can be written directly as a node. Each argument must implement
+/// [`RenderEscaped`]. See the [`i18n`](crate::i18n) module for details.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, t, ToHtml};
+///
+/// let name = "Ada";
+/// let frag = html! { a(href: "/profile", title: t!("Profile of {name}", name = name)) { "Profile" } };
+///
+/// assert_eq!(frag.to_html(), "<a href=\"/profile\" title=\"Profile of Ada\">Profile</a>");
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::Message::new(
+            $key,
+            &[$((::core::stringify!($name), &$value as &dyn $crate::RenderEscaped)),*],
+        )
+    };
+}
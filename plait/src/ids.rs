@@ -0,0 +1,145 @@
+//! Ids for wiring elements together, e.g. a `label` and its input or an input and its description.
+//!
+//! [`next`] hands out ids that are unique within a single top-level [`to_html`](crate::ToHtml::to_html) call: the
+//! first request for a prefix returns the prefix itself and later ones get `-1`, `-2`, ... appended. Ids generated by
+//! the `auto_id` attribute share the same namespace, so they never collide with each other.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, ids};
+//!
+//! let field = html! {
+//!     let id = ids::next("email");
+//!
+//!     label(for: id) { "Email" }
+//!     input(id: id, type: "email");
+//! };
+//!
+//! let frag = html! {
+//!     (field)
+//!     (field)
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<label for=\"email\">Email</label><input id=\"email\" type=\"email\">\
+//!     <label for=\"email-1\">Email</label><input id=\"email-1\" type=\"email\">"
+//! );
+//! ```
+//!
+//! # Async components
+//!
+//! An [async component](crate::async_component) renders its markup as soon as its data has loaded, before the page
+//! that embeds it is rendered. Every async component shares the ids of the async component awaiting it, so ids are
+//! unique across a tree of async components. To make them unique across the page as well, render the page inside
+//! [`scope`] too:
+//!
+//! ```
+//! use plait::{async_component, html, ids, RenderAsync, ToHtml};
+//!
+//! async_component! {
+//!     pub fn Search() {
+//!         let id = ids::next("query");
+//!         input(id: id, type: "search");
+//!     }
+//! }
+//!
+//! # let future = async {
+//! let page = ids::scope(async {
+//!     let search = Search {}.render_async().await;
+//!
+//!     html! {
+//!         let id = ids::next("query");
+//!
+//!         (search)
+//!         input(id: id);
+//!     }
+//!     .to_html()
+//! })
+//! .await;
+//!
+//! assert_eq!(page, "<input id=\"query\" type=\"search\"><input id=\"query-1\">");
+//! # };
+//! # let waker = std::task::Waker::noop();
+//! # assert!(std::pin::pin!(future).poll(&mut std::task::Context::from_waker(waker)).is_ready());
+//! ```
+//!
+//! # Stable ids
+//!
+//! Ids from [`next`] depend on what else the render contains, so a region re-rendered on its own (e.g. an htmx partial
+//! swap) can get different ids than in the full page. When an id is referenced from outside the region, such as by
+//! `aria-controls` or `aria-labelledby`, derive it from the data instead: [`stable`] hashes a prefix and a key, and
+//! [`component`] the path of a component and a key, into the same id in every render, process and machine.
+//!
+//! ```
+//! use plait::{html, ToHtml, ids};
+//!
+//! let item = 42;
+//! let details = html! {
+//!     let id = ids::stable("details", item);
+//!
+//!     button(aria_controls: id) { "Details" }
+//!     div(id: id) {}
+//! };
+//!
+//! assert_eq!(
+//!     details.to_html(),
+//!     "<button aria-controls=\"details-bd130b65\">Details</button><div id=\"details-bd130b65\"></div>"
+//! );
+//! ```
+use std::{any, fmt, fmt::Write as _};
+
+use crate::{slugify, utils::Fnv1a};
+
+/// Returns an id starting with `prefix` that hasn't been handed out yet during the current render.
+///
+/// Outside of a render (i.e. when not called while a [`to_html`](crate::ToHtml::to_html) call or a [`scope`] is in
+/// progress), `prefix` is returned unchanged.
+pub fn next(prefix: &str) -> String {
+    crate::scope::with(|scope| scope.ids.unique(prefix.to_owned()))
+        .unwrap_or_else(|| prefix.to_owned())
+}
+
+/// Runs `future` so that every render it runs, including those of the [async components](crate::async_component) it
+/// awaits, hands out ids from the same namespace; see [Async components](self#async-components).
+///
+/// The namespace is kept across `.await`s, even when the future moves between threads. Calls inside another `scope`
+/// share the namespace of the outermost one.
+pub fn scope<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    crate::scope::enter_async(future)
+}
+
+/// Returns the id `prefix-hash`, where `hash` is derived from `prefix` and `key`. The same `prefix` and `key` always
+/// give the same id, independently of the render it is generated in; see [Stable ids](self#stable-ids).
+///
+/// Stable ids aren't registered with [`next`], so use a key that identifies the element on the page (e.g. the id of a
+/// database row).
+pub fn stable(prefix: &str, key: impl fmt::Display) -> String {
+    hashed_id(prefix, prefix, key)
+}
+
+/// Returns a [stable id](stable) for the component `C` and `key`, prefixed with the slugified name of the component
+/// (`tabs-…` for `Tabs`). In a [`component!`](crate::component) body, pass `Self` as `C`.
+///
+/// The hash covers the full path of the component but not its generic arguments, so every instantiation of a generic
+/// component gives the same id for the same key.
+pub fn component<C: ?Sized>(key: impl fmt::Display) -> String {
+    let path = any::type_name::<C>();
+    let path = path.split_once('<').map_or(path, |(path, _)| path);
+    let name = path.rsplit("::").next().unwrap_or(path);
+
+    hashed_id(&slugify(name), path, key)
+}
+
+/// Returns `prefix-hash`, with the hash of `scope` and `key` folded to 32 bits.
+fn hashed_id(prefix: &str, scope: &str, key: impl fmt::Display) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(scope.as_bytes());
+    hasher.write(&[0]);
+    let _ = write!(hasher, "{key}");
+
+    let hash = hasher.finish();
+
+    format!("{prefix}-{:08x}", (hash ^ (hash >> 32)) as u32)
+}
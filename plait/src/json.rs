@@ -0,0 +1,162 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::{
+    Class, RenderEscaped, RenderMaybeAttributeEscaped, RenderMaybeAttributeRaw, RenderRaw,
+    utils::escape_html_to,
+};
+
+/// Renders a [`Value`] as text: strings are written without quotes, `null` renders nothing and everything else is
+/// written as JSON.
+fn render_value(
+    value: &Value,
+    f: &mut (dyn fmt::Write + '_),
+    write_str: fn(&mut (dyn fmt::Write + '_), &str) -> fmt::Result,
+) -> fmt::Result {
+    match value {
+        Value::Null => Ok(()),
+        Value::Bool(value) => f.write_str(if *value { "true" } else { "false" }),
+        Value::Number(number) => write!(f, "{number}"),
+        Value::String(string) => write_str(f, string),
+        Value::Array(_) | Value::Object(_) => write_str(f, &value.to_string()),
+    }
+}
+
+fn write_escaped(f: &mut (dyn fmt::Write + '_), s: &str) -> fmt::Result {
+    escape_html_to(f, s)
+}
+
+fn write_raw(f: &mut (dyn fmt::Write + '_), s: &str) -> fmt::Result {
+    f.write_str(s)
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl RenderEscaped for Value {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        render_value(self, f, write_escaped)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl RenderRaw for Value {
+    fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        render_value(self, f, write_raw)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl RenderMaybeAttributeEscaped for Value {
+    fn render_maybe_attribute_escaped(
+        &self,
+        name: &str,
+        f: &mut (dyn fmt::Write + '_),
+    ) -> fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Bool(value) => value.render_maybe_attribute_escaped(name, f),
+            _ => Some(self).render_maybe_attribute_escaped(name, f),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl RenderMaybeAttributeRaw for Value {
+    fn render_maybe_attribute_raw(&self, name: &str, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Bool(value) => value.render_maybe_attribute_raw(name, f),
+            _ => Some(self).render_maybe_attribute_raw(name, f),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl Class for Value {
+    fn should_skip(&self) -> bool {
+        match self {
+            Value::Null => true,
+            Value::String(string) => string.is_empty(),
+            _ => false,
+        }
+    }
+
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        RenderEscaped::render_escaped(self, f)
+    }
+}
+
+/// Wrapper that renders a value serialized as JSON, for embedding data in a page.
+///
+/// The value is serialized with `serde_json`, and the characters that could end or confuse the surrounding markup
+/// (`<`, `>`, `&`, U+2028 and U+2029) are written as `\uXXXX` escapes. These only occur inside JSON strings, so the
+/// output is still the same JSON, and reading it back with `JSON.parse()` gives the original value. This makes it safe
+/// as the content of a `<script type="application/json">` block and, with quotes escaped as usual, as an attribute
+/// value such as `data-*` or `hx-vals`.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::{html, Json, ToHtml};
+/// use serde_json::json;
+///
+/// let config = json!({ "user": "O'Brien", "note": "</script>" });
+///
+/// let frag = html! {
+///     script(type: "application/json") { (Json(&config)) }
+///     button(hx_vals: (Json(&config))) { "Save" }
+/// };
+///
+/// assert_eq!(
+///     frag.to_html(),
+///     r#"<script type="application/json">{"note":"\u003c/script\u003e","user":"O'Brien"}</script><button hx-vals="{&quot;note&quot;:&quot;\u003c/script\u003e&quot;,&quot;user&quot;:&quot;O&#39;Brien&quot;}">Save</button>"#
+/// );
+/// ```
+///
+/// Serialization errors, such as a map with non-string keys, are returned as [`fmt::Error`].
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T>
+where
+    T: serde::Serialize,
+{
+    /// Returns the value serialized as JSON, escaped for embedding in markup.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let json = serde_json::to_string(&self.0)?;
+
+        let mut escaped = String::with_capacity(json.len());
+        let mut last = 0;
+
+        for (index, c) in json.char_indices() {
+            let escape = match c {
+                '<' => "\\u003c",
+                '>' => "\\u003e",
+                '&' => "\\u0026",
+                '\u{2028}' => "\\u2028",
+                '\u{2029}' => "\\u2029",
+                _ => continue,
+            };
+
+            escaped.push_str(&json[last..index]);
+            escaped.push_str(escape);
+            last = index + c.len_utf8();
+        }
+
+        escaped.push_str(&json[last..]);
+
+        Ok(escaped)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T> RenderEscaped for Json<T>
+where
+    T: serde::Serialize,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        let json = self.to_json().map_err(|_| fmt::Error)?;
+        escape_html_to(f, &json)
+    }
+}
@@ -0,0 +1,110 @@
+//! Helpers for common layout chores.
+//!
+//! # Rows and columns
+//!
+//! Plain iterator adapters work in `for` loops, so fixed-size rows need no helper:
+//!
+//! ```
+//! use plait::{html, ToHtml};
+//!
+//! let items = [1, 2, 3, 4, 5];
+//!
+//! let frag = html! {
+//!     for row in items.chunks(2) {
+//!         div(class: "row") {
+//!             for item in row {
+//!                 span { (item) }
+//!             }
+//!         }
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<div class=\"row\"><span>1</span><span>2</span></div>\
+//!     <div class=\"row\"><span>3</span><span>4</span></div>\
+//!     <div class=\"row\"><span>5</span></div>"
+//! );
+//! ```
+//!
+//! For a fixed number of columns, [`Columns`] splits its children and [`ColumnsFrom`] splits the items of an iterator
+//! into balanced `<div class="column">` wrappers. Earlier columns get one extra item when the items don't divide
+//! evenly:
+//!
+//! ```
+//! use plait::{html, ToHtml, layout::{Columns, ColumnsFrom}};
+//!
+//! let frag = html! {
+//!     @Columns(count: 2) {
+//!         p { "a" }
+//!         p { "b" }
+//!         p { "c" }
+//!     }
+//!     @ColumnsFrom(count: 3, items: ["x", "y"]) {}
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<div class=\"columns\"><div class=\"column\"><p>a</p><p>b</p></div><div class=\"column\"><p>c</p></div></div>\
+//!     <div class=\"columns\"><div class=\"column\">x</div><div class=\"column\">y</div>\
+//!     <div class=\"column\"></div></div>"
+//! );
+//! ```
+use std::ops::Range;
+
+use crate::{Child, RenderEscaped, component, each_child};
+
+/// Returns the ranges of `len` items split into `count` balanced columns (at least one).
+fn column_ranges(len: usize, count: usize) -> impl Iterator<Item = Range<usize>> {
+    let count = count.max(1);
+    let (size, extra) = (len / count, len % count);
+    let mut start = 0;
+
+    (0..count).map(move |index| {
+        let end = start + size + usize::from(index < extra);
+        let range = start..end;
+        start = end;
+        range
+    })
+}
+
+component! {
+    /// Splits its children into `count` balanced columns.
+    ///
+    /// Each child becomes one item, with the same children as `for child in #children` (see
+    /// [`EachChild`](crate::EachChild)): every node, and every iteration of a loop. Columns are rendered as
+    /// `<div class="column">` inside a `<div class="columns">`, which receives any extra attributes.
+    pub fn Columns(count: usize) {
+        let items: Vec<Child> = each_child(&children, "default").collect();
+
+        div(class: "columns", #attrs) {
+            for range in column_ranges(items.len(), *count) {
+                div(class: "column") {
+                    for item in &items[range] {
+                        (item)
+                    }
+                }
+            }
+        }
+    }
+}
+
+component! {
+    /// Splits the items of `items` into `count` balanced columns.
+    ///
+    /// Items are rendered escaped (use [`html!`](crate::html) fragments for markup). Columns are rendered as
+    /// `<div class="column">` inside a `<div class="columns">`, which receives any extra attributes.
+    pub fn ColumnsFrom<I>(count: usize, items: I) where I: IntoIterator + Clone, I::Item: RenderEscaped {
+        let items: Vec<I::Item> = items.clone().into_iter().collect();
+
+        div(class: "columns", #attrs) {
+            for range in column_ranges(items.len(), *count) {
+                div(class: "column") {
+                    for item in &items[range] {
+                        (item)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -41,6 +41,10 @@
 //! };
 //! ```
 //!
+//! Void elements render without a trailing slash (`<br>`). Run the output through a
+//! [`RenderPipeline`](pipeline::RenderPipeline) with [`VoidElementSlash`](pipeline::VoidElementSlash) to render them as
+//! `<br />` for downstream tools that expect it; the rest of the output is unchanged.
+//!
 //! Snake-case identifiers are automatically converted to kebab-case:
 //!
 //! ```
@@ -50,6 +54,51 @@
 //! assert_eq!(frag.to_html(), "<my-element>content</my-element>");
 //! ```
 //!
+//! ## Nesting rules
+//!
+//! Elements that browsers would move or close while parsing are rejected at compile time, with an error pointing at
+//! the offending element: a `div` inside a `p`, an `li` outside `ul`, `ol` and `menu`, a `td` outside `tr`, an `a`
+//! inside another `a`, and so on. Only parents known inside the template are checked, so an element at the top level
+//! of a template or passed as children to a component is always accepted, and `svg`, `math` and `template` contents
+//! are not checked.
+//!
+//! Precede a node with `#[allow_invalid_nesting]` to skip the checks for it and its contents:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! {
+//!     p { #[allow_invalid_nesting] div { "block" } }
+//! };
+//! assert_eq!(frag.to_html(), "<p><div>block</div></p>");
+//! ```
+//!
+//! ## Attribute checks
+//!
+//! Precede a node with `#[deny(unknown_attributes)]` to reject attributes that aren't valid for the HTML element they
+//! are written on, such as `href` on a `div`, and use `#[allow(unknown_attributes)]` to skip the checks again for a node
+//! and its contents. The checks only apply where a template asks for them, so they never affect the templates of other
+//! crates. Global attributes, event handlers (`on*`), `data-*` and `aria-*` attributes and hyperscript's `_` are
+//! accepted on every element, and custom elements, unknown elements and `svg` and `math` contents are not checked.
+//! Allow attributes from other libraries by listing their prefixes in the `PLAIT_ATTRIBUTE_PREFIXES` environment
+//! variable when building, e.g. in `.cargo/config.toml`:
+//!
+//! ```toml
+//! [env]
+//! PLAIT_ATTRIBUTE_PREFIXES = "hx-,x-"
+//! ```
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! {
+//!     #[deny(unknown_attributes)]
+//!     nav {
+//!         a(href: "/", aria_current: "page") { "Home" }
+//!         #[allow(unknown_attributes)] span(href: "/about") { "About" }
+//!     }
+//! };
+//! assert_eq!(frag.to_html(), r#"<nav><a href="/" aria-current="page">Home</a><span href="/about">About</span></nav>"#);
+//! ```
+//!
 //! ## DOCTYPE
 //!
 //! Use `#doctype` to emit `<!DOCTYPE html>`:
@@ -83,7 +132,50 @@
 //! # assert_eq!(frag.to_html(), "Static text &lt;script&gt;alert(&#39;xss&#39;)&lt;/script&gt;<b>bold</b>");
 //! ```
 //!
-//! Expressions in `()` must implement [`RenderEscaped`]. Expressions in `#()` must implement [`RenderRaw`].
+//! Expressions in `()` must implement [`RenderEscaped`]. Expressions in `#()` must implement [`RenderRaw`]. Any
+//! [`Display`](std::fmt::Display) value can be embedded by wrapping it in [`Escaped`] (or [`Unescaped`] for trusted
+//! markup).
+//!
+//! Text literals are rendered as they are, braces included. For text with many values, `f!("...")` fills in
+//! `{placeholder}`s like `format!`: each `{name}`, `{user.name}` or `{count + 1}` is replaced with the escaped value of
+//! the expression, which must implement [`RenderEscaped`]. `{{` and `}}` are literal braces:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! struct User { name: &'static str }
+//! let user = User { name: "<Ada>" };
+//! let count = 3;
+//! let frag = html! {
+//!     p { f!("Hello, {user.name}! You have {count} items {{in}} your cart") }
+//!     p { "{count} is kept as it is" }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<p>Hello, &lt;Ada&gt;! You have 3 items {in} your cart</p><p>{count} is kept as it is</p>"
+//! );
+//! ```
+//!
+//! The text of `script` and `style` elements isn't HTML: browsers don't decode character references in it, so literals
+//! and `(expr)` values are written there as they are, without HTML escaping. Only the sequences that would end the
+//! element early are escaped: the `<` of `</script` and `<!--` is written as `\u003c` in scripts, which JavaScript
+//! strings and JSON read as `<`, and `</style` as `<\/style` in style sheets, which CSS reads the same way. This keeps
+//! the element intact but doesn't make a value safe to run, so serialize data for scripts with [`Json`] instead of
+//! placing it in a script as it is (see [Dynamic data](#dynamic-data)):
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let config = r#"{"user": "O'Brien", "note": "</script>"}"#;
+//! let frag = html! {
+//!     script(type: "application/json") { (config) }
+//!     script { "if (a < b) { init(); }" }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     r#"<script type="application/json">{"user": "O'Brien", "note": "\u003c/script>"}</script><script>if (a < b) { init(); }</script>"#
+//! );
+//! ```
 //!
 //! ## Attributes
 //!
@@ -107,6 +199,24 @@
 //! # assert_eq!(frag.to_html(), "<div class=\"container\" id=\"main\">content</div><button disabled>Can&#39;t click</button><input type=\"text\" value=\"hello\"><div class=\"raw-class\"></div>");
 //! ```
 //!
+//! Any Rust expression can be used as a value without parentheses, including `if`, `match` and block expressions, so
+//! the value can be picked inline instead of in a helper function or a `let`:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! # enum Size { Sm, Lg }
+//! let size = Size::Lg;
+//! let active = true;
+//!
+//! let frag = html! {
+//!     button(
+//!         class: { match size { Size::Sm => "p-1", Size::Lg => "p-4" } },
+//!         aria_pressed: if active { "true" } else { "false" },
+//!     ) { "Toggle" }
+//! };
+//! assert_eq!(frag.to_html(), r#"<button class="p-4" aria-pressed="true">Toggle</button>"#);
+//! ```
+//!
 //! Underscore-to-hyphen conversion applies to attribute names too:
 //!
 //! ```
@@ -117,6 +227,25 @@
 //! assert_eq!(frag.to_html(), "<div hx-target=\"body\"></div>");
 //! ```
 //!
+//! Prefix the name with `r#` to keep its underscores:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! { input(r#ng_model: "user.name", r#type: "text"); };
+//!
+//! assert_eq!(frag.to_html(), "<input ng_model=\"user.name\" type=\"text\">");
+//! ```
+//!
+//! A name made only of underscores is kept as it is, for the `_` attribute of
+//! [hyperscript](https://hyperscript.org):
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! { button(_: "on click toggle .open on #menu") { "Menu" } };
+//!
+//! assert_eq!(frag.to_html(), "<button _=\"on click toggle .open on #menu\">Menu</button>");
+//! ```
+//!
 //! Use string literals for attribute names that need special characters:
 //!
 //! ```
@@ -126,6 +255,38 @@
 //! assert_eq!(frag.to_html(), r#"<div @click="handler()"></div>"#);
 //! ```
 //!
+//! Newlines and tabs in the escaped values of [whitespace sensitive attributes](spec::WHITESPACE_SENSITIVE_ATTRIBUTES)
+//! such as `srcset` and `content` are written as character references, which some proxies and minifiers would
+//! otherwise mangle. Enable the `encode-attribute-whitespace` feature to encode them in every attribute value:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! { img(srcset: "/a.png 1x,\n/b.png 2x"); };
+//!
+//! assert_eq!(frag.to_html(), "<img srcset=\"/a.png 1x,&#10;/b.png 2x\">");
+//! ```
+//!
+//! ## CSS variables
+//!
+//! `css_vars: {name: value, ...}` renders a `style` attribute setting the CSS custom property `--name` to each value,
+//! with the same name conversion as attributes. Values are escaped like the values of a [`Style`](style::Style), and
+//! `None` values are left out. Use it instead of a separate `style` attribute on the same element:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let primary = "#3366ff";
+//! let radius: Option<&str> = None;
+//!
+//! let frag = html! {
+//!     div(class: "theme", css_vars: {primary: primary, card_spacing: "4px", radius: radius}) {}
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     r#"<div class="theme" style="--primary: #3366ff; --card-spacing: 4px"></div>"#
+//! );
+//! ```
+//!
 //! ## Optional attributes
 //!
 //! Append `?` to the attribute name (before the `:`) to make it conditional. The attribute is only rendered when the
@@ -145,9 +306,137 @@
 //! Values for `?` attributes must implement [`RenderMaybeAttributeEscaped`] (or [`RenderMaybeAttributeRaw`] when used
 //! with `#()`).
 //!
+//! ## Spreading attributes
+//!
+//! `#attrs(expr)` renders an attribute for each `(name, value)` pair of an [`IntoIterator`], such as a `HashMap`
+//! loaded from configuration or a CMS. Names are any [`AsRef<str>`] and values any [`RenderEscaped`]. Since the names
+//! are only known at runtime, pairs whose name isn't a [valid attribute name](spec::is_valid_attribute_name) are
+//! skipped instead of ending the tag early, and the values are escaped like `attr: (expr)` values:
+//!
+//! ```
+//! # use plait::{html, Sorted, ToHtml};
+//! use std::collections::HashMap;
+//!
+//! let config = HashMap::from([
+//!     ("data-theme", "dark"),
+//!     ("title", "\"Quoted\""),
+//!     ("onload=\"alert(1)\"", "x"),
+//! ]);
+//!
+//! let frag = html! {
+//!     div(class: "widget", #attrs(Sorted(&config))) {}
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     r#"<div class="widget" data-theme="dark" title="&quot;Quoted&quot;"></div>"#
+//! );
+//! ```
+//!
+//! The pairs are written in the order of the source, and only the first value of a repeated name is kept. A `HashMap`
+//! iterates in a different order on every run, so wrap unordered sources in [`Sorted`] to write their pairs sorted by
+//! name, as above. Validating a name doesn't make it safe: spread event handler attributes (`onclick`, ...) still run their value as a script, so filter the
+//! names of untrusted pairs first.
+//!
+//! ## Data attributes
+//!
+//! `data: data!(name: value, ...)` renders a `data-name` attribute for each pair, with the same name conversion as
+//! attributes. The [`data!`] macro builds a [`DataAttrs`] group, which can also be stored and spread with
+//! `#attrs(expr)`, or passed as an attribute of a component call to be forwarded through `#attrs`:
+//!
+//! ```
+//! # use plait::{data, html, ToHtml};
+//! let frag = html! {
+//!     button(data: data!(action: "click->menu#toggle", menu_id: 4)) { "Menu" }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     r#"<button data-action="click-&gt;menu#toggle" data-menu-id="4">Menu</button>"#
+//! );
+//! ```
+//!
+//! ## Attribute groups
+//!
+//! Attributes shared by several elements can be bound to a name with the [`attrs!`] macro, which builds an [`Attrs`]
+//! group, and spread onto each element with `#attrs(&name)`:
+//!
+//! ```
+//! # use plait::{attrs, html, ToHtml};
+//! let frag = html! {
+//!     let field = attrs!(class: "input", autocomplete: "off");
+//!     input(name: "first", #attrs(&field));
+//!     input(name: "last", #attrs(&field));
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     r#"<input name="first" class="input" autocomplete="off"><input name="last" class="input" autocomplete="off">"#
+//! );
+//! ```
+//!
+//! ## SVG and MathML
+//!
+//! SVG and MathML follow XML rules rather than HTML ones, so elements inside `svg` or `math` (and SVG or MathML
+//! elements such as `path` or `mfrac` anywhere, e.g. at the root of an icon component) are rendered differently:
+//!
+//! - Elements without children are self-closing, and SVG and MathML elements can be written `tag;` like void
+//!   elements.
+//! - Element and attribute names get their SVG or MathML case back (`linearGradient`, `viewBox`, `definitionURL`),
+//!   since the name conversion lowercases them.
+//! - A root `svg` or `math` element declares its namespace (`xmlns="http://www.w3.org/2000/svg"`), unless it sets
+//!   `xmlns` itself or has the `no_xmlns` marker.
+//!
+//! The content of `foreignObject` is HTML again, and so are elements that aren't MathML inside `math` (e.g. a `span`
+//! in `mtext`).
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! {
+//!     svg(viewBox: "0 0 24 24", stroke_width: 2) {
+//!         linearGradient(id: "fade") { stop(offset: "0"); }
+//!         path(d: "M4 12h16", stroke: "url(#fade)");
+//!     }
+//!     svg(no_xmlns) { circle(r: 4) {} }
+//!     math { msup { mi { "x" } mn { "2" } } mspace(width: "1em"); }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\" stroke-width=\"2\">\
+//!     <linearGradient id=\"fade\"><stop offset=\"0\"/></linearGradient><path d=\"M4 12h16\" stroke=\"url(#fade)\"/>\
+//!     </svg><svg><circle r=\"4\"/></svg><math xmlns=\"http://www.w3.org/1998/Math/MathML\">\
+//!     <msup><mi>x</mi><mn>2</mn></msup><mspace width=\"1em\"/></math>"
+//! );
+//! ```
+//!
+//! ## Automatic ids
+//!
+//! The `auto_id` marker gives an element an `id` derived from its text content with [`slugify()`], which is handy for
+//! linkable headings. Ids generated during the same [`to_html()`](ToHtml::to_html) call are kept unique by appending
+//! `-1`, `-2`, ...:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! {
+//!     h2(auto_id) { "Getting started" }
+//!     h2(auto_id, class: "again") { "Getting " em { "started" } }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<h2 id=\"getting-started\">Getting started</h2>\
+//!     <h2 id=\"getting-started-1\" class=\"again\">Getting <em>started</em></h2>"
+//! );
+//! ```
+//!
+//! For ids that aren't derived from content, [`ids::next`] hands out ids from the same namespace, and
+//! [`form::Labeled`] uses it to wire a `label` to its input. Ids that must match between a full page and a partial
+//! render of one of its regions come from [`ids::stable`] instead, which hashes a key into the same id in every render.
+//!
 //! ## Control flow
 //!
-//! Standard Rust `if`/`else`, `if let`, `for`, and `match` work inside templates:
+//! Standard Rust `if`/`else`, `if let`, `for`, `while`, `while let` and `match` work inside templates:
 //!
 //! ```
 //! # use plait::{html, ToHtml};
@@ -184,6 +473,25 @@
 //! # assert_eq!(frag.to_html(), r#"<span>hello</span>"#);
 //! ```
 //!
+//! Conditions can be let chains, as in Rust 2024 code:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! struct User { name: &'static str, active: bool }
+//!
+//! let user = Some(User { name: "Ada", active: true });
+//!
+//! let frag = html! {
+//!     if let Some(user) = &user && user.active {
+//!         span { (user.name) }
+//!     } else if let Some(user) = &user && let Some(initial) = user.name.get(..1) {
+//!         span(class: "inactive") { (initial) }
+//!     }
+//! };
+//!
+//! # assert_eq!(frag.to_html(), r#"<span>Ada</span>"#);
+//! ```
+//!
 //! ```
 //! # use plait::{html, ToHtml};
 //! let tag = "div";
@@ -199,6 +507,62 @@
 //! # assert_eq!(frag.to_html(), r#"<div>a div</div>"#);
 //! ```
 //!
+//! Add `, sep { ... }` after the iterator of a `for` loop to render a separator between items. The iterator is
+//! consumed lazily, so its length doesn't need to be known up front:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let tags = ["rust", "html", "templates"];
+//!
+//! let frag = html! {
+//!     p {
+//!         for tag in tags.iter().filter(|tag| **tag != "html"), sep { ", " } {
+//!             em { (tag) }
+//!         }
+//!     }
+//! };
+//!
+//! assert_eq!(frag.to_html(), "<p><em>rust</em>, <em>templates</em></p>");
+//! ```
+//!
+//! Add an `else` block after the body of a `for` loop to render it when the iterator yields nothing, without checking
+//! `is_empty()` first (which one-shot iterators can't do):
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let results: Vec<&str> = Vec::new();
+//!
+//! let frag = html! {
+//!     ul {
+//!         for result in results.iter() {
+//!             li { (result) }
+//!         } else {
+//!             li { "No results" }
+//!         }
+//!     }
+//! };
+//!
+//! assert_eq!(frag.to_html(), "<ul><li>No results</li></ul>");
+//! ```
+//!
+//! `while` and `while let` render their body for as long as the condition holds, e.g. to walk a cursor or token stream
+//! that isn't an [`Iterator`] without collecting it first. State the loop advances has to be created inside the
+//! template with a `let mut` binding, since every render starts over:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! # struct Cursor(u32);
+//! # impl Cursor { fn next_page(&mut self) -> Option<u32> { self.0 += 1; (self.0 <= 2).then_some(self.0) } }
+//! let frag = html! {
+//!     let mut cursor = Cursor(0);
+//!     while let Some(page) = cursor.next_page() {
+//!         a(href: #(format!("?page={page}"))) { (page) }
+//!     }
+//! };
+//!
+//! assert_eq!(frag.to_html(), r#"<a href="?page=1">1</a><a href="?page=2">2</a>"#);
+//! ```
+//!
 //! ## Let bindings
 //!
 //! Compute intermediate values within templates:
@@ -214,6 +578,25 @@
 //! assert_eq!(frag.to_html(), "Length: 5");
 //! ```
 //!
+//! A binding can shadow an earlier one, and `let ... else` renders its else block instead of the rest of the enclosing
+//! block when the pattern doesn't match:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let user: Option<&str> = None;
+//!
+//! let frag = html! {
+//!     div {
+//!         let Some(name) = user else {
+//!             a(href: "/login") { "Sign in" }
+//!         };
+//!         let name = name.to_uppercase();
+//!         "Hello, " (name)
+//!     }
+//! };
+//! assert_eq!(frag.to_html(), r#"<div><a href="/login">Sign in</a></div>"#);
+//! ```
+//!
 //! ## Nesting fragments
 //!
 //! [`HtmlFragment`] implements [`RenderEscaped`], so fragments can be embedded in other fragments:
@@ -225,6 +608,20 @@
 //! assert_eq!(outer.to_html(), "<div><p>inner content</p></div>");
 //! ```
 //!
+//! A nested `html! { ... }` (or its shorthand `@{ ... }`) in node position is not a separate fragment: its template is
+//! inlined into the enclosing one, so it shares its writer and its `let` bindings:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! let frag = html! {
+//!     div {
+//!         html! { p { "nested" } }
+//!         @{ p { "inline" } }
+//!     }
+//! };
+//! assert_eq!(frag.to_html(), "<div><p>nested</p><p>inline</p></div>");
+//! ```
+//!
 //! # Components
 //!
 //! Components are reusable template functions defined with the [`component!`] macro:
@@ -328,6 +725,169 @@
 //! };
 //! ```
 //!
+//! ## Named slots
+//!
+//! Layouts with several insertion points declare them with `#slot(name)` in the component body. At the call site,
+//! `slot(name) { ... }` blocks fill the named slots, and the remaining children fill `#children` (which can also be
+//! written `#slot(default)`). Slots that aren't filled render nothing:
+//!
+//! ```
+//! # use plait::{component, html, ToHtml};
+//! component! {
+//!     pub fn Layout() {
+//!         header { #slot(header) }
+//!         main { #children }
+//!         aside { #slot(sidebar) }
+//!     }
+//! }
+//!
+//! let page = html! {
+//!     @Layout {
+//!         slot(header) { h1 { "Dashboard" } }
+//!         p { "Welcome back!" }
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     page.to_html(),
+//!     "<header><h1>Dashboard</h1></header><main><p>Welcome back!</p></main><aside></aside>"
+//! );
+//! ```
+//!
+//! ## Wrapping each child
+//!
+//! `for child in #children` (or `#slot(name)`) iterates over the children of a component one at a time, so that the
+//! component can wrap each of them, e.g. in an `li`. Every node at the call site is a separate child, including every
+//! iteration of a loop and every node of the taken `if` or `match` branch (see [`EachChild`]):
+//!
+//! ```
+//! # use plait::{component, html, ToHtml};
+//! component! {
+//!     pub fn Breadcrumbs() {
+//!         nav(aria_label: "Breadcrumb") {
+//!             ol {
+//!                 for child in #children {
+//!                     li { (child) }
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let page = html! {
+//!     @Breadcrumbs {
+//!         a(href: "/") { "Home" }
+//!         for (href, label) in [("/docs", "Docs")] {
+//!             a(href: href) { (label) }
+//!         }
+//!         span { "Components" }
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     page.to_html(),
+//!     "<nav aria-label=\"Breadcrumb\"><ol><li><a href=\"/\">Home</a></li><li><a href=\"/docs\">Docs</a></li>\
+//!     <li><span>Components</span></li></ol></nav>"
+//! );
+//! ```
+//!
+//! ## Lazy props
+//!
+//! Props that are expensive to compute and not always rendered can be typed as `impl Fn() -> T` and called in the
+//! component body. At the call site, `@lazy(expr)` passes `expr` as the closure `|| expr`, so it is only evaluated if
+//! the component calls it:
+//!
+//! ```
+//! # use plait::{component, html, Html, ToHtml};
+//! component! {
+//!     pub fn Page(show_sidebar: bool, sidebar: impl Fn() -> Html) {
+//!         main { #children }
+//!         if *show_sidebar {
+//!             aside { (sidebar()) }
+//!         }
+//!     }
+//! }
+//!
+//! fn expensive_sidebar() -> Html {
+//!     unreachable!("the sidebar is hidden")
+//! }
+//!
+//! let page = html! {
+//!     @Page(show_sidebar: false, sidebar: @lazy(expensive_sidebar())) { "Content" }
+//! };
+//!
+//! assert_eq!(page.to_html(), "<main>Content</main>");
+//! ```
+//!
+//! ## Closure props
+//!
+//! List and table components can delegate rendering to their caller with a closure prop, such as
+//! `render_row: impl Fn(&'a Row) -> impl RenderEscaped`. Each `impl Trait`, including one in the return type of a
+//! closure, becomes a type parameter of the component. Name the lifetime of the argument after the items the
+//! component holds, so that the returned fragment can borrow from them; elided lifetimes (`impl Fn(&Row) -> String`)
+//! are higher-ranked, like in a function signature, and the result can't borrow from the argument:
+//!
+//! ```
+//! # use plait::{component, html, RenderEscaped, ToHtml};
+//! pub struct Row {
+//!     name: String,
+//! }
+//!
+//! component! {
+//!     pub fn List<'a>(rows: &'a [Row], render_row: impl Fn(&'a Row) -> impl RenderEscaped) {
+//!         ul {
+//!             for row in *rows {
+//!                 li { (render_row(row)) }
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let rows = [Row { name: "apples".to_owned() }, Row { name: "pears".to_owned() }];
+//! let list = html! {
+//!     @List(rows: &rows, render_row: |row| html! { b { (row.name) } }) {}
+//! };
+//!
+//! assert_eq!(list.to_html(), "<ul><li><b>apples</b></li><li><b>pears</b></li></ul>");
+//! ```
+//!
+//! ## Render props
+//!
+//! For the common case of a single closure prop that renders markup, name it `children` and type it
+//! [`impl RenderProp<T>`](RenderProp). The component renders it with `#children(value)`, and the call site writes its
+//! children as a closure, `{ |item| ... }`, whose body is a template:
+//!
+//! ```
+//! # use plait::{component, html, RenderProp, ToHtml};
+//! pub struct Row {
+//!     name: &'static str,
+//! }
+//!
+//! component! {
+//!     pub fn List<'a>(items: &'a [Row], children: impl RenderProp<&'a Row>) {
+//!         table {
+//!             for item in *items {
+//!                 #children(item)
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let rows = [Row { name: "apples" }, Row { name: "pears" }];
+//! let list = html! {
+//!     @List(items: &rows) { |item| tr { td { (item.name) } } }
+//! };
+//!
+//! assert_eq!(list.to_html(), "<table><tr><td>apples</td></tr><tr><td>pears</td></tr></table>");
+//! ```
+//!
+//! ## Iterator props
+//!
+//! A component can render its props more than once, so it can't consume an iterator. Type collection props as
+//! `impl RenderIter<Item = impl RenderEscaped>` to accept `&vec`, ranges or lazy adapters like `vec.iter().map(..)`
+//! without collecting them into a `Vec`; `for item in items` in the body iterates a fresh copy on each render. See
+//! [`RenderIter`] for an example.
+//!
 //! ## Primitive props
 //!
 //! Component props are received as references. For primitive types like `bool` or `u32`, dereference with `*` in the
@@ -361,6 +921,117 @@
 //! Values passed to [`classes!`] must implement the [`Class`] trait. This is implemented for `&str`, `Option<T>` where
 //! `T: Class`, and [`Classes<T>`](Classes).
 //!
+//! # Inline styles
+//!
+//! The [`style!`] macro works the same way for `style` attributes. It takes CSS property/value pairs, skips `None`
+//! values and escapes the others:
+//!
+//! ```
+//! # use plait::{html, ToHtml, style};
+//! let color = "teal";
+//! let width: Option<&str> = None;
+//!
+//! let frag = html! {
+//!     div(style: style!("color": color, "width": width)) {}
+//! };
+//! assert_eq!(frag.to_html(), r#"<div style="color: teal"></div>"#);
+//! ```
+//!
+//! Values must implement [`CssValue`](style::CssValue), which is implemented for strings, numbers, the length units in
+//! [`style`](mod@style) and `Option`s of these.
+//!
+//! # Dynamic data
+//!
+//! With the `serde_json` feature enabled, [`serde_json::Value`](https://docs.rs/serde_json/latest/serde_json/enum.Value.html)
+//! implements [`RenderEscaped`], [`RenderRaw`], the optional attribute traits and [`Class`]. This lets templates pull
+//! loosely-typed values out of a JSON context (e.g. CMS or theme data) with normal indexing. Strings are escaped and
+//! rendered without quotes, and missing keys index to `null`, which renders nothing (and omits `?` attributes):
+//!
+//! ```ignore
+//! use plait::{html, ToHtml};
+//! use serde_json::json;
+//!
+//! let ctx = json!({ "user": { "name": "<Alice>", "admin": true } });
+//!
+//! let frag = html! {
+//!     p(class?: ctx["user"]["class"], data_admin?: ctx["user"]["admin"]) {
+//!         (ctx["user"]["name"]) " " (ctx["user"]["missing"])
+//!     }
+//!     p { (ctx["title"].as_str().unwrap_or("Untitled")) }
+//! };
+//!
+//! assert_eq!(frag.to_html(), "<p data-admin>&lt;Alice&gt; </p><p>Untitled</p>");
+//! ```
+//!
+//! To embed a whole value for scripts to read, enable the `serde` feature and wrap anything that implements
+//! `serde::Serialize` in [`Json`]. It is serialized as JSON with `<`, `>` and `&` written as `\u` escapes, so it can be
+//! placed in a `<script type="application/json">` block or an attribute such as `data-*` or `hx-vals`:
+//!
+//! ```ignore
+//! # use plait::{html, Json, ToHtml};
+//! # use serde_json::json;
+//! let config = json!({ "next": "</script>" });
+//!
+//! let frag = html! {
+//!     script(type: "application/json", id: "config") { (Json(&config)) }
+//!     div(data_config: (Json(&config))) {}
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     r#"<script type="application/json" id="config">{"next":"\u003c/script\u003e"}</script><div data-config="{&quot;next&quot;:&quot;\u003c/script\u003e&quot;}"></div>"#
+//! );
+//! ```
+//!
+//! # Translations
+//!
+//! With the `i18n` feature enabled, `t!("key", name = value)` renders the message for `key` from a translation
+//! catalog provided in the render context at runtime, with each `{name}` placeholder replaced by the escaped value.
+//! The message is escaped like any other text. See the `i18n` module for details.
+//!
+//! ```ignore
+//! # use plait::{html, ToHtml};
+//! let name = "Ada";
+//! let frag = html! { p { t!("Hello, {name}!", name = name) } };
+//!
+//! // Without a catalog in the context, the key is the message
+//! assert_eq!(frag.to_html(), "<p>Hello, Ada!</p>");
+//! ```
+//!
+//! # Deterministic output
+//!
+//! Rendering is deterministic: the same template with the same values produces byte-identical output on every run,
+//! thread and platform, so rendered HTML can be hashed for cache keys and `ETag`s or checked into reproducible builds.
+//! Attributes are written in the order they appear in the template, `#attrs` spreads the attributes of a component call
+//! in the order they appear in the call, and `#attrs(expr)` writes its pairs in the order of its source. Ids generated
+//! with `auto_id` and [`ids::next`] are numbered from the start of each [`to_html()`](ToHtml::to_html) call rather than
+//! from a global counter. JSON objects are rendered in the order of their
+//! [`serde_json::Map`](https://docs.rs/serde_json/latest/serde_json/struct.Map.html).
+//!
+//! What plait can't order for you is an unordered collection such as a `HashMap` or `HashSet`, whose iteration order
+//! changes between runs. Spread one with `#attrs(Sorted(expr))`, and for a `for` loop iterate a `BTreeMap` instead, or
+//! sort the items first:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! use std::collections::HashMap;
+//!
+//! let stock = HashMap::from([("pears", 3), ("apples", 5), ("plums", 0)]);
+//!
+//! let mut items: Vec<_> = stock.iter().collect();
+//! items.sort();
+//!
+//! let frag = html! {
+//!     ul {
+//!         for (name, count) in &items {
+//!             li { (name) ": " (count) }
+//!         }
+//!     }
+//! };
+//!
+//! assert_eq!(frag.to_html(), "<ul><li>apples: 5</li><li>pears: 3</li><li>plums: 0</li></ul>");
+//! ```
+//!
 //! # Web framework integrations
 //!
 //! Plait provides optional integrations with popular Rust web frameworks. Both [`Html`] and [`HtmlFragment`] can be
@@ -436,12 +1107,111 @@
 //!     }.to_html()
 //! }
 //! ```
+//!
+//! # Performance
+//!
+//! [`html!`] does as much work as possible at compile time. Static text and attributes are escaped while the macro
+//! expands, and every run of static markup between two dynamic values — tags, attribute names and values, closing
+//! tags — becomes a single `&'static str` written with one `write_str` call. Identical literals are merged by the
+//! compiler, so a long class string repeated on every row of a table exists once in the binary and costs one `memcpy`
+//! per row, together with the surrounding tags:
+//!
+//! ```
+//! # use plait::{html, ToHtml};
+//! # let rows = [(1, "one")];
+//! let table = html! {
+//!     tbody {
+//!         for (id, name) in rows.iter() {
+//!             // Five writes per row: `<tr class="row"><td class="num">`, `id`,
+//!             // `</td><td class="name">`, `name` and `</td></tr>`.
+//!             tr(class: "row") {
+//!                 td(class: "num") { (id) }
+//!                 td(class: "name") { (name) }
+//!             }
+//!         }
+//!     }
+//! };
+//! # assert_eq!(table.to_html(), "<tbody><tr class=\"row\"><td class=\"num\">1</td><td class=\"name\">one</td></tr></tbody>");
+//! ```
+//!
+//! The `table` benchmark (`cargo bench --bench table`) renders a 10,000-row table like this with long Bootstrap-style
+//! class strings (3 MB of output) with [`html!`], which performs 5 writes per row, and with every tag and attribute
+//! part written separately (35 writes per row). The first takes about two thirds of the time of the second, e.g.
+//! 0.7 ms compared to 1.05 ms on one machine. [`ToHtml::to_html()`] also pre-allocates the output using the size hint
+//! computed by the macro, so the buffer rarely needs to grow.
+//!
+//! # Profiling
+//!
+//! The `profile` feature times every component call and records its output size. Place
+//! [`profile::overlay()`](profile::overlay) at the end of a page to render a collapsible overlay listing the hot
+//! components directly in the browser during development. See the [`profile`] module for details.
+//!
+//! The `tracing` feature enters a `TRACE` level [`tracing`](https://docs.rs/tracing) span named `component` around
+//! every component call, with the name of the component in its `name` field, so profilers built on `tracing` (such
+//! as flamegraph layers) show which components dominate render time. Without the feature, component calls aren't
+//! instrumented at all.
+extern crate self as plait;
+
+#[cfg(feature = "aria-patterns")]
+#[cfg_attr(docsrs, doc(cfg(feature = "aria-patterns")))]
+pub mod aria;
+mod asset;
+#[doc(hidden)]
+pub mod attribute_names;
+mod attrs;
+pub mod cache;
+pub mod charset;
 mod classes;
+pub mod comment;
 mod component;
+pub mod context;
+mod data_attrs;
+pub mod debug;
+pub mod flash;
+pub mod form;
 mod fragment;
+#[cfg(feature = "gallery")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gallery")))]
+pub mod gallery;
+#[cfg(feature = "syntect")]
+#[cfg_attr(docsrs, doc(cfg(feature = "syntect")))]
+pub mod highlight;
 mod html;
+#[cfg(feature = "i18n")]
+#[cfg_attr(docsrs, doc(cfg(feature = "i18n")))]
+pub mod i18n;
+pub mod ids;
+#[cfg(feature = "serde_json")]
+mod json;
+pub mod layout;
+pub mod links;
+pub mod locale;
+#[cfg(feature = "maud")]
+#[cfg_attr(docsrs, doc(cfg(feature = "maud")))]
+pub mod maud;
 mod maybe_attr;
+pub mod media;
+pub mod number;
+pub mod permission;
+pub mod pipeline;
+pub mod pool;
+#[cfg(feature = "profile")]
+#[cfg_attr(docsrs, doc(cfg(feature = "profile")))]
+pub mod profile;
 mod render;
+mod route;
+mod scope;
+pub mod site;
+mod slug;
+pub mod spec;
+pub mod stream;
+pub mod style;
+pub mod testing;
+mod text;
+pub mod toc;
+mod tokenizer;
+mod url;
+pub mod url_policy;
 mod utils;
 
 /// Generates an [`HtmlFragment`] from a template DSL.
@@ -474,22 +1244,87 @@ mod utils;
 /// | `tag(attrs) { ... }`                    | Element with attributes and children                    |
 /// | `tag;`                                  | Void element (e.g. `br;`, `img(src: "...");`)           |
 /// | `"text"`                                | Static text (HTML-escaped)                              |
+/// | `f!("Hello, {name}!")`                  | Text with escaped placeholders, like `format!`          |
 /// | `(expr)`                                | Escaped expression ([`RenderEscaped`])                  |
 /// | `#(expr)`                               | Raw expression ([`RenderRaw`])                          |
+/// | `t!("key", name = value)`               | Translated text (`i18n` feature)                        |
 /// | `#doctype`                              | `<!DOCTYPE html>`                                       |
+/// | `#[allow_invalid_nesting] node`         | Node skipping the compile-time nesting checks           |
+/// | `#[deny(unknown_attributes)] node`      | Node with compile-time attribute checks                 |
+/// | `#[allow(unknown_attributes)] node`     | Node skipping the compile-time attribute checks         |
 /// | `attr: "value"`                         | Static string attribute                                 |
 /// | `attr: (expr)`                          | Escaped expression attribute                            |
 /// | `attr: #(expr)`                         | Raw expression attribute                                |
 /// | `attr`                                  | Boolean attribute (always present)                      |
 /// | `attr?: expr`                           | Conditional attribute ([`RenderMaybeAttributeEscaped`]) |
 /// | `attr?: #(expr)`                        | Conditional raw attribute ([`RenderMaybeAttributeRaw`]) |
+/// | `#attrs(expr)`                          | Attributes from `(name, value)` pairs                   |
+/// | `data: data!(name: value, ...)`         | `data-*` attributes                                     |
+/// | `let x = attrs!(name: value, ...);`     | Attribute group, spread with `#attrs(&x)`               |
+/// | `css_vars: {name: expr, ...}`           | `style` with CSS custom properties (`--name`)           |
+/// | `auto_id`                               | `id` derived from the element's text ([`slugify()`])    |
+/// | `svg(no_xmlns) { ... }`                 | Root `svg` or `math` without the automatic `xmlns`      |
 /// | `if` / `else` / `if let`                | Conditional rendering                                   |
+/// | `if let pat = expr && cond { ... }`     | Conditional rendering with a let chain                  |
 /// | `for pat in iter { ... }`               | Loop                                                    |
+/// | `for pat in iter, sep { ... } { ... }`  | Loop with a separator between items                     |
+/// | `for pat in iter { ... } else { ... }`  | Loop with a block for an empty iterator                 |
+/// | `while cond { ... }` / `while let`      | Loop while a condition holds                            |
 /// | `match expr { ... }`                    | Pattern matching                                        |
 /// | `let x = expr;`                         | Let binding                                             |
+/// | `let pat = expr else { ... };`          | Let binding with a block for a non-matching pattern     |
 /// | `@Component(props; attrs) { children }` | Component call                                          |
+/// | `slot(name) { ... }`                    | Named slot content in a component call                  |
+/// | `#slot(name)`                           | Named slot in a component body                          |
+/// | `for child in #children { ... }`        | Loop over the children in a component body              |
+/// | `@Component(props) { \|item\| ... }`      | Children passed as a render prop ([`RenderProp`])       |
+/// | `#children(expr)`                       | Render prop in a component body                         |
+/// | `prop: @lazy(expr)`                     | Prop passed as the closure `\|\| expr`                    |
+/// | `html! { ... }`, `@{ ... }`             | Nested template, inlined into the enclosing one         |
+/// | `cache!(key = expr, ttl = 60s) { ... }` | Cached fragment ([`cache`])                             |
+/// | `@can(expr) { ... } @else { ... }`      | Permission check ([`permission`])                       |
+/// | `@debug { ... }`                        | Debug-only content ([`debug`])                          |
 pub use plait_macros::html;
 
+/// Renders a template straight into a [`fmt::Write`](std::fmt::Write) writer.
+///
+/// `html_to!(writer, { ... })` takes the same template syntax as [`html!`], but writes the output into `writer` (a
+/// mutable reference to any [`fmt::Write`](std::fmt::Write) implementation, such as a [`String`] or a
+/// [`fmt::Formatter`](std::fmt::Formatter)) instead of returning a fragment, and evaluates to a
+/// [`fmt::Result`](std::fmt::Result). This is handy where naming or storing the fragment type is awkward, e.g. in trait
+/// implementations with fixed signatures.
+///
+/// Unlike [`ToHtml::to_html()`], `html_to!` doesn't start a render of its own: ids generated with `auto_id` are only
+/// kept unique when it is used while a [`to_html()`](ToHtml::to_html) call is in progress.
+///
+/// # Example
+///
+/// ```
+/// use std::fmt;
+///
+/// use plait::html_to;
+///
+/// struct User {
+///     name: String,
+/// }
+///
+/// impl fmt::Display for User {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         html_to!(f, {
+///             span(class: "user") { (self.name) }
+///         })
+///     }
+/// }
+///
+/// let user = User { name: "Tom & Jerry".to_owned() };
+/// assert_eq!(user.to_string(), r#"<span class="user">Tom &amp; Jerry</span>"#);
+///
+/// let mut output = String::new();
+/// html_to!(&mut output, { p { "Hello" } }).unwrap();
+/// assert_eq!(output, "<p>Hello</p>");
+/// ```
+pub use plait_macros::html_to;
+
 /// Defines a reusable HTML component (struct + [`Component`] trait implementation).
 ///
 /// See the [crate-level documentation](crate#components) for full details.
@@ -518,6 +1353,67 @@ pub use plait_macros::html;
 ///
 /// - `&str` → auto-generated lifetime `&'plait_N str`
 /// - `impl Trait` → generic type parameter `P_N: Trait`
+/// - `impl Fn(&T) -> impl Trait` → `P_N: Fn(&T) -> P_M` and `P_M: Trait` (`&T` stays higher-ranked)
+/// - `name?: T` → optional field `name: Option<T>`
+///
+/// # Attributes
+///
+/// Outer attributes on the component (e.g. doc comments or `#[deprecated]`) are applied to the generated struct, and
+/// attributes on props are applied to the corresponding fields. Deprecating a component or a prop makes rustc warn at
+/// every call site that uses it:
+///
+/// ```
+/// # use plait::component;
+/// component! {
+///     #[deprecated(note = "use `Button` instead")]
+///     pub fn OldButton(#[deprecated(note = "use `text` instead")] label: &str) {
+///         button { (label) }
+///     }
+/// }
+/// ```
+///
+/// # Multiple components and modules
+///
+/// A single `component!` invocation can define several components. Prefix a component's name with a module path
+/// (`pub fn ui::Button`) to generate it into that module instead. The module is created by the macro and imports
+/// everything from its parent with `use super::*`; it is `pub` if any of its components is `pub`, and otherwise gets
+/// the visibility of its first component. Defining the same component twice in an invocation is reported as an error at
+/// the duplicate definition.
+///
+/// ```
+/// # use plait::{component, html, ToHtml};
+/// component! {
+///     pub fn ui::Icon(name: &str) {
+///         i(class: name) {}
+///     }
+///
+///     pub fn ui::Button(icon: &str) {
+///         button { @Icon(name: icon) {} #children }
+///     }
+/// }
+///
+/// let html = html! {
+///     @ui::Button(icon: "save") { "Save" }
+/// };
+///
+/// assert_eq!(html.to_html(), "<button><i class=\"save\"></i>Save</button>");
+/// ```
+///
+/// The invocation owns the modules it generates: it declares `mod ui` itself, so all components of a module must be
+/// defined in the same invocation. A second invocation generating into `ui`, or a `mod ui` written by hand next to it,
+/// fails to compile with "the name `ui` is defined multiple times". To add components to an existing module, invoke
+/// `component!` inside that module instead:
+///
+/// ```compile_fail,E0428
+/// # use plait::component;
+/// component! {
+///     pub fn ui::Icon(name: &str) { i(class: name) {} }
+/// }
+///
+/// component! {
+///     pub fn ui::Badge(text: &str) { span { (text) } }
+/// }
+/// ```
 ///
 /// # Calling
 ///
@@ -563,13 +1459,285 @@ pub use plait_macros::html;
 ///
 /// assert_eq!(html.to_html(), "<button class=\"btn primary\">Click</button>");
 /// ```
+///
+/// ## Optional props
+///
+/// Props declared with `name?: T` are fields of type `Option<T>` that can be left out at the call site, in which case
+/// they are `None`. Every other prop must be set, and a call missing one doesn't compile. Optional props are set with
+/// an `Option` like any other value:
+///
+/// ```
+/// # use plait::{component, html, ToHtml};
+/// component! {
+///     pub fn Card(title?: &str, body: &str) {
+///         article {
+///             if let Some(title) = title {
+///                 h2 { (title) }
+///             }
+///             p { (body) }
+///         }
+///     }
+/// }
+///
+/// let html = html! {
+///     @Card(body: "No title") {}
+///     @Card(title: Some("Hello"), body: "With a title") {}
+/// };
+///
+/// assert_eq!(
+///     html.to_html(),
+///     "<article><p>No title</p></article><article><h2>Hello</h2><p>With a title</p></article>"
+/// );
+/// ```
+///
+/// The type of an omitted prop must still be known, so prefer concrete types for optional props: leaving out an
+/// `icon?: impl RenderEscaped` prop doesn't compile, as nothing determines the type of the icon.
 pub use plait_macros::component;
 
+/// Defines a deprecated alias for a renamed component, so that templates calling the component by its old name keep
+/// compiling while rustc warns at each call.
+///
+/// `component_alias!(OldName = NewName)` generates a deprecated type alias `OldName` of the component `NewName`,
+/// which can be called with the same props, attributes and children. Visibility and outer attributes (such as doc
+/// comments) are applied to the alias. A `#[deprecated]` attribute replaces the default note, which names the new
+/// component.
+///
+/// ```
+/// # use plait::{component, component_alias, html, ToHtml};
+/// component! {
+///     pub fn Button(primary: bool) {
+///         button(class?: primary.then_some("primary")) { #children }
+///     }
+/// }
+///
+/// component_alias!(pub PrimaryButton = Button);
+///
+/// # #[allow(deprecated)]
+/// let html = html! {
+///     // warning: use of deprecated type alias `PrimaryButton`: `PrimaryButton` has been renamed to `Button`
+///     @PrimaryButton(primary: false) { "Save" }
+/// };
+/// assert_eq!(html.to_html(), "<button>Save</button>");
+/// ```
+///
+/// Components are generic when their props are: each `&str` or other borrowed prop adds a lifetime and each
+/// `impl Trait` prop adds a type parameter, in the order of the props. The alias of a generic component declares as
+/// many parameters after its name, and rustc reports the expected number if they don't match:
+///
+/// ```
+/// # use plait::{Class, classes, component, component_alias, html, ToHtml};
+/// component! {
+///     pub fn Card(title: &str, class: impl Class) {
+///         div(class: classes!("card", class)) { h2 { (title) } #children }
+///     }
+/// }
+///
+/// component_alias! {
+///     #[deprecated(note = "use `Card` instead")]
+///     pub Panel<'a, C> = Card
+/// }
+///
+/// # #[allow(deprecated)]
+/// let html = html! {
+///     @Panel(title: "Hello", class: "wide") { "Body" }
+/// };
+/// assert_eq!(html.to_html(), r#"<div class="card wide"><h2>Hello</h2>Body</div>"#);
+/// ```
+pub use plait_macros::component_alias;
+
+/// Defines components that load their data asynchronously, implementing [`RenderAsync`].
+///
+/// `async_component!` takes the same definitions as [`component!`], but the `let` bindings at the start of a body are
+/// evaluated in an `async` block, so they can `.await` data fetching. A component can load what it renders itself,
+/// instead of every caller fetching it up front and passing it down through props. The markup after the bindings is
+/// rendered like the body of a regular component.
+///
+/// Each definition generates a struct with a public field per prop, which is created with a struct literal and
+/// rendered with [`render_async()`](RenderAsync::render_async). The rendered [`Html`] is embedded in other templates
+/// with `(expr)`, so an async component renders its async children by awaiting them in its bindings:
+///
+/// ```
+/// use plait::{async_component, html, RenderAsync, ToHtml};
+///
+/// struct User {
+///     name: String,
+/// }
+///
+/// async fn find_user(id: u64) -> User {
+///     User { name: format!("User {id}") }
+/// }
+///
+/// async_component! {
+///     pub fn UserCard(id: u64) {
+///         let user = find_user(*id).await;
+///         div(class: "user") { (user.name) }
+///     }
+///
+///     pub fn Team<'a>(name: &'a str, members: &'a [u64]) {
+///         let cards = {
+///             let mut cards = Vec::new();
+///             for id in members.iter() {
+///                 cards.push(UserCard { id: *id }.render_async().await);
+///             }
+///             cards
+///         };
+///         section {
+///             h2 { (name) }
+///             for card in &cards {
+///                 (card)
+///             }
+///         }
+///     }
+/// }
+///
+/// # let future = async {
+/// let team = Team { name: "Core", members: &[1, 2] }.render_async().await;
+///
+/// assert_eq!(
+///     html! { main { (team) } }.to_html(),
+///     r#"<main><section><h2>Core</h2><div class="user">User 1</div><div class="user">User 2</div></section></main>"#
+/// );
+/// # };
+/// # let waker = std::task::Waker::noop();
+/// # assert!(std::pin::pin!(future).poll(&mut std::task::Context::from_waker(waker)).is_ready());
+/// ```
+///
+/// Async components don't take extra attributes or children, so `#attrs`, `#children` and `#slot(name)` can't be
+/// used in their bodies, and they can't be called with `@Component(...)`. Props are references in the body like in
+/// [`component!`], and optional props are set to `None` or `Some(...)` in the struct literal.
+///
+/// An async component renders its markup within [`ids::scope`], which the async components it awaits join, so ids
+/// from `auto_id` and [`ids::next`] are unique across all of them. The page embedding the result is rendered
+/// separately unless it is rendered inside [`ids::scope`] as well; see [Async components](ids#async-components).
+pub use plait_macros::async_component;
+
+/// Embeds a static asset (e.g. a stylesheet or script) into the binary at compile time, returning an [`Asset`].
+///
+/// The path is resolved relative to the calling crate's `Cargo.toml`. The macro computes a content hash for
+/// cache-busting URLs and fails to compile if the file cannot be read. The file must be valid UTF-8.
+///
+/// See [`Asset`] for how assets are rendered.
+///
+/// # Example
+///
+/// ```ignore
+/// use plait::{asset, html, Asset, ToHtml};
+///
+/// const APP_CSS: Asset = asset!("assets/app.css");
+///
+/// let page = html! {
+///     head { (APP_CSS) }
+/// };
+/// ```
+pub use plait_macros::asset;
+
+/// Builds a URL `String` from a template with `{placeholder}` interpolation, encoding each value for its position.
+///
+/// Placeholders hold Rust expressions (usually variables or field accesses) whose values implement
+/// [`Display`](std::fmt::Display). Values in the path are percent-encoded as path segments (so `/`, `?` and `#`
+/// cannot change the URL structure, and a value of `.` or `..` is encoded as `%2E` or `%2E%2E`), and values after `?` or `#` are percent-encoded as query components (so `&`,
+/// `=` and `+` cannot inject parameters). Use `{{` and `}}` for literal braces.
+///
+/// The template is validated at compile time: unbalanced braces, empty or invalid placeholders and characters that are
+/// never valid in a URL (whitespace, `"`, `<`, `>`, `` ` ``) are compile errors.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, url, ToHtml};
+///
+/// let id = "a/b";
+/// let query = "rust & html";
+///
+/// let frag = html! {
+///     a(href: (url!("/users/{id}/posts?q={query}&page={1 + 1}"))) { "Posts" }
+/// };
+///
+/// assert_eq!(
+///     frag.to_html(),
+///     r#"<a href="/users/a%2Fb/posts?q=rust%20%26%20html&amp;page=2">Posts</a>"#
+/// );
+/// ```
+pub use plait_macros::url;
+
+/// Registers a function as an example of a component in the [`gallery`].
+///
+/// The annotated function must take no arguments and return a value implementing [`ToHtml`] (typically the output
+/// of [`html!`]). The first argument names the component; the optional second argument names the example (defaulting
+/// to the function name).
+///
+/// # Example
+///
+/// ```
+/// use plait::{component, component_example, html, ToHtml};
+///
+/// component! {
+///     pub fn Button(label: &str) {
+///         button { (label) }
+///     }
+/// }
+///
+/// #[component_example(Button, "Primary")]
+/// fn button_primary() -> impl ToHtml {
+///     html! { @Button(label: "Save") {} }
+/// }
+///
+/// assert!(plait::gallery::render().contains("<h3>Primary</h3>"));
+/// ```
+#[cfg(feature = "gallery")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gallery")))]
+pub use plait_macros::component_example;
+
+/// Turns a function into a test checking the output of a component.
+///
+/// The annotated function must take no arguments and return a value implementing [`ToHtml`] (typically the output
+/// of [`html!`] calling the component with example props). The generated `#[test]` renders it and fails if the output
+/// doesn't parse as valid HTML (see [`testing::html_errors`]) or, when a snapshot string is given, if its
+/// [accessible names](testing::accessible_names) don't match the snapshot. Each line of the snapshot lists one element
+/// as `role "name"`.
+///
+/// # Example
+///
+/// ```
+/// use plait::{component, component_test, html, ToHtml};
+///
+/// component! {
+///     pub fn IconButton(label: &str, icon: &str) {
+///         button(type: "button", aria_label: label) { span(aria_hidden: "true") { (icon) } }
+///     }
+/// }
+///
+/// #[component_test(r#"button "Close""#)]
+/// fn icon_button_close() -> impl ToHtml {
+///     html! { @IconButton(label: "Close", icon: "×") {} }
+/// }
+///
+/// # plait::testing::assert_accessible_names(
+/// #     &html! { @IconButton(label: "Close", icon: "×") {} }.to_html(),
+/// #     r#"button "Close""#,
+/// # );
+/// ```
+pub use plait_macros::component_test;
+
 pub use self::{
+    asset::{Asset, AssetUrls},
+    attrs::{Attrs, Sorted},
     classes::{Class, Classes},
-    component::Component,
-    fragment::{HtmlFragment, PartialHtml},
+    component::{
+        Child, Component, EachChild, MissingProp, RenderAsync, RenderProp, RequiredProp, SetProp,
+        each_child, forward_slot, is_slot, render_component, render_prop, render_slot, start_child,
+    },
+    data_attrs::DataAttrs,
+    fragment::{HtmlFragment, PartialHtml, render, render_with_capacity},
     html::{Html, ToHtml},
-    maybe_attr::{RenderMaybeAttributeEscaped, RenderMaybeAttributeRaw},
-    render::{RenderEscaped, RenderRaw},
+    maybe_attr::{RenderMaybeAttributeEscaped, RenderMaybeAttributeRaw, render_attribute_pairs},
+    render::{Escaped, RenderEscaped, RenderIter, RenderRaw, Unescaped},
+    route::{Href, RouteHref},
+    slug::{slugify, write_auto_id},
+    text::{html_to_text, truncate_html},
+    url::{push_url_path_segment, push_url_query_component},
+    utils::{AttributeWhitespace, render_raw_text},
 };
+
+#[cfg(feature = "serde")]
+pub use self::json::Json;
@@ -0,0 +1,184 @@
+//! Link audits of rendered pages.
+//!
+//! [`audit`] collects every URL in the [URL attributes](crate::spec::URL_ATTRIBUTES) (`href`, `src`, `srcset`, ...) of
+//! rendered HTML into a [`LinkReport`]. Tests can use the report to check that a page doesn't ship dead in-page links,
+//! mixed-content `http:` URLs or links to internal pages that don't exist.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, links};
+//!
+//! let page = html! {
+//!     nav {
+//!         a(href: "#intro") { "Intro" }
+//!         a(href: "#missing") { "Missing" }
+//!         a(href: "/about") { "About" }
+//!     }
+//!     h2(id: "intro") { "Intro" }
+//!     img(src: "http://cdn.example.com/logo.png", srcset: "/logo.png 1x, /logo@2x.png 2x");
+//! };
+//!
+//! let report = links::audit(&page.to_html());
+//!
+//! assert_eq!(report.links().len(), 6);
+//!
+//! let broken: Vec<_> = report.broken_fragments().map(|link| link.url.as_str()).collect();
+//! assert_eq!(broken, ["#missing"]);
+//!
+//! let insecure: Vec<_> = report.insecure().map(|link| link.url.as_str()).collect();
+//! assert_eq!(insecure, ["http://cdn.example.com/logo.png"]);
+//!
+//! let internal: Vec<_> = report.internal().map(|link| link.url.as_str()).collect();
+//! assert_eq!(internal, ["/about", "/logo.png", "/logo@2x.png"]);
+//! ```
+use std::collections::BTreeSet;
+
+use crate::{
+    spec::is_url_attribute,
+    text::text_content,
+    tokenizer::{Token, Tokenizer, attribute, attributes},
+};
+
+/// A URL found by [`audit`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Link {
+    /// Lower-case name of the element, e.g. `a` or `img`.
+    pub element: String,
+    /// Lower-case name of the attribute, e.g. `href` or `srcset`.
+    pub attribute: String,
+    /// The URL, with character references decoded.
+    pub url: String,
+}
+
+impl Link {
+    /// Returns the scheme of the URL in lower case (`https`, `mailto`, ...), or `None` for relative URLs.
+    pub fn scheme(&self) -> Option<String> {
+        let (scheme, _) = self.url.split_once(':')?;
+
+        let is_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+        is_scheme.then(|| scheme.to_ascii_lowercase())
+    }
+
+    /// Returns `true` if the URL points to the same site: it has no scheme and isn't protocol-relative (`//host`).
+    pub fn is_internal(&self) -> bool {
+        self.scheme().is_none() && !self.url.starts_with("//")
+    }
+
+    /// Returns the fragment of an in-page link (`#id`), without the `#`.
+    pub fn fragment(&self) -> Option<&str> {
+        self.url.strip_prefix('#')
+    }
+}
+
+/// The URLs of a rendered page, in document order. See [`audit`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LinkReport {
+    links: Vec<Link>,
+    ids: BTreeSet<String>,
+}
+
+impl LinkReport {
+    /// Returns all links.
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    /// Returns the ids of the elements in the page.
+    pub fn ids(&self) -> &BTreeSet<String> {
+        &self.ids
+    }
+
+    /// Returns links using the insecure `http:` scheme, which browsers block or warn about on `https:` pages.
+    pub fn insecure(&self) -> impl Iterator<Item = &Link> {
+        self.links
+            .iter()
+            .filter(|link| link.scheme().as_deref() == Some("http"))
+    }
+
+    /// Returns protocol-relative links (`//host/path`).
+    pub fn protocol_relative(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|link| link.url.starts_with("//"))
+    }
+
+    /// Returns links to other pages of the same site: relative and root-relative URLs other than in-page `#id` links.
+    pub fn internal(&self) -> impl Iterator<Item = &Link> {
+        self.links
+            .iter()
+            .filter(|link| link.is_internal() && link.fragment().is_none())
+    }
+
+    /// Returns in-page `#id` links without a matching `id` in the page. `#` and `#top` always scroll to the top of the
+    /// page, so they are never broken.
+    pub fn broken_fragments(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter().filter(|link| {
+            link.fragment().is_some_and(|fragment| {
+                !fragment.is_empty()
+                    && !fragment.eq_ignore_ascii_case("top")
+                    && !self.ids.contains(fragment)
+            })
+        })
+    }
+
+    /// Returns internal links for which `exists` returns `false`, e.g. because no route or file matches their path.
+    ///
+    /// `exists` receives the URL without its query and fragment.
+    pub fn dead_internal<'a>(
+        &'a self,
+        mut exists: impl FnMut(&str) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a Link> {
+        self.internal().filter(move |link| {
+            let path = link.url.split(['?', '#']).next().unwrap_or_default();
+            !exists(path)
+        })
+    }
+}
+
+/// Collects the URLs and ids of rendered `html` into a [`LinkReport`].
+///
+/// `srcset` and `ping` hold several URLs, which are reported separately.
+pub fn audit(html: &str) -> LinkReport {
+    let mut report = LinkReport::default();
+
+    for token in Tokenizer::new(html) {
+        let Token::StartTag { name, source, .. } = token else {
+            continue;
+        };
+
+        if let Some(id) = attribute(source, "id") {
+            report.ids.insert(text_content(id));
+        }
+
+        for (attribute_name, value) in attributes(source) {
+            if !is_url_attribute(attribute_name) {
+                continue;
+            }
+
+            let attribute_name = attribute_name.to_ascii_lowercase();
+            let value = text_content(value);
+
+            let urls: Vec<&str> = match attribute_name.as_str() {
+                "srcset" => value
+                    .split(',')
+                    .filter_map(|candidate| candidate.split_whitespace().next())
+                    .collect(),
+                "ping" => value.split_whitespace().collect(),
+                _ => vec![value.trim()],
+            };
+
+            for url in urls.into_iter().filter(|url| !url.is_empty()) {
+                report.links.push(Link {
+                    element: name.to_ascii_lowercase(),
+                    attribute: attribute_name.clone(),
+                    url: url.to_owned(),
+                });
+            }
+        }
+    }
+
+    report
+}
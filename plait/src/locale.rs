@@ -0,0 +1,164 @@
+//! Language and text direction of the current render, for right-to-left support.
+//!
+//! [Provide](crate::context::provide) a [`Locale`] in the render context while a page is rendered. Components then
+//! query the text direction with [`dir()`] instead of receiving it as a prop, and the [`Document`] component sets the
+//! `lang` and `dir` attributes of the `html` element. User-generated text of unknown direction (names, titles, ...)
+//! should be wrapped in [`Bidi`], so that it doesn't reorder the text around it.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{component, context, html, ToHtml, locale::{self, Bidi, Direction, Document, Locale}};
+//!
+//! component! {
+//!     pub fn Back() {
+//!         let arrow = if locale::dir() == Direction::Rtl { "→" } else { "←" };
+//!
+//!         a(href: "/") { (arrow) " " #children }
+//!     }
+//! }
+//!
+//! let author = "إيمان";
+//!
+//! let page = html! {
+//!     @Document {
+//!         body {
+//!             @Back { "رجوع" }
+//!             p { "بقلم " @Bidi { (author) } }
+//!         }
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     context::provide(Locale::new("ar-EG"), || page.to_html()),
+//!     "<!DOCTYPE html><html lang=\"ar-EG\" dir=\"rtl\"><body><a href=\"/\">→ رجوع</a>\
+//!     <p>بقلم <bdi>إيمان</bdi></p></body></html>"
+//! );
+//! ```
+use std::{borrow::Cow, fmt};
+
+use crate::{RenderEscaped, component, context};
+
+/// The direction of text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right.
+    #[default]
+    Ltr,
+    /// Right to left.
+    Rtl,
+    /// Decided by the browser from the content.
+    Auto,
+}
+
+impl Direction {
+    /// Returns the value of the `dir` attribute for this direction.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+            Direction::Auto => "auto",
+        }
+    }
+}
+
+impl RenderEscaped for Direction {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Primary language subtags of languages written right to left.
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ks", "nqo", "ps", "sd", "syr", "ug", "ur", "yi",
+];
+
+/// Script subtags of scripts written right to left.
+const RTL_SCRIPTS: &[&str] = &[
+    "adlm", "arab", "hebr", "mand", "nkoo", "rohg", "samr", "syrc", "thaa",
+];
+
+/// The language of a page and the direction its text is written in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Locale {
+    lang: Cow<'static, str>,
+    dir: Direction,
+}
+
+impl Locale {
+    /// Creates a locale for the [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag `lang` (e.g. `"en"`,
+    /// `"ar-EG"` or `"az-Arab"`).
+    ///
+    /// The direction is right to left if the tag has the script subtag of a right-to-left script, or has no script
+    /// subtag and the language is written right to left. Use [`with_dir()`](Locale::with_dir) to override it.
+    pub fn new(lang: impl Into<Cow<'static, str>>) -> Self {
+        let lang = lang.into();
+        let mut subtags = lang.split(['-', '_']);
+
+        let language = subtags.next().unwrap_or_default();
+        let script = subtags
+            .next()
+            .filter(|subtag| subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()));
+
+        let is_rtl = match script {
+            Some(script) => RTL_SCRIPTS.contains(&script.to_ascii_lowercase().as_str()),
+            None => RTL_LANGUAGES.contains(&language.to_ascii_lowercase().as_str()),
+        };
+
+        Self {
+            lang,
+            dir: if is_rtl {
+                Direction::Rtl
+            } else {
+                Direction::Ltr
+            },
+        }
+    }
+
+    /// Sets the direction of the locale.
+    pub fn with_dir(mut self, dir: Direction) -> Self {
+        self.dir = dir;
+        self
+    }
+
+    /// Returns the language tag.
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// Returns the text direction.
+    pub fn dir(&self) -> Direction {
+        self.dir
+    }
+}
+
+/// Returns the text direction of the [`Locale`] in the render context, or [`Direction::Ltr`] if none has been
+/// provided.
+pub fn dir() -> Direction {
+    context::get::<Locale>().map_or(Direction::Ltr, |locale| locale.dir())
+}
+
+component! {
+    /// Renders `<!DOCTYPE html>` and an `html` element with the `lang` and `dir` of the [`Locale`] in the render
+    /// context around its children.
+    ///
+    /// Without a locale the attributes are left out. Extra attributes are added to the `html` element.
+    pub fn Document() {
+        let locale = context::get::<Locale>();
+        let lang = locale.as_deref().map(Locale::lang);
+        let dir = locale.as_deref().map(Locale::dir);
+
+        #doctype
+        html(lang?: lang, dir?: dir, #attrs) { #children }
+    }
+}
+
+component! {
+    /// Wraps its children in a `bdi` element, isolating text of unknown direction (such as user-generated names) from
+    /// the surrounding text.
+    ///
+    /// Extra attributes are added to the `bdi` element.
+    pub fn Bidi() {
+        bdi(#attrs) { #children }
+    }
+}
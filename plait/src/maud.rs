@@ -0,0 +1,89 @@
+//! Interop with [maud](https://docs.rs/maud) templates, for migrating between the two incrementally.
+//!
+//! - [`maud::PreEscaped`](::maud::PreEscaped) (and so [`maud::Markup`](::maud::Markup)) implements [`RenderEscaped`]
+//!   and [`RenderRaw`], so maud output can be embedded in [`html!`](crate::html) with `(markup)`. It is written as-is,
+//!   since maud has already escaped it.
+//! - [`Html`] and [`HtmlFragment`] implement [`maud::Render`](::maud::Render), so plait output can be embedded in
+//!   maud's `html!` with `(fragment)`.
+//! - [`IntoMaud`] converts plait output into a [`maud::Markup`](::maud::Markup) value.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, maud::IntoMaud};
+//!
+//! let avatar = maud::html! { img src="/ada.png" alt="Ada"; };
+//!
+//! let card = html! {
+//!     div(class: "card") { (avatar) span { "Ada" } }
+//! };
+//!
+//! let page = maud::html! {
+//!     main { (card) }
+//! };
+//!
+//! assert_eq!(
+//!     page.into_string(),
+//!     "<main><div class=\"card\"><img src=\"/ada.png\" alt=\"Ada\"><span>Ada</span></div></main>"
+//! );
+//!
+//! let markup: maud::Markup = html! { p { "<plait>" } }.into_maud();
+//! assert_eq!(markup.into_string(), "<p>&lt;plait&gt;</p>");
+//! ```
+use std::fmt;
+
+use ::maud::{Markup, PreEscaped, Render};
+
+use crate::{Html, HtmlFragment, RenderEscaped, RenderRaw, ToHtml};
+
+/// Conversion of plait output into [`maud::Markup`](::maud::Markup).
+pub trait IntoMaud {
+    /// Renders `self` into maud markup.
+    fn into_maud(self) -> Markup;
+}
+
+impl<T> IntoMaud for T
+where
+    T: ToHtml,
+{
+    fn into_maud(self) -> Markup {
+        PreEscaped(String::from(self.to_html()))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "maud")))]
+impl<T> RenderEscaped for PreEscaped<T>
+where
+    T: AsRef<str>,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self.0.as_ref())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "maud")))]
+impl<T> RenderRaw for PreEscaped<T>
+where
+    T: AsRef<str>,
+{
+    fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self.0.as_ref())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "maud")))]
+impl Render for Html {
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(self);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "maud")))]
+impl<F> Render for HtmlFragment<F>
+where
+    F: Fn(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+{
+    fn render_to(&self, buffer: &mut String) {
+        buffer.push_str(&self.to_html());
+    }
+}
@@ -1,6 +1,10 @@
 use std::fmt;
 
-use crate::{RenderEscaped, RenderRaw};
+use crate::{
+    RenderEscaped, RenderRaw, spec,
+    url_policy::UrlValue,
+    utils::{AttributeWhitespace, encodes_attribute_whitespace},
+};
 
 /// Trait for conditionally rendering an HTML attribute with a raw (unescaped) value.
 ///
@@ -78,6 +82,13 @@ pub trait RenderMaybeAttributeEscaped {
         name: &str,
         f: &mut (dyn fmt::Write + '_),
     ) -> fmt::Result;
+
+    #[doc(hidden)]
+    /// Returns `true` if the value of the attribute is a [`SafeUrl`](crate::url_policy::SafeUrl).
+    #[inline]
+    fn is_safe_url(&self) -> bool {
+        false
+    }
 }
 
 impl<T> RenderMaybeAttributeEscaped for &T
@@ -91,6 +102,10 @@ where
     ) -> fmt::Result {
         (**self).render_maybe_attribute_escaped(name, f)
     }
+
+    fn is_safe_url(&self) -> bool {
+        (**self).is_safe_url()
+    }
 }
 
 impl RenderMaybeAttributeEscaped for bool {
@@ -132,4 +147,57 @@ where
             None => Ok(()),
         }
     }
+
+    fn is_safe_url(&self) -> bool {
+        self.as_ref().is_some_and(RenderEscaped::is_safe_url)
+    }
+}
+
+#[doc(hidden)]
+/// Writes the ` name="value"` attributes of `pairs` (escaped) into `f`. This is used internally by the `#attrs(expr)`
+/// syntax in [`html!`](crate::html).
+///
+/// Attributes are written in the order of `pairs` (wrap unordered collections such as a `HashMap` in
+/// [`Sorted`](crate::Sorted)). Only the first value of a repeated name is written, names that aren't
+/// [valid attribute names](crate::spec::is_valid_attribute_name) are skipped, and the values of
+/// [URL attributes](crate::spec::URL_ATTRIBUTES) are checked against the [`UrlRules`](crate::url_policy::UrlRules) in
+/// the render context.
+pub fn render_attribute_pairs<I, K, V>(pairs: I, f: &mut (dyn fmt::Write + '_)) -> fmt::Result
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: RenderEscaped,
+{
+    let pairs: Vec<_> = pairs
+        .into_iter()
+        .filter(|(name, _)| spec::is_valid_attribute_name(name.as_ref()))
+        .collect();
+
+    for (index, (name, value)) in pairs.iter().enumerate() {
+        let name = name.as_ref();
+
+        if pairs[..index].iter().any(|(seen, _)| seen.as_ref() == name) {
+            continue;
+        }
+
+        let mut encoder;
+        let f: &mut (dyn fmt::Write + '_) = if encodes_attribute_whitespace(name) {
+            encoder = AttributeWhitespace(&mut *f);
+            &mut encoder
+        } else {
+            &mut *f
+        };
+
+        if spec::is_url_attribute(&name.to_ascii_lowercase()) {
+            UrlValue(value).render_maybe_attribute_escaped(name, f)?;
+        } else {
+            f.write_str(" ")?;
+            f.write_str(name)?;
+            f.write_str("=\"")?;
+            value.render_escaped(f)?;
+            f.write_str("\"")?;
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,462 @@
+//! Components for responsive images and embedded media.
+//!
+//! [`Picture`] renders a `<picture>` element from [`SourceSpec`]s and an [`ImgSpec`]. Browsers use the first
+//! `<source>` that matches, and the `<img>` must come last, so the component orders the sources instead of relying on
+//! the order of the slice: sources with a `media` query or a `type` are written first, in the order they were given,
+//! followed by the first source without either (any later one could never be picked). Sources without a `srcset`, or
+//! with a `type` that isn't a MIME type, are skipped.
+//!
+//! [`Video`] and [`Audio`] render a `<video>` or `<audio>` element with `controls`, a `<track>` per [`Track`] (such as
+//! captions) and their children as fallback content for browsers that can't play the media, typically a link to
+//! download it.
+//!
+//! [`LazyIframe`] embeds third-party content (videos, maps, social posts) behind a click-to-load facade: the
+//! `<iframe>` starts with a small placeholder document in its `srcdoc`, and loads the embed only when the placeholder
+//! is clicked, so nothing is requested from the third party until then. [`YouTube`] uses it for YouTube videos.
+//!
+//! The URLs of `src`, `srcset` and `poster` attributes are checked against the
+//! [`UrlRules`](crate::url_policy::UrlRules) in the render context like other URL attributes.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, media::{ImgSpec, Picture, SourceSpec, Track, Video}};
+//!
+//! let sources = [
+//!     SourceSpec::new("/hero.jpg"),
+//!     SourceSpec::new("/hero-wide.avif").media("(min-width: 800px)").mime_type("image/avif"),
+//!     SourceSpec::new("/hero.avif").mime_type("image/avif"),
+//! ];
+//!
+//! let frag = html! {
+//!     @Picture(sources: &sources, img: ImgSpec::new("/hero.jpg", "A mountain lake").size(1200, 600)) {}
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<picture>\
+//!     <source srcset=\"/hero-wide.avif\" media=\"(min-width: 800px)\" type=\"image/avif\">\
+//!     <source srcset=\"/hero.avif\" type=\"image/avif\">\
+//!     <source srcset=\"/hero.jpg\">\
+//!     <img src=\"/hero.jpg\" alt=\"A mountain lake\" width=\"1200\" height=\"600\">\
+//!     </picture>"
+//! );
+//!
+//! let captions = [Track::captions("/talk.en.vtt", "en", "English").as_default()];
+//!
+//! let frag = html! {
+//!     @Video(src: "/talk.mp4", poster: Some("/talk.jpg"), captions: &captions) {
+//!         a(href: "/talk.mp4") { "Download the talk" }
+//!     }
+//! };
+//!
+//! assert_eq!(
+//!     frag.to_html(),
+//!     "<video src=\"/talk.mp4\" poster=\"/talk.jpg\" controls preload=\"metadata\">\
+//!     <track src=\"/talk.en.vtt\" kind=\"captions\" srclang=\"en\" label=\"English\" default>\
+//!     <a href=\"/talk.mp4\">Download the talk</a>\
+//!     </video>"
+//! );
+//! ```
+use crate::{ToHtml, component, html, push_url_path_segment};
+
+/// A `<source>` of a [`Picture`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourceSpec<'a> {
+    /// The `srcset` attribute: one or more URLs with optional width (`480w`) or density (`2x`) descriptors.
+    pub srcset: &'a str,
+    /// The `media` attribute: a media query the source applies to.
+    pub media: Option<&'a str>,
+    /// The `type` attribute: the MIME type of the images, such as `image/avif`.
+    pub mime_type: Option<&'a str>,
+    /// The `sizes` attribute, for `srcset`s with width descriptors.
+    pub sizes: Option<&'a str>,
+    /// The intrinsic width of the images, in pixels.
+    pub width: Option<u32>,
+    /// The intrinsic height of the images, in pixels.
+    pub height: Option<u32>,
+}
+
+impl<'a> SourceSpec<'a> {
+    /// Creates a source for `srcset`.
+    pub const fn new(srcset: &'a str) -> Self {
+        Self {
+            srcset,
+            media: None,
+            mime_type: None,
+            sizes: None,
+            width: None,
+            height: None,
+        }
+    }
+
+    /// Sets the media query the source applies to.
+    pub const fn media(mut self, media: &'a str) -> Self {
+        self.media = Some(media);
+        self
+    }
+
+    /// Sets the MIME type of the images.
+    pub const fn mime_type(mut self, mime_type: &'a str) -> Self {
+        self.mime_type = Some(mime_type);
+        self
+    }
+
+    /// Sets the `sizes` attribute.
+    pub const fn sizes(mut self, sizes: &'a str) -> Self {
+        self.sizes = Some(sizes);
+        self
+    }
+
+    /// Sets the intrinsic width and height of the images, in pixels.
+    pub const fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Returns `true` if the source has a `media` query or a `type`, so a browser may skip it.
+    fn is_conditional(&self) -> bool {
+        self.media.is_some() || self.mime_type.is_some()
+    }
+
+    /// Returns `true` if the source can be rendered.
+    fn is_valid(&self) -> bool {
+        !self.srcset.trim().is_empty() && self.mime_type.is_none_or(is_mime_type)
+    }
+}
+
+/// The `<img>` of a [`Picture`], used when no source matches or `<picture>` isn't supported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImgSpec<'a> {
+    /// The `src` attribute.
+    pub src: &'a str,
+    /// The `alt` attribute, which is always rendered. Use `""` for decorative images.
+    pub alt: &'a str,
+    /// The `srcset` attribute.
+    pub srcset: Option<&'a str>,
+    /// The `sizes` attribute.
+    pub sizes: Option<&'a str>,
+    /// The intrinsic width of the image, in pixels.
+    pub width: Option<u32>,
+    /// The intrinsic height of the image, in pixels.
+    pub height: Option<u32>,
+    /// Whether to render `loading="lazy"`, for images below the fold.
+    pub lazy: bool,
+}
+
+impl<'a> ImgSpec<'a> {
+    /// Creates an image for `src` with the alternative text `alt`.
+    pub const fn new(src: &'a str, alt: &'a str) -> Self {
+        Self {
+            src,
+            alt,
+            srcset: None,
+            sizes: None,
+            width: None,
+            height: None,
+            lazy: false,
+        }
+    }
+
+    /// Sets the `srcset` attribute.
+    pub const fn srcset(mut self, srcset: &'a str) -> Self {
+        self.srcset = Some(srcset);
+        self
+    }
+
+    /// Sets the `sizes` attribute.
+    pub const fn sizes(mut self, sizes: &'a str) -> Self {
+        self.sizes = Some(sizes);
+        self
+    }
+
+    /// Sets the intrinsic width and height of the image, in pixels, so the page doesn't shift when it loads.
+    pub const fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Loads the image lazily.
+    pub const fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+}
+
+/// The kind of a [`Track`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackKind {
+    /// Transcription of the dialogue and sound effects, for viewers who can't hear the audio.
+    #[default]
+    Captions,
+    /// Translation of the dialogue.
+    Subtitles,
+    /// Text description of the video, for viewers who can't see it.
+    Descriptions,
+    /// Chapter titles, for navigating the media.
+    Chapters,
+    /// Data for scripts, not shown to viewers.
+    Metadata,
+}
+
+impl TrackKind {
+    /// Returns the value of the `kind` attribute.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            TrackKind::Captions => "captions",
+            TrackKind::Subtitles => "subtitles",
+            TrackKind::Descriptions => "descriptions",
+            TrackKind::Chapters => "chapters",
+            TrackKind::Metadata => "metadata",
+        }
+    }
+}
+
+/// A timed text `<track>` of a [`Video`] or [`Audio`], usually a WebVTT file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Track<'a> {
+    /// The URL of the track.
+    pub src: &'a str,
+    /// The kind of the track.
+    pub kind: TrackKind,
+    /// The language of the track, as a BCP 47 language tag. Required for subtitles.
+    pub srclang: Option<&'a str>,
+    /// The title of the track shown by the browser.
+    pub label: Option<&'a str>,
+    /// Whether the track is enabled unless the user's preferences pick another one.
+    pub default: bool,
+}
+
+impl<'a> Track<'a> {
+    /// Creates a track of `kind` for `src`.
+    pub const fn new(src: &'a str, kind: TrackKind) -> Self {
+        Self {
+            src,
+            kind,
+            srclang: None,
+            label: None,
+            default: false,
+        }
+    }
+
+    /// Creates a captions track for `src` in the language `srclang`, shown as `label`.
+    pub const fn captions(src: &'a str, srclang: &'a str, label: &'a str) -> Self {
+        Self {
+            src,
+            kind: TrackKind::Captions,
+            srclang: Some(srclang),
+            label: Some(label),
+            default: false,
+        }
+    }
+
+    /// Marks the track as the default one.
+    pub const fn as_default(mut self) -> Self {
+        self.default = true;
+        self
+    }
+}
+
+/// Returns `true` if `value` looks like a MIME type (`type/subtype`, with optional parameters).
+fn is_mime_type(value: &str) -> bool {
+    let essence = value.split(';').next().unwrap_or_default().trim();
+
+    match essence.split_once('/') {
+        Some((kind, subtype)) => {
+            let is_token = |part: &str| {
+                !part.is_empty()
+                    && part
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+            };
+
+            is_token(kind) && is_token(subtype)
+        }
+        None => false,
+    }
+}
+
+/// Returns the sources of a [`Picture`] in the order they are rendered.
+fn ordered_sources<'s, 'a>(sources: &'s [SourceSpec<'a>]) -> Vec<&'s SourceSpec<'a>> {
+    let valid = sources.iter().filter(|source| source.is_valid());
+
+    valid
+        .clone()
+        .filter(|source| source.is_conditional())
+        .chain(valid.filter(|source| !source.is_conditional()).take(1))
+        .collect()
+}
+
+component! {
+    /// A responsive image: a `<picture>` with a `<source>` per [`SourceSpec`] and a final `<img>`. See the
+    /// [module documentation](self) for how sources are ordered.
+    ///
+    /// Extra attributes are forwarded to the `<picture>` element.
+    pub fn Picture<'a>(sources: &'a [SourceSpec<'a>], img: ImgSpec<'a>) {
+        picture(#attrs) {
+            for source in ordered_sources(sources) {
+                source(
+                    srcset: (source.srcset),
+                    media?: source.media,
+                    type?: source.mime_type,
+                    sizes?: source.sizes,
+                    width?: source.width,
+                    height?: source.height,
+                );
+            }
+            img(
+                src: (img.src),
+                alt: (img.alt),
+                srcset?: img.srcset,
+                sizes?: img.sizes,
+                width?: img.width,
+                height?: img.height,
+                loading?: img.lazy.then_some("lazy"),
+            );
+        }
+    }
+}
+
+component! {
+    /// A video player: a `<video>` with `controls`, a `<track>` per [`Track`] and the children as fallback content.
+    ///
+    /// `poster` is the image shown before the video plays, and `captions` can be `&[]` for videos without speech. Only
+    /// the metadata is preloaded. Extra attributes (such as
+    /// `width`, `muted` or `playsinline`) are forwarded to the `<video>` element.
+    pub fn Video<'a>(src: &'a str, poster?: &'a str, captions: &'a [Track<'a>]) {
+        video(src: (src), poster?: poster, controls, preload: "metadata", #attrs) {
+            for track in captions.iter() {
+                @MediaTrack(track) {}
+            }
+            #children
+        }
+    }
+
+    /// An audio player: an `<audio>` with `controls`, a `<track>` per [`Track`] and the children as fallback
+    /// content.
+    ///
+    /// `captions` can be `&[]`, as browsers don't show the text tracks of audio elements. Only the metadata is
+    /// preloaded. Extra attributes are forwarded to the `<audio>` element.
+    pub fn Audio<'a>(src: &'a str, captions: &'a [Track<'a>]) {
+        audio(src: (src), controls, preload: "metadata", #attrs) {
+            for track in captions.iter() {
+                @MediaTrack(track) {}
+            }
+            #children
+        }
+    }
+
+    fn MediaTrack<'a>(track: &'a Track<'a>) {
+        track(
+            src: (track.src),
+            kind: (track.kind.as_str()),
+            srclang?: track.srclang,
+            label?: track.label,
+            default?: track.default,
+        );
+    }
+}
+
+/// The `sandbox` of a [`LazyIframe`] without one: the embed can run scripts and open popups (such as links to the
+/// third-party site), but can't navigate the page, submit forms or download files.
+///
+/// It doesn't include `allow-same-origin`: the placeholder in the iframe's `srcdoc` has the origin of the page, so
+/// `allow-scripts` together with `allow-same-origin` would let it escape the sandbox entirely.
+pub const DEFAULT_SANDBOX: &str = "allow-scripts allow-popups allow-popups-to-escape-sandbox";
+
+/// The permissions YouTube embeds need for playback and sharing.
+const YOUTUBE_ALLOW: &str = "accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture; web-share";
+
+/// The styles of the placeholder document of a [`LazyIframe`].
+const FACADE_STYLE: &str = "*{margin:0;padding:0}html,body{height:100%;overflow:hidden}\
+    a{position:relative;display:flex;align-items:center;justify-content:center;height:100%;\
+    background:#000;color:#fff;font:16px/1.5 sans-serif;text-decoration:none}\
+    img{position:absolute;inset:0;width:100%;height:100%;object-fit:cover}\
+    span{position:relative;padding:.5em 1em;border-radius:.25em;background:rgba(0,0,0,.75)}\
+    a:hover span,a:focus span{background:#c00}";
+
+/// Returns `true` if `url` is an absolute `http:` or `https:` URL.
+fn is_http_url(url: &str) -> bool {
+    ["http://", "https://"].iter().any(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })
+}
+
+/// Returns `prefix` followed by `id` as a percent-encoded path segment and `suffix`.
+fn embed_url(prefix: &str, id: &str, suffix: &str) -> String {
+    let mut url = String::from(prefix);
+    push_url_path_segment(&mut url, id);
+    url.push_str(suffix);
+    url
+}
+
+component! {
+    /// A click-to-load `<iframe>` for third-party content.
+    ///
+    /// The iframe's `srcdoc` is a placeholder linking to `src`, with `label` (`"Load content"` by default) over the
+    /// `poster` image, and the browser navigates the iframe to `src` when it is clicked. Browsers without `srcdoc`
+    /// support load `src` directly. The iframe is also lazy-loaded and doesn't send the full page URL as the referrer.
+    /// `src` must be an absolute `http:` or `https:` URL; any other URL (such as `javascript:`) is left out, so only
+    /// the placeholder is rendered.
+    ///
+    /// `title` describes the content for screen readers, and `allow` lists the permissions policy features the embed
+    /// may use. `sandbox` defaults to [`DEFAULT_SANDBOX`]; pass `Some("")` for the most restrictive sandbox. Avoid
+    /// adding `allow-same-origin` to a sandbox that has `allow-scripts`, since the placeholder shares the page's
+    /// origin. Extra attributes (such as `width`, `height` or `allowfullscreen`) are forwarded to the `<iframe>`
+    /// element.
+    pub fn LazyIframe<'a>(
+        src: &'a str,
+        title: &'a str,
+        label?: &'a str,
+        poster?: &'a str,
+        allow?: &'a str,
+        sandbox?: &'a str,
+    ) {
+        let src = is_http_url(src).then_some(src);
+
+        let placeholder = html! {
+            style { (FACADE_STYLE) }
+            a(href?: src) {
+                if let Some(poster) = poster {
+                    img(src: (poster), alt: "");
+                }
+                span { (label.unwrap_or("Load content")) }
+            }
+        }
+        .to_html();
+
+        iframe(
+            src?: src,
+            srcdoc: (&*placeholder),
+            title: (title),
+            allow?: allow,
+            sandbox: (sandbox.unwrap_or(DEFAULT_SANDBOX)),
+            loading: "lazy",
+            referrerpolicy: "strict-origin-when-cross-origin",
+            #attrs,
+        ) {}
+    }
+
+    /// A click-to-load YouTube video: a [`LazyIframe`] for the video `id`, using the video's thumbnail as the poster.
+    ///
+    /// The video is embedded from `youtube-nocookie.com`, which doesn't set cookies before the video plays, and starts
+    /// playing when the placeholder is clicked. Extra attributes are forwarded to the `<iframe>` element.
+    pub fn YouTube<'a>(id: &'a str, title: &'a str) {
+        let src = embed_url("https://www.youtube-nocookie.com/embed/", id, "?autoplay=1");
+        let poster = embed_url("https://i.ytimg.com/vi/", id, "/hqdefault.jpg");
+
+        @LazyIframe(
+            src: &src,
+            title: title,
+            label: Some("Play video"),
+            poster: Some(&poster),
+            allow: Some(YOUTUBE_ALLOW),
+            sandbox: None;
+            allowfullscreen,
+            #attrs
+        ) {}
+    }
+}
@@ -0,0 +1,287 @@
+//! Formatting of money and percentages without going through float `Display`.
+//!
+//! [`Money`] renders an amount held in minor units (cents) of a currency, and [`Percent`] renders a ratio as a
+//! percentage with a fixed number of decimals. Money is formatted with integer arithmetic only, and a percentage is
+//! rounded once to its decimals, so templates don't need ad-hoc `format!("{:.2}")` calls.
+//!
+//! Separators and the position of the currency symbol come from the [`NumberFormat`] in the render
+//! [`context`](crate::context). Without one, the format is picked from the language of the
+//! [`Locale`](crate::locale::Locale) in the context, and falls back to English conventions.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{context, html, ToHtml, locale::Locale, number::{Money, NumberFormat, Percent}};
+//!
+//! let page = html! {
+//!     p { (Money(123450, "USD")) " (" (Percent(0.123, 1)) " off)" }
+//! };
+//!
+//! assert_eq!(page.to_html(), "<p>$1,234.50 (12.3% off)</p>");
+//!
+//! assert_eq!(
+//!     context::provide(Locale::new("de-DE"), || html! { p { (Money(123450, "EUR")) } }.to_html()),
+//!     "<p>1.234,50\u{a0}€</p>"
+//! );
+//!
+//! let swiss = NumberFormat::new().with_group_separator(Some('\''));
+//! assert_eq!(
+//!     context::provide(swiss, || html! { p { (Money(-100000, "CHF")) } }.to_html()),
+//!     "<p>-CHF\u{a0}1&#39;000.00</p>"
+//! );
+//! ```
+use std::fmt;
+
+use crate::{RenderEscaped, context, locale::Locale, utils::escape_html_to};
+
+/// Currency codes with their symbol and number of minor unit digits.
+const CURRENCIES: &[(&str, Option<&str>, u32)] = &[
+    ("AUD", Some("A$"), 2),
+    ("BHD", None, 3),
+    ("BRL", Some("R$"), 2),
+    ("CAD", Some("CA$"), 2),
+    ("CLP", None, 0),
+    ("CNY", Some("CN¥"), 2),
+    ("EUR", Some("€"), 2),
+    ("GBP", Some("£"), 2),
+    ("ILS", Some("₪"), 2),
+    ("INR", Some("₹"), 2),
+    ("ISK", None, 0),
+    ("JPY", Some("¥"), 0),
+    ("KRW", Some("₩"), 0),
+    ("KWD", None, 3),
+    ("MXN", Some("MX$"), 2),
+    ("OMR", None, 3),
+    ("TND", None, 3),
+    ("USD", Some("$"), 2),
+    ("VND", Some("₫"), 0),
+];
+
+/// Languages writing `1.234,5` with the currency symbol after the amount.
+const COMMA_DOT_SUFFIX_LANGUAGES: &[&str] =
+    &["ca", "da", "de", "el", "es", "hr", "it", "ro", "sl", "sr"];
+
+/// Languages writing `1.234,5` with the currency symbol before the amount.
+const COMMA_DOT_PREFIX_LANGUAGES: &[&str] = &["id", "nl", "pt", "tr"];
+
+/// Languages writing `1 234,5` with the currency symbol after the amount.
+const COMMA_SPACE_SUFFIX_LANGUAGES: &[&str] = &[
+    "be", "bg", "cs", "et", "fi", "fr", "hu", "lt", "lv", "nb", "nn", "no", "pl", "ru", "sk", "sv",
+    "uk",
+];
+
+/// Separators and currency placement used by [`Money`] and [`Percent`]. See the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumberFormat {
+    decimal_separator: char,
+    group_separator: Option<char>,
+    currency_after: bool,
+}
+
+impl NumberFormat {
+    /// Creates the English format: `1,234.5` with the currency symbol before the amount.
+    pub const fn new() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: Some(','),
+            currency_after: false,
+        }
+    }
+
+    /// Returns the usual format for the [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag `lang`.
+    ///
+    /// Only the primary language subtag is looked at, so regional conventions (such as `de-CH`) need a format of their
+    /// own. Unknown languages use the English format.
+    pub fn for_lang(lang: &str) -> Self {
+        let language = lang
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let language = language.as_str();
+
+        let (decimal_separator, group_separator, currency_after) =
+            if COMMA_DOT_SUFFIX_LANGUAGES.contains(&language) {
+                (',', '.', true)
+            } else if COMMA_DOT_PREFIX_LANGUAGES.contains(&language) {
+                (',', '.', false)
+            } else if COMMA_SPACE_SUFFIX_LANGUAGES.contains(&language) {
+                (',', '\u{202f}', true)
+            } else {
+                return Self::new();
+            };
+
+        Self {
+            decimal_separator,
+            group_separator: Some(group_separator),
+            currency_after,
+        }
+    }
+
+    /// Returns the format in the render context, or the format of the [`Locale`] in the render context, or
+    /// [`NumberFormat::new()`] if neither has been provided.
+    pub fn current() -> Self {
+        if let Some(format) = context::get::<NumberFormat>() {
+            return *format;
+        }
+
+        context::get::<Locale>().map_or(Self::new(), |locale| Self::for_lang(locale.lang()))
+    }
+
+    /// Sets the character between the integer and fractional parts.
+    pub fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Sets the character between groups of thousands, or `None` to not group digits.
+    pub fn with_group_separator(mut self, separator: Option<char>) -> Self {
+        self.group_separator = separator;
+        self
+    }
+
+    /// Sets whether the currency symbol is written after the amount (`1,00 €`) instead of before it (`€1,00`).
+    pub fn with_currency_after(mut self, currency_after: bool) -> Self {
+        self.currency_after = currency_after;
+        self
+    }
+
+    /// Writes `value / 10^decimals` as a decimal number.
+    fn write_fixed(
+        &self,
+        f: &mut (dyn fmt::Write + '_),
+        value: i128,
+        decimals: u32,
+    ) -> fmt::Result {
+        if value < 0 {
+            f.write_char('-')?;
+        }
+
+        let value = value.unsigned_abs();
+        let scale = 10u128.pow(decimals);
+
+        self.write_grouped(f, value / scale)?;
+
+        if decimals > 0 {
+            f.write_char(self.decimal_separator)?;
+            write!(f, "{:0width$}", value % scale, width = decimals as usize)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_grouped(&self, f: &mut (dyn fmt::Write + '_), value: u128) -> fmt::Result {
+        let digits = value.to_string();
+
+        let Some(separator) = self.group_separator else {
+            return f.write_str(&digits);
+        };
+
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index).is_multiple_of(3) {
+                f.write_char(separator)?;
+            }
+
+            f.write_char(digit)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An amount of money in the minor units of a currency, e.g. `Money(1999, "USD")` for $19.99.
+///
+/// The currency is an [ISO 4217](https://www.iso.org/iso-4217-currency-codes.html) code. Common currencies are written
+/// with their symbol and number of minor digits (`Money(500, "JPY")` is ¥500); others are written with their code
+/// and two minor digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money<'a>(pub i64, pub &'a str);
+
+impl Money<'_> {
+    fn write(&self, f: &mut (dyn fmt::Write + '_), format: &NumberFormat) -> fmt::Result {
+        let Money(amount, code) = *self;
+
+        let (symbol, decimals) = CURRENCIES
+            .iter()
+            .find(|(currency, _, _)| currency.eq_ignore_ascii_case(code))
+            .map_or((None, 2), |(_, symbol, decimals)| (*symbol, *decimals));
+
+        if format.currency_after {
+            format.write_fixed(f, i128::from(amount), decimals)?;
+            f.write_char('\u{a0}')?;
+            f.write_str(symbol.unwrap_or(code))
+        } else {
+            if amount < 0 {
+                f.write_char('-')?;
+            }
+
+            match symbol {
+                Some(symbol) => f.write_str(symbol)?,
+                None => {
+                    f.write_str(code)?;
+                    f.write_char('\u{a0}')?;
+                }
+            }
+
+            format.write_fixed(f, i128::from(amount).abs(), decimals)
+        }
+    }
+}
+
+impl fmt::Display for Money<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &NumberFormat::current())
+    }
+}
+
+impl RenderEscaped for Money<'_> {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        let mut buffer = String::new();
+        self.write(&mut buffer, &NumberFormat::current())?;
+
+        escape_html_to(f, &buffer)
+    }
+}
+
+/// A ratio written as a percentage with a number of decimals, e.g. `Percent(0.123, 1)` for 12.3%.
+///
+/// The percentage is rounded half away from zero to the number of decimals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percent(pub f64, pub u8);
+
+impl Percent {
+    fn write(&self, f: &mut (dyn fmt::Write + '_), format: &NumberFormat) -> fmt::Result {
+        let Percent(ratio, decimals) = *self;
+        let decimals = u32::from(decimals.min(18));
+
+        if !ratio.is_finite() {
+            return write!(f, "{ratio}%");
+        }
+
+        let scaled = (ratio * 100.0 * 10f64.powi(decimals as i32)).round() as i128;
+
+        format.write_fixed(f, scaled, decimals)?;
+        f.write_char('%')
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &NumberFormat::current())
+    }
+}
+
+impl RenderEscaped for Percent {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        let mut buffer = String::new();
+        self.write(&mut buffer, &NumberFormat::current())?;
+
+        escape_html_to(f, &buffer)
+    }
+}
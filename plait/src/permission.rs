@@ -0,0 +1,97 @@
+//! Permission checks for authorization-gated markup.
+//!
+//! Wrap a [`PermissionChecker`] in [`Permissions`] and [`provide`](crate::context::provide) it in the render context
+//! while the page is rendered. Templates then check permissions with `@can`, without a policy object being threaded
+//! through every component as a prop:
+//!
+//! ```
+//! use plait::{component, context, html, ToHtml, permission::Permissions};
+//!
+//! component! {
+//!     pub fn PostActions(id: u32) {
+//!         @can("posts.edit") {
+//!             a(href: (format!("/posts/{id}/edit"))) { "Edit" }
+//!         } @else @can("posts.report") {
+//!             button { "Report" }
+//!         } @else {
+//!             span { "Read only" }
+//!         }
+//!     }
+//! }
+//!
+//! let page = html! { @PostActions(id: 7) {} };
+//!
+//! let editor = Permissions::new(|permission: &str| permission == "posts.edit");
+//! assert_eq!(
+//!     context::provide(editor, || page.to_html()),
+//!     "<a href=\"/posts/7/edit\">Edit</a>"
+//! );
+//!
+//! // Without `Permissions` in the context every check fails
+//! assert_eq!(page.to_html(), "<span>Read only</span>");
+//! ```
+//!
+//! The permission can be any expression implementing `AsRef<str>`. [`can`] performs the same check from Rust code.
+use std::{collections::HashSet, fmt, rc::Rc};
+
+use crate::context;
+
+/// Decides whether the current user has a permission.
+pub trait PermissionChecker {
+    /// Returns `true` if `permission` is granted.
+    fn can(&self, permission: &str) -> bool;
+}
+
+impl<F> PermissionChecker for F
+where
+    F: Fn(&str) -> bool,
+{
+    fn can(&self, permission: &str) -> bool {
+        self(permission)
+    }
+}
+
+impl PermissionChecker for HashSet<String> {
+    fn can(&self, permission: &str) -> bool {
+        self.contains(permission)
+    }
+}
+
+impl PermissionChecker for HashSet<&'static str> {
+    fn can(&self, permission: &str) -> bool {
+        self.contains(permission)
+    }
+}
+
+/// The [`PermissionChecker`] of the current render, provided with [`provide`](crate::context::provide).
+#[derive(Clone)]
+pub struct Permissions {
+    checker: Rc<dyn PermissionChecker>,
+}
+
+impl Permissions {
+    /// Creates permissions checked by `checker`.
+    pub fn new(checker: impl PermissionChecker + 'static) -> Self {
+        Self {
+            checker: Rc::new(checker),
+        }
+    }
+
+    /// Returns `true` if `permission` is granted.
+    pub fn can(&self, permission: &str) -> bool {
+        self.checker.can(permission)
+    }
+}
+
+impl fmt::Debug for Permissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Permissions").finish_non_exhaustive()
+    }
+}
+
+/// Returns `true` if `permission` is granted by the [`Permissions`] in the render context.
+///
+/// Permissions are denied when no [`Permissions`] have been provided.
+pub fn can(permission: &(impl AsRef<str> + ?Sized)) -> bool {
+    context::get::<Permissions>().is_some_and(|permissions| permissions.can(permission.as_ref()))
+}
@@ -0,0 +1,472 @@
+//! Post-processing of whole rendered documents.
+//!
+//! A [`RenderPipeline`] chains [`PostProcessor`]s, each transforming the output of the previous one. Processors don't
+//! work on the HTML string directly: they receive it as a stream of [`Token`]s (text, start and end tags, comments,
+//! ...) and write their output, so they can rewrite a tag or drop some whitespace without matching markup with
+//! regular expressions. Writing the [`source()`](Token::source) of a token copies it unchanged.
+//!
+//! Closures taking [`Tokens`] and the output `String` implement [`PostProcessor`] too. Built-in processors:
+//!
+//! - [`Minify`] removes insignificant whitespace and comments.
+//! - [`AttributeQuotes`] rewrites attribute values with single quotes or without quotes where that is safe.
+//! - [`VoidElementSlash`] closes void elements with a trailing slash (`<br />`).
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, pipeline::{PostProcessor, RenderPipeline, Token, Tokens}};
+//!
+//! /// Adds a `nonce` attribute to every `script` element.
+//! struct ScriptNonce(String);
+//!
+//! impl PostProcessor for ScriptNonce {
+//!     fn process(&self, tokens: Tokens<'_>, out: &mut String) {
+//!         for token in tokens {
+//!             match token {
+//!                 Token::StartTag { name, source, .. } if name.eq_ignore_ascii_case("script") => {
+//!                     let (start, rest) = source.split_at(1 + name.len());
+//!                     out.push_str(start);
+//!                     out.push_str(&format!(" nonce=\"{}\"", self.0));
+//!                     out.push_str(rest);
+//!                 }
+//!                 token => out.push_str(token.source()),
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let pipeline = RenderPipeline::new()
+//!     .then(ScriptNonce("r4nd0m".to_owned()))
+//!     .then(|tokens: Tokens<'_>, out: &mut String| {
+//!         for token in tokens {
+//!             out.push_str(&token.source().replace("/static/", "https://cdn.example.com/"));
+//!         }
+//!     });
+//!
+//! let page = html! {
+//!     script(src: "/static/app.js") {}
+//! };
+//!
+//! assert_eq!(
+//!     pipeline.render(&page),
+//!     "<script nonce=\"r4nd0m\" src=\"https://cdn.example.com/app.js\"></script>"
+//! );
+//! ```
+use std::fmt;
+
+use crate::{
+    Html, ToHtml, spec,
+    text::is_block_element,
+    tokenizer::{Tokenizer, attribute_entries},
+};
+
+pub use crate::tokenizer::Token;
+
+/// The [`Token`]s of a rendered document, in order.
+pub struct Tokens<'a>(Tokenizer<'a>);
+
+impl<'a> Tokens<'a> {
+    /// Splits `html` into tokens.
+    pub fn new(html: &'a str) -> Self {
+        Self(Tokenizer::new(html))
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+impl fmt::Debug for Tokens<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tokens").finish_non_exhaustive()
+    }
+}
+
+/// A transformation of a whole rendered document, applied by a [`RenderPipeline`].
+pub trait PostProcessor {
+    /// Writes the processed document to `out`, reading the document from `tokens`.
+    fn process(&self, tokens: Tokens<'_>, out: &mut String);
+}
+
+impl<F> PostProcessor for F
+where
+    F: Fn(Tokens<'_>, &mut String),
+{
+    fn process(&self, tokens: Tokens<'_>, out: &mut String) {
+        self(tokens, out)
+    }
+}
+
+/// A chain of [`PostProcessor`]s applied to rendered documents. See the [module documentation](self).
+///
+/// Pipelines are built once (e.g. at startup) and can be shared between threads.
+#[derive(Default)]
+pub struct RenderPipeline {
+    processors: Vec<Box<dyn PostProcessor + Send + Sync>>,
+}
+
+impl RenderPipeline {
+    /// Creates a pipeline without processors, which leaves documents unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `processor` to the pipeline. It runs on the output of the processors added before it.
+    pub fn then(mut self, processor: impl PostProcessor + Send + Sync + 'static) -> Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    /// Runs the processors on rendered `html`.
+    pub fn process(&self, html: &str) -> Html {
+        let mut html = html.to_owned();
+
+        for processor in &self.processors {
+            let mut out = String::with_capacity(html.len());
+            processor.process(Tokens::new(&html), &mut out);
+            html = out;
+        }
+
+        Html::new_unchecked(html)
+    }
+
+    /// Renders `content` and runs the processors on the output.
+    pub fn render(&self, content: &impl ToHtml) -> Html {
+        self.process(&content.to_html())
+    }
+}
+
+impl fmt::Debug for RenderPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderPipeline")
+            .field("processors", &self.processors.len())
+            .finish()
+    }
+}
+
+/// A conservative HTML minifier.
+///
+/// - Runs of whitespace in text collapse to a single space, and whitespace next to the tags of block-level elements
+///   (`div`, `p`, `li`, `head`, `script`, ...) is removed. Whitespace between inline elements is kept, since it is
+///   rendered.
+/// - Comments are removed, except conditional comments, `googleoff`/`googleon` directives and comments starting with
+///   `!` (e.g. `<!--! license -->`), which carry meaning.
+/// - The content of `pre`, `textarea`, `script` and `style` elements and the tags themselves are left unchanged.
+///
+/// # Example
+///
+/// ```
+/// use plait::pipeline::{Minify, RenderPipeline};
+///
+/// let pipeline = RenderPipeline::new().then(Minify);
+///
+/// assert_eq!(
+///     pipeline.process(
+///         "<ul>\n  <li>One   <b>two</b> <i>three</i></li>\n  <!-- TODO -->\n</ul>\n<pre>  keep\n  this</pre>"
+///     ),
+///     "<ul><li>One <b>two</b> <i>three</i></li></ul><pre>  keep\n  this</pre>"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Minify;
+
+impl PostProcessor for Minify {
+    fn process(&self, tokens: Tokens<'_>, out: &mut String) {
+        let mut preformatted = 0usize;
+        let mut text = String::new();
+        let mut after_boundary = true;
+
+        for token in tokens {
+            match token {
+                Token::Text(content) if preformatted == 0 => {
+                    text.push_str(content);
+                    continue;
+                }
+                Token::Other(source) if is_removable_comment(source) => continue,
+                _ => {}
+            }
+
+            let boundary = is_boundary(&token);
+            push_minified_text(out, &text, after_boundary, boundary);
+            text.clear();
+
+            match token {
+                Token::StartTag {
+                    name,
+                    self_closing: false,
+                    ..
+                } if is_preformatted(name) => preformatted += 1,
+                Token::EndTag { name, .. } if is_preformatted(name) => {
+                    preformatted = preformatted.saturating_sub(1);
+                }
+                _ => {}
+            }
+
+            out.push_str(token.source());
+            after_boundary = boundary;
+        }
+
+        push_minified_text(out, &text, after_boundary, true);
+    }
+}
+
+fn is_preformatted(name: &str) -> bool {
+    name.eq_ignore_ascii_case("pre") || name.eq_ignore_ascii_case("textarea")
+}
+
+/// Returns `true` if whitespace next to `token` is never rendered.
+fn is_boundary(token: &Token<'_>) -> bool {
+    match token {
+        Token::StartTag { name, .. } | Token::EndTag { name, .. } => {
+            let name = name.to_ascii_lowercase();
+
+            is_block_element(&name)
+                || matches!(
+                    name.as_str(),
+                    "base"
+                        | "body"
+                        | "col"
+                        | "colgroup"
+                        | "dialog"
+                        | "head"
+                        | "hgroup"
+                        | "html"
+                        | "legend"
+                        | "link"
+                        | "menu"
+                        | "meta"
+                        | "script"
+                        | "search"
+                        | "style"
+                        | "tbody"
+                        | "template"
+                        | "tfoot"
+                        | "thead"
+                        | "title"
+                )
+        }
+        Token::Other(source) => !source.starts_with("<!--"),
+        Token::Text(_) | Token::RawText(_) => false,
+    }
+}
+
+/// Returns `true` if the markup declaration `source` is a comment without meaning to browsers or crawlers.
+fn is_removable_comment(source: &str) -> bool {
+    let Some(text) = source.strip_prefix("<!--") else {
+        return false;
+    };
+
+    !(text.starts_with("[if")
+        || text.starts_with("<![endif]")
+        || text.starts_with('!')
+        || text.starts_with("googleoff:")
+        || text.starts_with("googleon:"))
+}
+
+/// Writes `text` with runs of whitespace collapsed, removing leading and trailing whitespace next to boundaries.
+fn push_minified_text(out: &mut String, text: &str, trim_start: bool, trim_end: bool) {
+    let is_whitespace = |c: char| c.is_ascii_whitespace();
+
+    if text.trim_start_matches(is_whitespace).is_empty() {
+        if !text.is_empty() && !trim_start && !trim_end {
+            out.push(' ');
+        }
+
+        return;
+    }
+
+    if text.starts_with(is_whitespace) && !trim_start {
+        out.push(' ');
+    }
+
+    let mut has_words = false;
+
+    for word in text.split(is_whitespace).filter(|word| !word.is_empty()) {
+        if has_words {
+            out.push(' ');
+        }
+
+        out.push_str(word);
+        has_words = true;
+    }
+
+    if text.ends_with(is_whitespace) && !trim_end {
+        out.push(' ');
+    }
+}
+
+/// How [`AttributeQuotes`] writes attribute values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuoteStyle {
+    /// `name="value"`, as [`html!`](crate::html) renders them.
+    #[default]
+    Double,
+    /// `name='value'`.
+    Single,
+    /// `name=value` for values that are valid without quotes, `name` for empty values, and quotes otherwise (the
+    /// quote character that doesn't appear in the value, or double quotes).
+    Minimal,
+}
+
+/// Rewrites the quotes around the attribute values of every start tag in a [`QuoteStyle`].
+///
+/// Values are written as they were, except for the new quote character, which is replaced by its character reference
+/// (`&quot;` or `&#39;`) so it can't end the value. [`html!`](crate::html) escapes both quote characters in escaped
+/// values, so this only changes raw values. An empty value and no value are equivalent in HTML, so
+/// [`QuoteStyle::Minimal`] drops empty values.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, ToHtml, pipeline::{AttributeQuotes, QuoteStyle, RenderPipeline}};
+///
+/// let page = html! {
+///     a(href: "/about", class: "nav link", title: "") { "About" }
+///     img(src: "/logo.png");
+/// };
+///
+/// let minimal = RenderPipeline::new().then(AttributeQuotes(QuoteStyle::Minimal));
+/// assert_eq!(
+///     minimal.render(&page),
+///     "<a href=/about class=\"nav link\" title>About</a><img src=/logo.png>"
+/// );
+///
+/// let single = RenderPipeline::new().then(AttributeQuotes(QuoteStyle::Single));
+/// assert_eq!(
+///     single.render(&page),
+///     "<a href='/about' class='nav link' title=''>About</a><img src='/logo.png'>"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AttributeQuotes(pub QuoteStyle);
+
+impl PostProcessor for AttributeQuotes {
+    fn process(&self, tokens: Tokens<'_>, out: &mut String) {
+        for token in tokens {
+            match token {
+                Token::StartTag {
+                    name,
+                    source,
+                    self_closing,
+                } => self.push_start_tag(out, name, source, self_closing),
+                token => out.push_str(token.source()),
+            }
+        }
+    }
+}
+
+impl AttributeQuotes {
+    fn push_start_tag(&self, out: &mut String, name: &str, source: &str, self_closing: bool) {
+        out.push('<');
+        out.push_str(name);
+
+        let mut unquoted = false;
+
+        for (attribute, value) in attribute_entries(source) {
+            out.push(' ');
+            out.push_str(attribute);
+            unquoted = false;
+
+            let Some(value) = value else {
+                continue;
+            };
+
+            let quote = match self.0 {
+                QuoteStyle::Double => '"',
+                QuoteStyle::Single => '\'',
+                QuoteStyle::Minimal if value.is_empty() => continue,
+                QuoteStyle::Minimal if is_valid_unquoted(value) => {
+                    out.push('=');
+                    out.push_str(value);
+                    unquoted = true;
+                    continue;
+                }
+                QuoteStyle::Minimal if value.contains('"') && !value.contains('\'') => '\'',
+                QuoteStyle::Minimal => '"',
+            };
+
+            let reference = if quote == '"' { "&quot;" } else { "&#39;" };
+
+            out.push('=');
+            out.push(quote);
+            for part in value.split_inclusive(quote) {
+                match part.strip_suffix(quote) {
+                    Some(part) => {
+                        out.push_str(part);
+                        out.push_str(reference);
+                    }
+                    None => out.push_str(part),
+                }
+            }
+            out.push(quote);
+        }
+
+        if self_closing {
+            // `<img src=a.png/>` would end the value with `/`
+            if unquoted {
+                out.push(' ');
+            }
+
+            out.push('/');
+        }
+
+        out.push('>');
+    }
+}
+
+/// Returns `true` if `value` can be written as an unquoted attribute value. Values ending with `/` are quoted, so the
+/// tag can't be mistaken for a self-closing tag.
+fn is_valid_unquoted(value: &str) -> bool {
+    !value.is_empty()
+        && !value.ends_with('/')
+        && !value
+            .chars()
+            .any(|c| c.is_ascii_whitespace() || matches!(c, '"' | '\'' | '=' | '<' | '>' | '`'))
+}
+
+/// Closes the start tags of void elements with a trailing slash (`<br />`), for downstream tools that expect it.
+///
+/// Only void elements (`br`, `img`, `input`, ...) are changed, and the rest of the document is left as it was, so the
+/// output is still HTML rather than XHTML. Tags that already end with `/>`, such as SVG elements, are unchanged.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, ToHtml, pipeline::{RenderPipeline, VoidElementSlash}};
+///
+/// let page = html! {
+///     p { "Line" br; }
+///     img(src: "/logo.png", alt: "Logo");
+/// };
+///
+/// let pipeline = RenderPipeline::new().then(VoidElementSlash);
+///
+/// assert_eq!(
+///     pipeline.render(&page),
+///     "<p>Line<br /></p><img src=\"/logo.png\" alt=\"Logo\" />"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VoidElementSlash;
+
+impl PostProcessor for VoidElementSlash {
+    fn process(&self, tokens: Tokens<'_>, out: &mut String) {
+        for token in tokens {
+            match token {
+                Token::StartTag {
+                    name,
+                    source,
+                    self_closing: false,
+                } if spec::is_void_element(name) && source.ends_with('>') => {
+                    out.push_str(source[..source.len() - 1].trim_end());
+                    out.push_str(" />");
+                }
+                token => out.push_str(token.source()),
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Reusable output buffers for high-throughput servers.
+//!
+//! Every [`to_html()`](crate::ToHtml::to_html) call allocates a fresh `String` and grows it as the page is written. A
+//! [`BufferPool`] keeps the buffers of earlier renders around, so a render starts from a buffer that already has the
+//! capacity of a typical page:
+//!
+//! - [`BufferPool::render`] renders into a pooled buffer and returns an [`Html`] with a single allocation of the exact
+//!   size of the page.
+//! - [`BufferPool::render_with`] lends the rendered page to a closure as a `&str` without copying it, e.g. to write it
+//!   to a socket.
+//! - [`BufferPool::take`] hands out a [`PooledBuffer`] directly; it goes back to the pool when it is dropped.
+//!
+//! [`global()`] is a pool shared by all threads and [`with_local()`] gives access to a pool owned by the current
+//! thread, which avoids contention between worker threads. [`BufferPool::stats`] reports how often a pooled buffer
+//! could be reused.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, pool};
+//!
+//! let pool = pool::global();
+//!
+//! for name in ["Alice", "Bob"] {
+//!     let page = pool.render(&html! { p { "Hello, " (name) } });
+//!     assert!(page.starts_with("<p>Hello, "));
+//! }
+//!
+//! let length = pool.render_with(&html! { p { "Hello" } }, |page| page.len());
+//! assert_eq!(length, 12);
+//!
+//! assert!(pool.stats().hits >= 2);
+//! ```
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::{Html, RenderEscaped};
+
+/// Default maximum number of idle buffers kept by a pool.
+pub const DEFAULT_MAX_BUFFERS: usize = 64;
+
+/// Default maximum capacity, in bytes, of a buffer kept by a pool.
+pub const DEFAULT_MAX_CAPACITY: usize = 1024 * 1024;
+
+static GLOBAL: BufferPool = BufferPool::new();
+
+thread_local! {
+    static LOCAL: BufferPool = const { BufferPool::new() };
+}
+
+/// Returns the buffer pool shared by all threads.
+pub fn global() -> &'static BufferPool {
+    &GLOBAL
+}
+
+/// Calls `f` with the buffer pool of the current thread.
+pub fn with_local<R>(f: impl FnOnce(&BufferPool) -> R) -> R {
+    LOCAL.with(f)
+}
+
+/// A pool of reusable `String` buffers. See the [module documentation](self).
+#[derive(Debug)]
+pub struct BufferPool {
+    buffers: Mutex<Vec<String>>,
+    max_buffers: usize,
+    max_capacity: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    discarded: AtomicU64,
+}
+
+impl BufferPool {
+    /// Creates an empty pool that keeps up to [`DEFAULT_MAX_BUFFERS`] buffers of up to [`DEFAULT_MAX_CAPACITY`] bytes.
+    pub const fn new() -> Self {
+        Self::with_limits(DEFAULT_MAX_BUFFERS, DEFAULT_MAX_CAPACITY)
+    }
+
+    /// Creates an empty pool that keeps up to `max_buffers` idle buffers.
+    ///
+    /// Buffers that have grown beyond `max_capacity` bytes are dropped instead of being returned to the pool, so a
+    /// single unusually large page doesn't pin its memory for the lifetime of the pool.
+    pub const fn with_limits(max_buffers: usize, max_capacity: usize) -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+            max_buffers,
+            max_capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            discarded: AtomicU64::new(0),
+        }
+    }
+
+    /// Takes an empty buffer from the pool, or allocates a new one if the pool is empty.
+    pub fn take(&self) -> PooledBuffer<'_> {
+        let buffer = self.lock().pop();
+
+        let buffer = match buffer {
+            Some(buffer) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                buffer
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                String::new()
+            }
+        };
+
+        PooledBuffer { buffer, pool: self }
+    }
+
+    /// Renders `value` into a pooled buffer and returns a copy of the output.
+    pub fn render(&self, value: &(impl RenderEscaped + ?Sized)) -> Html {
+        self.render_with(value, |html| Html::new_unchecked(html.to_owned()))
+    }
+
+    /// Renders `value` into a pooled buffer and calls `f` with the output.
+    pub fn render_with<R>(
+        &self,
+        value: &(impl RenderEscaped + ?Sized),
+        f: impl FnOnce(&str) -> R,
+    ) -> R {
+        let mut buffer = self.take();
+
+        crate::scope::enter(|| value.render_escaped(&mut *buffer)).unwrap();
+
+        f(&buffer)
+    }
+
+    /// Returns the usage statistics of the pool.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            discarded: self.discarded.load(Ordering::Relaxed),
+            idle: self.lock().len(),
+        }
+    }
+
+    /// Drops all idle buffers and resets the statistics.
+    pub fn clear(&self) {
+        self.lock().clear();
+
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.discarded.store(0, Ordering::Relaxed);
+    }
+
+    fn give_back(&self, mut buffer: String) {
+        if buffer.capacity() == 0 {
+            return;
+        }
+
+        if buffer.capacity() <= self.max_capacity {
+            let mut buffers = self.lock();
+
+            if buffers.len() < self.max_buffers {
+                buffer.clear();
+                buffers.push(buffer);
+                return;
+            }
+        }
+
+        self.discarded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        self.buffers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Usage statistics of a [`BufferPool`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Number of times a buffer was reused from the pool.
+    pub hits: u64,
+    /// Number of times the pool was empty and a new buffer was allocated.
+    pub misses: u64,
+    /// Number of buffers that were dropped instead of being returned, because the pool was full or they were too large.
+    pub discarded: u64,
+    /// Number of idle buffers currently in the pool.
+    pub idle: usize,
+}
+
+impl PoolStats {
+    /// Returns the fraction of [`take()`](BufferPool::take) calls that reused a buffer, between `0.0` and `1.0`.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// A buffer taken from a [`BufferPool`], returned to the pool when it is dropped.
+///
+/// The buffer dereferences to [`String`] and is empty when it is taken.
+pub struct PooledBuffer<'a> {
+    buffer: String,
+    pool: &'a BufferPool,
+}
+
+impl PooledBuffer<'_> {
+    /// Takes the buffer out of the pool for good, e.g. to pass its contents on without copying them.
+    pub fn into_string(mut self) -> String {
+        std::mem::take(&mut self.buffer)
+    }
+}
+
+impl Deref for PooledBuffer<'_> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl DerefMut for PooledBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl fmt::Debug for PooledBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PooledBuffer").field(&self.buffer).finish()
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        let buffer = std::mem::take(&mut self.buffer);
+        self.pool.give_back(buffer);
+    }
+}
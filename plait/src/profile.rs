@@ -0,0 +1,148 @@
+//! Development-time profiling of component renders.
+//!
+//! When the `profile` feature is enabled, every component call made from an [`html!`](crate::html) template is timed
+//! and its output size is measured. Records are collected per thread and can be drained with [`take_records`] or
+//! rendered directly into the page as a collapsible overlay with [`overlay`].
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{component, html, ToHtml};
+//!
+//! component! {
+//!     pub fn Greeting(name: &str) {
+//!         p { "Hello, " (name) }
+//!     }
+//! }
+//!
+//! let page = html! {
+//!     body {
+//!         @Greeting(name: "World") {}
+//!         (plait::profile::overlay())
+//!     }
+//! };
+//!
+//! let html = page.to_html();
+//! assert!(html.contains("<details id=\"plait-profile\""));
+//! assert!(html.contains("Greeting"));
+//! ```
+//!
+//! The overlay drains the records collected so far on the current thread, so it should be placed after all the
+//! components it is meant to report on (typically at the end of `body`).
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{Html, ToHtml, component::component_name, html};
+
+thread_local! {
+    static RECORDS: RefCell<Vec<RenderRecord>> = const { RefCell::new(Vec::new()) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Timing and size information for a single component render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderRecord {
+    /// Name of the component type (without module path or generic parameters).
+    pub component: &'static str,
+    /// Nesting depth of the render (`0` for components called directly from a top-level template).
+    pub depth: usize,
+    /// Wall-clock time spent rendering the component, including nested components.
+    pub duration: Duration,
+    /// Number of bytes written by the component, including nested components.
+    pub bytes: usize,
+}
+
+/// Drains and returns all render records collected on the current thread, in call order.
+pub fn take_records() -> Vec<RenderRecord> {
+    RECORDS.with(|records| std::mem::take(&mut *records.borrow_mut()))
+}
+
+/// Drains the render records collected on the current thread and renders them as a collapsible HTML overlay.
+///
+/// The overlay is a fixed-position `<details id="plait-profile">` element listing each component render with its
+/// time and output size, indented by nesting depth.
+pub fn overlay() -> Html {
+    let records = take_records();
+    let total: Duration = records
+        .iter()
+        .filter(|record| record.depth == 0)
+        .map(|record| record.duration)
+        .sum();
+
+    html! {
+        details(
+            id: "plait-profile",
+            style: "position:fixed;right:0;bottom:0;z-index:2147483647;max-height:50vh;overflow:auto;\
+                    padding:4px 8px;background:#fff;color:#000;border:1px solid #888;font:12px monospace"
+        ) {
+            summary {
+                "plait: " (records.len()) " component renders in " (total.as_micros()) " µs"
+            }
+            table {
+                thead {
+                    tr { th { "Component" } th { "Time (µs)" } th { "Bytes" } }
+                }
+                tbody {
+                    for record in records.iter() {
+                        tr {
+                            td(style: #(format!("padding-left:{}em", record.depth))) { (record.component) }
+                            td { (record.duration.as_micros()) }
+                            td { (record.bytes) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    .to_html()
+}
+
+/// Runs `render` against `f`, recording the time taken and the bytes written under the name `type_name`.
+pub(crate) fn record(
+    type_name: &'static str,
+    f: &mut (dyn fmt::Write + '_),
+    render: impl FnOnce(&mut (dyn fmt::Write + '_)) -> fmt::Result,
+) -> fmt::Result {
+    let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+    let index = RECORDS.with(|records| {
+        let mut records = records.borrow_mut();
+        records.push(RenderRecord {
+            component: component_name(type_name),
+            depth,
+            duration: Duration::ZERO,
+            bytes: 0,
+        });
+        records.len() - 1
+    });
+
+    let mut writer = CountingWriter { inner: f, bytes: 0 };
+    let start = Instant::now();
+    let result = render(&mut writer);
+    let duration = start.elapsed();
+
+    DEPTH.with(|d| d.set(depth));
+    RECORDS.with(|records| {
+        // Records may have been drained mid-render (e.g. by an overlay inside a component)
+        if let Some(record) = records.borrow_mut().get_mut(index) {
+            record.duration = duration;
+            record.bytes = writer.bytes;
+        }
+    });
+
+    result
+}
+
+struct CountingWriter<'a, 'b> {
+    inner: &'a mut (dyn fmt::Write + 'b),
+    bytes: usize,
+}
+
+impl fmt::Write for CountingWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.bytes += s.len();
+        self.inner.write_str(s)
+    }
+}
@@ -1,4 +1,11 @@
+mod display;
 mod escaped;
+mod iter;
 mod raw;
 
-pub use self::{escaped::RenderEscaped, raw::RenderRaw};
+pub use self::{
+    display::{Escaped, Unescaped},
+    escaped::RenderEscaped,
+    iter::RenderIter,
+    raw::RenderRaw,
+};
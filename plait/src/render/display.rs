@@ -0,0 +1,122 @@
+use std::fmt::{self, Write};
+
+use crate::{RenderEscaped, RenderRaw, utils::escape_html_to};
+
+/// Renders a [`Display`](fmt::Display) value as HTML-escaped text.
+///
+/// The output of `T`'s [`Display`](fmt::Display) implementation is escaped while it is written, without an
+/// intermediate `String`. Both `(expr)` and `#(expr)` render it escaped. This is the easiest way to implement
+/// [`RenderEscaped`] for a type that already implements [`Display`](fmt::Display).
+///
+/// # Example
+///
+/// ```
+/// use std::fmt;
+///
+/// use plait::{html, Escaped, RenderEscaped, ToHtml};
+///
+/// struct Version(u32, u32);
+///
+/// impl fmt::Display for Version {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "v{}.{} <beta>", self.0, self.1)
+///     }
+/// }
+///
+/// impl RenderEscaped for Version {
+///     fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+///         Escaped(self).render_escaped(f)
+///     }
+/// }
+///
+/// let version = Version(1, 2);
+/// let frag = html! { span(title: (Escaped(&version))) { (version) } };
+///
+/// assert_eq!(
+///     frag.to_html(),
+///     "<span title=\"v1.2 &lt;beta&gt;\">v1.2 &lt;beta&gt;</span>"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Escaped<T>(pub T);
+
+/// Renders a [`Display`](fmt::Display) value as-is, without escaping.
+///
+/// Use this for types whose [`Display`](fmt::Display) output is trusted HTML. Both `(expr)` and `#(expr)` write it
+/// unchanged, so never wrap values that may contain user input.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, ToHtml, Unescaped};
+///
+/// let icon = "<svg><use href=\"#star\"/></svg>";
+/// let frag = html! { span { (Unescaped(icon)) } };
+///
+/// assert_eq!(frag.to_html(), "<span><svg><use href=\"#star\"/></svg></span>");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Unescaped<T>(pub T);
+
+/// A writer escaping everything written through it.
+struct EscapingWriter<'a, 'b>(&'a mut (dyn fmt::Write + 'b));
+
+impl Write for EscapingWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        escape_html_to(self.0, s)
+    }
+}
+
+impl<T> RenderEscaped for Escaped<T>
+where
+    T: fmt::Display,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        write!(EscapingWriter(f), "{}", self.0)
+    }
+}
+
+impl<T> RenderRaw for Escaped<T>
+where
+    T: fmt::Display,
+{
+    fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.render_escaped(f)
+    }
+}
+
+impl<T> fmt::Display for Escaped<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_escaped(f)
+    }
+}
+
+impl<T> RenderEscaped for Unescaped<T>
+where
+    T: fmt::Display,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<T> RenderRaw for Unescaped<T>
+where
+    T: fmt::Display,
+{
+    fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.render_escaped(f)
+    }
+}
+
+impl<T> fmt::Display for Unescaped<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
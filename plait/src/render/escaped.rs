@@ -16,13 +16,29 @@ use crate::utils::escape_html_to;
 /// | `Option<T: RenderEscaped>`                                 | Renders inner value, or nothing for `None` |
 /// | `Cow<'_, T: RenderEscaped>`                                | Delegates to inner value                   |
 /// | Integer types (`u8`–`u128`, `i8`–`i128`, `usize`, `isize`) | Formatted via [`itoa`]                     |
-/// | Float types (`f32`, `f64`)                                 | Formatted via [`ryu`]                      |
+/// | Float types (`f32`, `f64`)                                 | Plain decimal, formatted via [`ryu`]       |
 /// | [`Html`](crate::Html)                                      | Written as-is (already escaped)            |
 /// | [`HtmlFragment`](crate::HtmlFragment)                      | Renders the fragment                       |
 /// | `&T` where `T: RenderEscaped`                              | Delegates to inner value                   |
+/// | [`Escaped<T: Display>`](crate::Escaped)                    | `Display` output, HTML-escaped             |
+/// | [`Unescaped<T: Display>`](crate::Unescaped)                | `Display` output, written as-is            |
+/// | `serde_json::Value` (`serde_json` feature)                 | Strings escaped, `null` renders nothing    |
+/// | `maud::PreEscaped<T>` (`maud` feature)                     | Written as-is (already escaped)            |
+/// | [`SafeUrl<T: Url>`](crate::url_policy::SafeUrl)            | The URL, HTML-escaped                      |
+///
+/// For your own types, the quickest implementation is usually to delegate to [`Escaped`](crate::Escaped), which
+/// escapes the type's [`Display`](fmt::Display) output.
 pub trait RenderEscaped {
     /// Writes the HTML-escaped representation of `self` into `f`.
     fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result;
+
+    #[doc(hidden)]
+    /// Returns `true` if `self` is a [`SafeUrl`](crate::url_policy::SafeUrl), whose URL isn't checked against the
+    /// [`UrlRules`](crate::url_policy::UrlRules) in the render context.
+    #[inline]
+    fn is_safe_url(&self) -> bool {
+        false
+    }
 }
 
 impl<T> RenderEscaped for &T
@@ -33,6 +49,11 @@ where
     fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
         (**self).render_escaped(f)
     }
+
+    #[inline]
+    fn is_safe_url(&self) -> bool {
+        (**self).is_safe_url()
+    }
 }
 
 impl RenderEscaped for str {
@@ -67,6 +88,11 @@ where
             None => Ok(()),
         }
     }
+
+    #[inline]
+    fn is_safe_url(&self) -> bool {
+        self.as_ref().is_some_and(RenderEscaped::is_safe_url)
+    }
 }
 
 impl<'a, T> RenderEscaped for Cow<'a, T>
@@ -77,6 +103,11 @@ where
     fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
         self.as_ref().render_escaped(f)
     }
+
+    #[inline]
+    fn is_safe_url(&self) -> bool {
+        self.as_ref().is_safe_url()
+    }
 }
 
 macro_rules! impl_render_escaped_itoa {
@@ -119,8 +150,7 @@ macro_rules! impl_render_escaped_ryu {
         impl RenderEscaped for $ty {
             #[inline]
             fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
-                let mut buffer = ryu::Buffer::new();
-                f.write_str(buffer.format(*self))
+                crate::utils::write_float(f, *self)
             }
         }
 
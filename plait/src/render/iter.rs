@@ -0,0 +1,55 @@
+/// Trait for collections a component can iterate every time it renders.
+///
+/// Components render through `&self`, so a prop typed as a plain iterator could only be consumed once. Props typed as
+/// `impl RenderIter<Item = impl RenderEscaped>` instead accept any [`IntoIterator`] that is [`Clone`] - a reference to
+/// a collection (`&names`), or a lazy iterator adapter (`names.iter().map(..)`) - and produce a fresh iterator with
+/// [`render_iter()`](RenderIter::render_iter) without collecting the items into a `Vec`.
+///
+/// In a [`component!`](crate::component) body, `for item in items` over such a prop calls `render_iter()` for you.
+///
+/// # Example
+///
+/// ```
+/// use plait::{component, html, RenderEscaped, RenderIter, ToHtml};
+///
+/// component! {
+///     pub fn List(items: impl RenderIter<Item = impl RenderEscaped>) {
+///         ul {
+///             for item in items {
+///                 li { (item) }
+///             }
+///         }
+///     }
+/// }
+///
+/// let names = ["apples", "pears"];
+/// let list = html! {
+///     @List(items: names.iter().map(|name| name.to_uppercase())) {}
+/// };
+///
+/// assert_eq!(list.to_html(), "<ul><li>APPLES</li><li>PEARS</li></ul>");
+/// ```
+pub trait RenderIter {
+    /// The type of the items.
+    type Item;
+
+    /// The iterator over the items.
+    type Iter: Iterator<Item = Self::Item>;
+
+    /// Returns a new iterator over the items.
+    fn render_iter(&self) -> Self::Iter;
+}
+
+impl<I> RenderIter for I
+where
+    I: IntoIterator + Clone,
+{
+    type Item = I::Item;
+
+    type Iter = I::IntoIter;
+
+    #[inline]
+    fn render_iter(&self) -> Self::Iter {
+        self.clone().into_iter()
+    }
+}
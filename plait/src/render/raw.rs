@@ -9,7 +9,8 @@ use std::{borrow::Cow, fmt};
 ///
 /// The same types that implement [`RenderEscaped`](crate::RenderEscaped) also implement `RenderRaw`. For `&str` and
 /// `String`, the output is written verbatim (no escaping). Numeric and boolean types produce the same output as their
-/// escaped counterparts since they contain no HTML-special characters.
+/// escaped counterparts since they contain no HTML-special characters. [`Escaped`](crate::Escaped) is always escaped
+/// and [`Unescaped`](crate::Unescaped) never is, whichever syntax embeds them.
 pub trait RenderRaw {
     /// Writes the raw (unescaped) representation of `self` into `f`.
     fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result;
@@ -109,8 +110,7 @@ macro_rules! impl_render_raw_ryu {
         impl RenderRaw for $ty {
             #[inline]
             fn render_raw(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
-                let mut buffer = ryu::Buffer::new();
-                f.write_str(buffer.format(*self))
+                crate::utils::write_float(f, *self)
             }
         }
 
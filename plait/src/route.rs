@@ -0,0 +1,111 @@
+use std::fmt;
+
+use crate::{RenderEscaped, utils::escape_html_to};
+
+/// Trait for typed route values that resolve to a URL.
+///
+/// Implement this trait for your route types (typed router paths, or a plain enum of your application's routes) to
+/// resolve them to URLs in one place. Wrap a route in [`Href`] to use it as an attribute value (e.g. `href:`,
+/// `action:` or `src:`), so that changing a route is type-checked into every template that links to it.
+///
+/// The resolved URL is HTML-escaped when rendered, so implementors only need to produce a valid URL (e.g. with the
+/// [`url!`](crate::url) macro).
+///
+/// # Built-in implementations
+///
+/// | Type                       | Behavior                       |
+/// |----------------------------|--------------------------------|
+/// | `&str`, `String`           | The string is used as the URL  |
+/// | `&T` where `T: RouteHref`  | Delegates to inner value       |
+///
+/// # Example
+///
+/// ```
+/// use std::fmt;
+///
+/// use plait::{html, url, Href, RouteHref, ToHtml};
+///
+/// enum Route {
+///     Home,
+///     User { id: u32 },
+/// }
+///
+/// impl RouteHref for Route {
+///     fn render_href(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+///         match self {
+///             Route::Home => f.write_str("/"),
+///             Route::User { id } => f.write_str(&url!("/users/{id}")),
+///         }
+///     }
+/// }
+///
+/// let frag = html! {
+///     a(href: (Href(Route::Home))) { "Home" }
+///     form(action: (Href(Route::User { id: 7 })), method: "post") {}
+/// };
+///
+/// assert_eq!(
+///     frag.to_html(),
+///     r#"<a href="/">Home</a><form action="/users/7" method="post"></form>"#
+/// );
+/// ```
+pub trait RouteHref {
+    /// Writes the (unescaped) URL of the route into `f`.
+    fn render_href(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result;
+}
+
+impl<T> RouteHref for &T
+where
+    T: RouteHref + ?Sized,
+{
+    fn render_href(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        (**self).render_href(f)
+    }
+}
+
+impl RouteHref for str {
+    fn render_href(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl RouteHref for String {
+    fn render_href(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+/// A wrapper that renders a [`RouteHref`] value as an HTML-escaped URL.
+///
+/// `Href<T>` implements [`RenderEscaped`] and [`Display`](std::fmt::Display), so it can be used as the value of any
+/// URL attribute. See [`RouteHref`] for an example.
+pub struct Href<T>(pub T);
+
+/// Escapes everything written through it before forwarding it to the inner writer.
+struct EscapingWriter<'a, 'b> {
+    inner: &'a mut (dyn fmt::Write + 'b),
+}
+
+impl fmt::Write for EscapingWriter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        escape_html_to(self.inner, s)
+    }
+}
+
+impl<T> RenderEscaped for Href<T>
+where
+    T: RouteHref,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.0.render_href(&mut EscapingWriter { inner: f })
+    }
+}
+
+impl<T> fmt::Display for Href<T>
+where
+    T: RouteHref,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_escaped(f)
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    cell::{Cell, RefCell},
+    future::poll_fn,
+    pin::pin,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::slug::UniqueIds;
+
+/// A render scope that async renders keep across `.await`s.
+type SharedScope = Arc<Mutex<RenderScope>>;
+
+thread_local! {
+    static SCOPE: RefCell<Option<SharedScope>> = const { RefCell::new(None) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// The scope of the async render being polled on this thread, if any.
+    static ASYNC_SCOPE: RefCell<Option<SharedScope>> = const { RefCell::new(None) };
+}
+
+/// State shared by everything rendered during a single top-level [`to_html`](crate::ToHtml::to_html) call or
+/// [`ids::scope`](crate::ids::scope).
+#[derive(Debug, Default)]
+pub(crate) struct RenderScope {
+    /// Ids generated with `auto_id` or [`ids::next`](crate::ids::next) so far.
+    pub(crate) ids: UniqueIds,
+}
+
+/// Runs `f` inside a render scope. Nested calls share the scope of the outermost call, and calls made while an
+/// [async scope](enter_async) is polled share the async scope.
+pub(crate) fn enter<R>(f: impl FnOnce() -> R) -> R {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let depth = DEPTH.with(|depth| {
+                depth.set(depth.get() - 1);
+                depth.get()
+            });
+
+            if depth == 0 {
+                SCOPE.with(|scope| scope.borrow_mut().take());
+            }
+        }
+    }
+
+    let depth = DEPTH.with(|depth| {
+        depth.set(depth.get() + 1);
+        depth.get()
+    });
+
+    if depth == 1 {
+        let shared = ASYNC_SCOPE
+            .with(|scope| scope.borrow().clone())
+            .unwrap_or_default();
+
+        SCOPE.with(|scope| *scope.borrow_mut() = Some(shared));
+    }
+
+    let _guard = Guard;
+
+    f()
+}
+
+/// Runs `future`, entering one render scope for every render it runs, including those of the futures it awaits.
+/// Nested calls share the scope of the outermost call.
+pub(crate) async fn enter_async<F: Future>(future: F) -> F::Output {
+    /// Restores the async scope of the caller when dropped.
+    struct Restore(Option<SharedScope>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ASYNC_SCOPE.with(|scope| *scope.borrow_mut() = self.0.take());
+        }
+    }
+
+    let mut future = pin!(future);
+    let mut shared = None;
+
+    poll_fn(|cx| {
+        // The scope is taken from the future polling this one on its first poll, and kept for later polls
+        let shared = shared.get_or_insert_with(|| {
+            ASYNC_SCOPE
+                .with(|scope| scope.borrow().clone())
+                .unwrap_or_default()
+        });
+
+        let _restore = Restore(ASYNC_SCOPE.with(|scope| scope.replace(Some(shared.clone()))));
+
+        future.as_mut().poll(cx)
+    })
+    .await
+}
+
+/// Calls `f` with the current render scope, or returns `None` outside of a render scope. The async scope being polled
+/// counts as a render scope, so that the `let` bindings of async components share it too.
+pub(crate) fn with<R>(f: impl FnOnce(&mut RenderScope) -> R) -> Option<R> {
+    let shared = SCOPE
+        .with(|scope| scope.borrow().clone())
+        .or_else(|| ASYNC_SCOPE.with(|scope| scope.borrow().clone()))?;
+
+    let mut scope = shared.lock().unwrap_or_else(PoisonError::into_inner);
+    Some(f(&mut scope))
+}
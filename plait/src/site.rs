@@ -0,0 +1,583 @@
+//! Static site generation: rendering pages to files.
+//!
+//! A [`StaticSite`] maps URL paths to pages and [builds](StaticSite::build) them into HTML files in an output
+//! directory, rendering several pages in parallel. Each page is written to `index.html` in the directory of its path
+//! (`/about` to `about/index.html`), unless the last segment of its path has an extension (`/404.html`). Static files
+//! are [copied](StaticSite::copy) into the output directory next to the pages, and embedded [`Asset`]s are written to
+//! the path of their [URL](Asset::url).
+//!
+//! Builds are incremental: a file is only written if its content changed. Every build also writes a
+//! [`manifest.json`](MANIFEST_FILE) listing the file and the content hash of each page and static file, e.g. for
+//! deployment tools that upload changed files only:
+//!
+//! ```json
+//! {
+//!   "/": { "file": "index.html", "hash": "a0c3b5e2d4f6e8a1" },
+//!   "/about": { "file": "about/index.html", "hash": "9e1f7d3c5b2a4e6f" }
+//! }
+//! ```
+//!
+//! Internal links (`href` and `src` attributes with a path, absolute or relative to the page) are checked against the
+//! generated pages and files, and reported by [`Manifest::broken_links`]. Use
+//! [`check_links()`](StaticSite::check_links) to fail the build instead. With a [`sitemap()`](StaticSite::sitemap)
+//! base URL, a [`sitemap.xml`](SITEMAP_FILE) listing the HTML pages is written as well.
+//!
+//! Pages are rendered on worker threads, so values [provided](crate::context::provide) in the render context of the
+//! thread calling [`build()`](StaticSite::build) are not available to them. Use [`threads(1)`](StaticSite::threads)
+//! to render every page on the calling thread instead.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use plait::{html, site::StaticSite};
+//!
+//! let title = "About";
+//!
+//! let manifest = StaticSite::new("dist")
+//!     .page("/", html! { a(href: "/about") { "About" } })
+//!     .page("/about", html! { h1 { (title) } img(src: "/static/logo.svg"); })
+//!     .page("/404.html", html! { h1 { "Not found" } })
+//!     .copy("/static", "assets/static")
+//!     .sitemap("https://example.com")
+//!     .check_links()
+//!     .build()?;
+//!
+//! for page in manifest.pages() {
+//!     println!("{} -> {} ({})", page.path, page.file.display(), page.hash);
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    thread,
+};
+
+use percent_encoding::percent_decode_str;
+
+use crate::{
+    Asset, ToHtml,
+    tokenizer::{Token, Tokenizer, decode_attribute},
+    url::push_url_path_segment,
+    utils::{escape_html_to, fnv1a_64, write_json_string},
+};
+
+/// Name of the manifest written into the output directory by [`StaticSite::build`].
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Name of the sitemap written into the output directory by [`StaticSite::build`] if a
+/// [base URL](StaticSite::sitemap) is set.
+pub const SITEMAP_FILE: &str = "sitemap.xml";
+
+/// Source of a static file of a site.
+enum StaticFile {
+    /// A file or directory on disk.
+    Copy(PathBuf),
+    /// The content of an embedded asset.
+    Embedded(&'static str),
+}
+
+/// A set of pages rendered into an output directory. See the [module documentation](self).
+pub struct StaticSite<'a> {
+    out_dir: PathBuf,
+    pages: Vec<(String, Box<dyn ToHtml + Sync + 'a>)>,
+    files: Vec<(String, StaticFile)>,
+    sitemap: Option<String>,
+    check_links: bool,
+    threads: usize,
+}
+
+impl<'a> StaticSite<'a> {
+    /// Creates a site without pages, built into `out_dir`.
+    pub fn new(out_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            out_dir: out_dir.into(),
+            pages: Vec::new(),
+            files: Vec::new(),
+            sitemap: None,
+            check_links: false,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        }
+    }
+
+    /// Adds the page served at `path`, e.g. `"/"` or `"/blog/hello"`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` doesn't start with `/`, contains empty, `.` or `..` segments, or was already added.
+    pub fn page(mut self, path: &str, page: impl ToHtml + Sync + 'a) -> Self {
+        self.assert_new_path(path);
+        self.pages.push((path.to_owned(), Box::new(page)));
+        self
+    }
+
+    /// Copies the file or directory `source` to `path`, e.g. `.copy("/static", "assets")` to serve `assets/app.css`
+    /// at `/static/app.css`. Directories are copied recursively when the site is built.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` is not a valid page path or was already added.
+    pub fn copy(mut self, path: &str, source: impl Into<PathBuf>) -> Self {
+        self.assert_new_path(path);
+        self.files
+            .push((path.to_owned(), StaticFile::Copy(source.into())));
+        self
+    }
+
+    /// Writes the content of the embedded `asset` to the path of its [URL](Asset::url).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path of the asset was already added.
+    pub fn asset(mut self, asset: Asset) -> Self {
+        let path = format!("/{}", asset.path().trim_start_matches('/'));
+
+        self.assert_new_path(&path);
+        self.files
+            .push((path, StaticFile::Embedded(asset.content())));
+        self
+    }
+
+    /// Writes a [`sitemap.xml`](SITEMAP_FILE) listing the HTML pages of the site (except `/404.html`) under
+    /// `base_url`, e.g. `"https://example.com"`.
+    pub fn sitemap(mut self, base_url: &str) -> Self {
+        self.sitemap = Some(base_url.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// Makes [`build()`](StaticSite::build) fail with an [`io::ErrorKind::InvalidData`] error listing the broken
+    /// internal links, after writing the site.
+    pub fn check_links(mut self) -> Self {
+        self.check_links = true;
+        self
+    }
+
+    /// Sets the number of threads pages are rendered on. Defaults to the available parallelism.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Returns the output directory.
+    pub fn out_dir(&self) -> &Path {
+        &self.out_dir
+    }
+
+    /// Renders every page into the output directory, writing the files whose content changed, the manifest and the
+    /// sitemap.
+    pub fn build(&self) -> io::Result<Manifest> {
+        fs::create_dir_all(&self.out_dir)?;
+
+        let threads = self.threads.min(self.pages.len()).max(1);
+
+        let results: Vec<io::Result<(ManifestPage, Vec<String>)>> = if threads == 1 {
+            self.pages
+                .iter()
+                .map(|(path, page)| self.build_page(path, &**page))
+                .collect()
+        } else {
+            thread::scope(|scope| {
+                let workers: Vec<_> = (0..threads)
+                    .map(|worker| {
+                        scope.spawn(move || {
+                            self.pages
+                                .iter()
+                                .skip(worker)
+                                .step_by(threads)
+                                .map(|(path, page)| self.build_page(path, &**page))
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+
+                workers
+                    .into_iter()
+                    .flat_map(|worker| match worker.join() {
+                        Ok(results) => results,
+                        Err(panic) => std::panic::resume_unwind(panic),
+                    })
+                    .collect()
+            })
+        };
+
+        let mut pages = Vec::with_capacity(results.len());
+        let mut links = Vec::new();
+
+        for result in results {
+            let (page, page_links) = result?;
+            links.extend(page_links.into_iter().map(|href| (page.path.clone(), href)));
+            pages.push(page);
+        }
+
+        let mut assets = Vec::new();
+
+        for (path, file) in &self.files {
+            match file {
+                StaticFile::Copy(source) => self.copy_files(path, source, &mut assets)?,
+                StaticFile::Embedded(content) => assets.push(self.write_file(
+                    path,
+                    PathBuf::from(&path[1..]),
+                    content.as_bytes(),
+                )?),
+            }
+        }
+
+        pages.sort_by(|a, b| a.path.cmp(&b.path));
+        assets.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let files: HashSet<&Path> = pages
+            .iter()
+            .chain(&assets)
+            .map(|file| file.file.as_path())
+            .collect();
+        let asset_paths: HashSet<&str> = assets.iter().map(|asset| asset.path.as_str()).collect();
+
+        let mut broken_links: Vec<BrokenLink> = links
+            .into_iter()
+            .filter(|(page, href)| {
+                resolve_link(page, href).is_some_and(|target| {
+                    !asset_paths.contains(target.as_str())
+                        && !files.contains(page_file(&target).as_path())
+                })
+            })
+            .map(|(page, href)| BrokenLink { page, href })
+            .collect();
+        broken_links.sort_by(|a, b| (&a.page, &a.href).cmp(&(&b.page, &b.href)));
+        broken_links.dedup();
+
+        let manifest = Manifest {
+            pages,
+            assets,
+            broken_links,
+        };
+
+        fs::write(self.out_dir.join(MANIFEST_FILE), manifest.to_json())?;
+
+        if let Some(base_url) = &self.sitemap {
+            fs::write(
+                self.out_dir.join(SITEMAP_FILE),
+                manifest.to_sitemap(base_url),
+            )?;
+        }
+
+        if self.check_links && !manifest.broken_links.is_empty() {
+            let links: Vec<String> = manifest
+                .broken_links
+                .iter()
+                .map(|link| format!("{} links to {}", link.page, link.href))
+                .collect();
+
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("broken links: {}", links.join(", ")),
+            ));
+        }
+
+        Ok(manifest)
+    }
+
+    /// Panics if `path` is not a valid page path or was already added.
+    fn assert_new_path(&self, path: &str) {
+        assert!(
+            path.starts_with('/'),
+            "page path must start with `/`: {path:?}"
+        );
+        assert!(
+            path[1..]
+                .strip_suffix('/')
+                .unwrap_or(&path[1..])
+                .split('/')
+                .all(|segment| !matches!(segment, "." | "..")
+                    && (!segment.is_empty() || path == "/")
+                    && !segment.contains('\\')),
+            "invalid page path: {path:?}"
+        );
+        assert!(
+            self.pages
+                .iter()
+                .map(|(existing, _)| existing)
+                .chain(self.files.iter().map(|(existing, _)| existing))
+                .all(|existing| existing != path),
+            "page {path:?} is added more than once"
+        );
+    }
+
+    /// Renders a page, returning its manifest entry and the links it contains.
+    fn build_page(
+        &self,
+        path: &str,
+        page: &(dyn ToHtml + Sync + 'a),
+    ) -> io::Result<(ManifestPage, Vec<String>)> {
+        let html = page.to_html();
+        let links = links(&html);
+
+        Ok((
+            self.write_file(path, page_file(path), html.as_bytes())?,
+            links,
+        ))
+    }
+
+    /// Copies the file or directory `source` to the URL `path`.
+    fn copy_files(
+        &self,
+        path: &str,
+        source: &Path,
+        files: &mut Vec<ManifestPage>,
+    ) -> io::Result<()> {
+        if !source.is_dir() {
+            let file = PathBuf::from(path.trim_matches('/'));
+            files.push(self.write_file(path, file, &fs::read(source)?)?);
+            return Ok(());
+        }
+
+        let mut entries = fs::read_dir(source)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+
+        for entry in entries {
+            let Some(name) = entry.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            let entry_path = format!("{}/{name}", path.trim_end_matches('/'));
+            self.copy_files(&entry_path, &entry, files)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `content` to `file` in the output directory if it changed, returning its manifest entry.
+    fn write_file(&self, path: &str, file: PathBuf, content: &[u8]) -> io::Result<ManifestPage> {
+        let full_path = self.out_dir.join(&file);
+
+        let changed = match fs::read(&full_path) {
+            Ok(existing) => existing != content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => true,
+            Err(error) => return Err(error),
+        };
+
+        if changed {
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(&full_path, content)?;
+        }
+
+        Ok(ManifestPage {
+            path: path.to_owned(),
+            file,
+            hash: format!("{:016x}", fnv1a_64(content)),
+            changed,
+        })
+    }
+}
+
+impl fmt::Debug for StaticSite<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticSite")
+            .field("out_dir", &self.out_dir)
+            .field(
+                "pages",
+                &self.pages.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+            )
+            .field(
+                "files",
+                &self.files.iter().map(|(path, _)| path).collect::<Vec<_>>(),
+            )
+            .field("sitemap", &self.sitemap)
+            .field("check_links", &self.check_links)
+            .field("threads", &self.threads)
+            .finish()
+    }
+}
+
+/// Returns the file of the page at `path`, relative to the output directory.
+fn page_file(path: &str) -> PathBuf {
+    let mut file: PathBuf = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    let has_extension = !path.ends_with('/')
+        && path
+            .rsplit('/')
+            .next()
+            .is_some_and(|segment| segment.contains('.'));
+
+    if !has_extension {
+        file.push("index.html");
+    }
+
+    file
+}
+
+/// Returns the (decoded) values of the `href` and `src` attributes in `html`.
+fn links(html: &str) -> Vec<String> {
+    Tokenizer::new(html)
+        .filter(|token| matches!(token, Token::StartTag { .. }))
+        .flat_map(|token| [token.attribute("href"), token.attribute("src")])
+        .flatten()
+        .map(decode_attribute)
+        .collect()
+}
+
+/// Resolves the link `href` on the page at `page` to a path of the site, or returns `None` for external links,
+/// fragment-only links and links with a scheme (`https:`, `mailto:`, ...).
+fn resolve_link(page: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    let href = &href[..href.find(['?', '#']).unwrap_or(href.len())];
+
+    let has_scheme = href
+        .find(':')
+        .is_some_and(|colon| !href[..colon].contains('/'));
+
+    if href.is_empty() || has_scheme || href.starts_with("//") {
+        return None;
+    }
+
+    let base = if href.starts_with('/') {
+        ""
+    } else {
+        &page[..page.rfind('/').map_or(0, |slash| slash + 1)]
+    };
+
+    let joined = format!("{base}{href}");
+    let mut segments: Vec<&str> = Vec::new();
+
+    for segment in joined.split('/').skip(1) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let path = format!("/{}", segments.join("/"));
+    let decoded: Cow<'_, str> = percent_decode_str(&path).decode_utf8_lossy();
+
+    Some(decoded.into_owned())
+}
+
+/// The pages and static files written by a [`StaticSite::build`], sorted by path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pages: Vec<ManifestPage>,
+    assets: Vec<ManifestPage>,
+    broken_links: Vec<BrokenLink>,
+}
+
+impl Manifest {
+    /// Returns the pages of the site, sorted by path.
+    pub fn pages(&self) -> &[ManifestPage] {
+        &self.pages
+    }
+
+    /// Returns the page at `path`, if any.
+    pub fn page(&self, path: &str) -> Option<&ManifestPage> {
+        self.pages.iter().find(|page| page.path == path)
+    }
+
+    /// Returns the copied and embedded static files of the site, sorted by path.
+    pub fn assets(&self) -> &[ManifestPage] {
+        &self.assets
+    }
+
+    /// Returns the pages and static files whose file was written by this build.
+    pub fn changed(&self) -> impl Iterator<Item = &ManifestPage> {
+        self.pages
+            .iter()
+            .chain(&self.assets)
+            .filter(|page| page.changed)
+    }
+
+    /// Returns the internal links that point to neither a page nor a static file of the site, sorted by page.
+    pub fn broken_links(&self) -> &[BrokenLink] {
+        &self.broken_links
+    }
+
+    /// Returns the manifest as JSON, as written to [`MANIFEST_FILE`].
+    pub fn to_json(&self) -> String {
+        let mut entries: Vec<&ManifestPage> = self.pages.iter().chain(&self.assets).collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut json = String::from("{\n");
+
+        for (index, page) in entries.into_iter().enumerate() {
+            if index > 0 {
+                json.push_str(",\n");
+            }
+
+            json.push_str("  ");
+            write_json_string(&mut json, &page.path);
+            json.push_str(": { \"file\": ");
+            write_json_string(&mut json, &page.file.to_string_lossy().replace('\\', "/"));
+            json.push_str(", \"hash\": ");
+            write_json_string(&mut json, &page.hash);
+            json.push_str(" }");
+        }
+
+        json.push_str("\n}\n");
+        json
+    }
+
+    /// Returns a sitemap listing the HTML pages (except `/404.html`) under `base_url`, as written to
+    /// [`SITEMAP_FILE`].
+    pub fn to_sitemap(&self, base_url: &str) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+
+        let pages = self.pages.iter().filter(|page| {
+            page.path != "/404.html"
+                && page
+                    .file
+                    .extension()
+                    .is_some_and(|extension| extension == "html")
+        });
+
+        for page in pages {
+            let mut url = base_url.trim_end_matches('/').to_owned();
+
+            for segment in page.path.split('/').skip(1) {
+                url.push('/');
+                push_url_path_segment(&mut url, segment);
+            }
+
+            xml.push_str("  <url><loc>");
+            let _ = escape_html_to(&mut xml, &url);
+            xml.push_str("</loc></url>\n");
+        }
+
+        xml.push_str("</urlset>\n");
+        xml
+    }
+}
+
+/// A page or static file written by a [`StaticSite::build`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ManifestPage {
+    /// The URL path of the page or file.
+    pub path: String,
+    /// The file, relative to the output directory.
+    pub file: PathBuf,
+    /// The hex-encoded hash of the file's content.
+    pub hash: String,
+    /// Whether the file was written by this build, because its content changed or it didn't exist.
+    pub changed: bool,
+}
+
+/// An internal link to a path that is neither a page nor a static file of the site.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BrokenLink {
+    /// The path of the page containing the link.
+    pub page: String,
+    /// The link, as written in the page.
+    pub href: String,
+}
@@ -0,0 +1,82 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{text::text_content, utils::escape_html_to};
+
+/// Converts `text` into a URL- and id-friendly slug.
+///
+/// Letters are lower-cased, runs of whitespace, `-` and `_` become a single `-`, other punctuation is dropped and
+/// leading and trailing dashes are trimmed. Non-ASCII letters and digits are kept. Returns `"section"` if nothing is
+/// left.
+///
+/// # Example
+///
+/// ```
+/// use plait::slugify;
+///
+/// assert_eq!(slugify("Hello, World!"), "hello-world");
+/// assert_eq!(slugify("  Getting   started_guide "), "getting-started-guide");
+/// assert_eq!(slugify("Ünïcödé"), "ünïcödé");
+/// assert_eq!(slugify("!!!"), "section");
+/// ```
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut pending_dash = false;
+
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.extend(c.to_lowercase());
+        } else if c.is_whitespace() || c == '-' || c == '_' {
+            pending_dash = true;
+        }
+    }
+
+    if slug.is_empty() {
+        slug.push_str("section");
+    }
+
+    slug
+}
+
+#[doc(hidden)]
+/// Writes an ` id="..."` attribute derived from the rendered `content` of an element. This is used internally by the
+/// `auto_id` attribute of the `html!` macro.
+pub fn write_auto_id(f: &mut (dyn fmt::Write + '_), content: &str) -> fmt::Result {
+    let id = slugify(&text_content(content));
+    let id = crate::scope::with(|scope| scope.ids.unique(id.clone())).unwrap_or(id);
+
+    f.write_str(" id=\"")?;
+    escape_html_to(f, &id)?;
+    f.write_str("\"")
+}
+
+/// Hands out unique ids, appending `-1`, `-2`, ... to ids that have already been used.
+#[derive(Debug, Default)]
+pub(crate) struct UniqueIds {
+    used: HashSet<String>,
+}
+
+impl UniqueIds {
+    /// Marks `id` as used without modifying it.
+    pub(crate) fn reserve(&mut self, id: &str) {
+        self.used.insert(id.to_owned());
+    }
+
+    /// Returns `id`, or the first `{id}-{n}` that hasn't been used yet, and marks it as used.
+    pub(crate) fn unique(&mut self, id: String) -> String {
+        let id = if self.used.contains(&id) {
+            (1..)
+                .map(|n| format!("{id}-{n}"))
+                .find(|candidate| !self.used.contains(candidate))
+                .unwrap()
+        } else {
+            id
+        };
+
+        self.used.insert(id.clone());
+        id
+    }
+}
@@ -0,0 +1,156 @@
+//! Element and attribute metadata from the HTML standard.
+//!
+//! These are the tables plait itself uses to render and post-process HTML, published so that tooling and downstream
+//! builders can stay consistent with the [`html!`](crate::html) macro instead of keeping their own lists. The tables
+//! are sorted and lower-case; lookups are ASCII case-insensitive binary searches that don't allocate.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::spec;
+//!
+//! assert!(spec::is_void_element("br"));
+//! assert!(!spec::is_void_element("div"));
+//! assert!(spec::is_rcdata_element("TEXTAREA"));
+//! assert!(spec::is_url_attribute("href"));
+//! assert!(spec::is_boolean_attribute("disabled"));
+//!
+//! assert!(spec::is_valid_attribute_name("data-id"));
+//! assert!(!spec::is_valid_attribute_name("onclick=alert(1) x"));
+//! ```
+
+/// [Void elements](https://html.spec.whatwg.org/multipage/syntax.html#void-elements): elements without children or
+/// an end tag.
+///
+/// The [`html!`](crate::html) macro renders these without an end tag and rejects children for them.
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// [Raw text elements](https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements): elements whose content
+/// is not parsed as HTML and can't contain character references.
+pub const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// [Escapable raw text elements](https://html.spec.whatwg.org/multipage/syntax.html#escapable-raw-text-elements)
+/// (RCDATA): elements whose content is text, but can contain character references.
+pub const RCDATA_ELEMENTS: &[&str] = &["textarea", "title"];
+
+/// Attributes whose value is a URL, or a list of URLs (`ping`, `srcset`).
+pub const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "cite",
+    "data",
+    "formaction",
+    "href",
+    "manifest",
+    "ping",
+    "poster",
+    "src",
+    "srcset",
+];
+
+/// Attributes whose value is a list or a setting that proxies and minifiers tend to mangle when it spans several lines.
+/// The [`html!`](crate::html) macro writes newlines and tabs in their values as character references (e.g. `&#10;`),
+/// which browsers read the same way. Enable the `encode-attribute-whitespace` feature to do so for every attribute.
+pub const WHITESPACE_SENSITIVE_ATTRIBUTES: &[&str] =
+    &["content", "imagesizes", "imagesrcset", "sizes", "srcset"];
+
+/// [Boolean attributes](https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#boolean-attributes): their
+/// presence means `true` and their absence `false`, whatever their value.
+pub const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+    "shadowrootclonable",
+    "shadowrootdelegatesfocus",
+    "shadowrootserializable",
+];
+
+/// Looks up `name` in a sorted, lower-case table without allocating.
+fn contains(table: &[&str], name: &str) -> bool {
+    table
+        .binary_search_by(|entry| {
+            entry
+                .bytes()
+                .cmp(name.bytes().map(|byte| byte.to_ascii_lowercase()))
+        })
+        .is_ok()
+}
+
+/// Returns `true` if `tag` is a [void element](VOID_ELEMENTS).
+pub fn is_void_element(tag: &str) -> bool {
+    contains(VOID_ELEMENTS, tag)
+}
+
+/// Returns `true` if `tag` is a [raw text element](RAW_TEXT_ELEMENTS).
+pub fn is_raw_text_element(tag: &str) -> bool {
+    contains(RAW_TEXT_ELEMENTS, tag)
+}
+
+/// Returns `true` if `tag` is an [escapable raw text element](RCDATA_ELEMENTS).
+pub fn is_rcdata_element(tag: &str) -> bool {
+    contains(RCDATA_ELEMENTS, tag)
+}
+
+/// Returns `true` if the value of the attribute `name` is a [URL](URL_ATTRIBUTES).
+pub fn is_url_attribute(name: &str) -> bool {
+    contains(URL_ATTRIBUTES, name)
+}
+
+/// Returns `true` if the value of the attribute `name` is [sensitive to whitespace](WHITESPACE_SENSITIVE_ATTRIBUTES).
+pub fn is_whitespace_sensitive_attribute(name: &str) -> bool {
+    contains(WHITESPACE_SENSITIVE_ATTRIBUTES, name)
+}
+
+/// Returns `true` if `name` is a [boolean attribute](BOOLEAN_ATTRIBUTES).
+pub fn is_boolean_attribute(name: &str) -> bool {
+    contains(BOOLEAN_ATTRIBUTES, name)
+}
+
+/// Returns `true` if `c` can't appear in an element or attribute name without ending it early: ASCII whitespace and
+/// control characters, `/`, `>`, `=`, `"`, `'` and `<`.
+fn is_name_delimiter(c: char) -> bool {
+    c.is_ascii_control() || matches!(c, ' ' | '/' | '>' | '=' | '"' | '\'' | '<')
+}
+
+/// Returns `true` if `name` can be written as an element name: it starts with an ASCII letter and contains no
+/// whitespace, control characters, `/`, `>`, `=`, `"`, `'` or `<`.
+///
+/// Builders writing element names that aren't known at compile time should check them with this function, since an
+/// invalid name could end the tag and inject markup.
+pub fn is_valid_element_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic()) && !name.contains(is_name_delimiter)
+}
+
+/// Returns `true` if `name` can be written as an attribute name: it is not empty and contains no whitespace, control
+/// characters, `/`, `>`, `=`, `"`, `'` or `<`.
+///
+/// The [`html!`](crate::html) macro checks string literal attribute names with the same rules. Builders writing
+/// attribute names that aren't known at compile time should check them with this function, since an invalid name
+/// could end the attribute or tag and inject markup.
+pub fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(is_name_delimiter)
+}
@@ -0,0 +1,315 @@
+//! Framing for streaming rendered fragments to the browser.
+//!
+//! Live-updating pages (e.g. with the htmx `sse` extension) can use rendered fragments directly as the wire format:
+//!
+//! - [`SseEvent`] frames a fragment as a [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html)
+//!   message, splitting multi-line HTML into several `data:` lines so it arrives unchanged.
+//! - [`Multipart`] frames fragments as the parts of a chunked `multipart/mixed` response.
+//! - [`WsEnvelope`] wraps fragments and the selectors of the elements they replace in JSON messages for pushing over
+//!   WebSockets.
+//!
+//! All of them produce plain strings, so they work with any HTTP or WebSocket server or framework.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, stream::SseEvent};
+//!
+//! let count = 3;
+//! let frag = html! {
+//!     ul {
+//!         li { "Unread: " (count) }
+//!     }
+//! };
+//!
+//! let event = SseEvent::new(&frag.to_html()).event("inbox").id("42");
+//!
+//! assert_eq!(
+//!     event.to_string(),
+//!     "event: inbox\nid: 42\ndata: <ul><li>Unread: 3</li></ul>\n\n"
+//! );
+//!
+//! assert_eq!(
+//!     SseEvent::new("<p>\nline two</p>").to_string(),
+//!     "data: <p>\ndata: line two</p>\n\n"
+//! );
+//! ```
+use std::{borrow::Cow, fmt};
+
+use crate::utils::write_json_string;
+
+/// Writes `value` with line breaks (`\r\n`, `\r` or `\n`) removed, for fields that must fit on a single line.
+fn write_single_line(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    for part in value.split(['\r', '\n']) {
+        f.write_str(part)?;
+    }
+
+    Ok(())
+}
+
+/// A Server-Sent Events message carrying rendered HTML.
+///
+/// The message is rendered with [`Display`](fmt::Display). Every line of the data becomes its own `data:` field, which
+/// the browser joins back together with `\n`. Line breaks in the event name and id are removed, since they would end
+/// the field early.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SseEvent {
+    data: String,
+    event: Option<String>,
+    id: Option<String>,
+    retry: Option<u64>,
+}
+
+impl SseEvent {
+    /// Creates a message carrying `data`, typically the output of [`to_html()`](crate::ToHtml::to_html).
+    pub fn new(data: &str) -> Self {
+        Self {
+            data: data.to_owned(),
+            event: None,
+            id: None,
+            retry: None,
+        }
+    }
+
+    /// Sets the event type (the `event:` field). Clients listen for it by name, e.g. with `sse-swap="{event}"` in
+    /// htmx. Without it, the browser dispatches a `message` event.
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = Some(event.into());
+        self
+    }
+
+    /// Sets the event id (the `id:` field), which the browser sends back in `Last-Event-ID` when reconnecting.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the reconnection time in milliseconds (the `retry:` field).
+    pub fn retry(mut self, milliseconds: u64) -> Self {
+        self.retry = Some(milliseconds);
+        self
+    }
+}
+
+impl fmt::Display for SseEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(event) = &self.event {
+            f.write_str("event: ")?;
+            write_single_line(f, event)?;
+            f.write_str("\n")?;
+        }
+
+        if let Some(id) = &self.id {
+            f.write_str("id: ")?;
+            write_single_line(f, id)?;
+            f.write_str("\n")?;
+        }
+
+        if let Some(retry) = self.retry {
+            writeln!(f, "retry: {retry}")?;
+        }
+
+        let data = self.data.replace("\r\n", "\n");
+
+        for line in data.split(['\r', '\n']) {
+            f.write_str("data: ")?;
+            f.write_str(line)?;
+            f.write_str("\n")?;
+        }
+
+        f.write_str("\n")
+    }
+}
+
+/// Framing for a `multipart/mixed` response whose parts are rendered fragments.
+///
+/// Send [`content_type()`](Multipart::content_type) as the `Content-Type` of the response, then write one
+/// [`part()`](Multipart::part) per fragment as it becomes available, and finally [`end()`](Multipart::end).
+///
+/// The boundary must not appear in any of the fragments. Use a random or otherwise unguessable boundary when the
+/// fragments contain user input.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, ToHtml, stream::Multipart};
+///
+/// let multipart = Multipart::new("plait-boundary");
+/// assert_eq!(multipart.content_type(), "multipart/mixed; boundary=\"plait-boundary\"");
+///
+/// let body = [
+///     multipart.part(&html! { p { "first" } }.to_html()),
+///     multipart.part(&html! { p { "second" } }.to_html()),
+///     multipart.end(),
+/// ]
+/// .concat();
+///
+/// assert_eq!(
+///     body,
+///     "--plait-boundary\r\nContent-Type: text/html; charset=utf-8\r\n\r\n<p>first</p>\r\n\
+///     --plait-boundary\r\nContent-Type: text/html; charset=utf-8\r\n\r\n<p>second</p>\r\n\
+///     --plait-boundary--\r\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Multipart {
+    boundary: String,
+}
+
+impl Multipart {
+    /// Creates multipart framing with the given boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `boundary` is empty, longer than 70 characters or contains characters other than ASCII letters,
+    /// digits and `'()+_,-./:=?`, as required by [RFC 2046](https://www.rfc-editor.org/rfc/rfc2046#section-5.1.1).
+    pub fn new(boundary: impl Into<String>) -> Self {
+        let boundary = boundary.into();
+
+        assert!(
+            !boundary.is_empty()
+                && boundary.len() <= 70
+                && boundary
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || b"'()+_,-./:=?".contains(&byte)),
+            "invalid multipart boundary: {boundary:?}"
+        );
+
+        Self { boundary }
+    }
+
+    /// Returns the boundary.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Returns the `Content-Type` header value of the response.
+    pub fn content_type(&self) -> String {
+        format!("multipart/mixed; boundary=\"{}\"", self.boundary)
+    }
+
+    /// Returns `html` framed as a `text/html` part.
+    pub fn part(&self, html: &str) -> String {
+        format!(
+            "--{}\r\nContent-Type: text/html; charset=utf-8\r\n\r\n{html}\r\n",
+            self.boundary
+        )
+    }
+
+    /// Returns the closing delimiter, written after the last part.
+    pub fn end(&self) -> String {
+        format!("--{}--\r\n", self.boundary)
+    }
+}
+
+/// JSON envelopes for pushing rendered fragments over WebSockets.
+///
+/// Each fragment is sent together with the CSS selector of the element it updates, as an object with a `target` and
+/// an `html` field. [`batch()`](WsEnvelope::batch) sends several fragments in one message, as an array of such objects
+/// in a `fragments` field. All three field names can be changed to match what the client expects.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, ToHtml, stream::WsEnvelope};
+///
+/// let count = html! { span { "3" } }.to_html();
+/// let item = html! { li { "New \"post\"" } }.to_html();
+///
+/// let envelope = WsEnvelope::new();
+///
+/// assert_eq!(
+///     envelope.message("#count", &count),
+///     r##"{"target":"#count","html":"<span>3</span>"}"##
+/// );
+///
+/// assert_eq!(
+///     envelope.batch([("#count", &*count), ("#feed", &*item)]),
+///     r##"{"fragments":[{"target":"#count","html":"<span>3</span>"},{"target":"#feed","html":"<li>New &quot;post&quot;</li>"}]}"##
+/// );
+///
+/// let custom = WsEnvelope::new().target_field("selector").html_field("content");
+///
+/// assert_eq!(
+///     custom.message("#count", &count),
+///     r##"{"selector":"#count","content":"<span>3</span>"}"##
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WsEnvelope {
+    target_field: Cow<'static, str>,
+    html_field: Cow<'static, str>,
+    batch_field: Cow<'static, str>,
+}
+
+impl Default for WsEnvelope {
+    fn default() -> Self {
+        Self {
+            target_field: Cow::Borrowed("target"),
+            html_field: Cow::Borrowed("html"),
+            batch_field: Cow::Borrowed("fragments"),
+        }
+    }
+}
+
+impl WsEnvelope {
+    /// Creates an envelope with the default field names: `target`, `html` and `fragments`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the field holding the target selector.
+    pub fn target_field(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.target_field = name.into();
+        self
+    }
+
+    /// Sets the name of the field holding the rendered HTML.
+    pub fn html_field(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.html_field = name.into();
+        self
+    }
+
+    /// Sets the name of the field holding the fragments of a batched message.
+    pub fn batch_field(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.batch_field = name.into();
+        self
+    }
+
+    fn write_fragment(&self, json: &mut String, target: &str, html: &str) {
+        json.push('{');
+        write_json_string(json, &self.target_field);
+        json.push(':');
+        write_json_string(json, target);
+        json.push(',');
+        write_json_string(json, &self.html_field);
+        json.push(':');
+        write_json_string(json, html);
+        json.push('}');
+    }
+
+    /// Returns a message updating the element matching `target` with `html`.
+    pub fn message(&self, target: &str, html: &str) -> String {
+        let mut json = String::with_capacity(html.len() + target.len() + 32);
+        self.write_fragment(&mut json, target, html);
+        json
+    }
+
+    /// Returns a single message with several `(target, html)` updates, applied in order.
+    pub fn batch<'a>(&self, fragments: impl IntoIterator<Item = (&'a str, &'a str)>) -> String {
+        let mut json = String::from("{");
+        write_json_string(&mut json, &self.batch_field);
+        json.push_str(":[");
+
+        for (index, (target, html)) in fragments.into_iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+
+            self.write_fragment(&mut json, target, html);
+        }
+
+        json.push_str("]}");
+        json
+    }
+}
@@ -0,0 +1,303 @@
+//! Typed builder for `style` attributes.
+//!
+//! [`Style`] collects CSS declarations one property at a time. Values implement [`CssValue`]: strings, numbers, the
+//! length units of this module ([`Px`], [`Em`], [`Rem`], [`Pct`]) and `Option`s of these, with `None` leaving the
+//! property out. A `Style` renders as the value of a `style` attribute with `style: (style)`, or `style?: style` to
+//! leave the attribute out when no property is set.
+//!
+//! Values can't end their declaration early: `;`, `{` and `}` in a value are escaped, so a value like
+//! `red; background: url(...)` stays a single (invalid) value instead of adding a property.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, style::{Px, Style}};
+//!
+//! let color: Option<&str> = Some("rebeccapurple");
+//! let height: Option<Px<u32>> = None;
+//!
+//! let style = Style::new()
+//!     .prop("width", Px(32))
+//!     .prop("height", height)
+//!     .prop("color", color);
+//!
+//! assert_eq!(
+//!     html! { div(style: (style)) {} }.to_html(),
+//!     "<div style=\"width: 32px; color: rebeccapurple\"></div>"
+//! );
+//!
+//! assert_eq!(
+//!     html! { div(style?: Style::new().prop("width", height)) {} }.to_html(),
+//!     "<div></div>"
+//! );
+//! ```
+use std::{borrow::Cow, fmt};
+
+use crate::{RenderEscaped, RenderMaybeAttributeEscaped, utils::escape_html_to};
+
+/// Trait for values of CSS properties set with [`Style::prop`].
+///
+/// # Built-in implementations
+///
+/// | Type                                    | Behavior                                              |
+/// |-----------------------------------------|-------------------------------------------------------|
+/// | `str`, `String`, `Cow<str>`             | Skipped if empty; otherwise written as-is             |
+/// | Integers and floats                     | Written as numbers                                    |
+/// | [`Px`], [`Em`], [`Rem`], [`Pct`]        | Written as numbers followed by their unit             |
+/// | `Option<T: CssValue>`                   | Skipped if `None`; otherwise delegates to inner value |
+/// | `&T` where `T: CssValue`                | Delegates to inner value                              |
+pub trait CssValue {
+    /// Returns `true` if the property should be omitted.
+    fn should_skip(&self) -> bool {
+        false
+    }
+
+    /// Writes the value into `f`.
+    fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result;
+}
+
+impl<T> CssValue for &T
+where
+    T: CssValue + ?Sized,
+{
+    fn should_skip(&self) -> bool {
+        (**self).should_skip()
+    }
+
+    fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        (**self).render_css(f)
+    }
+}
+
+impl<T> CssValue for Option<T>
+where
+    T: CssValue,
+{
+    fn should_skip(&self) -> bool {
+        self.as_ref().is_none_or(|value| value.should_skip())
+    }
+
+    fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        match self {
+            Some(value) => value.render_css(f),
+            None => Ok(()),
+        }
+    }
+}
+
+impl CssValue for str {
+    fn should_skip(&self) -> bool {
+        self.trim().is_empty()
+    }
+
+    fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self.trim())
+    }
+}
+
+impl CssValue for String {
+    fn should_skip(&self) -> bool {
+        self.as_str().should_skip()
+    }
+
+    fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.as_str().render_css(f)
+    }
+}
+
+impl CssValue for Cow<'_, str> {
+    fn should_skip(&self) -> bool {
+        (**self).should_skip()
+    }
+
+    fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        (**self).render_css(f)
+    }
+}
+
+macro_rules! impl_css_value_for_number {
+    ($($ty:ty),*) => {
+        $(
+            impl CssValue for $ty {
+                fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+                    write!(f, "{self}")
+                }
+            }
+        )*
+    };
+}
+
+impl_css_value_for_number!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+macro_rules! css_unit {
+    ($(#[$meta:meta])* $name:ident, $unit:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Hash, Default)]
+        pub struct $name<T>(pub T);
+
+        impl<T> CssValue for $name<T>
+        where
+            T: fmt::Display,
+        {
+            fn render_css(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+                write!(f, concat!("{}", $unit), self.0)
+            }
+        }
+    };
+}
+
+css_unit!(
+    /// A length in pixels, e.g. `Px(32)` is `32px`.
+    Px,
+    "px"
+);
+css_unit!(
+    /// A length relative to the font size of the element, e.g. `Em(1.5)` is `1.5em`.
+    Em,
+    "em"
+);
+css_unit!(
+    /// A length relative to the font size of the root element, e.g. `Rem(2)` is `2rem`.
+    Rem,
+    "rem"
+);
+css_unit!(
+    /// A percentage, e.g. `Pct(50)` is `50%`.
+    Pct,
+    "%"
+);
+
+/// Returns `true` if `name` can be a CSS property name: ASCII letters, digits, `-` and `_`, starting with a letter,
+/// `-` or `_`. Custom properties (`--name`) are accepted.
+fn is_valid_property_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '-' || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Writes `value` into `f`, escaping the characters that would end its declaration or rule.
+struct CssEscaper<'a>(&'a mut String);
+
+impl fmt::Write for CssEscaper<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                ';' | '{' | '}' => {
+                    self.0.push('\\');
+                    self.0.push(c);
+                }
+                c if c.is_control() => self.0.push(' '),
+                c => self.0.push(c),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// CSS declarations for a `style` attribute. See the [module documentation](self).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Style {
+    css: String,
+}
+
+impl Style {
+    /// Creates a style without properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the property `name` to `value`, or leaves it out if the value [should be skipped](CssValue::should_skip).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid property name (ASCII letters, digits, `-` and `_`).
+    pub fn prop(mut self, name: &str, value: impl CssValue) -> Self {
+        assert!(
+            is_valid_property_name(name),
+            "invalid CSS property name: {name:?}"
+        );
+
+        if value.should_skip() {
+            return self;
+        }
+
+        if !self.css.is_empty() {
+            self.css.push_str("; ");
+        }
+
+        self.css.push_str(name);
+        self.css.push_str(": ");
+        let _ = value.render_css(&mut CssEscaper(&mut self.css));
+
+        self
+    }
+
+    /// Returns `true` if no property is set.
+    pub fn is_empty(&self) -> bool {
+        self.css.is_empty()
+    }
+
+    /// Returns the declarations as CSS text, before HTML escaping.
+    pub fn as_str(&self) -> &str {
+        &self.css
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.css)
+    }
+}
+
+impl RenderEscaped for Style {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        escape_html_to(f, &self.css)
+    }
+}
+
+impl RenderMaybeAttributeEscaped for Style {
+    fn render_maybe_attribute_escaped(
+        &self,
+        name: &str,
+        f: &mut (dyn fmt::Write + '_),
+    ) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        f.write_str(" ")?;
+        f.write_str(name)?;
+        f.write_str("=\"")?;
+        self.render_escaped(f)?;
+        f.write_str("\"")
+    }
+}
+
+/// Combines CSS property/value pairs into a single [`Style`] value for a `style` attribute.
+///
+/// Properties whose value is `None` or an empty string are skipped, values are escaped so that they can't end their
+/// declaration, and declarations are separated by `;`. Each value must implement [`CssValue`].
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, style, ToHtml};
+///
+/// let color = "rebeccapurple";
+/// let hidden = true;
+///
+/// let frag = html! {
+///     div(style: style!("color": color, "display": if hidden { Some("none") } else { None })) {}
+/// };
+/// assert_eq!(frag.to_html(), r#"<div style="color: rebeccapurple; display: none"></div>"#);
+/// ```
+#[macro_export]
+macro_rules! style {
+    ($($name:literal : $value:expr),+ $(,)?) => {
+        $crate::style::Style::new()$(.prop($name, $value))+
+    };
+}
@@ -0,0 +1,855 @@
+//! Checks for component output, used by [`#[component_test]`](crate::component_test).
+//!
+//! - [`html_errors`] checks that rendered HTML parses cleanly: every element is closed by its own end tag, void
+//!   elements have no end tags, and no tag repeats an attribute or an id.
+//! - [`accessible_names`] lists the elements of rendered HTML that assistive technology presents (links, buttons,
+//!   form controls, images, headings and landmarks), one per line as `role "accessible name"`. Comparing this list
+//!   against a snapshot catches icon buttons without a label or images that lost their `alt` text.
+//! - [`inline_scripts`] lists the inline event handlers (`onclick` and other `on*` attributes), inline `<script>`
+//!   elements and `javascript:` URLs of rendered HTML, which a strict
+//!   [Content Security Policy](https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP) blocks. Asserting that the list
+//!   is empty keeps templates working under such a policy.
+//! - [`outline_issues`] checks the outline of a rendered page: a single `main` landmark and `h1` heading, heading
+//!   levels that don't skip, and labels on landmarks that appear more than once.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, testing};
+//!
+//! let frag = html! {
+//!     nav(aria_label: "Primary") {
+//!         a(href: "/") { img(src: "/logo.svg", alt: "Home"); }
+//!         button(type: "button") { span(aria_hidden: "true") { "☰" } }
+//!     }
+//! };
+//!
+//! let html = frag.to_html();
+//!
+//! assert!(testing::html_errors(&html).is_empty());
+//! assert_eq!(
+//!     testing::accessible_names(&html),
+//!     "navigation \"Primary\"\nlink \"Home\"\nimg \"Home\"\nbutton"
+//! );
+//!
+//! assert_eq!(
+//!     testing::html_errors("<ul><li>One</ul>"),
+//!     ["`<li>` is closed by `</ul>`"]
+//! );
+//! ```
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::{
+    spec::{is_url_attribute, is_void_element},
+    text::text_content,
+    tokenizer::{Token, Tokenizer, attributes, decode_attribute},
+};
+
+/// An element of parsed HTML.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.attribute("hidden").is_some() || self.attribute("aria-hidden") == Some("true")
+    }
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// Parses `html` into a tree, collecting the problems found on the way.
+fn parse(html: &str) -> (Element, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut ids = BTreeSet::new();
+    let mut stack = vec![Element::default()];
+
+    for token in Tokenizer::new(html) {
+        match token {
+            Token::Text(text) => {
+                let text = text_content(text);
+
+                if !text.is_empty() {
+                    stack.last_mut().unwrap().children.push(Node::Text(text));
+                }
+            }
+            Token::StartTag {
+                name,
+                source,
+                self_closing,
+            } => {
+                let name = name.to_ascii_lowercase();
+                let mut element = Element {
+                    name,
+                    ..Element::default()
+                };
+
+                for (attribute, value) in attributes(source) {
+                    let attribute = attribute.to_ascii_lowercase();
+
+                    if element.attribute(&attribute).is_some() {
+                        errors.push(format!(
+                            "`<{}>` has a duplicate `{attribute}` attribute",
+                            element.name
+                        ));
+                        continue;
+                    }
+
+                    element.attributes.push((attribute, text_content(value)));
+                }
+
+                if let Some(id) = element.attribute("id")
+                    && !ids.insert(id.to_owned())
+                {
+                    errors.push(format!("duplicate id `{id}`"));
+                }
+
+                let is_foreign = stack
+                    .iter()
+                    .chain([&element])
+                    .any(|element| matches!(element.name.as_str(), "svg" | "math"));
+
+                if is_void_element(&element.name) || (self_closing && is_foreign) {
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .children
+                        .push(Node::Element(element));
+                } else {
+                    if self_closing {
+                        errors.push(format!(
+                            "`<{}/>` is not a void element and can't be self-closing",
+                            element.name
+                        ));
+                    }
+
+                    stack.push(element);
+                }
+            }
+            Token::EndTag { name, .. } => {
+                let name = name.to_ascii_lowercase();
+
+                if is_void_element(&name) {
+                    errors.push(format!("`<{name}>` is a void element and has no end tag"));
+                    continue;
+                }
+
+                let Some(index) = stack.iter().skip(1).rposition(|open| open.name == name) else {
+                    errors.push(format!("`</{name}>` has no matching start tag"));
+                    continue;
+                };
+
+                while stack.len() > index + 1 {
+                    let element = stack.pop().unwrap();
+
+                    if element.name != name {
+                        errors.push(format!("`<{}>` is closed by `</{name}>`", element.name));
+                    }
+
+                    stack
+                        .last_mut()
+                        .unwrap()
+                        .children
+                        .push(Node::Element(element));
+                }
+            }
+            Token::RawText(_) | Token::Other(_) => {}
+        }
+    }
+
+    while stack.len() > 1 {
+        let element = stack.pop().unwrap();
+        errors.push(format!("`<{}>` is never closed", element.name));
+        stack
+            .last_mut()
+            .unwrap()
+            .children
+            .push(Node::Element(element));
+    }
+
+    (stack.pop().unwrap(), errors)
+}
+
+/// Returns the problems that keep rendered `html` from parsing cleanly, or an empty list if there are none.
+///
+/// Browsers recover from every one of these problems, but often by building a different tree than the markup appears
+/// to describe.
+pub fn html_errors(html: &str) -> Vec<String> {
+    parse(html).1
+}
+
+/// Returns the role and accessible name of every element of rendered `html` presented by assistive technology, one per
+/// line and in document order. See the [module documentation](self).
+///
+/// Each line has the form `role "name"`, or just `role` when the element has no accessible name. Roles are taken from
+/// the `role` attribute or the element's implicit role, and names are computed from `aria-labelledby`, `aria-label`,
+/// `alt`, associated `label`s, the content of the element or `title`, following a simplified version of the
+/// [accessible name computation](https://www.w3.org/TR/accname-1.2/). Content hidden with `hidden` or
+/// `aria-hidden="true"` is skipped.
+pub fn accessible_names(html: &str) -> String {
+    let (root, _) = parse(html);
+
+    let mut index = Index::default();
+    index.collect(&root);
+
+    let mut lines = Vec::new();
+    collect_names(&root, &index, Ancestors::default(), &mut lines);
+    lines.join("\n")
+}
+
+/// Panics with a list of the problems if rendered `html` doesn't parse cleanly (see [`html_errors`]).
+#[track_caller]
+pub fn assert_valid_html(html: &str) {
+    let errors = html_errors(html);
+
+    if !errors.is_empty() {
+        panic!("invalid HTML:\n  - {}\n\nin: {html}", errors.join("\n  - "));
+    }
+}
+
+/// Panics if the [`accessible_names`] of rendered `html` don't match `snapshot`. Leading and trailing whitespace of
+/// every line of `snapshot` is ignored, as are empty lines.
+#[track_caller]
+pub fn assert_accessible_names(html: &str, snapshot: &str) {
+    let actual = accessible_names(html);
+    let expected = snapshot
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if actual != expected {
+        panic!(
+            "accessible names don't match the snapshot\n\nexpected:\n{expected}\n\nactual:\n{actual}\n\nin: {html}"
+        );
+    }
+}
+
+/// Returns the inline scripts of rendered `html` that a Content Security Policy without `'unsafe-inline'` blocks, one
+/// description per script in document order, or an empty list if there are none.
+///
+/// Three kinds of inline scripts are reported:
+///
+/// - event handler attributes such as `onclick` or `onload`,
+/// - `<script>` elements without a `src`, unless they have a `nonce` or are data blocks (such as
+///   `type="application/ld+json"`), which aren't executed,
+/// - `javascript:` URLs in URL attributes such as `href` or `action`.
+///
+/// # Example
+///
+/// ```
+/// use plait::testing;
+///
+/// assert_eq!(
+///     testing::inline_scripts(
+///         r#"<button onclick="save()">Save</button><a href=" JavaScript:void(0)">Link</a><script>init()</script>"#
+///     ),
+///     [
+///         "`onclick` attribute on `<button>`",
+///         "`javascript:` URL in the `href` attribute of `<a>`",
+///         "inline `<script>`",
+///     ]
+/// );
+///
+/// assert!(
+///     testing::inline_scripts(r#"<script src="/app.js"></script><script nonce="r4nd0m">init()</script>"#).is_empty()
+/// );
+/// ```
+pub fn inline_scripts(html: &str) -> Vec<String> {
+    let mut scripts = Vec::new();
+
+    for token in Tokenizer::new(html) {
+        let Token::StartTag { name, source, .. } = token else {
+            continue;
+        };
+
+        let name = name.to_ascii_lowercase();
+
+        for (attribute, value) in attributes(source) {
+            let attribute = attribute.to_ascii_lowercase();
+
+            if attribute.len() > 2 && attribute.starts_with("on") {
+                scripts.push(format!("`{attribute}` attribute on `<{name}>`"));
+            } else if is_url_attribute(&attribute) && is_javascript_url(&decode_attribute(value)) {
+                scripts.push(format!(
+                    "`javascript:` URL in the `{attribute}` attribute of `<{name}>`"
+                ));
+            }
+        }
+
+        if name == "script"
+            && token.attribute("src").is_none()
+            && token.attribute("nonce").is_none()
+            && is_script_type(&decode_attribute(
+                token.attribute("type").unwrap_or_default(),
+            ))
+        {
+            scripts.push("inline `<script>`".to_owned());
+        }
+    }
+
+    scripts
+}
+
+/// Panics with a list of the inline scripts of rendered `html` if there are any (see [`inline_scripts`]).
+#[track_caller]
+pub fn assert_no_inline_scripts(html: &str) {
+    let scripts = inline_scripts(html);
+
+    if !scripts.is_empty() {
+        panic!(
+            "inline scripts:\n  - {}\n\nin: {html}",
+            scripts.join("\n  - ")
+        );
+    }
+}
+
+/// Returns `true` if browsers run `url` as a script when it is followed. Browsers ignore leading whitespace and control
+/// characters, and tabs and newlines anywhere in a URL.
+fn is_javascript_url(url: &str) -> bool {
+    let mut scheme = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'));
+
+    "javascript:".chars().all(|expected| {
+        scheme
+            .next()
+            .is_some_and(|c| c.eq_ignore_ascii_case(&expected))
+    })
+}
+
+/// Returns `true` if a `<script>` with the `type` attribute `script_type` is executed, rather than being a data block.
+fn is_script_type(script_type: &str) -> bool {
+    let script_type = script_type.trim().to_ascii_lowercase();
+    let essence = script_type.split(';').next().unwrap_or_default().trim();
+
+    matches!(
+        essence,
+        "" | "module"
+            | "importmap"
+            | "speculationrules"
+            | "text/javascript"
+            | "application/javascript"
+            | "application/ecmascript"
+            | "text/ecmascript"
+    )
+}
+
+/// A problem with the outline of a rendered page, found by [`outline_issues`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OutlineIssue {
+    /// The page has no `main` landmark.
+    MissingMain,
+    /// The page has more than one `main` landmark.
+    MultipleMain {
+        /// The number of `main` landmarks.
+        count: usize,
+    },
+    /// The page has no level 1 heading.
+    MissingH1,
+    /// The page has more than one level 1 heading.
+    MultipleH1 {
+        /// The number of level 1 headings.
+        count: usize,
+    },
+    /// A heading is more than one level below the heading before it, e.g. an `h4` right after an `h2`.
+    SkippedHeadingLevel {
+        /// The level of the heading before it.
+        previous: u8,
+        /// The level of the heading.
+        level: u8,
+        /// The text of the heading.
+        text: String,
+    },
+    /// A landmark has no label, while other landmarks on the page have the same role.
+    UnlabeledLandmark {
+        /// The role of the landmark, such as `navigation`.
+        role: String,
+    },
+    /// Several landmarks with the same role have the same label.
+    DuplicateLandmarkLabel {
+        /// The role of the landmarks.
+        role: String,
+        /// The label of the landmarks.
+        label: String,
+    },
+}
+
+impl fmt::Display for OutlineIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutlineIssue::MissingMain => f.write_str("the page has no `main` landmark"),
+            OutlineIssue::MultipleMain { count } => {
+                write!(f, "the page has {count} `main` landmarks")
+            }
+            OutlineIssue::MissingH1 => f.write_str("the page has no `h1` heading"),
+            OutlineIssue::MultipleH1 { count } => write!(f, "the page has {count} `h1` headings"),
+            OutlineIssue::SkippedHeadingLevel {
+                previous,
+                level,
+                text,
+            } => write!(
+                f,
+                "heading {text:?} skips from level {previous} to level {level}"
+            ),
+            OutlineIssue::UnlabeledLandmark { role } => write!(
+                f,
+                "a `{role}` landmark has no label to tell it apart from the other `{role}` landmarks"
+            ),
+            OutlineIssue::DuplicateLandmarkLabel { role, label } => {
+                write!(f, "several `{role}` landmarks are labeled {label:?}")
+            }
+        }
+    }
+}
+
+/// The headings and landmarks of a page, in document order.
+#[derive(Debug, Default)]
+struct Outline {
+    /// The level and text of each heading.
+    headings: Vec<(u8, String)>,
+    /// The role and label of each landmark.
+    landmarks: Vec<(String, String)>,
+}
+
+/// Returns the problems with the outline of the rendered page `html`, or an empty list if there are none.
+///
+/// The page should have exactly one `main` landmark and one level 1 heading, and each heading should be at most one
+/// level below the heading before it. Landmarks with a role that appears more than once (such as several `nav`
+/// elements) need distinct labels, set with `aria-label` or `aria-labelledby`, so that screen reader users can tell
+/// them apart. Headings and landmarks are found like in [`accessible_names`], including `role` and `aria-level`
+/// attributes, and hidden content is skipped.
+///
+/// # Example
+///
+/// ```
+/// use plait::testing::{self, OutlineIssue};
+///
+/// let issues = testing::outline_issues(
+///     "<nav aria-label=\"Primary\"></nav><main><h1>Title</h1><h3>Details</h3></main><nav></nav>",
+/// );
+///
+/// assert_eq!(
+///     issues,
+///     [
+///         OutlineIssue::SkippedHeadingLevel { previous: 1, level: 3, text: "Details".to_owned() },
+///         OutlineIssue::UnlabeledLandmark { role: "navigation".to_owned() },
+///     ]
+/// );
+/// assert_eq!(issues[0].to_string(), "heading \"Details\" skips from level 1 to level 3");
+/// ```
+pub fn outline_issues(html: &str) -> Vec<OutlineIssue> {
+    let (root, _) = parse(html);
+
+    let mut index = Index::default();
+    index.collect(&root);
+
+    let mut outline = Outline::default();
+    collect_outline(&root, &index, false, &mut outline);
+
+    let mut issues = Vec::new();
+
+    match outline
+        .landmarks
+        .iter()
+        .filter(|(role, _)| role == "main")
+        .count()
+    {
+        0 => issues.push(OutlineIssue::MissingMain),
+        1 => {}
+        count => issues.push(OutlineIssue::MultipleMain { count }),
+    }
+
+    match outline
+        .headings
+        .iter()
+        .filter(|(level, _)| *level == 1)
+        .count()
+    {
+        0 => issues.push(OutlineIssue::MissingH1),
+        1 => {}
+        count => issues.push(OutlineIssue::MultipleH1 { count }),
+    }
+
+    for pair in outline.headings.windows(2) {
+        let [(previous, _), (level, text)] = pair else {
+            continue;
+        };
+
+        if *level > previous + 1 {
+            issues.push(OutlineIssue::SkippedHeadingLevel {
+                previous: *previous,
+                level: *level,
+                text: text.clone(),
+            });
+        }
+    }
+
+    let mut reported = BTreeSet::new();
+
+    for (role, label) in &outline.landmarks {
+        let same_role = outline
+            .landmarks
+            .iter()
+            .filter(|(other, _)| other == role)
+            .count();
+
+        if role == "main" || same_role < 2 {
+            continue;
+        }
+
+        if label.is_empty() {
+            issues.push(OutlineIssue::UnlabeledLandmark { role: role.clone() });
+        } else if outline
+            .landmarks
+            .iter()
+            .filter(|other| other.0 == *role && other.1 == *label)
+            .count()
+            > 1
+            && reported.insert((role, label))
+        {
+            issues.push(OutlineIssue::DuplicateLandmarkLabel {
+                role: role.clone(),
+                label: label.clone(),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Panics with a list of the problems if the outline of the rendered page `html` has any (see [`outline_issues`]).
+#[track_caller]
+pub fn assert_valid_outline(html: &str) {
+    let issues = outline_issues(html);
+
+    if !issues.is_empty() {
+        let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        panic!(
+            "invalid outline:\n  - {}\n\nin: {html}",
+            issues.join("\n  - ")
+        );
+    }
+}
+
+fn collect_outline(element: &Element, index: &Index, in_section: bool, outline: &mut Outline) {
+    for child in &element.children {
+        let Node::Element(child) = child else {
+            continue;
+        };
+
+        if child.is_hidden() || matches!(child.name.as_str(), "template" | "noscript") {
+            continue;
+        }
+
+        let ancestors = Ancestors {
+            in_section,
+            label: None,
+        };
+
+        match role(child, index, ancestors).as_deref() {
+            Some("heading") => {
+                let level = child
+                    .attribute("aria-level")
+                    .and_then(|level| level.trim().parse().ok())
+                    .or_else(|| child.name.strip_prefix('h')?.parse().ok())
+                    .unwrap_or(2);
+
+                outline.headings.push((level, content_name(child)));
+            }
+            Some(
+                role @ ("banner" | "complementary" | "contentinfo" | "form" | "main" | "navigation"
+                | "region" | "search"),
+            ) => {
+                let label = own_name(child, index, ancestors);
+
+                // Forms are only landmarks when they are labeled
+                if role != "form" || !label.is_empty() {
+                    outline.landmarks.push((role.to_owned(), label));
+                }
+            }
+            _ => {}
+        }
+
+        let in_section = in_section
+            || matches!(
+                child.name.as_str(),
+                "article" | "aside" | "main" | "nav" | "section"
+            );
+
+        collect_outline(child, index, in_section, outline);
+    }
+}
+
+/// The text of the elements with an id and the `label`s of form controls, needed to compute names.
+#[derive(Debug, Default)]
+struct Index {
+    ids: BTreeMap<String, String>,
+    labels: BTreeMap<String, Vec<String>>,
+}
+
+impl Index {
+    fn collect(&mut self, element: &Element) {
+        if let Some(id) = element.attribute("id") {
+            self.ids.insert(id.to_owned(), content_name(element));
+        }
+
+        if element.name == "label"
+            && let Some(target) = element.attribute("for")
+        {
+            self.labels
+                .entry(target.to_owned())
+                .or_default()
+                .push(content_name(element));
+        }
+
+        for child in &element.children {
+            if let Node::Element(child) = child {
+                self.collect(child);
+            }
+        }
+    }
+}
+
+fn is_labelable(element: &Element) -> bool {
+    match element.name.as_str() {
+        "input" => element.attribute("type") != Some("hidden"),
+        "button" | "meter" | "output" | "progress" | "select" | "textarea" => true,
+        _ => false,
+    }
+}
+
+/// What the ancestors of an element change about its role and name.
+#[derive(Debug, Default, Clone, Copy)]
+struct Ancestors<'a> {
+    /// Whether the element is inside a sectioning element, which takes the landmark roles away from `header` and
+    /// `footer`.
+    in_section: bool,
+    /// The content of the `label` without a `for` attribute wrapping the element.
+    label: Option<&'a str>,
+}
+
+/// Returns the role of `element`.
+fn role(element: &Element, index: &Index, ancestors: Ancestors<'_>) -> Option<String> {
+    if let Some(role) = element
+        .attribute("role")
+        .and_then(|role| role.split_whitespace().next())
+    {
+        return Some(role.to_ascii_lowercase());
+    }
+
+    let role = match element.name.as_str() {
+        "a" | "area" if element.attribute("href").is_some() => "link",
+        "button" => "button",
+        "img" => match element.attribute("alt") {
+            Some("") => return None,
+            _ => "img",
+        },
+        "input" => match element
+            .attribute("type")
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("hidden") => return None,
+            Some("button" | "submit" | "reset" | "image") => "button",
+            Some("checkbox") => "checkbox",
+            Some("radio") => "radio",
+            Some("range") => "slider",
+            Some("number") => "spinbutton",
+            Some("search") => "searchbox",
+            _ => "textbox",
+        },
+        "select" if element.attribute("multiple").is_some() => "listbox",
+        "select" => "combobox",
+        "textarea" => "textbox",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "nav" => "navigation",
+        "main" => "main",
+        "aside" => "complementary",
+        "header" if !ancestors.in_section => "banner",
+        "footer" if !ancestors.in_section => "contentinfo",
+        "dialog" => "dialog",
+        "form" => "form",
+        "section" if !own_name(element, index, ancestors).is_empty() => "region",
+        _ => return None,
+    };
+
+    Some(role.to_owned())
+}
+
+/// Returns `true` if elements with `role` are named by their content.
+fn is_named_by_content(role: &str) -> bool {
+    matches!(
+        role,
+        "button"
+            | "cell"
+            | "checkbox"
+            | "columnheader"
+            | "heading"
+            | "link"
+            | "menuitem"
+            | "menuitemcheckbox"
+            | "menuitemradio"
+            | "option"
+            | "radio"
+            | "rowheader"
+            | "switch"
+            | "tab"
+            | "tooltip"
+            | "treeitem"
+    )
+}
+
+/// Returns the name given to `element` by its attributes and labels.
+fn own_name(element: &Element, index: &Index, ancestors: Ancestors<'_>) -> String {
+    if let Some(ids) = element.attribute("aria-labelledby") {
+        let name = ids
+            .split_whitespace()
+            .filter_map(|id| index.ids.get(id))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if !name.trim().is_empty() {
+            return name;
+        }
+    }
+
+    if let Some(label) = element.attribute("aria-label")
+        && !label.trim().is_empty()
+    {
+        return label.trim().to_owned();
+    }
+
+    match element.name.as_str() {
+        "img" | "area" => return element.attribute("alt").unwrap_or_default().to_owned(),
+        "input" => match element.attribute("type") {
+            Some("button" | "submit" | "reset") => {
+                return element.attribute("value").unwrap_or_default().to_owned();
+            }
+            Some("image") => return element.attribute("alt").unwrap_or_default().to_owned(),
+            _ => {}
+        },
+        _ => {}
+    }
+
+    if is_labelable(element) {
+        let mut labels: Vec<&str> = element
+            .attribute("id")
+            .and_then(|id| index.labels.get(id))
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        labels.extend(ancestors.label);
+
+        let label = labels.join(" ");
+
+        if !label.trim().is_empty() {
+            return label;
+        }
+    }
+
+    String::new()
+}
+
+/// Returns the name computed from the visible content of `element`.
+fn content_name(element: &Element) -> String {
+    let mut name = String::new();
+    push_content(element, &mut name);
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn push_content(element: &Element, name: &mut String) {
+    for child in &element.children {
+        match child {
+            Node::Text(text) => name.push_str(text),
+            Node::Element(child) if child.is_hidden() => {}
+            Node::Element(child) => {
+                name.push(' ');
+
+                if let Some(label) = child.attribute("aria-label")
+                    && !label.trim().is_empty()
+                {
+                    name.push_str(label);
+                } else if child.name == "img" {
+                    name.push_str(child.attribute("alt").unwrap_or_default());
+                } else {
+                    push_content(child, name);
+                }
+
+                name.push(' ');
+            }
+        }
+    }
+}
+
+fn collect_names(
+    element: &Element,
+    index: &Index,
+    ancestors: Ancestors<'_>,
+    lines: &mut Vec<String>,
+) {
+    for child in &element.children {
+        let Node::Element(child) = child else {
+            continue;
+        };
+
+        if child.is_hidden() || matches!(child.name.as_str(), "template" | "noscript") {
+            continue;
+        }
+
+        if let Some(role) = role(child, index, ancestors) {
+            let mut name = own_name(child, index, ancestors);
+
+            if name.is_empty() && is_named_by_content(&role) {
+                name = content_name(child);
+            }
+
+            if name.is_empty()
+                && let Some(title) = child.attribute("title")
+            {
+                name = title.trim().to_owned();
+            }
+
+            if name.is_empty() {
+                lines.push(role);
+            } else {
+                lines.push(format!("{role} {name:?}"));
+            }
+        }
+
+        let label = (child.name == "label" && child.attribute("for").is_none())
+            .then(|| content_name(child));
+
+        let ancestors = Ancestors {
+            in_section: ancestors.in_section
+                || matches!(
+                    child.name.as_str(),
+                    "article" | "aside" | "main" | "nav" | "section"
+                ),
+            label: label.as_deref().or(ancestors.label),
+        };
+
+        collect_names(child, index, ancestors, lines);
+    }
+}
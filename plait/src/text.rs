@@ -0,0 +1,248 @@
+use crate::{
+    Html,
+    spec::is_void_element,
+    tokenizer::{Token, Tokenizer},
+    utils::decode_char_reference,
+};
+
+/// Converts rendered HTML to plain text.
+///
+/// Tags, comments and the content of `script` and `style` elements are removed, character references are decoded and
+/// runs of whitespace are collapsed into a single space. Block-level elements (paragraphs, headings, list items, ...)
+/// and `<br>` separate the text around them with a space. Use this to generate `meta` descriptions or search index
+/// text from already rendered fragments.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, html_to_text, ToHtml};
+///
+/// let article = html! {
+///     h1 { "Fish & Chips" }
+///     p { "A " em { "classic" } "   dish." }
+///     p { "Serve hot." }
+/// }
+/// .to_html();
+///
+/// assert_eq!(html_to_text(&article), "Fish & Chips A classic dish. Serve hot.");
+/// ```
+pub fn html_to_text(html: &Html) -> String {
+    text_content(html)
+}
+
+/// Returns the text content of rendered HTML. See [`html_to_text`].
+pub(crate) fn text_content(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+
+    for token in Tokenizer::new(html) {
+        match token {
+            Token::Text(content) => push_text(&mut text, content),
+            Token::StartTag { name, .. } | Token::EndTag { name, .. } => {
+                if is_block_element(&name.to_ascii_lowercase()) {
+                    push_whitespace(&mut text);
+                }
+            }
+            Token::RawText(_) | Token::Other(_) => {}
+        }
+    }
+
+    text.truncate(text.trim_end().len());
+    text
+}
+
+/// Appends `content` to `text`, decoding character references and collapsing whitespace.
+fn push_text(text: &mut String, content: &str) {
+    let mut index = 0;
+
+    while index < content.len() {
+        let (c, len) = if content[index..].starts_with('&') {
+            decode_char_reference(&content[index..])
+        } else {
+            let c = content[index..].chars().next().unwrap();
+            (c, c.len_utf8())
+        };
+
+        if c.is_whitespace() && c != '\u{a0}' {
+            push_whitespace(text);
+        } else {
+            text.push(c);
+        }
+
+        index += len;
+    }
+}
+
+fn push_whitespace(text: &mut String) {
+    if !text.is_empty() && !text.ends_with(' ') {
+        text.push(' ');
+    }
+}
+
+pub(crate) fn is_block_element(tag: &str) -> bool {
+    matches!(
+        tag,
+        "address"
+            | "article"
+            | "aside"
+            | "blockquote"
+            | "br"
+            | "caption"
+            | "dd"
+            | "details"
+            | "div"
+            | "dl"
+            | "dt"
+            | "fieldset"
+            | "figcaption"
+            | "figure"
+            | "footer"
+            | "form"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "header"
+            | "hr"
+            | "li"
+            | "main"
+            | "nav"
+            | "ol"
+            | "option"
+            | "p"
+            | "pre"
+            | "section"
+            | "summary"
+            | "table"
+            | "td"
+            | "th"
+            | "tr"
+            | "ul"
+    )
+}
+
+/// Truncates rendered HTML to at most `max_chars` characters of text, keeping the markup well-formed.
+///
+/// Only text counts towards the limit: tags don't, character references count as one character and runs of whitespace
+/// count as a single character. When the text is cut, it is cut at the last word boundary before the limit, an ellipsis
+/// (`…`) is appended and every element that is still open is closed. HTML that fits within the limit is returned
+/// unchanged.
+///
+/// # Example
+///
+/// ```
+/// use plait::{html, truncate_html, ToHtml};
+///
+/// let article = html! {
+///     p { "Plait is a " strong { "modern HTML templating" } " library." }
+///     p { "It embraces composition." }
+/// }
+/// .to_html();
+///
+/// assert_eq!(
+///     truncate_html(&article, 20),
+///     "<p>Plait is a <strong>modern…</strong></p>"
+/// );
+/// ```
+pub fn truncate_html(html: &Html, max_chars: usize) -> Html {
+    let mut output = String::with_capacity(html.len().min(max_chars * 2));
+    let mut open_elements: Vec<&str> = Vec::new();
+    let mut remaining = max_chars;
+    let mut has_text = false;
+
+    for token in Tokenizer::new(html) {
+        match token {
+            Token::Text(text) => match cut_text(text, remaining, has_text) {
+                Ok(count) => {
+                    output.push_str(text);
+                    remaining -= count;
+                    has_text = has_text || count > 0;
+                }
+                Err(cut) => {
+                    output.push_str(text[..cut].trim_end());
+                    output.push('…');
+
+                    for name in open_elements.iter().rev() {
+                        output.push_str("</");
+                        output.push_str(name);
+                        output.push('>');
+                    }
+
+                    return Html::new_unchecked(output);
+                }
+            },
+            Token::StartTag {
+                name,
+                source,
+                self_closing,
+            } => {
+                output.push_str(source);
+
+                if !self_closing && !is_void_element(name) {
+                    open_elements.push(name);
+                }
+            }
+            Token::EndTag { name, source } => {
+                output.push_str(source);
+
+                if let Some(index) = open_elements
+                    .iter()
+                    .rposition(|open| open.eq_ignore_ascii_case(name))
+                {
+                    open_elements.truncate(index);
+                }
+            }
+            Token::RawText(text) | Token::Other(text) => output.push_str(text),
+        }
+    }
+
+    html.clone()
+}
+
+/// Counts the characters of `text` if it fits in `remaining` characters, or returns the byte offset to cut it at.
+///
+/// The cut is moved back to the last whitespace so that words aren't split, unless the word is the very first text of
+/// the document (`has_text` is `false`).
+fn cut_text(text: &str, remaining: usize, has_text: bool) -> Result<usize, usize> {
+    let mut count = 0;
+    let mut last_whitespace = None;
+    let mut in_whitespace = false;
+    let mut index = 0;
+
+    while index < text.len() {
+        let (c, len) = if text[index..].starts_with('&') {
+            decode_char_reference(&text[index..])
+        } else {
+            let c = text[index..].chars().next().unwrap();
+            (c, c.len_utf8())
+        };
+
+        if c.is_whitespace() {
+            if !in_whitespace {
+                if count == remaining {
+                    return Err(index);
+                }
+
+                count += 1;
+                last_whitespace = Some(index);
+            }
+            in_whitespace = true;
+        } else {
+            if count == remaining {
+                return Err(match last_whitespace {
+                    Some(whitespace) => whitespace,
+                    None if has_text => 0,
+                    None => index,
+                });
+            }
+
+            count += 1;
+            in_whitespace = false;
+        }
+
+        index += len;
+    }
+
+    Ok(count)
+}
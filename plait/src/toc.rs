@@ -0,0 +1,212 @@
+//! Tables of contents generated from rendered headings.
+//!
+//! [`generate`] scans rendered HTML for `h1`–`h6` headings and returns a nested [`TableOfContents`] of their text and
+//! ids. Headings without an `id` get one derived from their text (made unique within the document), so every entry can
+//! be linked to. Render the table of contents with the [`Toc`] component.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{html, ToHtml, toc::{self, Toc}};
+//!
+//! let content = html! {
+//!     h1 { "Guide" }
+//!     h2 { "Install" }
+//!     h2(id: "use") { "Usage" }
+//!     h3 { "Advanced usage" }
+//! };
+//!
+//! let (content, toc) = toc::generate(&content.to_html());
+//!
+//! assert_eq!(
+//!     content,
+//!     "<h1 id=\"guide\">Guide</h1><h2 id=\"install\">Install</h2>\
+//!     <h2 id=\"use\">Usage</h2><h3 id=\"advanced-usage\">Advanced usage</h3>"
+//! );
+//!
+//! let page = html! {
+//!     @Toc(toc: &toc) {}
+//!     main { (content) }
+//! };
+//!
+//! assert!(page.to_html().starts_with(
+//!     "<nav class=\"toc\"><ol><li><a href=\"#guide\">Guide</a>\
+//!     <ol><li><a href=\"#install\">Install</a></li><li><a href=\"#use\">Usage</a>"
+//! ));
+//! ```
+use crate::{
+    Html, ToHtml, component, html,
+    slug::{UniqueIds, slugify},
+    text::text_content,
+    tokenizer::{Token, Tokenizer, decode_attribute},
+    utils::escape_html_to,
+};
+
+/// A table of contents: the top-level headings of a document, each with its nested subheadings.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TableOfContents {
+    entries: Vec<TocEntry>,
+}
+
+impl TableOfContents {
+    /// Returns the top-level entries.
+    pub fn entries(&self) -> &[TocEntry] {
+        &self.entries
+    }
+
+    /// Returns `true` if the document has no headings.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn push(&mut self, entry: TocEntry) {
+        let mut entries = &mut self.entries;
+
+        while entries.last().is_some_and(|last| last.level < entry.level) {
+            entries = &mut entries.last_mut().unwrap().children;
+        }
+
+        entries.push(entry);
+    }
+}
+
+/// A heading in a [`TableOfContents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// Heading level (`1` for `h1` to `6` for `h6`).
+    pub level: u8,
+    /// The heading's `id`.
+    pub id: String,
+    /// Text content of the heading.
+    pub text: String,
+    /// Subheadings that follow this heading, up to the next heading of the same or a higher level.
+    pub children: Vec<TocEntry>,
+}
+
+/// Extracts a table of contents from rendered `html`.
+///
+/// Returns `html` with an `id` added to every heading that doesn't have one, together with the table of contents.
+/// Generated ids are slugs of the heading's text, suffixed with `-1`, `-2`, ... when they collide with another id in
+/// the document.
+pub fn generate(html: &str) -> (Html, TableOfContents) {
+    let mut ids = UniqueIds::default();
+
+    for token in Tokenizer::new(html) {
+        if let Some(id) = token.attribute("id") {
+            ids.reserve(&decode_attribute(id));
+        }
+    }
+
+    let mut output = String::with_capacity(html.len());
+    let mut toc = TableOfContents::default();
+    let mut tokens = Tokenizer::new(html);
+
+    while let Some(token) = tokens.next() {
+        let Some((name, start_tag, level)) = heading_start(&token) else {
+            output.push_str(token.source());
+            continue;
+        };
+
+        let mut content = String::new();
+        let mut end_tag = None;
+
+        for token in tokens.by_ref() {
+            if let Token::EndTag {
+                name: end_name,
+                source,
+            } = token
+                && end_name.eq_ignore_ascii_case(name)
+            {
+                end_tag = Some(source);
+                break;
+            }
+
+            content.push_str(token.source());
+        }
+
+        let Some(end_tag) = end_tag else {
+            // An unclosed heading is left as it was.
+            output.push_str(start_tag);
+            output.push_str(&content);
+            continue;
+        };
+
+        let text = text_content(&content);
+
+        let id = match token.attribute("id") {
+            Some(id) => {
+                output.push_str(start_tag);
+                decode_attribute(id)
+            }
+            None => {
+                let id = ids.unique(slugify(&text));
+                let name_end = 1 + name.len();
+                output.push_str(&start_tag[..name_end]);
+                output.push_str(" id=\"");
+                // Slugs can't contain characters that need escaping, but keep the output well-formed regardless.
+                let _ = escape_html_to(&mut output, &id);
+                output.push('"');
+                output.push_str(&start_tag[name_end..]);
+                id
+            }
+        };
+
+        output.push_str(&content);
+        output.push_str(end_tag);
+
+        toc.push(TocEntry {
+            level,
+            id,
+            text,
+            children: Vec::new(),
+        });
+    }
+
+    (Html::new_unchecked(output), toc)
+}
+
+/// Returns the name, source and level of an `h1`–`h6` start tag. Comments and the content of `script` and `style`
+/// elements are separate tokens, so headings written inside them are never matched.
+fn heading_start<'a>(token: &Token<'a>) -> Option<(&'a str, &'a str, u8)> {
+    match *token {
+        Token::StartTag {
+            name,
+            source,
+            self_closing: false,
+        } => match name.as_bytes() {
+            [b'h' | b'H', level @ b'1'..=b'6'] => Some((name, source, level - b'0')),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn render_entries(entries: &[TocEntry]) -> Html {
+    html! {
+        ol {
+            for entry in entries {
+                li {
+                    a(href: (format!("#{}", entry.id))) { (entry.text) }
+
+                    if !entry.children.is_empty() {
+                        (render_entries(&entry.children))
+                    }
+                }
+            }
+        }
+    }
+    .to_html()
+}
+
+component! {
+    /// Renders a [`TableOfContents`] as nested ordered lists of links inside a `<nav class="toc">` element.
+    ///
+    /// Nothing is rendered for an empty table of contents. Extra attributes are forwarded to the `nav` element.
+    pub fn Toc(toc: &TableOfContents) {
+        if !toc.is_empty() {
+            nav(class: "toc", #attrs) {
+                (render_entries(toc.entries()))
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! A small, forgiving tokenizer for rendered HTML, shared by the post-processing helpers.
+
+use crate::{spec::is_raw_text_element, utils::decode_char_reference};
+
+/// A piece of rendered HTML.
+///
+/// Writing the [`source()`](Token::source) of every token in order reproduces the HTML exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// Text, which may contain character references.
+    Text(&'a str),
+    /// Unescaped content of a `script` or `style` element.
+    RawText(&'a str),
+    /// A start tag. `source` is the whole tag, including `<` and `>`.
+    StartTag {
+        /// Name of the element, as written.
+        name: &'a str,
+        /// The whole tag.
+        source: &'a str,
+        /// Whether the tag ends with `/>`.
+        self_closing: bool,
+    },
+    /// An end tag. `source` is the whole tag, including `</` and `>`.
+    EndTag {
+        /// Name of the element, as written.
+        name: &'a str,
+        /// The whole tag.
+        source: &'a str,
+    },
+    /// A comment, doctype or other markup declaration.
+    Other(&'a str),
+}
+
+impl<'a> Token<'a> {
+    /// Returns the source of the token.
+    pub fn source(&self) -> &'a str {
+        match self {
+            Token::Text(source) | Token::RawText(source) | Token::Other(source) => source,
+            Token::StartTag { source, .. } | Token::EndTag { source, .. } => source,
+        }
+    }
+
+    /// Returns the raw (still escaped) value of the attribute `name` of a start tag, or `""` for an attribute without
+    /// a value. Attribute names are matched case-insensitively.
+    pub fn attribute(&self, name: &str) -> Option<&'a str> {
+        match self {
+            Token::StartTag { source, .. } => attribute(source, name),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator over the [`Token`]s of rendered HTML.
+pub(crate) struct Tokenizer<'a> {
+    rest: &'a str,
+    raw_text_element: Option<&'a str>,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub(crate) fn new(html: &'a str) -> Self {
+        Self {
+            rest: html,
+            raw_text_element: None,
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (token, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        token
+    }
+
+    fn raw_text(&mut self, element: &str) -> Token<'a> {
+        let bytes = self.rest.as_bytes();
+        let mut offset = 0;
+
+        let len = loop {
+            match self.rest[offset..].find("</") {
+                Some(index) => {
+                    let start = offset + index;
+                    let name_end = start + 2 + element.len();
+
+                    // Compare bytes, since `name_end` may fall inside a multi-byte character of the text.
+                    let is_end_tag = bytes
+                        .get(start + 2..name_end)
+                        .is_some_and(|name| name.eq_ignore_ascii_case(element.as_bytes()))
+                        && bytes.get(name_end).is_none_or(|byte| {
+                            byte.is_ascii_whitespace() || matches!(byte, b'/' | b'>')
+                        });
+
+                    if is_end_tag {
+                        break start;
+                    }
+
+                    offset = start + 2;
+                }
+                None => break self.rest.len(),
+            }
+        };
+
+        Token::RawText(self.advance(len))
+    }
+
+    fn tag(&mut self) -> Token<'a> {
+        let bytes = self.rest.as_bytes();
+        let is_end = bytes.get(1) == Some(&b'/');
+        let name_start = if is_end { 2 } else { 1 };
+
+        if !bytes
+            .get(name_start)
+            .is_some_and(|byte| byte.is_ascii_alphabetic())
+        {
+            // Not a tag (e.g. `a < b` in text written without escaping).
+            let len = self.rest[1..]
+                .find('<')
+                .map_or(self.rest.len(), |index| index + 1);
+            return Token::Text(self.advance(len));
+        }
+
+        let name_len = self.rest[name_start..]
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(self.rest.len() - name_start);
+
+        let mut quote = None;
+        let mut end = self.rest.len();
+
+        for (index, byte) in bytes.iter().enumerate().skip(name_start + name_len) {
+            match (quote, byte) {
+                (None, b'"' | b'\'') => quote = Some(*byte),
+                (Some(q), _) if q == *byte => quote = None,
+                (None, b'>') => {
+                    end = index + 1;
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let source = self.advance(end);
+        let name = &source[name_start..name_start + name_len];
+
+        if is_end {
+            Token::EndTag { name, source }
+        } else {
+            if is_raw_text_element(name) {
+                self.raw_text_element = Some(name);
+            }
+
+            Token::StartTag {
+                name,
+                source,
+                self_closing: source.ends_with("/>"),
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        if let Some(element) = self.raw_text_element.take() {
+            let token = self.raw_text(element);
+
+            if token != Token::RawText("") {
+                return Some(token);
+            }
+        }
+
+        if self.rest.starts_with("<!--") {
+            let len = self
+                .rest
+                .find("-->")
+                .map_or(self.rest.len(), |index| index + 3);
+            return Some(Token::Other(self.advance(len)));
+        }
+
+        if self.rest.starts_with("<!") || self.rest.starts_with("<?") {
+            let len = self
+                .rest
+                .find('>')
+                .map_or(self.rest.len(), |index| index + 1);
+            return Some(Token::Other(self.advance(len)));
+        }
+
+        if self.rest.starts_with('<') {
+            return Some(self.tag());
+        }
+
+        let len = self.rest.find('<').unwrap_or(self.rest.len());
+        Some(Token::Text(self.advance(len)))
+    }
+}
+
+/// Decodes the character references in the raw value of an attribute, keeping its whitespace.
+pub(crate) fn decode_attribute(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+
+        let (c, len) = decode_char_reference(&rest[index..]);
+        decoded.push(c);
+        rest = &rest[index + len..];
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Returns the raw (still escaped) value of the attribute `name` in the start tag `source`, or `""` for an attribute
+/// without a value. Attribute names are matched case-insensitively.
+pub(crate) fn attribute<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    attributes(source)
+        .find(|(attribute_name, _)| attribute_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+}
+
+/// Returns the names and raw (still escaped) values of the attributes in the start tag `source`, in order. Attributes
+/// without a value have the value `""`.
+pub(crate) fn attributes(source: &str) -> impl Iterator<Item = (&str, &str)> {
+    attribute_entries(source).map(|(name, value)| (name, value.unwrap_or_default()))
+}
+
+/// Returns the names and raw (still escaped) values of the attributes in the start tag `source`, in order, with `None`
+/// for attributes written without a value.
+pub(crate) fn attribute_entries(source: &str) -> impl Iterator<Item = (&str, Option<&str>)> {
+    let source = source
+        .strip_prefix('<')
+        .unwrap_or_default()
+        .trim_end_matches('>');
+    let mut rest = source.trim_start_matches(|c: char| !c.is_ascii_whitespace() && c != '/');
+
+    std::iter::from_fn(move || {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        let name_len = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let (attribute_name, after_name) = rest.split_at(name_len);
+        let after_name = after_name.trim_start();
+
+        let (value, after_value) = match after_name.strip_prefix('=') {
+            Some(after_equals) => {
+                let after_equals = after_equals.trim_start();
+
+                match after_equals.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let value = &after_equals[1..];
+                        let len = value.find(quote).unwrap_or(value.len());
+                        (Some(&value[..len]), &value[(len + 1).min(value.len())..])
+                    }
+                    _ => {
+                        let len = after_equals
+                            .find(|c: char| c.is_ascii_whitespace())
+                            .unwrap_or(after_equals.len());
+                        let (value, after_value) = after_equals.split_at(len);
+                        (Some(value), after_value)
+                    }
+                }
+            }
+            None => (None, after_name),
+        };
+
+        rest = after_value;
+        Some((attribute_name, value))
+    })
+}
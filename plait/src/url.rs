@@ -0,0 +1,77 @@
+use std::fmt::{self, Write};
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+
+/// Characters that are percent-encoded in a path segment: everything except RFC 3986 unreserved characters and
+/// sub-delimiters that are safe inside a segment.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b',')
+    .remove(b';')
+    .remove(b':')
+    .remove(b'@');
+
+/// Characters that are percent-encoded in a query or fragment component: everything except RFC 3986 unreserved
+/// characters.
+const QUERY_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// A [`fmt::Write`] adapter that percent-encodes everything written through it.
+struct PercentEncoder<'a> {
+    buffer: &'a mut String,
+    set: &'static AsciiSet,
+}
+
+impl fmt::Write for PercentEncoder<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for chunk in utf8_percent_encode(s, self.set) {
+            self.buffer.push_str(chunk);
+        }
+
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+/// Appends `value` to `buffer` as a percent-encoded path segment. This is used internally by the `url!` macro.
+///
+/// A value made only of dots has its dots encoded too, so that `.` or `..` can't be resolved as a relative path
+/// segment (e.g. `/users/{id}/edit` becoming `/users/../edit`).
+pub fn push_url_path_segment(buffer: &mut String, value: &(impl fmt::Display + ?Sized)) {
+    let start = buffer.len();
+
+    let mut encoder = PercentEncoder {
+        buffer,
+        set: PATH_SEGMENT,
+    };
+    let _ = write!(encoder, "{value}");
+
+    let dots = buffer.len() - start;
+
+    if dots > 0 && buffer[start..].bytes().all(|byte| byte == b'.') {
+        buffer.truncate(start);
+        buffer.extend(std::iter::repeat_n("%2E", dots));
+    }
+}
+
+#[doc(hidden)]
+/// Appends `value` to `buffer` as a percent-encoded query component. This is used internally by the `url!` macro.
+pub fn push_url_query_component(buffer: &mut String, value: &(impl fmt::Display + ?Sized)) {
+    let mut encoder = PercentEncoder {
+        buffer,
+        set: QUERY_COMPONENT,
+    };
+    let _ = write!(encoder, "{value}");
+}
@@ -0,0 +1,432 @@
+//! Rules for the URLs rendered into URL attributes.
+//!
+//! [Provide](crate::context::provide) a [`UrlRules`] in the render context to upgrade or block insecure `http:` URLs
+//! and protocol-relative `//host` URLs, which load over `http:` when the page itself was served over `http:`. The rules
+//! apply to every escaped dynamic value of a [URL attribute](crate::spec::URL_ATTRIBUTES) (`href: (url)`,
+//! `src?: url`, ...). Each URL of a `srcset` or `ping` attribute is checked separately.
+//!
+//! Blocked URLs are removed, and an attribute left without URLs is not rendered at all. Set a [`UrlPolicy`] with
+//! [`UrlRules::on_blocked`] to replace them with a fallback URL, or to find them during development by panicking or
+//! (with the `tracing` feature) logging them instead. Literal values and raw values (`href: #(url)`) are trusted and
+//! left as they are, and so are [`SafeUrl`] values, which wrap URLs that are valid by construction (such as the paths
+//! of a typed router) without giving up escaping.
+//!
+//! # Example
+//!
+//! ```
+//! use plait::{context, html, ToHtml, url_policy::{HttpUrls, ProtocolRelativeUrls, UrlRules}};
+//!
+//! let avatar = "http://cdn.example.com/avatar.png";
+//! let script = "//cdn.example.com/app.js";
+//! let homepage = "http://example.com";
+//!
+//! let page = html! {
+//!     img(src: (avatar));
+//!     script(src: (script)) {}
+//!     a(href: (homepage)) { "Homepage" }
+//! };
+//!
+//! let rules = UrlRules::new()
+//!     .http(HttpUrls::Upgrade)
+//!     .protocol_relative(ProtocolRelativeUrls::Block)
+//!     .attribute("href", UrlRules::new());
+//!
+//! assert_eq!(
+//!     context::provide(rules, || page.to_html()),
+//!     "<img src=\"https://cdn.example.com/avatar.png\"><script></script>\
+//!     <a href=\"http://example.com\">Homepage</a>"
+//! );
+//! ```
+//!
+//! A route that already produces a valid URL can return a [`SafeUrl`] and be used directly as an attribute value.
+//! Other values implementing [`Url`], such as a typed route wrapped in [`Href`], can be wrapped in `SafeUrl` too:
+//!
+//! ```
+//! use plait::{context, html, url, ToHtml, url_policy::{HttpUrls, SafeUrl, UrlRules}};
+//!
+//! mod routes {
+//!     use plait::{url, url_policy::SafeUrl};
+//!
+//!     pub fn user_profile(id: u32) -> SafeUrl<String> {
+//!         SafeUrl(url!("http://legacy.example.com/users/{id}"))
+//!     }
+//! }
+//!
+//! let page = html! {
+//!     a(href: routes::user_profile(7)) { "Profile" }
+//! };
+//!
+//! assert_eq!(
+//!     context::provide(UrlRules::new().http(HttpUrls::Block), || page.to_html()),
+//!     "<a href=\"http://legacy.example.com/users/7\">Profile</a>"
+//! );
+//! ```
+use std::{borrow::Cow, fmt};
+
+use crate::{
+    Href, RenderEscaped, RenderMaybeAttributeEscaped, RouteHref, context, utils::escape_html_to,
+};
+
+/// What to do with URLs using the insecure `http:` scheme.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpUrls {
+    /// Render them unchanged.
+    #[default]
+    Allow,
+    /// Rewrite them to `https:`.
+    Upgrade,
+    /// Remove them.
+    Block,
+}
+
+/// What to do with protocol-relative URLs (`//host/path`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProtocolRelativeUrls {
+    /// Render them unchanged.
+    #[default]
+    Allow,
+    /// Prefix them with `https:`.
+    Upgrade,
+    /// Remove them.
+    Block,
+}
+
+/// What to do with a URL blocked by the [`UrlRules`] in the render context.
+///
+/// The enum is non-exhaustive because the `Log` variant only exists with the `tracing` feature, and features are
+/// shared by every crate using plait: a `match` written without `tracing` must keep compiling when another dependency
+/// enables it.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum UrlPolicy {
+    /// Remove the URL, and the attribute if no URLs are left.
+    #[default]
+    Strip,
+    /// Replace the URL with another URL, such as `#` or `about:blank`. The fallback is written as it is, so it must
+    /// be escaped.
+    Fallback(Cow<'static, str>),
+    /// Panic with a message naming the attribute and the URL, e.g. in debug builds or tests.
+    Panic,
+    /// Log the attribute and the URL as a `tracing` warning and remove the URL.
+    #[cfg(feature = "tracing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+    Log,
+}
+
+impl UrlPolicy {
+    /// Returns what replaces the `url` blocked in the attribute `name`.
+    fn blocked<'a>(&self, name: &str, url: &str) -> Option<Cow<'a, str>> {
+        match self {
+            UrlPolicy::Strip => None,
+            UrlPolicy::Fallback(fallback) => Some(Cow::Owned(fallback.to_string())),
+            UrlPolicy::Panic => panic!("blocked URL `{url}` in the `{name}` attribute"),
+            #[cfg(feature = "tracing")]
+            UrlPolicy::Log => {
+                tracing::warn!(attribute = name, url, "blocked URL");
+                None
+            }
+        }
+    }
+}
+
+/// Rules applied to the URLs rendered into URL attributes. See the [module documentation](self).
+///
+/// By default every URL is allowed. Rules set with [`attribute()`](UrlRules::attribute) replace these rules for one
+/// attribute.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UrlRules {
+    http: HttpUrls,
+    protocol_relative: ProtocolRelativeUrls,
+    attributes: Vec<(Cow<'static, str>, UrlRules)>,
+    on_blocked: UrlPolicy,
+}
+
+impl UrlRules {
+    /// Creates rules allowing every URL.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what happens to `http:` URLs.
+    pub fn http(mut self, http: HttpUrls) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sets what happens to protocol-relative URLs.
+    pub fn protocol_relative(mut self, protocol_relative: ProtocolRelativeUrls) -> Self {
+        self.protocol_relative = protocol_relative;
+        self
+    }
+
+    /// Sets what happens to blocked URLs, for every attribute. The policy of rules set with
+    /// [`attribute()`](UrlRules::attribute) is ignored.
+    pub fn on_blocked(mut self, policy: UrlPolicy) -> Self {
+        self.on_blocked = policy;
+        self
+    }
+
+    /// Uses `rules` instead of these rules for the attribute `name` (e.g. `"href"`), matched case-insensitively.
+    pub fn attribute(mut self, name: impl Into<Cow<'static, str>>, rules: UrlRules) -> Self {
+        self.attributes.push((name.into(), rules));
+        self
+    }
+
+    /// Returns the rules applied to the attribute `name`.
+    pub fn for_attribute(&self, name: &str) -> &UrlRules {
+        self.attributes
+            .iter()
+            .rev()
+            .find(|(attribute, _)| attribute.eq_ignore_ascii_case(name))
+            .map_or(self, |(_, rules)| rules.for_attribute(name))
+    }
+
+    /// Applies these rules to `url`, ignoring per-attribute rules. Returns `None` if the URL is blocked, or the URL
+    /// (upgraded to `https:` if needed) otherwise.
+    pub fn apply<'a>(&self, url: &'a str) -> Option<Cow<'a, str>> {
+        let trimmed = url.trim_matches(|c: char| c <= ' ');
+
+        if is_protocol_relative(trimmed) {
+            return match self.protocol_relative {
+                ProtocolRelativeUrls::Allow => Some(Cow::Borrowed(url)),
+                ProtocolRelativeUrls::Upgrade => Some(Cow::Owned(format!("https:{trimmed}"))),
+                ProtocolRelativeUrls::Block => None,
+            };
+        }
+
+        match http_rest(trimmed) {
+            Some(rest) => match self.http {
+                HttpUrls::Allow => Some(Cow::Borrowed(url)),
+                HttpUrls::Upgrade => Some(Cow::Owned(format!("https:{rest}"))),
+                HttpUrls::Block => None,
+            },
+            None => Some(Cow::Borrowed(url)),
+        }
+    }
+
+    /// Applies the rules of the attribute `name` to its rendered `value`, which holds several URLs for `srcset` and
+    /// `ping`. Returns `None` if no URLs are left.
+    fn apply_attribute<'a>(&self, name: &str, value: &'a str) -> Option<Cow<'a, str>> {
+        let rules = self.for_attribute(name);
+
+        let separator = if name.eq_ignore_ascii_case("srcset") {
+            ","
+        } else if name.eq_ignore_ascii_case("ping") {
+            " "
+        } else {
+            return rules
+                .apply(value)
+                .or_else(|| self.on_blocked.blocked(name, value));
+        };
+
+        let urls: Vec<Cow<'a, str>> = value
+            .split(separator)
+            .filter(|url| !url.trim().is_empty())
+            .filter_map(|url| {
+                rules
+                    .apply(url)
+                    .or_else(|| self.on_blocked.blocked(name, url.trim()))
+            })
+            .collect();
+
+        (!urls.is_empty()).then(|| Cow::Owned(urls.join(separator)))
+    }
+}
+
+/// Trait for values that are valid URLs by construction, and can be wrapped in a [`SafeUrl`].
+///
+/// # Built-in implementations
+///
+/// | Type                         | Behavior                                   |
+/// |------------------------------|--------------------------------------------|
+/// | `&str`, `String`             | The string is used as the URL              |
+/// | [`Href<T: RouteHref>`](Href) | The URL of the route                       |
+/// | `&T` where `T: Url`          | Delegates to inner value                   |
+pub trait Url {
+    /// Writes the (unescaped) URL into `f`.
+    fn write_url(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result;
+}
+
+impl<T> Url for &T
+where
+    T: Url + ?Sized,
+{
+    fn write_url(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        (**self).write_url(f)
+    }
+}
+
+impl Url for str {
+    fn write_url(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl Url for String {
+    fn write_url(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl<T> Url for Href<T>
+where
+    T: RouteHref,
+{
+    fn write_url(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        self.0.render_href(f)
+    }
+}
+
+/// A URL that has already been validated, and is rendered into URL attributes without being checked against the
+/// [`UrlRules`] in the render context. See the [module documentation](self).
+///
+/// The URL is still HTML-escaped. Wrapping a value in `SafeUrl` asserts that it is a URL the page should link to, so
+/// only wrap URLs built by the application, never URLs taken from user input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SafeUrl<T>(pub T);
+
+impl<T> RenderEscaped for SafeUrl<T>
+where
+    T: Url,
+{
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        let mut url = String::new();
+        self.0.write_url(&mut url)?;
+
+        escape_html_to(f, &url)
+    }
+
+    fn is_safe_url(&self) -> bool {
+        true
+    }
+}
+
+impl<T> fmt::Display for SafeUrl<T>
+where
+    T: Url,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_url(f)
+    }
+}
+
+/// Returns `true` if `url` starts with two slashes, where browsers treat `\` like `/`.
+fn is_protocol_relative(url: &str) -> bool {
+    let mut chars = url.chars().filter(|c| !matches!(c, '\t' | '\n' | '\r'));
+
+    matches!(
+        (chars.next(), chars.next()),
+        (Some('/' | '\\'), Some('/' | '\\'))
+    )
+}
+
+/// Returns the part of an `http:` URL after the scheme, matching the scheme case-insensitively and ignoring the tabs
+/// and line breaks browsers remove from URLs.
+fn http_rest(url: &str) -> Option<&str> {
+    let mut expected = b"http:".iter();
+    let mut next = expected.next();
+
+    for (index, byte) in url.bytes().enumerate() {
+        let Some(expected_byte) = next else {
+            return Some(&url[index..]);
+        };
+
+        if matches!(byte, b'\t' | b'\n' | b'\r') {
+            continue;
+        }
+
+        if !byte.eq_ignore_ascii_case(expected_byte) {
+            return None;
+        }
+
+        next = expected.next();
+    }
+
+    next.is_none().then_some("")
+}
+
+/// Writes ` name="value"`, or nothing if the rules in the render context block every URL of `value`.
+fn write_attribute(
+    rules: &UrlRules,
+    name: &str,
+    value: &str,
+    f: &mut (dyn fmt::Write + '_),
+) -> fmt::Result {
+    match rules.apply_attribute(name, value) {
+        Some(value) => {
+            f.write_str(" ")?;
+            f.write_str(name)?;
+            f.write_str("=\"")?;
+            f.write_str(&value)?;
+            f.write_str("\"")
+        }
+        None => Ok(()),
+    }
+}
+
+#[doc(hidden)]
+/// The value of a URL attribute, checked against the [`UrlRules`] in the render context. This is used internally by
+/// the `html!` macro for `name: (expr)` URL attributes.
+pub struct UrlValue<T>(pub T);
+
+impl<T> RenderMaybeAttributeEscaped for UrlValue<T>
+where
+    T: RenderEscaped,
+{
+    fn render_maybe_attribute_escaped(
+        &self,
+        name: &str,
+        f: &mut (dyn fmt::Write + '_),
+    ) -> fmt::Result {
+        match context::get::<UrlRules>() {
+            Some(rules) if !self.0.is_safe_url() => {
+                let mut value = String::new();
+                self.0.render_escaped(&mut value)?;
+                write_attribute(&rules, name, &value, f)
+            }
+            _ => {
+                f.write_str(" ")?;
+                f.write_str(name)?;
+                f.write_str("=\"")?;
+                self.0.render_escaped(f)?;
+                f.write_str("\"")
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+/// The value of an optional URL attribute, checked against the [`UrlRules`] in the render context. This is used
+/// internally by the `html!` macro for `name?: expr` URL attributes.
+pub struct MaybeUrlValue<T>(pub T);
+
+impl<T> RenderMaybeAttributeEscaped for MaybeUrlValue<T>
+where
+    T: RenderMaybeAttributeEscaped,
+{
+    fn render_maybe_attribute_escaped(
+        &self,
+        name: &str,
+        f: &mut (dyn fmt::Write + '_),
+    ) -> fmt::Result {
+        let rules = match context::get::<UrlRules>() {
+            Some(rules) if !self.0.is_safe_url() => rules,
+            _ => return self.0.render_maybe_attribute_escaped(name, f),
+        };
+
+        let mut attribute = String::new();
+        self.0
+            .render_maybe_attribute_escaped(name, &mut attribute)?;
+
+        let value = attribute
+            .strip_prefix(' ')
+            .and_then(|attribute| attribute.strip_prefix(name))
+            .and_then(|attribute| attribute.strip_prefix("=\""))
+            .and_then(|attribute| attribute.strip_suffix('"'));
+
+        match value {
+            Some(value) => write_attribute(&rules, name, value, f),
+            None => f.write_str(&attribute),
+        }
+    }
+}
@@ -1,4 +1,13 @@
-use std::fmt;
+use std::{
+    cell::Cell,
+    fmt::{self, Write as _},
+};
+
+use crate::{
+    RenderEscaped,
+    charset::{Charset, write_ascii},
+    spec,
+};
 
 /// Escapes HTML-special characters in `input` and writes the result into `writer`.
 ///
@@ -12,8 +21,66 @@ use std::fmt;
 /// | `"`       | `&quot;`    |
 /// | `'`       | `&#39;`     |
 ///
-/// Characters that don't need escaping are written through in bulk for performance.
+/// Characters that don't need escaping are written through in bulk for performance. Non-ASCII characters are written
+/// as numeric character references if the render context asks for [`Charset::Ascii`]. Inside [`render_raw_text`],
+/// `input` is written as it is.
 pub fn escape_html_to(writer: &mut (impl fmt::Write + ?Sized), input: &str) -> fmt::Result {
+    if RAW_TEXT.get() {
+        return writer.write_str(input);
+    }
+
+    if !input.is_ascii() && Charset::current() == Charset::Ascii {
+        return escape_html_utf8_to(&mut AsciiWriter(writer), input);
+    }
+
+    escape_html_utf8_to(writer, input)
+}
+
+/// Writes into the inner writer, encoding non-ASCII characters as numeric character references.
+struct AsciiWriter<'a, W: ?Sized>(&'a mut W);
+
+impl<W> fmt::Write for AsciiWriter<'_, W>
+where
+    W: fmt::Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        write_ascii(self.0, s)
+    }
+}
+
+#[doc(hidden)]
+/// Writes into the inner writer, encoding newlines, carriage returns and tabs as numeric character references. This is
+/// used internally for the values of [whitespace sensitive attributes](crate::spec::WHITESPACE_SENSITIVE_ATTRIBUTES),
+/// or of every attribute with the `encode-attribute-whitespace` feature.
+pub struct AttributeWhitespace<'a, W: ?Sized>(pub &'a mut W);
+
+impl<W> fmt::Write for AttributeWhitespace<'_, W>
+where
+    W: fmt::Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut last = 0;
+
+        for (index, c) in s.match_indices(['\n', '\r', '\t']) {
+            self.0.write_str(&s[last..index])?;
+            self.0.write_str(match c {
+                "\n" => "&#10;",
+                "\r" => "&#13;",
+                _ => "&#9;",
+            })?;
+            last = index + 1;
+        }
+
+        self.0.write_str(&s[last..])
+    }
+}
+
+/// Returns `true` if newlines and tabs in the value of the attribute `name` are written as character references.
+pub(crate) fn encodes_attribute_whitespace(name: &str) -> bool {
+    cfg!(feature = "encode-attribute-whitespace") || spec::is_whitespace_sensitive_attribute(name)
+}
+
+fn escape_html_utf8_to(writer: &mut (impl fmt::Write + ?Sized), input: &str) -> fmt::Result {
     let bytes = input.as_bytes();
     let mut last = 0usize;
     let mut i = 0usize;
@@ -46,3 +113,173 @@ pub fn escape_html_to(writer: &mut (impl fmt::Write + ?Sized), input: &str) -> f
 
     Ok(())
 }
+
+thread_local! {
+    /// Whether escaped values are being rendered as the text of a `script` or `style` element.
+    static RAW_TEXT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Restores the previous raw text state when dropped.
+struct RawTextGuard(bool);
+
+impl Drop for RawTextGuard {
+    fn drop(&mut self) {
+        RAW_TEXT.set(self.0);
+    }
+}
+
+#[doc(hidden)]
+/// Renders `value` as the text of the [raw text element](crate::spec::RAW_TEXT_ELEMENTS) `tag`. This is used
+/// internally by `(expr)` inside `script` and `style` elements.
+///
+/// Character references aren't decoded in raw text, so text is written without escaping, except for the sequences
+/// that would end the element early (see [`escape_raw_text_to`]).
+pub fn render_raw_text(
+    value: &(impl RenderEscaped + ?Sized),
+    tag: &str,
+    f: &mut (dyn fmt::Write + '_),
+) -> fmt::Result {
+    let mut text = String::new();
+
+    {
+        let _guard = RawTextGuard(RAW_TEXT.replace(true));
+        value.render_escaped(&mut text)?;
+    }
+
+    escape_raw_text_to(f, &text, tag)
+}
+
+/// Writes `input` as the text of the raw text element `tag`: as it is, except for the `<` of a sequence that would end
+/// the element early. In `script` the `<` of `</script` and `<!--` is written as `\u003c`, which JavaScript strings and
+/// JSON both read as `<`, and in `style` `</style` is written as `<\/style`, which CSS reads the same way.
+/// Keep in sync with `escape_raw_text_to` in `plait-macros`.
+pub(crate) fn escape_raw_text_to(
+    writer: &mut (impl fmt::Write + ?Sized),
+    input: &str,
+    tag: &str,
+) -> fmt::Result {
+    let mut last = 0;
+
+    for (index, _) in input.match_indices('<') {
+        let rest = &input.as_bytes()[index + 1..];
+
+        let ends_element = rest.len() > tag.len()
+            && rest[0] == b'/'
+            && rest[1..=tag.len()].eq_ignore_ascii_case(tag.as_bytes());
+        let starts_comment = tag == "script" && rest.starts_with(b"!--");
+
+        if tag == "script" && (ends_element || starts_comment) {
+            writer.write_str(&input[last..index])?;
+            writer.write_str("\\u003c")?;
+            last = index + 1;
+        } else if ends_element {
+            writer.write_str(&input[last..=index])?;
+            writer.write_str("\\")?;
+            last = index + 1;
+        }
+    }
+
+    writer.write_str(&input[last..])
+}
+
+/// Writes a float as a plain decimal number.
+///
+/// `ryu` switches to scientific notation for very small and very large values (`1e-7`), which some CSS and JavaScript
+/// consumers of attribute values don't expect, so those values are written with [`Display`](fmt::Display) instead.
+#[cfg(feature = "ryu")]
+pub(crate) fn write_float<T>(f: &mut (dyn fmt::Write + '_), value: T) -> fmt::Result
+where
+    T: ryu::Float + fmt::Display,
+{
+    let mut buffer = ryu::Buffer::new();
+    let formatted = buffer.format(value);
+
+    if formatted.contains('e') {
+        write!(f, "{value}")
+    } else {
+        f.write_str(formatted)
+    }
+}
+
+/// Decodes the character reference at the start of `s` (which starts with `&`), returning the character and the
+/// number of bytes consumed. Unknown references decode to a literal `&`.
+pub(crate) fn decode_char_reference(s: &str) -> (char, usize) {
+    let Some(end) = s[1..].find(';').map(|end| end + 1).filter(|end| *end <= 32) else {
+        return ('&', 1);
+    };
+
+    let name = &s[1..end];
+    let decoded = match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => name
+            .strip_prefix("#x")
+            .or_else(|| name.strip_prefix("#X"))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .or_else(|| name.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+            .and_then(char::from_u32),
+    };
+
+    match decoded {
+        Some(c) => (c, end + 1),
+        None => ('&', 1),
+    }
+}
+
+/// The 64-bit FNV-1a hash, as used for the hashes of [`Asset`](crate::Asset)s.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`.
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv1a::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Writes `value` as a JSON string literal, escaping quotes, backslashes and control characters.
+pub(crate) fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+}
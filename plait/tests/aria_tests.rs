@@ -0,0 +1,120 @@
+#![cfg(feature = "aria-patterns")]
+
+use plait::{
+    ToHtml,
+    aria::{Dialog, Disclosure, Menu, MenuItem, Tabs},
+    html,
+};
+
+#[test]
+fn test_disclosure() {
+    let html = html! {
+        @Disclosure(id: "details", label: "More", open: true; class: "toggle") {
+            p { "Hidden content" }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<button type=\"button\" aria-expanded=\"true\" aria-controls=\"details\" class=\"toggle\">More</button>\
+        <div id=\"details\"><p>Hidden content</p></div>"
+    );
+}
+
+#[test]
+fn test_disclosure_closed() {
+    let html = html! {
+        @Disclosure(id: "details", label: "More", open: false) { "content" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<button type=\"button\" aria-expanded=\"false\" aria-controls=\"details\">More</button>\
+        <div id=\"details\" hidden>content</div>"
+    );
+}
+
+#[test]
+fn test_dialog() {
+    let html = html! {
+        @Dialog(id: "confirm", title: "Delete post?"; class: "modal") {
+            p { "Cannot be undone." }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div role=\"dialog\" id=\"confirm\" aria-modal=\"true\" aria-labelledby=\"confirm-title\" class=\"modal\">\
+        <h2 id=\"confirm-title\">Delete post?</h2><p>Cannot be undone.</p></div>"
+    );
+}
+
+#[test]
+fn test_dialog_escapes_title() {
+    let html = html! {
+        @Dialog(id: "d", title: "<script>") {}
+    };
+
+    assert!(
+        html.to_html()
+            .contains("<h2 id=\"d-title\">&lt;script&gt;</h2>")
+    );
+}
+
+#[test]
+fn test_tabs() {
+    let tabs = [
+        ("One", html! { p { "First" } }.to_html()),
+        ("Two", html! { p { "Second" } }.to_html()),
+    ];
+
+    let html = html! {
+        @Tabs(id: "settings", tabs: &tabs, selected: 1) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div id=\"settings\"><div role=\"tablist\">\
+        <button type=\"button\" role=\"tab\" id=\"settings-tab-0\" aria-selected=\"false\" \
+        aria-controls=\"settings-panel-0\" tabindex=\"-1\">One</button>\
+        <button type=\"button\" role=\"tab\" id=\"settings-tab-1\" aria-selected=\"true\" \
+        aria-controls=\"settings-panel-1\">Two</button></div>\
+        <div role=\"tabpanel\" id=\"settings-panel-0\" aria-labelledby=\"settings-tab-0\" tabindex=\"0\" hidden>\
+        <p>First</p></div>\
+        <div role=\"tabpanel\" id=\"settings-panel-1\" aria-labelledby=\"settings-tab-1\" tabindex=\"0\">\
+        <p>Second</p></div></div>"
+    );
+}
+
+#[test]
+fn test_tabs_empty() {
+    let tabs: Vec<(&str, &str)> = Vec::new();
+
+    let html = html! {
+        @Tabs(id: "t", tabs: &tabs, selected: 0) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div id=\"t\"><div role=\"tablist\"></div></div>"
+    );
+}
+
+#[test]
+fn test_menu() {
+    let html = html! {
+        @Menu(id: "actions", label: "Actions", open: false) {
+            @MenuItem() { "Edit" }
+            @MenuItem(; data_action: "delete") { "Delete" }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<button type=\"button\" id=\"actions-button\" aria-haspopup=\"menu\" aria-expanded=\"false\" \
+        aria-controls=\"actions\">Actions</button>\
+        <ul role=\"menu\" id=\"actions\" aria-labelledby=\"actions-button\" hidden>\
+        <li role=\"menuitem\" tabindex=\"-1\">Edit</li>\
+        <li role=\"menuitem\" tabindex=\"-1\" data-action=\"delete\">Delete</li></ul>"
+    );
+}
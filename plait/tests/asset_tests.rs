@@ -0,0 +1,95 @@
+use plait::{Asset, AssetUrls, ToHtml, asset, context, html};
+
+const SMALL_CSS: Asset = asset!("tests/assets/small.css");
+const APP_JS: Asset = asset!("tests/assets/app.js");
+
+#[test]
+fn test_asset_content_and_hash() {
+    assert_eq!(SMALL_CSS.path(), "tests/assets/small.css");
+    assert_eq!(SMALL_CSS.content(), ".btn {\n  color: red;\n}\n");
+    assert_eq!(SMALL_CSS.hash().len(), 16);
+    assert_ne!(SMALL_CSS.hash(), APP_JS.hash());
+    assert_eq!(
+        SMALL_CSS.url(),
+        format!("/tests/assets/small.css?v={}", SMALL_CSS.hash())
+    );
+}
+
+#[test]
+fn test_asset_inline_css() {
+    let html = html! {
+        head { (SMALL_CSS) }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<head><style>.btn {\n  color: red;\n}\n</style></head>"
+    );
+}
+
+#[test]
+fn test_asset_linked_css() {
+    let html = html! {
+        head { (SMALL_CSS.inline_threshold(0)) }
+    };
+
+    assert_eq!(
+        html.to_html().to_string(),
+        format!(
+            "<head><link rel=\"stylesheet\" href=\"/tests/assets/small.css?v={}\"></head>",
+            SMALL_CSS.hash()
+        )
+    );
+}
+
+#[test]
+fn test_asset_js() {
+    let html = html! {
+        (APP_JS) (APP_JS.inline_threshold(0))
+    };
+
+    assert_eq!(
+        html.to_html().to_string(),
+        format!(
+            "<script>console.log(\"plait\");\n</script><script src=\"/tests/assets/app.js?v={}\"></script>",
+            APP_JS.hash()
+        )
+    );
+}
+
+#[test]
+fn test_asset_urls_resolver() {
+    let html = html! {
+        (SMALL_CSS.inline_threshold(0)) (APP_JS.inline_threshold(0))
+    };
+
+    assert_eq!(
+        context::provide(AssetUrls::prefix("https://cdn.example.com/"), || html
+            .to_html())
+        .to_string(),
+        format!(
+            "<link rel=\"stylesheet\" href=\"https://cdn.example.com/tests/assets/small.css?v={}\">\
+            <script src=\"https://cdn.example.com/tests/assets/app.js?v={}\"></script>",
+            SMALL_CSS.hash(),
+            APP_JS.hash()
+        )
+    );
+
+    let urls = AssetUrls::new(|asset| {
+        let name = asset.path().trim_start_matches("tests/assets/");
+        format!("/static/{name}?v={}&x=\"", asset.hash())
+    });
+
+    assert_eq!(
+        context::provide(urls.clone(), || SMALL_CSS.url()),
+        format!("/static/small.css?v={}&x=\"", SMALL_CSS.hash())
+    );
+    assert_eq!(
+        context::provide(urls, || html! { (SMALL_CSS.inline_threshold(0)) }.to_html()).to_string(),
+        format!(
+            "<link rel=\"stylesheet\" href=\"/static/small.css?v={}&amp;x=&quot;\">",
+            SMALL_CSS.hash()
+        )
+    );
+    assert_eq!(SMALL_CSS.url(), SMALL_CSS.default_url());
+}
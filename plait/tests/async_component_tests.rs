@@ -0,0 +1,152 @@
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use plait::{Html, RenderAsync, ToHtml, async_component, html, ids};
+
+/// Runs a future that completes without waiting on anything.
+fn block_on(future: impl Future<Output = Html>) -> Html {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(html) => html,
+        Poll::Pending => panic!("the future is not ready"),
+    }
+}
+
+struct Db {
+    users: Vec<&'static str>,
+}
+
+impl Db {
+    async fn find(&self, id: usize) -> Option<&'static str> {
+        self.users.get(id).copied()
+    }
+}
+
+async_component! {
+    pub fn UserBadge<'a>(db: &'a Db, id: usize, prefix?: &'a str) {
+        let user = db.find(*id).await;
+        let name = user.unwrap_or("unknown");
+        span(class: "badge") {
+            if let Some(prefix) = prefix {
+                (prefix) " "
+            }
+            (name)
+        }
+        let length = name.len();
+        small { (length) }
+    }
+
+    pub fn Field(label: &'static str) {
+        let id = ids::next("field");
+        label(for: id) { (label) }
+        input(id: id);
+    }
+
+    pub fn Fieldset() {
+        let fields = [Field { label: "A" }.render_async().await, Field { label: "B" }.render_async().await];
+        let id = ids::next("field");
+        fieldset(id: id) {
+            for field in &fields {
+                (field)
+            }
+        }
+    }
+
+    pub fn UserList<'a>(db: &'a Db, ids: &'a [usize]) {
+        let badges = {
+            let mut badges = Vec::new();
+            for id in ids.iter() {
+                badges.push(UserBadge { db, id: *id, prefix: None }.render_async().await);
+            }
+            badges
+        };
+        ul {
+            for badge in &badges {
+                li { (badge) }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_async_component() {
+    let db = Db {
+        users: vec!["Alice", "Bob"],
+    };
+
+    let badge = block_on(
+        UserBadge {
+            db: &db,
+            id: 1,
+            prefix: Some("@"),
+        }
+        .render_async(),
+    );
+
+    assert_eq!(badge, "<span class=\"badge\">@ Bob</span><small>3</small>");
+}
+
+#[test]
+fn test_nested_async_components() {
+    let db = Db {
+        users: vec!["Alice", "Bob"],
+    };
+
+    let list = block_on(
+        UserList {
+            db: &db,
+            ids: &[0, 5],
+        }
+        .render_async(),
+    );
+    let page = html! { main { (list) } };
+
+    assert_eq!(
+        page.to_html(),
+        "<main><ul>\
+        <li><span class=\"badge\">Alice</span><small>5</small></li>\
+        <li><span class=\"badge\">unknown</span><small>7</small></li>\
+        </ul></main>"
+    );
+}
+
+#[test]
+fn test_async_components_share_ids() {
+    let fieldset = block_on(Fieldset {}.render_async());
+
+    assert_eq!(
+        fieldset,
+        "<fieldset id=\"field-2\"><label for=\"field\">A</label><input id=\"field\">\
+        <label for=\"field-1\">B</label><input id=\"field-1\"></fieldset>"
+    );
+}
+
+#[test]
+fn test_ids_scope_covers_the_page() {
+    let page = block_on(ids::scope(async {
+        let field = Field { label: "A" }.render_async().await;
+
+        html! {
+            (field)
+            input(id: ids::next("field"));
+        }
+        .to_html()
+    }));
+
+    assert_eq!(
+        page,
+        "<label for=\"field\">A</label><input id=\"field\"><input id=\"field-1\">"
+    );
+}
+
+#[test]
+fn test_async_components_are_send() {
+    fn assert_send(_: impl Future + Send) {}
+
+    let db = Db {
+        users: vec!["Alice"],
+    };
+
+    assert_send(UserList { db: &db, ids: &[0] }.render_async());
+}
@@ -0,0 +1,53 @@
+use plait::{Attrs, ToHtml, attrs, component, data, html};
+
+#[test]
+fn test_attrs_macro() {
+    let id = 3;
+    let common = attrs!(class: "card", data_id: id, r#type: "note").set("bad name", "x");
+
+    assert_eq!(common.len(), 3);
+    assert_eq!(
+        common.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["class", "data-id", "type"]
+    );
+}
+
+#[test]
+fn test_attrs_in_template_let() {
+    let html = html! {
+        let common = attrs!(class: "item", title: "<\"x\">");
+        for label in ["a", "b"] {
+            li(#attrs(&common)) { (label) }
+        }
+        p(#attrs(common.clone()), id: "last") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<li class=\"item\" title=\"&lt;&quot;x&quot;&gt;\">a</li>\
+        <li class=\"item\" title=\"&lt;&quot;x&quot;&gt;\">b</li>\
+        <p class=\"item\" title=\"&lt;&quot;x&quot;&gt;\" id=\"last\"></p>"
+    );
+}
+
+#[test]
+fn test_attrs_extend_and_forward() {
+    component! {
+        fn Panel() {
+            section(#attrs) { #children }
+        }
+    }
+
+    let group = Attrs::new()
+        .set("role", "region")
+        .extend(data!(panel: "main"));
+
+    let html = html! {
+        @Panel(; #attrs(&group)) { "Body" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        r#"<section role="region" data-panel="main">Body</section>"#
+    );
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "axum")]
+
+use axum::{
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+};
+use plait::{ToHtml, html};
+
+fn content_type(response: &Response) -> &str {
+    response.headers()[CONTENT_TYPE].to_str().unwrap()
+}
+
+#[test]
+fn test_fragment_into_response() {
+    let name = "<World>";
+    let response = html! { p { "Hello, " (name) } }.into_response();
+
+    assert!(response.status().is_success());
+    assert_eq!(content_type(&response), "text/html; charset=utf-8");
+}
+
+#[test]
+fn test_html_into_response() {
+    let response = html! { p { "Hello" } }.to_html().into_response();
+
+    assert_eq!(content_type(&response), "text/html; charset=utf-8");
+}
@@ -0,0 +1,308 @@
+use std::{
+    cell::Cell,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use plait::{
+    Html, ToHtml,
+    cache::{self, CacheEntry, CacheKey, CacheStore, LruCache, RenderCache, RevalidateJob},
+    context, html,
+};
+
+fn entry(html: &str) -> CacheEntry {
+    CacheEntry {
+        html: Html::new_unchecked(html.to_owned()),
+        stale_at: None,
+    }
+}
+
+#[test]
+fn test_cache_without_render_cache() {
+    let renders = &Cell::new(0);
+
+    let html = html! {
+        cache!(key = 1) {
+            ({ renders.set(renders.get() + 1); "value" })
+        }
+    };
+
+    assert_eq!(html.to_html(), "value");
+    assert_eq!(html.to_html(), "value");
+    assert_eq!(renders.get(), 2);
+}
+
+#[test]
+fn test_cache_reuses_output() {
+    let renders = &Cell::new(0);
+    let cache = RenderCache::new(LruCache::new(10));
+
+    let section = |page: u32| {
+        html! {
+            section {
+                cache!(key = ("list", page)) {
+                    ({ renders.set(renders.get() + 1); "" })
+                    "page " (page)
+                }
+            }
+        }
+    };
+
+    context::provide(cache.clone(), || {
+        assert_eq!(section(1).to_html(), "<section>page 1</section>");
+        assert_eq!(section(1).to_html(), "<section>page 1</section>");
+        assert_eq!(section(2).to_html(), "<section>page 2</section>");
+    });
+
+    assert_eq!(renders.get(), 2);
+}
+
+#[test]
+fn test_cache_invalidate_and_clear() {
+    let renders = &Cell::new(0);
+    let cache = RenderCache::new(LruCache::new(10));
+
+    let html = html! {
+        cache!(key = "sidebar") {
+            ({ renders.set(renders.get() + 1); "sidebar" })
+        }
+    };
+
+    let render = || context::provide(cache.clone(), || html.to_html());
+
+    render();
+    render();
+    assert_eq!(renders.get(), 1);
+
+    cache.invalidate("sidebar");
+    render();
+    assert_eq!(renders.get(), 2);
+
+    cache.clear();
+    render();
+    assert_eq!(renders.get(), 3);
+}
+
+#[test]
+fn test_cache_ttl() {
+    let renders = &Cell::new(0);
+    let cache = RenderCache::new(LruCache::new(10));
+
+    let html = html! {
+        cache!(key = "short", ttl = 20ms) {
+            ({ renders.set(renders.get() + 1); "short" })
+        }
+        cache!(key = "long", ttl = Duration::from_secs(60)) {
+            ({ renders.set(renders.get() + 1); "long" })
+        }
+    };
+
+    let render = || context::provide(cache.clone(), || html.to_html());
+
+    assert_eq!(render(), "shortlong");
+    assert_eq!(renders.get(), 2);
+
+    thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(render(), "shortlong");
+    assert_eq!(renders.get(), 3);
+}
+
+#[test]
+fn test_lru_cache_evicts_least_recently_used() {
+    let cache = LruCache::new(2);
+
+    cache.insert(CacheKey::new(&1), entry("one"), None);
+    cache.insert(CacheKey::new(&2), entry("two"), None);
+    assert_eq!(cache.get(&CacheKey::new(&1)), Some(entry("one")));
+
+    cache.insert(CacheKey::new(&3), entry("three"), None);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&CacheKey::new(&1)), Some(entry("one")));
+    assert_eq!(cache.get(&CacheKey::new(&2)), None);
+    assert_eq!(cache.get(&CacheKey::new(&3)), Some(entry("three")));
+}
+
+#[test]
+fn test_cache_keys_compare_the_whole_key() {
+    assert_eq!(
+        CacheKey::new(&("dashboard", 7)),
+        CacheKey::new(&("dashboard", 7))
+    );
+    assert_ne!(
+        CacheKey::new(&("dashboard", 7)),
+        CacheKey::new(&("dashboard", 8))
+    );
+    assert_ne!(CacheKey::new(&("ab", "c")), CacheKey::new(&("a", "bc")));
+    assert_eq!(
+        CacheKey::new(&"sidebar").as_bytes(),
+        CacheKey::new(&"sidebar").as_bytes()
+    );
+
+    let cache = LruCache::new(10);
+
+    cache.insert(CacheKey::new(&("user", 1)), entry("one"), None);
+    cache.insert(CacheKey::new(&("user", 2)), entry("two"), None);
+
+    assert_eq!(cache.get(&CacheKey::new(&("user", 1))), Some(entry("one")));
+    assert_eq!(cache.get(&CacheKey::new(&("user", 2))), Some(entry("two")));
+}
+
+#[test]
+fn test_lru_cache_zero_capacity() {
+    let cache = LruCache::new(0);
+
+    cache.insert(CacheKey::new(&1), entry("one"), None);
+
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&CacheKey::new(&1)), None);
+}
+
+fn counter_fragment(renders: &Arc<AtomicUsize>) -> impl FnOnce() -> Html + Send + 'static {
+    let renders = renders.clone();
+
+    move || {
+        let count = renders.fetch_add(1, Ordering::SeqCst) + 1;
+        html! { span { "render " (count) } }.to_html()
+    }
+}
+
+#[test]
+fn test_stale_while_revalidate_without_render_cache() {
+    let renders = Arc::new(AtomicUsize::new(0));
+
+    let html = cache::stale_while_revalidate(
+        "counter",
+        Duration::from_secs(60),
+        Duration::from_secs(60),
+        counter_fragment(&renders),
+    );
+
+    assert_eq!(html, "<span>render 1</span>");
+}
+
+#[test]
+fn test_stale_while_revalidate_serves_stale_and_revalidates() {
+    let renders = Arc::new(AtomicUsize::new(0));
+    let jobs: Arc<Mutex<Vec<RevalidateJob>>> = Arc::default();
+
+    let cache = RenderCache::new(LruCache::new(10)).with_executor({
+        let jobs = jobs.clone();
+        move |job| jobs.lock().unwrap().push(job)
+    });
+
+    let render = || {
+        context::provide(cache.clone(), || {
+            cache::stale_while_revalidate(
+                "counter",
+                Duration::from_millis(20),
+                Duration::from_secs(60),
+                counter_fragment(&renders),
+            )
+        })
+    };
+
+    assert_eq!(render(), "<span>render 1</span>");
+    assert_eq!(render(), "<span>render 1</span>");
+    assert!(jobs.lock().unwrap().is_empty());
+
+    thread::sleep(Duration::from_millis(50));
+
+    // Stale: served from the cache, with a single background re-render queued
+    assert_eq!(render(), "<span>render 1</span>");
+    assert_eq!(render(), "<span>render 1</span>");
+    assert_eq!(jobs.lock().unwrap().len(), 1);
+    assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+    let job = jobs.lock().unwrap().pop().unwrap();
+    job();
+
+    assert_eq!(renders.load(Ordering::SeqCst), 2);
+    assert_eq!(render(), "<span>render 2</span>");
+}
+
+#[test]
+fn test_stale_while_revalidate_without_executor() {
+    let renders = Arc::new(AtomicUsize::new(0));
+    let cache = RenderCache::new(LruCache::new(10));
+
+    let render = || {
+        cache.stale_while_revalidate(
+            "counter",
+            Duration::from_millis(20),
+            Duration::from_secs(60),
+            counter_fragment(&renders),
+        )
+    };
+
+    assert_eq!(render(), "<span>render 1</span>");
+
+    thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(render(), "<span>render 2</span>");
+    assert_eq!(render(), "<span>render 2</span>");
+}
+
+#[test]
+fn test_stale_while_revalidate_expired() {
+    let renders = Arc::new(AtomicUsize::new(0));
+    let jobs: Arc<Mutex<Vec<RevalidateJob>>> = Arc::default();
+
+    let cache = RenderCache::new(LruCache::new(10)).with_executor({
+        let jobs = jobs.clone();
+        move |job| jobs.lock().unwrap().push(job)
+    });
+
+    let render = || {
+        cache.stale_while_revalidate(
+            "counter",
+            Duration::from_millis(10),
+            Duration::from_millis(10),
+            counter_fragment(&renders),
+        )
+    };
+
+    assert_eq!(render(), "<span>render 1</span>");
+
+    thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(render(), "<span>render 2</span>");
+    assert!(jobs.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_cached_wrapper() {
+    let renders = &Cell::new(0);
+
+    let footer = html! {
+        footer { ({ renders.set(renders.get() + 1); "Footer" }) }
+    };
+    let page = html! {
+        main { "Content" }
+        (cache::Cached::new(("footer", 1), &footer).ttl(Duration::from_secs(60)))
+    };
+
+    let cache = RenderCache::default();
+
+    assert_eq!(
+        context::provide(cache.clone(), || page.to_html()),
+        "<main>Content</main><footer>Footer</footer>"
+    );
+    assert_eq!(
+        context::provide(cache.clone(), || page.to_html()),
+        "<main>Content</main><footer>Footer</footer>"
+    );
+    assert_eq!(renders.get(), 1);
+
+    cache.invalidate(&("footer", 1));
+    context::provide(cache, || page.to_html());
+    assert_eq!(renders.get(), 2);
+
+    page.to_html();
+    assert_eq!(renders.get(), 3);
+}
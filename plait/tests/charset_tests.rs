@@ -0,0 +1,52 @@
+use plait::{ToHtml, charset::Charset, context, html, html_to};
+
+#[test]
+fn test_charset_ascii_encodes_static_and_escaped_text() {
+    let name = "Zoë <3";
+    let page = html! {
+        p(title: (name), data_label: "naïve") { "Café " (name) " … 🎉" }
+    };
+
+    assert_eq!(
+        context::provide(Charset::Ascii, || page.to_html()),
+        "<p title=\"Zo&#xeb; &lt;3\" data-label=\"na&#xef;ve\">Caf&#xe9; Zo&#xeb; &lt;3 &#x2026; &#x1f389;</p>"
+    );
+}
+
+#[test]
+fn test_charset_utf8_is_the_default() {
+    let page = html! { p { "Café " ("Zoë") } };
+
+    assert_eq!(page.to_html(), "<p>Café Zoë</p>");
+    assert_eq!(
+        context::provide(Charset::Utf8, || page.to_html()),
+        "<p>Café Zoë</p>"
+    );
+    assert_eq!(Charset::current(), Charset::Utf8);
+}
+
+#[test]
+fn test_charset_ascii_leaves_raw_values() {
+    let markup = String::from("<b>Zoë</b>");
+    let page = html! { p { #(markup) } };
+
+    assert_eq!(
+        context::provide(Charset::Ascii, || page.to_html()),
+        "<p><b>Zoë</b></p>"
+    );
+}
+
+#[test]
+fn test_charset_ascii_html_to() {
+    let mut out = String::new();
+
+    context::provide(Charset::Ascii, || html_to!(&mut out, { em { "é" } })).unwrap();
+
+    assert_eq!(out, "<em>&#xe9;</em>");
+}
+
+#[test]
+fn test_charset_as_str() {
+    assert_eq!(Charset::Utf8.as_str(), "utf-8");
+    assert_eq!(Charset::Ascii.as_str(), "us-ascii");
+}
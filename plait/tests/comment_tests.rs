@@ -0,0 +1,80 @@
+use plait::{
+    ToHtml,
+    comment::{Comment, ConditionalComment, GoogleOff},
+    html,
+};
+
+#[test]
+fn test_comment() {
+    let comment = Comment::new("build 42");
+
+    let html = html! {
+        (comment) #(comment)
+    };
+
+    assert_eq!(html.to_html(), "<!--build 42--><!--build 42-->");
+}
+
+#[test]
+fn test_comment_rejects_early_termination() {
+    for text in ["a --> b", "a --!> b", "<!-- nested", ">", "-> a", "a <!-"] {
+        assert!(Comment::try_new(text).is_none(), "{text:?}");
+    }
+
+    for text in ["", "a - b", "a -- b", "<!- a", "a >"] {
+        assert!(Comment::try_new(text).is_some(), "{text:?}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid comment text")]
+fn test_comment_new_panics() {
+    Comment::new(String::from("--><script>"));
+}
+
+#[test]
+fn test_googleoff() {
+    let html = html! {
+        (Comment::googleoff(GoogleOff::All))
+        footer { "legal" }
+        (Comment::googleon(GoogleOff::All))
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<!--googleoff: all--><footer>legal</footer><!--googleon: all-->"
+    );
+}
+
+#[test]
+fn test_conditional_comment_hidden() {
+    let comment = ConditionalComment::hidden("lt IE 9", html! { p { "Please upgrade" } });
+
+    assert_eq!(
+        comment.as_str(),
+        "<!--[if lt IE 9]><p>Please upgrade</p><![endif]-->"
+    );
+
+    assert!(ConditionalComment::try_hidden("IE", "--> <script>").is_some());
+    assert!(ConditionalComment::try_hidden("IE", html! { #("--> <script>") }).is_none());
+    assert!(ConditionalComment::try_hidden("IE]><script>", "").is_none());
+    assert!(ConditionalComment::try_hidden("", "").is_none());
+}
+
+#[test]
+fn test_conditional_comment_revealed() {
+    let html = html! {
+        (ConditionalComment::revealed("!(IE 6)|(IE 7)", "a < b"))
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<!--[if !(IE 6)|(IE 7)]><!-->a &lt; b<!--<![endif]-->"
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid conditional comment")]
+fn test_conditional_comment_revealed_panics() {
+    ConditionalComment::revealed("IE]>", "");
+}
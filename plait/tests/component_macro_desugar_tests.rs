@@ -411,3 +411,46 @@ fn test_form_field_optional() {
          </div>"
     );
 }
+
+// Closure props, with `impl Trait` in the return type desugared to another type parameter
+pub struct Row {
+    pub name: String,
+    pub count: u32,
+}
+
+component! {
+    pub fn Table<'a>(rows: &'a [Row], render_row: impl Fn(&'a Row) -> impl RenderEscaped, format: impl Fn(&str) -> String) {
+        table {
+            for row in *rows {
+                tr { (render_row(row)) td { (format(&row.count.to_string())) } }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_closure_props() {
+    let rows = [
+        Row {
+            name: "apples".to_owned(),
+            count: 5,
+        },
+        Row {
+            name: "<pears>".to_owned(),
+            count: 3,
+        },
+    ];
+
+    let html = html! {
+        @Table(
+            rows: &rows,
+            render_row: |row| html! { td { (row.name) } },
+            format: |count| format!("{count} left"),
+        ) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<table><tr><td>apples</td><td>5 left</td></tr><tr><td>&lt;pears&gt;</td><td>3 left</td></tr></table>"
+    );
+}
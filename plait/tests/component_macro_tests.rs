@@ -1,4 +1,8 @@
-use plait::{Class, RenderEscaped, ToHtml, classes, component, html};
+use std::cell::Cell;
+
+use plait::{
+    Class, RenderEscaped, RenderIter, RenderProp, ToHtml, classes, component, component_alias, html,
+};
 
 component! {
     pub fn Button<'a>(class: Option<&'a str>) {
@@ -176,3 +180,470 @@ fn test_shorthand_with_ref_lifetime() {
         "<button class=\"btn primary\">Submit</button>"
     );
 }
+
+// --- Deprecation tests ---
+
+component! {
+    #[deprecated(note = "use `Button` instead")]
+    pub fn OldButton(label: &str) {
+        button { (label) }
+    }
+}
+
+component! {
+    pub fn Tag(#[deprecated(note = "use `text` instead")] label: &str, text: &str) {
+        span { (label) (text) }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_component() {
+    let html = html! {
+        @OldButton(label: "Old") {}
+        @Tag(label: "a", text: "b") {}
+    };
+
+    assert_eq!(html.to_html(), "<button>Old</button><span>ab</span>");
+}
+
+component_alias!(pub RenamedBadge<'a> = Badge);
+
+component_alias! {
+    /// The old name of `Tag`.
+    pub OldTag<'a, 'b> = Tag
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_component_alias() {
+    let html = html! {
+        @RenamedBadge(label: "new") {}
+        @OldTag(label: "a", text: "b") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<span class=\"badge\">new</span><span>ab</span>"
+    );
+}
+
+component! {
+    pub fn Badge(label: &str) {
+        span(class: "badge") { (label) }
+    }
+
+    pub fn BadgeList(labels: &[&str]) {
+        for label in labels.iter() {
+            @Badge(label) {}
+        }
+    }
+}
+
+#[test]
+fn test_multiple_components_in_one_invocation() {
+    let html = html! {
+        @BadgeList(labels: &["a", "b"]) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<span class=\"badge\">a</span><span class=\"badge\">b</span>"
+    );
+}
+
+component! {
+    pub fn ui::Card(title: &str) {
+        div(class: "card") {
+            @Badge(label: title) {}
+            #children
+        }
+    }
+
+    pub fn ui::forms::Submit() {
+        button(type: "submit") { #children }
+    }
+
+    fn ui::Private() {
+        "private"
+    }
+
+    pub fn ui::WithPrivate() {
+        @Private() {}
+    }
+}
+
+#[test]
+fn test_component_in_module() {
+    let html = html! {
+        @ui::Card(title: "Hello") {
+            @ui::forms::Submit() { "Go" }
+        }
+        @ui::WithPrivate() {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div class=\"card\"><span class=\"badge\">Hello</span><button type=\"submit\">Go</button></div>private"
+    );
+}
+
+mod nested {
+    use plait::component;
+
+    component! {
+        fn parts::Inner() {
+            "inner"
+        }
+    }
+
+    pub fn render() -> String {
+        use plait::{ToHtml, html};
+
+        html! { @parts::Inner() {} }.to_html().to_string()
+    }
+}
+
+#[test]
+fn test_private_component_in_module_is_visible_to_parent() {
+    assert_eq!(nested::render(), "inner");
+}
+
+component! {
+    pub fn LazySidebar(open: bool, sidebar: impl Fn() -> String) {
+        if *open {
+            aside { (sidebar()) }
+        }
+    }
+}
+
+#[test]
+fn test_component_lazy_prop() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let render_sidebar = || {
+        calls.set(calls.get() + 1);
+        "<Menu>".to_owned()
+    };
+
+    let closed = html! { @LazySidebar(open: false, sidebar: @lazy(render_sidebar())) {} };
+    assert_eq!(closed.to_html(), "");
+    assert_eq!(calls.get(), 0);
+
+    let opened = html! { @LazySidebar(open: true, sidebar: @lazy(render_sidebar())) {} };
+    assert_eq!(opened.to_html(), "<aside>&lt;Menu&gt;</aside>");
+    assert_eq!(calls.get(), 1);
+}
+
+component! {
+    pub fn SlotLayout(title: &str) {
+        header { h1 { (title) } #slot(actions) }
+        main { #children }
+        footer { #slot(footer) }
+    }
+}
+
+component! {
+    pub fn SlotPanel() {
+        @SlotLayout(title: "Panel") {
+            slot(footer) { "Panel footer" }
+            section { #children }
+            slot(actions) { #slot(actions) }
+        }
+    }
+}
+
+#[test]
+fn test_component_named_slots() {
+    let page = html! {
+        @SlotLayout(title: "Home") {
+            slot(actions) { button { "Edit" } }
+            p { "Before" }
+            slot(default) { p { "Default" } }
+            slot(footer) { "© 2024" }
+            p { "After" }
+        }
+    };
+
+    assert_eq!(
+        page.to_html(),
+        "<header><h1>Home</h1><button>Edit</button></header>\
+        <main><p>Before</p><p>Default</p><p>After</p></main>\
+        <footer>© 2024</footer>"
+    );
+}
+
+#[test]
+fn test_component_named_slots_unfilled() {
+    let page = html! { @SlotLayout(title: "Empty") { "Body" } };
+
+    assert_eq!(
+        page.to_html(),
+        "<header><h1>Empty</h1></header><main>Body</main><footer></footer>"
+    );
+}
+
+#[test]
+fn test_component_named_slots_forwarded() {
+    let page = html! {
+        @SlotPanel {
+            slot(actions) { a(href: "/new") { "New" } }
+            @SlotLayout(title: "Inner") { "Inner body" }
+        }
+    };
+
+    assert_eq!(
+        page.to_html(),
+        "<header><h1>Panel</h1><a href=\"/new\">New</a></header>\
+        <main><section>\
+        <header><h1>Inner</h1></header><main>Inner body</main><footer></footer>\
+        </section></main>\
+        <footer>Panel footer</footer>"
+    );
+}
+
+component! {
+    pub fn OptionalCard(title?: &str, body: &str, count?: u32) {
+        section {
+            if let Some(title) = title {
+                h2 { (title) }
+            }
+            p(data_count?: count) { (body) }
+        }
+    }
+}
+
+#[test]
+fn test_component_optional_props() {
+    let body = String::from("Body");
+
+    let omitted = html! { @OptionalCard(body: &body) {} };
+    assert_eq!(omitted.to_html(), "<section><p>Body</p></section>");
+
+    let title = Some("Title");
+    let set = html! { @OptionalCard(title, body: "Body", count: Some(3)) {} };
+    assert_eq!(
+        set.to_html(),
+        "<section><h2>Title</h2><p data-count=\"3\">Body</p></section>"
+    );
+
+    let card = OptionalCard {
+        title: None,
+        body: "Body",
+        count: None,
+    };
+    assert_eq!(
+        html! { @OptionalCard(body: card.body, title: card.title) {} }.to_html(),
+        "<section><p>Body</p></section>"
+    );
+}
+
+component! {
+    pub fn TagList(tags: impl RenderIter<Item = impl RenderEscaped>) {
+        ul {
+            for tag in tags {
+                li { (tag) }
+            }
+        }
+        p {
+            for tag in tags, sep { ", " } { (tag) }
+        }
+    }
+}
+
+#[test]
+fn test_component_render_iter_prop() {
+    let tags = vec!["rust".to_owned(), "<html>".to_owned()];
+
+    let html = html! {
+        @TagList(tags: &tags) {}
+        @TagList(tags: tags.iter().map(|tag| tag.len())) {}
+        @TagList(tags: 1..3) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul><li>rust</li><li>&lt;html&gt;</li></ul><p>rust, &lt;html&gt;</p>\
+        <ul><li>4</li><li>6</li></ul><p>4, 6</p>\
+        <ul><li>1</li><li>2</li></ul><p>1, 2</p>"
+    );
+}
+
+// --- Each child tests ---
+
+component! {
+    pub fn List() {
+        ul(#attrs) {
+            for child in #children, sep { "\n" } {
+                li(data_index: (child.index())) { (child) }
+            } else {
+                li { "Empty" }
+            }
+        }
+    }
+
+    pub fn Steps() {
+        @List(; class: "steps") {
+            #children
+        }
+    }
+
+    pub fn Tabs() {
+        div {
+            for tab in #slot(tabs) {
+                button { (tab) }
+            }
+            #children
+        }
+    }
+}
+
+#[test]
+fn test_each_child() {
+    let items = ["b", "c"];
+    let show = false;
+
+    let html = html! {
+        @List {
+            let first = "a";
+            @Button(class: None) { (first) }
+            for item in items {
+                (item)
+            }
+            if show {
+                "hidden"
+            } else {
+                em { "d" } "e"
+            }
+        }
+        @List {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul><li data-index=\"0\"><button class=\"btn\">a</button></li>\n\
+        <li data-index=\"1\">b</li>\n<li data-index=\"2\">c</li>\n\
+        <li data-index=\"3\"><em>d</em></li>\n<li data-index=\"4\">e</li></ul>\
+        <ul><li>Empty</li></ul>"
+    );
+}
+
+#[test]
+fn test_each_child_forwarded_and_slots() {
+    let html = html! {
+        @Steps {
+            "One"
+            @Tabs {
+                slot(tabs) { "A" "B" }
+                "Panel"
+            }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul class=\"steps\"><li data-index=\"0\">One</li>\n\
+        <li data-index=\"1\"><div><button>A</button><button>B</button>Panel</div></li></ul>"
+    );
+}
+
+component! {
+    pub fn Plain() {
+        #children
+    }
+}
+
+#[test]
+fn test_each_child_renders_children_once() {
+    let renders = Cell::new(0);
+    let render = || {
+        renders.set(renders.get() + 1);
+        renders.get()
+    };
+
+    let html = html! {
+        @List {
+            @Plain { "x" }
+            (render())
+            (render())
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul><li data-index=\"0\">x</li>\n<li data-index=\"1\">1</li>\n<li data-index=\"2\">2</li></ul>"
+    );
+    assert_eq!(renders.get(), 2);
+}
+
+struct Fruit {
+    name: &'static str,
+    price: u32,
+}
+
+component! {
+    pub fn FruitTable<'a>(fruits: &'a [Fruit], children: impl RenderProp<(usize, &'a Fruit)>) {
+        table(#attrs) {
+            for (index, fruit) in fruits.iter().enumerate() {
+                #children((index, fruit))
+            }
+        }
+    }
+
+    pub fn Repeat(times: usize, children: impl RenderProp<usize>) {
+        for index in 0..*times {
+            #children(index)
+        }
+    }
+}
+
+#[test]
+fn test_render_prop_children() {
+    let fruits = [
+        Fruit {
+            name: "apple",
+            price: 3,
+        },
+        Fruit {
+            name: "pear",
+            price: 4,
+        },
+    ];
+    let currency = String::from("€");
+
+    let html = html! {
+        @FruitTable(fruits: &fruits; class: "fruits") { |(index, fruit)|
+            tr(data_index: index) {
+                td { (fruit.name) }
+                td { (fruit.price) " " (currency) }
+                td { @Button(class: None) { "Buy " (fruit.name) } }
+            }
+        }
+        p { (currency) }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<table class=\"fruits\">\
+        <tr data-index=\"0\"><td>apple</td><td>3 €</td><td><button class=\"btn\">Buy apple</button></td></tr>\
+        <tr data-index=\"1\"><td>pear</td><td>4 €</td><td><button class=\"btn\">Buy pear</button></td></tr>\
+        </table><p>€</p>"
+    );
+}
+
+#[test]
+fn test_nested_render_props() {
+    let html = html! {
+        @Repeat(times: 2) { |row|
+            @Repeat(times: 3) { |column| span { (row) "." (column) } }
+            br;
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<span>0.0</span><span>0.1</span><span>0.2</span><br>\
+        <span>1.0</span><span>1.1</span><span>1.2</span><br>"
+    );
+}
@@ -0,0 +1,65 @@
+use plait::{ToHtml, component, context, html};
+
+#[derive(Debug, PartialEq)]
+struct Theme(&'static str);
+
+component! {
+    pub fn Themed() {
+        if let Some(theme) = context::get::<Theme>() {
+            div(class: theme.0) { #children }
+        } else {
+            div { #children }
+        }
+    }
+}
+
+#[test]
+fn test_context_get_without_provide() {
+    assert_eq!(context::get::<Theme>(), None);
+}
+
+#[test]
+fn test_context_provide() {
+    let value = context::provide(Theme("dark"), context::get::<Theme>);
+
+    assert_eq!(value.as_deref(), Some(&Theme("dark")));
+    assert_eq!(context::get::<Theme>(), None);
+}
+
+#[test]
+fn test_context_nested_provide_shadows() {
+    context::provide(Theme("dark"), || {
+        context::provide(1u32, || {
+            assert_eq!(context::get::<Theme>().as_deref(), Some(&Theme("dark")));
+            assert_eq!(context::get::<u32>().as_deref(), Some(&1));
+
+            context::provide(Theme("light"), || {
+                assert_eq!(context::get::<Theme>().as_deref(), Some(&Theme("light")));
+            });
+        });
+
+        assert_eq!(context::get::<Theme>().as_deref(), Some(&Theme("dark")));
+        assert_eq!(context::get::<u32>(), None);
+    });
+}
+
+#[test]
+fn test_context_removed_after_panic() {
+    let result = std::panic::catch_unwind(|| context::provide(Theme("dark"), || panic!("boom")));
+
+    assert!(result.is_err());
+    assert_eq!(context::get::<Theme>(), None);
+}
+
+#[test]
+fn test_context_in_component() {
+    let html = html! {
+        @Themed() { "hi" }
+    };
+
+    assert_eq!(
+        context::provide(Theme("dark"), || html.to_html()),
+        "<div class=\"dark\">hi</div>"
+    );
+    assert_eq!(html.to_html(), "<div>hi</div>");
+}
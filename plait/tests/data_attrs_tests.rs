@@ -0,0 +1,66 @@
+use plait::{DataAttrs, ToHtml, component, data, html};
+
+#[test]
+fn test_data_macro() {
+    let user_id = 7;
+
+    let html = html! {
+        div(id: "user", data: data!(user_id: user_id, r#type: "admin", note: "<\"x\">")) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        r#"<div id="user" data-user-id="7" data-type="admin" data-note="&lt;&quot;x&quot;&gt;"></div>"#
+    );
+}
+
+#[test]
+fn test_data_attrs_spread() {
+    let attrs = DataAttrs::new()
+        .set("controller", "dropdown")
+        .set("bad name", "x")
+        .set("action_target", "menu");
+
+    assert_eq!(attrs.len(), 2);
+    assert_eq!(
+        attrs.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["data-controller", "data-action-target"]
+    );
+
+    let html = html! {
+        div(#attrs(&attrs)) {}
+        span(#attrs(attrs.clone())) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        r#"<div data-controller="dropdown" data-action-target="menu"></div><span data-controller="dropdown" data-action-target="menu"></span>"#
+    );
+}
+
+#[test]
+fn test_data_macro_in_component_attributes() {
+    component! {
+        fn Card() {
+            div(class: "card", #attrs) { #children }
+        }
+    }
+
+    let html = html! {
+        @Card(; data: data!(id: 3, kind: "note")) { "Body" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        r#"<div class="card" data-id="3" data-kind="note">Body</div>"#
+    );
+}
+
+#[test]
+fn test_data_attribute_of_object() {
+    let html = html! {
+        object(data: "/movie.swf") {}
+    };
+
+    assert_eq!(html.to_html(), r#"<object data="/movie.swf"></object>"#);
+}
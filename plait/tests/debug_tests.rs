@@ -0,0 +1,40 @@
+use plait::{ToHtml, debug, html};
+
+#[test]
+fn test_debug_block() {
+    let html = html! {
+        p { "content" }
+        @debug {
+            pre { "debug panel" }
+        }
+    };
+
+    if cfg!(debug_assertions) {
+        assert_eq!(html.to_html(), "<p>content</p><pre>debug panel</pre>");
+    } else {
+        assert_eq!(html.to_html(), "<p>content</p>");
+    }
+
+    debug::set_enabled(true);
+    assert!(debug::is_enabled());
+    assert_eq!(html.to_html(), "<p>content</p><pre>debug panel</pre>");
+    debug::set_enabled(false);
+}
+
+#[test]
+fn test_dump() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct State {
+        note: &'static str,
+    }
+
+    let html = html! {
+        (debug::dump(&State { note: "--> <!-- ---" }))
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<!-- State { note: \"- -> <!- - - - -\" } -->"
+    );
+}
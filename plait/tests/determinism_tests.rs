@@ -0,0 +1,84 @@
+use std::{collections::HashMap, thread};
+
+use plait::{Sorted, ToHtml, classes, component, html, ids};
+
+component! {
+    fn Card(title: &str) {
+        section(class: classes!("card", Some("raised")), #attrs) {
+            h2(auto_id) { (title) }
+            #children
+        }
+    }
+}
+
+fn page() -> String {
+    let rows = [(1, "one"), (2, "two"), (2, "two")];
+
+    html! {
+        @Card(title: "Rows"; id: "table", data_kind: "table", aria_label: "Rows") {
+            let field = ids::next("field");
+            input(id: field, type: "text", required);
+            for (number, name) in rows {
+                p(data_number: number) { (name) }
+            }
+        }
+        @Card(title: "Rows") {}
+    }
+    .to_html()
+    .to_string()
+}
+
+#[test]
+fn test_output_is_identical_across_renders() {
+    let first = page();
+
+    assert_eq!(
+        first,
+        "<section class=\"card raised\" id=\"table\" data-kind=\"table\" aria-label=\"Rows\">\
+        <h2 id=\"rows\">Rows</h2><input id=\"field\" type=\"text\" required>\
+        <p data-number=\"1\">one</p><p data-number=\"2\">two</p><p data-number=\"2\">two</p></section>\
+        <section class=\"card raised\"><h2 id=\"rows-1\">Rows</h2></section>"
+    );
+
+    for _ in 0..10 {
+        assert_eq!(page(), first);
+    }
+}
+
+#[test]
+fn test_output_is_identical_across_threads() {
+    let expected = page();
+
+    let outputs: Vec<String> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..4).map(|_| scope.spawn(page)).collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    for output in outputs {
+        assert_eq!(output, expected);
+    }
+}
+
+#[test]
+fn test_sorted_spread_is_identical_across_maps() {
+    let render = || {
+        let config: HashMap<_, _> = (0..16)
+            .map(|index| (format!("data-key-{index:02}"), index))
+            .collect();
+
+        html! { div(#attrs(Sorted(&config))) {} }
+            .to_html()
+            .to_string()
+    };
+
+    let first = render();
+
+    assert!(first.starts_with(r#"<div data-key-00="0" data-key-01="1" data-key-02="2""#));
+
+    for _ in 0..10 {
+        assert_eq!(render(), first);
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt;
+
+use plait::{Escaped, RenderEscaped, ToHtml, Unescaped, html};
+
+struct Point(i32, i32);
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}, {}>", self.0, self.1)
+    }
+}
+
+impl RenderEscaped for Point {
+    fn render_escaped(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        Escaped(self).render_escaped(f)
+    }
+}
+
+#[test]
+fn test_escaped_display() {
+    let point = Point(1, -2);
+
+    let html = html! {
+        span(title: Escaped(&point)) { (Escaped(&point)) " " #(Escaped(&point)) }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<span title=\"&lt;1, -2&gt;\">&lt;1, -2&gt; &lt;1, -2&gt;</span>"
+    );
+}
+
+#[test]
+fn test_escaped_delegating_impl() {
+    let points = [Point(0, 0), Point(3, 4)];
+
+    let html = html! {
+        ul {
+            for point in &points {
+                li { (point) }
+            }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul><li>&lt;0, 0&gt;</li><li>&lt;3, 4&gt;</li></ul>"
+    );
+}
+
+#[test]
+fn test_escaped_split_writes() {
+    struct Quoted;
+
+    impl fmt::Display for Quoted {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("\"a")?;
+            f.write_str("&b\"")
+        }
+    }
+
+    assert_eq!(Escaped(Quoted).to_string(), "&quot;a&amp;b&quot;");
+}
+
+#[test]
+fn test_unescaped_display() {
+    let html = html! {
+        div { (Unescaped("<b>bold</b>")) #(Unescaped(format_args!("<i>{}</i>", 1))) }
+    };
+
+    assert_eq!(html.to_html(), "<div><b>bold</b><i>1</i></div>");
+}
+
+#[test]
+fn test_optional_escaped_attribute() {
+    let none: Option<Escaped<Point>> = None;
+
+    let html = html! {
+        span(title?: Some(Escaped(Point(1, 2))), id?: none) {}
+    };
+
+    assert_eq!(html.to_html(), "<span title=\"&lt;1, 2&gt;\"></span>");
+}
@@ -0,0 +1,81 @@
+use plait::{
+    ToHtml, component, context,
+    flash::{self, Flash, FlashMessage, FlashMessages, Level},
+    html,
+};
+
+#[test]
+fn test_flash_messages_without_context() {
+    let html = html! {
+        @FlashMessages() {}
+    };
+
+    assert_eq!(html.to_html(), "");
+}
+
+#[test]
+fn test_flash_messages_empty() {
+    let html = html! {
+        @FlashMessages() {}
+    };
+
+    assert_eq!(context::provide(Flash::new(), || html.to_html()), "");
+}
+
+#[test]
+fn test_flash_messages_levels_and_escaping() {
+    let html = html! {
+        @FlashMessages(; id: "flash") {}
+    };
+
+    let flash = Flash::new();
+    flash.info("Heads up");
+    flash.error("<b>Failed</b>");
+
+    assert_eq!(
+        context::provide(flash, || html.to_html()),
+        "<div class=\"flash-messages\" role=\"alert\" id=\"flash\">\
+        <p class=\"flash flash-info\">Heads up</p>\
+        <p class=\"flash flash-error\">&lt;b&gt;Failed&lt;/b&gt;</p></div>"
+    );
+}
+
+component! {
+    pub fn Saves() {
+        let _ = flash::push(Level::Success, "Saved");
+        "saved"
+    }
+}
+
+#[test]
+fn test_flash_push_during_render() {
+    assert!(!flash::push(Level::Info, "dropped"));
+
+    let html = html! {
+        @Saves() {}
+        @FlashMessages() {}
+    };
+
+    let flash = Flash::from_iter([FlashMessage {
+        level: Level::Warning,
+        text: "Loaded".to_owned(),
+    }]);
+
+    assert_eq!(
+        context::provide(flash, || html.to_html()),
+        "saved<div class=\"flash-messages\" role=\"alert\">\
+        <p class=\"flash flash-warning\">Loaded</p>\
+        <p class=\"flash flash-success\">Saved</p></div>"
+    );
+}
+
+#[test]
+fn test_flash_take() {
+    let flash = Flash::new();
+    flash.warning("a");
+    flash.success("b");
+
+    assert!(!flash.is_empty());
+    assert_eq!(flash.take().len(), 2);
+    assert!(flash.is_empty());
+}
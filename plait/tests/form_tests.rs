@@ -0,0 +1,280 @@
+use plait::{
+    ToHtml, context,
+    form::{
+        CsrfToken, FieldFor, FormModel, FormWith, Labeled, Method, MethodField, Options,
+        SelectFrom, ValidationErrors,
+    },
+    html,
+};
+
+#[derive(FormModel)]
+struct Profile {
+    email: String,
+    #[form(rename = "display-name")]
+    name: &'static str,
+    age: Option<u32>,
+    subscribed: bool,
+    #[form(skip)]
+    #[allow(dead_code)]
+    password: String,
+}
+
+fn profile() -> Profile {
+    Profile {
+        email: "a@example.com".to_owned(),
+        name: "<Alice>",
+        age: None,
+        subscribed: true,
+        password: "secret".to_owned(),
+    }
+}
+
+#[test]
+fn test_form_model_derive() {
+    let profile = profile();
+
+    assert_eq!(
+        profile.field_value("email").as_deref(),
+        Some("a@example.com")
+    );
+    assert_eq!(
+        profile.field_value("display-name").as_deref(),
+        Some("<Alice>")
+    );
+    assert_eq!(profile.field_value("name"), None);
+    assert_eq!(profile.field_value("age"), None);
+    assert_eq!(profile.field_value("subscribed").as_deref(), Some("true"));
+    assert_eq!(profile.field_value("password"), None);
+}
+
+#[test]
+fn test_form_model_field_constants() {
+    assert_eq!(Profile::EMAIL.name(), "email");
+    assert_eq!(Profile::NAME.name(), "display-name");
+    assert_eq!(Profile::AGE.name(), "age");
+    assert_eq!(Profile::SUBSCRIBED.name(), "subscribed");
+}
+
+#[test]
+fn test_validation_errors() {
+    let mut errors = ValidationErrors::new();
+    assert!(errors.is_empty());
+
+    errors.add("email", "Required");
+    errors.add("email", "Invalid");
+
+    assert!(!errors.is_empty());
+    assert!(errors.has("email"));
+    assert!(!errors.has("age"));
+    assert_eq!(errors.get("email"), ["Required", "Invalid"]);
+    assert!(errors.get("age").is_empty());
+}
+
+#[test]
+fn test_field_for_valid() {
+    let profile = profile();
+    let errors = ValidationErrors::new();
+
+    let html = html! {
+        @FieldFor(model: &profile, field: Profile::NAME, errors: &errors; type: "text", required) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<input name=\"display-name\" id=\"display-name\" value=\"&lt;Alice&gt;\" type=\"text\" required>"
+    );
+}
+
+#[test]
+fn test_field_for_invalid() {
+    let profile = profile();
+    let mut errors = ValidationErrors::new();
+    errors.add("age", "Required");
+    errors.add("age", "Must be a number");
+
+    let html = html! {
+        @FieldFor(model: &profile, field: Profile::AGE, errors: &errors) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<input name=\"age\" id=\"age\" aria-invalid=\"true\" aria-describedby=\"age-error\"><span id=\"age-error\" class=\"field-error\">Required Must be a number</span>"
+    );
+}
+
+#[derive(Options, Clone, Copy, PartialEq, Debug)]
+enum Color {
+    Red,
+    #[option(value = "dark-blue", label = "Dark <blue>")]
+    DarkBlue,
+}
+
+#[test]
+fn test_options_derive() {
+    assert_eq!(Color::options(), &[Color::Red, Color::DarkBlue]);
+    assert_eq!(Color::Red.value(), "Red");
+    assert_eq!(Color::Red.label(), "Red");
+    assert_eq!(Color::DarkBlue.value(), "dark-blue");
+    assert_eq!(Color::DarkBlue.label(), "Dark <blue>");
+}
+
+#[test]
+fn test_select_from_enum() {
+    let html = html! {
+        @SelectFrom(options: Color::options(), selected: Some(Color::DarkBlue.value()); name: "color") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<select name=\"color\"><option value=\"Red\">Red</option>\
+        <option value=\"dark-blue\" selected>Dark &lt;blue&gt;</option></select>"
+    );
+}
+
+#[test]
+fn test_select_from_pairs() {
+    let options = vec![("1".to_owned(), "One"), ("\"2\"".to_owned(), "Two")];
+
+    let html = html! {
+        @SelectFrom(options: &options, selected: None) {
+            option(value: "") { "Pick" }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<select><option value=\"\">Pick</option><option value=\"1\">One</option>\
+        <option value=\"&quot;2&quot;\">Two</option></select>"
+    );
+}
+
+#[derive(FormModel)]
+struct Preferences {
+    color: Color,
+}
+
+#[test]
+fn test_options_form_value() {
+    let preferences = Preferences {
+        color: Color::DarkBlue,
+    };
+
+    let html = html! {
+        @FieldFor(model: &preferences, field: Preferences::COLOR, errors: &ValidationErrors::new()) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<input name=\"color\" id=\"color\" value=\"dark-blue\">"
+    );
+}
+
+#[test]
+fn test_form_with_get_and_post() {
+    let html = html! {
+        @FormWith(method: Method::Get, action: "/search") {}
+        @FormWith(method: Method::Post, action: "/posts"; class: "new") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<form method=\"get\" action=\"/search\"></form>\
+        <form method=\"post\" action=\"/posts\" class=\"new\"></form>"
+    );
+}
+
+#[test]
+fn test_form_with_spoofed_method() {
+    let html = html! {
+        @FormWith(method: Method::Patch, action: "/posts/1") {
+            button { "Save" }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<form method=\"post\" action=\"/posts/1\">\
+        <input type=\"hidden\" name=\"_method\" value=\"PATCH\"><button>Save</button></form>"
+    );
+
+    let output = context::provide(MethodField("http_method"), || html.to_html());
+
+    assert_eq!(
+        output,
+        "<form method=\"post\" action=\"/posts/1\">\
+        <input type=\"hidden\" name=\"http_method\" value=\"PATCH\"><button>Save</button></form>"
+    );
+}
+
+#[test]
+fn test_form_with_csrf_token() {
+    let html = html! {
+        @FormWith(method: Method::Get, action: "/search") {}
+        @FormWith(method: Method::Put, action: "/posts/1") {}
+    };
+
+    let output = context::provide(CsrfToken::new("csrf", "a\"b"), || html.to_html());
+
+    assert_eq!(
+        output,
+        "<form method=\"get\" action=\"/search\"></form>\
+        <form method=\"post\" action=\"/posts/1\">\
+        <input type=\"hidden\" name=\"_method\" value=\"PUT\">\
+        <input type=\"hidden\" name=\"csrf\" value=\"a&quot;b\"></form>"
+    );
+}
+
+#[test]
+fn test_labeled_generates_id() {
+    let html = html! {
+        @Labeled(label: "Username") {
+            input(type: "hidden", name: "token", value: "abc");
+            input(name: "username");
+        }
+        @Labeled(label: "Username") {
+            input(name: "username");
+        }
+        @Labeled(label: "Notes") {
+            textarea {}
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<label for=\"username\">Username</label>\
+        <input type=\"hidden\" name=\"token\" value=\"abc\"><input id=\"username\" name=\"username\">\
+        <label for=\"username-1\">Username</label><input id=\"username-1\" name=\"username\">\
+        <label for=\"field\">Notes</label><textarea id=\"field\"></textarea>"
+    );
+}
+
+#[test]
+fn test_labeled_keeps_existing_id() {
+    let html = html! {
+        @Labeled(label: "Size") {
+            select(id: "size&shape", name: "size") {
+                option { "Small" }
+            }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<label for=\"size&amp;shape\">Size</label>\
+        <select id=\"size&amp;shape\" name=\"size\"><option>Small</option></select>"
+    );
+}
+
+#[test]
+fn test_labeled_without_control() {
+    let html = html! {
+        @Labeled(label: "Nothing"; class: "hint") {
+            span { "No control here" }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<label class=\"hint\">Nothing</label><span>No control here</span>"
+    );
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "gallery")]
+
+use plait::{ToHtml, component, component_example, gallery, html};
+
+component! {
+    pub fn Button(label: &str) {
+        button(class: "btn") { (label) }
+    }
+}
+
+#[component_example(Button, "Primary")]
+fn button_primary() -> impl ToHtml {
+    html! { @Button(label: "Save") {} }
+}
+
+#[component_example(Button)]
+fn button_danger() -> impl ToHtml {
+    html! { @Button(label: "<Delete>") {} }
+}
+
+#[test]
+fn test_gallery_examples() {
+    let examples = gallery::examples();
+
+    assert_eq!(examples.len(), 2);
+
+    assert_eq!(examples[0].component(), "Button");
+    assert_eq!(examples[0].name(), "Primary");
+    assert_eq!(examples[0].render(), "<button class=\"btn\">Save</button>");
+
+    assert_eq!(examples[1].component(), "Button");
+    assert_eq!(examples[1].name(), "button_danger");
+}
+
+#[test]
+fn test_gallery_render() {
+    let page = gallery::render();
+
+    assert!(page.starts_with("<!DOCTYPE html><html lang=\"en\">"));
+    assert!(page.contains("<li><a href=\"#component-Button\">Button</a></li>"));
+    assert!(page.contains(
+        "<article class=\"plait-example\"><h3>Primary</h3><div class=\"plait-example-output\"><button class=\"btn\">Save</button></div><pre><code>&lt;button class=&quot;btn&quot;&gt;Save&lt;/button&gt;</code></pre></article>"
+    ));
+    assert!(page.contains("&lt;Delete&gt;"));
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "syntect")]
+
+use plait::{
+    ToHtml, context,
+    highlight::{self, CodeBlock, Highlighting},
+    html,
+};
+
+#[test]
+fn test_code_block_classes() {
+    let html = html! {
+        @CodeBlock(lang: "rust", code: "fn main() {}\n"; id: "example") {}
+    };
+
+    let html = html.to_html();
+
+    assert!(
+        html.starts_with("<pre class=\"code-block\" id=\"example\"><code class=\"language-rust\">")
+    );
+    assert!(html.contains("<span class=\"hl-storage hl-type hl-function hl-rust\">fn</span>"));
+    assert!(html.ends_with("</code></pre>"));
+}
+
+#[test]
+fn test_code_block_escapes_code() {
+    let html = html! {
+        @CodeBlock(lang: "html", code: "<script>alert(\"x\")</script>") {}
+    };
+
+    let html = html.to_html();
+
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("&lt;"));
+}
+
+#[test]
+fn test_code_block_unknown_language() {
+    let html = html! {
+        @CodeBlock(lang: "\"><x", code: "a < b") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<pre class=\"code-block\"><code class=\"language-&quot;&gt;&lt;x\">\
+        <span class=\"hl-text hl-plain\">a &lt; b</span></code></pre>"
+    );
+}
+
+#[test]
+fn test_code_block_inline_styles() {
+    let html = html! {
+        @CodeBlock(lang: "rs", code: "let x = 1;") {}
+    };
+
+    let output = context::provide(
+        Highlighting::InlineStyles {
+            theme: "base16-ocean.dark",
+        },
+        || html.to_html(),
+    );
+
+    assert!(output.starts_with(
+        "<pre class=\"code-block\" style=\"background-color:#2b303b;\"><code class=\"language-rs\"><span style=\""
+    ));
+    assert!(!output.contains("class=\"hl-"));
+
+    let fallback = context::provide(Highlighting::InlineStyles { theme: "missing" }, || {
+        html.to_html()
+    });
+
+    assert_eq!(fallback, html.to_html());
+}
+
+#[test]
+fn test_theme_css() {
+    assert!(highlight::themes().any(|theme| theme == "InspiredGitHub"));
+
+    let css = highlight::theme_css("InspiredGitHub").unwrap();
+
+    assert!(css.contains(".hl-code"));
+    assert!(highlight::theme_css("missing").is_none());
+}
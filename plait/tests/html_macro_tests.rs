@@ -1,4 +1,9 @@
-use plait::{ToHtml, html};
+// `test_html_macro_for_loop` renders a `Vec` on purpose, which clippy suggests replacing with an array
+#![allow(clippy::useless_vec)]
+
+use std::collections::HashMap;
+
+use plait::{Sorted, ToHtml, component, html, html_to};
 
 #[test]
 fn test_html_macro_text() {
@@ -170,6 +175,33 @@ fn test_html_macro_if_let_else() {
     assert_eq!(html.to_html(), "Hello<div></div>")
 }
 
+#[test]
+fn test_html_macro_if_let_chain() {
+    let render = |value: Option<Option<u32>>| {
+        html! {
+            if let Some(outer) = value && let Some(inner) = outer && inner > 1 {
+                "big " (inner)
+            } else if let Some(Some(inner)) = value && inner == 1 {
+                "one"
+            } else if value.is_some() {
+                "empty"
+            } else {
+                "none"
+            }
+            let mut counts = [2, 1, 0, 3].into_iter();
+            while let Some(count) = counts.next() && count > 0 {
+                " " (count)
+            }
+        }
+        .to_html()
+    };
+
+    assert_eq!(render(Some(Some(5))), "big 5 2 1");
+    assert_eq!(render(Some(Some(1))), "one 2 1");
+    assert_eq!(render(Some(None)), "empty 2 1");
+    assert_eq!(render(None), "none 2 1");
+}
+
 #[test]
 fn test_html_macro_for_loop() {
     let numbers = vec![1, 2, 3];
@@ -183,6 +215,163 @@ fn test_html_macro_for_loop() {
     assert_eq!(html.to_html(), "<li>1</li><li>2</li><li>3</li>")
 }
 
+#[test]
+fn test_html_macro_for_loop_separator() {
+    let numbers = [1, 2, 3];
+
+    let html = html! {
+        for number in numbers.iter(), sep { ", " } {
+            (number)
+        }
+    };
+
+    assert_eq!(html.to_html(), "1, 2, 3")
+}
+
+#[test]
+fn test_html_macro_for_loop_separator_edge_cases() {
+    let empty: [u32; 0] = [];
+    let single = ["only"];
+
+    let html = html! {
+        ul {
+            for item in empty.iter(), sep { li(class: "sep") {} } {
+                li { (item) }
+            }
+        }
+        ul {
+            for item in single.iter(), sep { li(class: "sep") {} } {
+                li { (item) }
+            }
+        }
+    };
+
+    assert_eq!(html.to_html(), "<ul></ul><ul><li>only</li></ul>")
+}
+
+#[test]
+fn test_html_macro_for_loop_else() {
+    let render = |items: &[&str]| {
+        html! {
+            ul {
+                for item in items.iter().filter(|item| !item.is_empty()) {
+                    li { (item) }
+                } else {
+                    li(class: "empty") { "No items" }
+                }
+            }
+            p {
+                for item in items, sep { ", " } {
+                    (item)
+                } else {
+                    "none"
+                }
+            }
+        }
+        .to_html()
+    };
+
+    assert_eq!(
+        render(&[]),
+        "<ul><li class=\"empty\">No items</li></ul><p>none</p>"
+    );
+    assert_eq!(
+        render(&[""]),
+        "<ul><li class=\"empty\">No items</li></ul><p></p>"
+    );
+    assert_eq!(
+        render(&["a", "b"]),
+        "<ul><li>a</li><li>b</li></ul><p>a, b</p>"
+    );
+}
+
+#[test]
+fn test_html_macro_nested_for_loop_separators() {
+    let rows = [[1, 2], [3, 4]];
+
+    let html = html! {
+        for row in rows.iter(), sep { br; } {
+            for cell in row.iter().map(|cell| cell * 10), sep { " | " } {
+                (cell)
+            }
+        }
+    };
+
+    assert_eq!(html.to_html(), "10 | 20<br>30 | 40")
+}
+
+#[test]
+fn test_html_macro_while_loop() {
+    struct Pages {
+        next: u32,
+        last: u32,
+    }
+
+    impl Pages {
+        fn next_page(&mut self) -> Option<u32> {
+            (self.next <= self.last).then(|| {
+                self.next += 1;
+                self.next - 1
+            })
+        }
+    }
+
+    let last = 3;
+
+    let html = html! {
+        ol {
+            let mut pages = Pages { next: 1, last };
+            while let Some(page) = pages.next_page() {
+                li { "Page " (page) }
+            }
+        }
+        let remaining = std::cell::Cell::new(2);
+        while remaining.get() > 0 {
+            span { (remaining.replace(remaining.get() - 1)) }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ol><li>Page 1</li><li>Page 2</li><li>Page 3</li></ol><span>2</span><span>1</span>"
+    );
+
+    // Every render starts from a fresh state
+    assert_eq!(html.to_html(), html.to_html());
+}
+
+#[test]
+fn test_html_macro_let_else() {
+    let users = [Some("alice"), None, Some("bob")];
+
+    let html = html! {
+        ul {
+            for user in users {
+                let Some(name) = user else {
+                    li { "Anonymous" }
+                };
+                let name = name.to_uppercase();
+                li { (name) }
+            }
+        }
+        div {
+            let Some(first) = users[0] else {
+                "no first user"
+            };
+            let Some(second) = users[1] else {
+                "only " (first)
+            };
+            "both " (first) " and " (second)
+        }
+        p { "end" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul><li>ALICE</li><li>Anonymous</li><li>BOB</li></ul><div>only alice</div><p>end</p>"
+    );
+}
+
 #[test]
 fn test_html_macro_match() {
     let element = "div";
@@ -287,6 +476,20 @@ fn test_html_macro_attribute_ident_rename() {
     assert_eq!(html.to_html(), "<div hx-target=\"body\"></div>")
 }
 
+#[test]
+fn test_html_macro_attribute_raw_ident_name() {
+    let model = Some("user.name");
+
+    let html = html! {
+        input(r#ng_model: "x", r#type: "text", r#data_my_field?: model, r#http_equiv);
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<input ng_model=\"x\" type=\"text\" data_my_field=\"user.name\" http_equiv>"
+    )
+}
+
 #[test]
 fn test_html_macro_attribute_raw_expr() {
     let html = html! {
@@ -407,3 +610,566 @@ fn test_html_macro_multiple_attributes() {
         "<button id=\"button\" checked class=\"btn\" type=\"submit\">Hello World</button>"
     )
 }
+
+#[test]
+fn test_html_macro_control_flow_attribute_values() {
+    #[derive(Clone, Copy)]
+    enum Size {
+        Sm,
+        Lg,
+    }
+
+    let render = |size: Size, active: bool| {
+        html! {
+            a(
+                class: { match size { Size::Sm => "p-1", Size::Lg => "p-4" } },
+                href: if active { "/current" } else { "/other" },
+                title: { let label = ["G", "o"]; label.concat() },
+                aria_current?: { if active { Some("page") } else { None } },
+                data_size: #(match size { Size::Sm => "sm", Size::Lg => "lg" }),
+            ) {}
+        }
+        .to_html()
+    };
+
+    assert_eq!(
+        render(Size::Lg, true),
+        "<a class=\"p-4\" href=\"/current\" title=\"Go\" aria-current=\"page\" data-size=\"lg\"></a>"
+    );
+    assert_eq!(
+        render(Size::Sm, false),
+        "<a class=\"p-1\" href=\"/other\" title=\"Go\" data-size=\"sm\"></a>"
+    );
+}
+
+#[test]
+fn test_html_macro_numeric_attributes() {
+    let count = 42;
+    let offset = -7i64;
+    let tiny = 1e-7;
+    let huge = 2.5e16f64;
+    let ratio = 0.5f32;
+
+    let html = html! {
+        div(
+            data_count: count,
+            data_offset: (offset),
+            data_tiny: (tiny),
+            data_huge: (huge),
+            data_ratio?: Some(ratio),
+            data_literal: 1e-7,
+            data_hex: 0x2A,
+        ) {
+            (tiny) " " #(huge)
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div data-count=\"42\" data-offset=\"-7\" data-tiny=\"0.0000001\" data-huge=\"25000000000000000\" \
+         data-ratio=\"0.5\" data-literal=\"0.0000001\" data-hex=\"42\">0.0000001 25000000000000000</div>"
+    );
+}
+
+#[test]
+fn test_html_macro_coalesces_static_markup() {
+    struct WriteCounter(Vec<String>);
+
+    impl std::fmt::Write for WriteCounter {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            self.0.push(s.to_owned());
+            Ok(())
+        }
+    }
+
+    let rows = [(1, "one"), (2, "two")];
+
+    let html = html! {
+        tbody {
+            for (id, name) in rows.iter() {
+                tr(class: "table-row border-bottom") {
+                    td(class: "text-end font-monospace") { (id) }
+                    td(class: "text-start") { (name) }
+                }
+            }
+        }
+    };
+
+    let mut writer = WriteCounter(Vec::new());
+    plait::RenderEscaped::render_escaped(&html, &mut writer).unwrap();
+
+    assert_eq!(
+        writer.0,
+        [
+            "<tbody>",
+            "<tr class=\"table-row border-bottom\"><td class=\"text-end font-monospace\">",
+            "1",
+            "</td><td class=\"text-start\">",
+            "one",
+            "</td></tr>",
+            "<tr class=\"table-row border-bottom\"><td class=\"text-end font-monospace\">",
+            "2",
+            "</td><td class=\"text-start\">",
+            "two",
+            "</td></tr>",
+            "</tbody>",
+        ]
+    );
+}
+
+component! {
+    fn Badge(label: &str) {
+        span(class: "badge", #attrs) { (label) }
+    }
+}
+
+#[test]
+fn test_html_to_string() {
+    let items = ["a", "b"];
+    let mut output = String::from("<!-- start -->");
+
+    html_to!(&mut output, {
+        let count = items.len();
+
+        ul(data_count: count) {
+            for item in items.iter() {
+                li { @Badge(label: item; id: "x") {} }
+            }
+        }
+    })
+    .unwrap();
+
+    assert_eq!(
+        output,
+        "<!-- start --><ul data-count=\"2\"><li><span class=\"badge\" id=\"x\">a</span></li>\
+        <li><span class=\"badge\" id=\"x\">b</span></li></ul>"
+    );
+}
+
+#[test]
+fn test_html_to_formatter() {
+    struct Link<'a>(&'a str);
+
+    impl std::fmt::Display for Link<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            html_to!(f, { a(href: self.0) { "link" } })
+        }
+    }
+
+    assert_eq!(
+        Link("/?a=1&b=2").to_string(),
+        "<a href=\"/?a=1&amp;b=2\">link</a>"
+    );
+}
+
+#[test]
+fn test_html_to_propagates_errors() {
+    struct FailingWriter;
+
+    impl std::fmt::Write for FailingWriter {
+        fn write_str(&mut self, _: &str) -> std::fmt::Result {
+            Err(std::fmt::Error)
+        }
+    }
+
+    let mut writer = FailingWriter;
+
+    assert!(html_to!(&mut writer, { p { "x" } }).is_err());
+}
+
+#[test]
+fn test_html_to_inside_fragment() {
+    let html = html! {
+        div {
+            #({
+                let mut inner = String::new();
+                html_to!(&mut inner, { b { "bold" } }).unwrap();
+                inner
+            })
+        }
+    };
+
+    assert_eq!(html.to_html(), "<div><b>bold</b></div>");
+}
+
+#[test]
+fn test_html_macro_nested_html() {
+    let items = ["a", "b"];
+
+    let html = html! {
+        ul {
+            for item in items {
+                html! { li { (item) } }
+            }
+            html!(li { "c" })
+            html![li { "d" }]
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul><li>a</li><li>b</li><li>c</li><li>d</li></ul>"
+    );
+}
+
+#[test]
+fn test_html_macro_inline_fragment() {
+    let name = "World";
+
+    let html = html! {
+        div {
+            @{
+                let greeting = "Hello";
+                span { (greeting) ", " (name) }
+            }
+            @{}
+        }
+    };
+
+    assert_eq!(html.to_html(), "<div><span>Hello, World</span></div>");
+}
+
+#[test]
+fn test_html_macro_css_vars() {
+    let accent = "red; color: blue";
+    let gap: Option<&str> = None;
+    let frag = html! {
+        div(css_vars: {accent: accent, grid_gap: gap, r#z_index: 3}) {}
+        span(css_vars?: {gap: gap}) {}
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        r#"<div style="--accent: red\; color: blue; --z-index: 3"></div><span></span>"#
+    );
+}
+
+#[test]
+fn test_html_macro_allow_invalid_nesting() {
+    let frag = html! {
+        p { #[allow_invalid_nesting] div { "block" } }
+        #[allow_invalid_nesting] tr { td { "cell" } }
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        "<p><div>block</div></p><tr><td>cell</td></tr>"
+    );
+}
+
+#[test]
+fn test_html_macro_attribute_pairs() {
+    let config = HashMap::from([
+        ("data-theme".to_owned(), "<dark>".to_owned()),
+        ("title".to_owned(), "Widget".to_owned()),
+        ("bad name".to_owned(), "x".to_owned()),
+        ("a\"><script>".to_owned(), "x".to_owned()),
+    ]);
+    let pairs = [("rel", "next"), ("href", "/page/2"), ("rel", "prev")];
+
+    let frag = html! {
+        div(class: "widget", #attrs(Sorted(&config))) {}
+        a(#attrs(pairs)) {}
+        span(#attrs(Vec::<(&str, &str)>::new())) {}
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        "<div class=\"widget\" data-theme=\"&lt;dark&gt;\" title=\"Widget\"></div>\
+        <a rel=\"next\" href=\"/page/2\"></a><span></span>"
+    );
+}
+
+#[test]
+fn test_html_macro_svg() {
+    let size = 24;
+    let frag = html! {
+        svg(viewBox: "0 0 24 24", width: (size), stroke_width: 2) {
+            defs {
+                linearGradient(id: "fade", gradientTransform: "rotate(90)") {
+                    stop(offset: "0");
+                    stop(offset: "1") {}
+                }
+            }
+            path(d: "M0 0L24 24", fill: "url(#fade)");
+            a(href: "/home") { circle(cx: 12, cy: 12, r: 4); }
+            foreignObject(width: 10, height: 10) { p { "text" } br; }
+        }
+        svg(no_xmlns, "viewBox": "0 0 1 1") { svg {} }
+        svg(xmlns: "http://www.w3.org/2000/svg") {}
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\" width=\"24\" stroke-width=\"2\">\
+        <defs><linearGradient id=\"fade\" gradientTransform=\"rotate(90)\"><stop offset=\"0\"/><stop offset=\"1\"/>\
+        </linearGradient></defs><path d=\"M0 0L24 24\" fill=\"url(#fade)\"/>\
+        <a href=\"/home\"><circle cx=\"12\" cy=\"12\" r=\"4\"/></a>\
+        <foreignObject width=\"10\" height=\"10\"><p>text</p><br></foreignObject></svg>\
+        <svg viewBox=\"0 0 1 1\"><svg/></svg><svg xmlns=\"http://www.w3.org/2000/svg\"/>"
+    );
+}
+
+#[test]
+fn test_html_macro_svg_component() {
+    component! {
+        fn Icon(d: &str) {
+            path(d: d, stroke_linecap: "round");
+        }
+    }
+
+    let frag = html! {
+        svg(viewBox: "0 0 24 24") { @Icon(d: "M1 1") {} }
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\"><path d=\"M1 1\" stroke-linecap=\"round\"/></svg>"
+    );
+}
+
+#[test]
+fn test_html_macro_mathml() {
+    let exponent = 2;
+    let frag = html! {
+        p {
+            math(display: "block") {
+                mrow {
+                    msup { mi { "x" } mn { (exponent) } }
+                    mo { "+" }
+                    mfrac { mn { "1" } mn { "2" } }
+                    mspace(width: "1em");
+                    mtext { span { "text" } br; }
+                }
+                annotation_xml(encoding: "text/html", definitionURL: "/def") { svg {} }
+            }
+        }
+        math(no_xmlns) { none {} }
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        "<p><math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\"><mrow>\
+        <msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mfrac><mn>1</mn><mn>2</mn></mfrac><mspace width=\"1em\"/>\
+        <mtext><span>text</span><br></mtext></mrow>\
+        <annotation-xml encoding=\"text/html\" definitionURL=\"/def\"><svg xmlns=\"http://www.w3.org/2000/svg\"/>\
+        </annotation-xml></math></p><math><none/></math>"
+    );
+}
+
+#[test]
+fn test_html_macro_raw_text_elements() {
+    let config = r#"{"name": "O'Brien", "bio": "</script><script>alert(1)"}"#;
+    let selector = "a[href^='http'] > b";
+    let frag = html! {
+        script(type: "application/json") { (config) }
+        script { "if (a < b && c) { x = '<!-- </SCRIPT'; }" }
+        style { (selector) " { color: red } /* </style> */" }
+        p { (selector) }
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        "<script type=\"application/json\">{\"name\": \"O'Brien\", \"bio\": \"\\u003c/script><script>alert(1)\"}</script>\
+        <script>if (a < b && c) { x = '\\u003c!-- \\u003c/SCRIPT'; }</script>\
+        <style>a[href^='http'] > b { color: red } /* <\\/style> */</style>\
+        <p>a[href^=&#39;http&#39;] &gt; b</p>"
+    );
+}
+
+#[test]
+fn test_html_macro_render_into() {
+    let mut buffer = String::from("<!-- header -->");
+
+    html! { span(id: (plait::ids::next("item"))) {} }.render_into(&mut buffer);
+    html! { span(id: (plait::ids::next("item"))) {} }.render_into(&mut buffer);
+
+    assert_eq!(
+        buffer,
+        "<!-- header --><span id=\"item\"></span><span id=\"item\"></span>"
+    );
+
+    let capacity = buffer.capacity();
+    buffer.clear();
+    html! { "reused" }.render_into(&mut buffer);
+
+    assert_eq!(buffer, "reused");
+    assert_eq!(buffer.capacity(), capacity);
+}
+
+#[test]
+fn test_fragment_into_owned() {
+    fn assert_send_static<T: Send + Clone + 'static>(_: &T) {}
+
+    struct Cache {
+        header: plait::Html,
+    }
+
+    let title = String::from("Fish & chips");
+    let name = title.as_str();
+    let cache = Cache {
+        header: html! { h1 { (name) } }.into_owned(),
+    };
+    drop(title);
+
+    assert_send_static(&cache.header);
+
+    let header = cache.header.clone();
+    let page = std::thread::spawn(move || html! { header { (header) } }.to_html())
+        .join()
+        .unwrap();
+
+    assert_eq!(page, "<header><h1>Fish &amp; chips</h1></header>");
+    assert_eq!(cache.header.to_html(), "<h1>Fish &amp; chips</h1>");
+}
+
+#[test]
+fn test_render_functions() {
+    let name = "Alice";
+    let fragment = html! { p(id: plait::ids::next("p")) { "Hi " (name) } };
+
+    assert_eq!(plait::render(&fragment), "<p id=\"p\">Hi Alice</p>");
+
+    let output = plait::render_with_capacity(&fragment, 1024);
+    assert_eq!(output, "<p id=\"p\">Hi Alice</p>");
+    assert!(output.capacity() >= 1024);
+}
+
+#[test]
+fn test_write_io() {
+    struct FailingWriter(usize);
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.0 == 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "closed",
+                ));
+            }
+
+            self.0 -= 1;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let items = ["a & b", "c"];
+    let fragment = html! { ul { for item in items { li { (item) } } } };
+
+    let mut output = std::io::BufWriter::new(Vec::new());
+    fragment.write_io(&mut output).unwrap();
+    assert_eq!(
+        output.into_inner().unwrap(),
+        b"<ul><li>a &amp; b</li><li>c</li></ul>"
+    );
+
+    let error = fragment.write_io(&mut FailingWriter(2)).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_fragment_size_hint() {
+    let fragment = html! { div { h1 { "Title" } br; } };
+    assert_eq!(fragment.size_hint(), fragment.to_html().len());
+
+    let show = true;
+    let fragment = html! {
+        if show { p { "A much longer paragraph" } } else { p { "Short" } }
+    };
+    assert_eq!(fragment.size_hint(), "<p>A much longer paragraph</p>".len());
+
+    let name = "a rather long name that is longer than the template";
+    let fragment = html! { p { (name) } };
+    assert!(fragment.size_hint() >= "p { (name) }".len());
+    assert!(fragment.size_hint() < fragment.to_html().len());
+}
+
+#[test]
+fn test_hyperscript_attribute() {
+    let count = 3;
+
+    let html = html! {
+        button(_: "on click increment :count", x?: Some(count)) { "+" }
+        div(_?: None::<&str>) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<button _=\"on click increment :count\" x=\"3\">+</button><div></div>"
+    );
+}
+
+#[test]
+fn test_text_interpolation() {
+    struct User {
+        name: String,
+    }
+
+    let user = User {
+        name: "<Ada>".to_owned(),
+    };
+    let count = 3;
+    let pair = ("a", 2);
+
+    let html = html! {
+        p { f!("Hello, {user.name}! You have {count} items") }
+        p { f!("{pair.0}{pair.1} {{count}} {count + 1}") }
+        style { f!("p {{ width: {count}px; }}") }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<p>Hello, &lt;Ada&gt;! You have 3 items</p>\
+        <p>a2 {count} 4</p>\
+        <style>p { width: 3px; }</style>"
+    );
+}
+
+#[test]
+fn test_text_literal_braces() {
+    let html = html! {
+        p { "{{ x }} {name} {} }" }
+        style { "p { color: red; }" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<p>{{ x }} {name} {} }</p><style>p { color: red; }</style>"
+    );
+}
+
+#[test]
+fn test_attribute_whitespace_encoding() {
+    let srcset = "/a.png 1x,\n\t/b.png 2x";
+    let content = Some("width=device-width,\r\ninitial-scale=1");
+    let title = "two\nlines";
+    let pairs = [("sizes", "(max-width: 600px)\t480px")];
+
+    let html = html! {
+        img(srcset: "/a.png 1x,\n/b.png 2x", alt: "\"a\"");
+        img(srcset: (srcset), #attrs(pairs));
+        meta(name: "viewport", content?: content);
+        p(title: (title)) {}
+    };
+
+    let title = if cfg!(feature = "encode-attribute-whitespace") {
+        "two&#10;lines"
+    } else {
+        "two\nlines"
+    };
+
+    assert_eq!(
+        html.to_html(),
+        format!(
+            "<img srcset=\"/a.png 1x,&#10;/b.png 2x\" alt=\"&quot;a&quot;\">\
+            <img srcset=\"/a.png 1x,&#10;&#9;/b.png 2x\" sizes=\"(max-width: 600px)&#9;480px\">\
+            <meta name=\"viewport\" content=\"width=device-width,&#13;&#10;initial-scale=1\">\
+            <p title=\"{title}\"></p>"
+        )
+        .as_str()
+    );
+}
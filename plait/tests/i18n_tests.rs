@@ -0,0 +1,61 @@
+#![cfg(feature = "i18n")]
+
+use std::collections::HashMap;
+
+use plait::{
+    ToHtml, component, context, html,
+    i18n::{self, Translations},
+    t,
+};
+
+fn french() -> Translations {
+    Translations::new(HashMap::from([
+        ("greeting", "Bonjour, {name} !"),
+        ("items", "{count} articles dans {{le panier}} de {name}"),
+        ("markup", "<b>{missing}</b>"),
+    ]))
+}
+
+component! {
+    pub fn Cart(owner: &str, count: usize) {
+        p(title: t!("greeting", name = owner)) { t!("items", count = count, name = owner) }
+    }
+}
+
+#[test]
+fn test_translations() {
+    let owner = "<Ada>";
+    let page = html! {
+        h1 { t!("greeting", name = owner) }
+        @Cart(owner, count: 2) {}
+        p { t!("markup") }
+    };
+
+    assert_eq!(
+        context::provide(french(), || page.to_html()),
+        "<h1>Bonjour, &lt;Ada&gt; !</h1>\
+        <p title=\"Bonjour, &lt;Ada&gt; !\">2 articles dans {le panier} de &lt;Ada&gt;</p>\
+        <p>&lt;b&gt;{missing}&lt;/b&gt;</p>"
+    );
+}
+
+#[test]
+fn test_untranslated_keys() {
+    let page = html! { p { t!("Hello, {name}!", name = "world") } };
+
+    assert_eq!(page.to_html(), "<p>Hello, world!</p>");
+    assert_eq!(
+        context::provide(french(), || page.to_html()),
+        "<p>Hello, world!</p>"
+    );
+}
+
+#[test]
+fn test_translator_closure() {
+    let translations = Translations::new(|key: &str| (key == "title").then(|| "Titre".to_owned()));
+
+    context::provide(translations, || {
+        assert_eq!(i18n::translate("title"), "Titre");
+        assert_eq!(i18n::translate("body"), "body");
+    });
+}
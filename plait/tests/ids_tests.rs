@@ -0,0 +1,69 @@
+use plait::{ToHtml, component, html, ids};
+
+#[test]
+fn test_next_outside_render() {
+    assert_eq!(ids::next("email"), "email");
+    assert_eq!(ids::next("email"), "email");
+}
+
+#[test]
+fn test_next_unique_per_render() {
+    let html = html! {
+        for _ in 0..3 {
+            span(id: (ids::next("item"))) {}
+        }
+    };
+
+    let expected =
+        "<span id=\"item\"></span><span id=\"item-1\"></span><span id=\"item-2\"></span>";
+
+    assert_eq!(html.to_html(), expected);
+    assert_eq!(html.to_html(), expected);
+}
+
+#[test]
+fn test_next_shares_namespace_with_auto_id() {
+    let html = html! {
+        h2(auto_id) { "Intro" }
+        div(id: (ids::next("intro"))) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<h2 id=\"intro\">Intro</h2><div id=\"intro-1\"></div>"
+    );
+}
+
+#[test]
+fn test_stable() {
+    assert_eq!(ids::stable("details", 42), ids::stable("details", "42"));
+    assert_ne!(ids::stable("details", 42), ids::stable("details", 43));
+    assert_ne!(ids::stable("details", 42), ids::stable("panel", 42));
+
+    // Ids must not change between releases, or pages rendered by different versions stop matching
+    assert_eq!(ids::stable("details", 42), "details-bd130b65");
+}
+
+component! {
+    fn Disclosure<T: std::fmt::Display>(key: T) {
+        let id = ids::component::<Self>(key);
+
+        button(aria_controls: id) {}
+        div(id: id) {}
+    }
+}
+
+#[test]
+fn test_component() {
+    let first = html! { @Disclosure(key: 7) {} }.to_html().to_string();
+    let partial = html! { @Disclosure(key: "7") {} }.to_html().to_string();
+
+    let id = ids::component::<Disclosure<u8>>(7);
+
+    assert!(id.starts_with("disclosure-"));
+    assert_eq!(first, partial);
+    assert_eq!(
+        first,
+        format!("<button aria-controls=\"{id}\"></button><div id=\"{id}\"></div>")
+    );
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "serde_json")]
+
+use plait::{ToHtml, classes, html};
+use serde_json::json;
+
+#[test]
+fn test_json_value_text() {
+    let ctx = json!({
+        "user": { "name": "<Alice>", "age": 30, "admin": true, "tags": ["a", "b"] }
+    });
+
+    let html = html! {
+        (ctx["user"]["name"]) "|" (ctx["user"]["age"]) "|" (ctx["user"]["admin"]) "|" (ctx["user"]["tags"])
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "&lt;Alice&gt;|30|true|[&quot;a&quot;,&quot;b&quot;]"
+    );
+}
+
+#[test]
+fn test_json_value_raw() {
+    let ctx = json!({ "body": "<b>bold</b>" });
+
+    let html = html! {
+        #(ctx["body"])
+    };
+
+    assert_eq!(html.to_html(), "<b>bold</b>");
+}
+
+#[test]
+fn test_json_value_missing_key() {
+    let ctx = json!({ "user": {} });
+
+    let html = html! {
+        span { (ctx["user"]["name"]) (ctx["missing"]["deeply"]) }
+    };
+
+    assert_eq!(html.to_html(), "<span></span>");
+}
+
+#[test]
+fn test_json_value_maybe_attribute() {
+    let ctx = json!({ "title": "Hi \"there\"", "hidden": true, "disabled": false });
+
+    let html = html! {
+        div(title?: ctx["title"], hidden?: ctx["hidden"], disabled?: ctx["disabled"], id?: ctx["id"]) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div title=\"Hi &quot;there&quot;\" hidden></div>"
+    );
+}
+
+#[test]
+fn test_json_value_class() {
+    let ctx = json!({ "theme": "dark", "empty": "" });
+
+    let html = html! {
+        div(class: classes!("base", &ctx["theme"], &ctx["empty"], &ctx["missing"])) {}
+    };
+
+    assert_eq!(html.to_html(), "<div class=\"base dark\"></div>");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_json_embedding() {
+    use plait::Json;
+
+    let config = json!({ "user": "O'Brien", "note": "</script>", "sep": "a\u{2028}b & c" });
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&Json(&config).to_json().unwrap()).unwrap();
+    assert_eq!(parsed, config);
+
+    let html = html! {
+        script(type: "application/json") { (Json(&config)) }
+        button(hx_vals: (Json(&config))) { "Save" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        r#"<script type="application/json">{"note":"\u003c/script\u003e","sep":"a\u2028b \u0026 c","user":"O'Brien"}</script><button hx-vals="{&quot;note&quot;:&quot;\u003c/script\u003e&quot;,&quot;sep&quot;:&quot;a\u2028b \u0026 c&quot;,&quot;user&quot;:&quot;O&#39;Brien&quot;}">Save</button>"#
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_json_serialization_error() {
+    use std::collections::BTreeMap;
+
+    let map = BTreeMap::from([((1, 2), "pair")]);
+
+    assert!(plait::Json(&map).to_json().is_err());
+}
@@ -0,0 +1,106 @@
+use plait::{
+    ToHtml, html,
+    layout::{Columns, ColumnsFrom},
+};
+
+#[test]
+fn test_columns_balanced() {
+    let items = [1, 2, 3, 4, 5, 6, 7];
+
+    let html = html! {
+        @Columns(count: 3; id: "grid") {
+            for item in items.iter() {
+                article { (item) }
+            }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div class=\"columns\" id=\"grid\">\
+        <div class=\"column\"><article>1</article><article>2</article><article>3</article></div>\
+        <div class=\"column\"><article>4</article><article>5</article></div>\
+        <div class=\"column\"><article>6</article><article>7</article></div></div>"
+    );
+}
+
+#[test]
+fn test_columns_nested_elements_and_text() {
+    let html = html! {
+        @Columns(count: 2) {
+            div { p { "nested" } br; }
+            "text"
+            img(src: "a.png");
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div class=\"columns\">\
+        <div class=\"column\"><div><p>nested</p><br></div>text</div>\
+        <div class=\"column\"><img src=\"a.png\"></div></div>"
+    );
+}
+
+#[test]
+fn test_columns_adjacent_text_children() {
+    let html = html! {
+        @Columns(count: 2) {
+            "a"
+            "b"
+            script { "let x = '</a" "é';" }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div class=\"columns\"><div class=\"column\">ab</div>\
+        <div class=\"column\"><script>let x = '</aé';</script></div></div>"
+    );
+}
+
+#[test]
+fn test_columns_zero_count() {
+    let html = html! {
+        @Columns(count: 0) { "a" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div class=\"columns\"><div class=\"column\">a</div></div>"
+    );
+}
+
+#[test]
+fn test_columns_from() {
+    let items = vec!["<a>".to_owned(), "b".to_owned(), "c".to_owned()];
+
+    let html = html! {
+        @ColumnsFrom(count: 2, items: &items; data_layout: "wide") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<div class=\"columns\" data-layout=\"wide\"><div class=\"column\">&lt;a&gt;b</div><div class=\"column\">c</div></div>"
+    );
+}
+
+#[test]
+fn test_chunks_in_for_loop() {
+    let items = ["a", "b", "c"];
+
+    let html = html! {
+        for (index, row) in items.chunks(2).enumerate() {
+            ul(data_row: index) {
+                for item in row {
+                    li { (item) }
+                }
+            }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<ul data-row=\"0\"><li>a</li><li>b</li></ul><ul data-row=\"1\"><li>c</li></ul>"
+    );
+}
@@ -0,0 +1,108 @@
+use plait::{ToHtml, html, links};
+
+#[test]
+fn test_audit_collects_urls_in_order() {
+    let html = html! {
+        link(rel: "stylesheet", href: "/app.css");
+        a(href: "/posts?page=2&amp;sort=new", ping: "/ping /track") { "Next" }
+        form(action: "/search") {
+            button(formaction: "/search/advanced") { "Go" }
+        }
+        video(src: "/intro.mp4", poster: "/intro.jpg") {}
+        a(href: "mailto:team@example.com") { "Mail" }
+    };
+
+    let report = links::audit(&html.to_html());
+    let urls: Vec<_> = report
+        .links()
+        .iter()
+        .map(|link| {
+            (
+                link.element.as_str(),
+                link.attribute.as_str(),
+                link.url.as_str(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        urls,
+        [
+            ("link", "href", "/app.css"),
+            ("a", "href", "/posts?page=2&amp;sort=new"),
+            ("a", "ping", "/ping"),
+            ("a", "ping", "/track"),
+            ("form", "action", "/search"),
+            ("button", "formaction", "/search/advanced"),
+            ("video", "src", "/intro.mp4"),
+            ("video", "poster", "/intro.jpg"),
+            ("a", "href", "mailto:team@example.com"),
+        ]
+    );
+}
+
+#[test]
+fn test_audit_decodes_and_ignores_case() {
+    let report = links::audit("<A HREF='/a?x=1&amp;y=2'>a</A><IMG SRC=HTTP://example.com/x.png>");
+
+    assert_eq!(report.links()[0].attribute, "href");
+    assert_eq!(report.links()[0].url, "/a?x=1&y=2");
+    assert_eq!(report.links()[1].element, "img");
+    assert_eq!(report.insecure().count(), 1);
+}
+
+#[test]
+fn test_broken_fragments() {
+    let html = html! {
+        a(href: "#") { "Top" }
+        a(href: "#top") { "Top" }
+        a(href: "#section") { "Section" }
+        a(href: "#gone") { "Gone" }
+        section(id: "section") {}
+    };
+
+    let report = links::audit(&html.to_html());
+    let broken: Vec<_> = report.broken_fragments().map(|link| &link.url).collect();
+
+    assert_eq!(broken, ["#gone"]);
+    assert!(report.ids().contains("section"));
+}
+
+#[test]
+fn test_insecure_and_protocol_relative() {
+    let html = html! {
+        script(src: "//cdn.example.com/app.js") {}
+        img(src: "https://example.com/a.png");
+        iframe(src: "http://example.com/embed") {}
+    };
+
+    let report = links::audit(&html.to_html());
+
+    let insecure: Vec<_> = report.insecure().map(|link| &link.url).collect();
+    assert_eq!(insecure, ["http://example.com/embed"]);
+
+    let protocol_relative: Vec<_> = report.protocol_relative().map(|link| &link.url).collect();
+    assert_eq!(protocol_relative, ["//cdn.example.com/app.js"]);
+
+    assert_eq!(report.internal().count(), 0);
+}
+
+#[test]
+fn test_dead_internal_links() {
+    let html = html! {
+        a(href: "/") { "Home" }
+        a(href: "/about#team") { "Team" }
+        a(href: "/old-page?ref=nav") { "Old" }
+        a(href: "https://example.com/") { "External" }
+    };
+
+    let report = links::audit(&html.to_html());
+    let routes = ["/", "/about"];
+
+    let dead: Vec<_> = report
+        .dead_internal(|path| routes.contains(&path))
+        .map(|link| &link.url)
+        .collect();
+
+    assert_eq!(dead, ["/old-page?ref=nav"]);
+}
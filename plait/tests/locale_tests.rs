@@ -0,0 +1,56 @@
+use plait::{
+    ToHtml, context, html,
+    locale::{self, Bidi, Direction, Document, Locale},
+};
+
+#[test]
+fn test_locale_direction() {
+    for lang in ["ar", "he-IL", "fa_IR", "ur", "az-Arab", "ckb"] {
+        assert_eq!(Locale::new(lang).dir(), Direction::Rtl, "{lang}");
+    }
+
+    for lang in ["en", "en-US", "tr", "az-Latn", "pa-Guru", ""] {
+        assert_eq!(Locale::new(lang).dir(), Direction::Ltr, "{lang}");
+    }
+
+    let locale = Locale::new("en").with_dir(Direction::Auto);
+    assert_eq!(locale.lang(), "en");
+    assert_eq!(locale.dir(), Direction::Auto);
+}
+
+#[test]
+fn test_dir_from_context() {
+    assert_eq!(locale::dir(), Direction::Ltr);
+    assert_eq!(
+        context::provide(Locale::new("he"), locale::dir),
+        Direction::Rtl
+    );
+}
+
+#[test]
+fn test_document() {
+    let page = html! { @Document(; class: "no-js") { body { "Hi" } } };
+
+    assert_eq!(
+        page.to_html(),
+        "<!DOCTYPE html><html class=\"no-js\"><body>Hi</body></html>"
+    );
+    assert_eq!(
+        context::provide(Locale::new("fa"), || page.to_html()),
+        "<!DOCTYPE html><html lang=\"fa\" dir=\"rtl\" class=\"no-js\"><body>Hi</body></html>"
+    );
+}
+
+#[test]
+fn test_bidi() {
+    let name = "<دانا>";
+    let frag = html! {
+        @Bidi { (name) }
+        @Bidi(; dir: (Direction::Auto)) { "x" }
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        "<bdi>&lt;دانا&gt;</bdi><bdi dir=\"auto\">x</bdi>"
+    );
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "maud")]
+
+use plait::{ToHtml, html, maud::IntoMaud};
+
+#[test]
+fn test_maud_markup_in_plait() {
+    let name = "<Ada>";
+    let markup = maud::html! { b { (name) } };
+
+    let html = html! {
+        p { (markup) " " #(maud::PreEscaped("<i>raw</i>")) }
+    };
+
+    assert_eq!(html.to_html(), "<p><b>&lt;Ada&gt;</b> <i>raw</i></p>");
+}
+
+#[test]
+fn test_plait_in_maud() {
+    let fragment = html! { span(class: "x") { "<fragment>" } };
+    let rendered = html! { em { "html" } }.to_html();
+
+    let markup = maud::html! {
+        div { (fragment) (rendered) }
+    };
+
+    assert_eq!(
+        markup.into_string(),
+        "<div><span class=\"x\">&lt;fragment&gt;</span><em>html</em></div>"
+    );
+}
+
+#[test]
+fn test_into_maud() {
+    let fragment = html! { p { "a & b" } };
+
+    assert_eq!(fragment.into_maud().into_string(), "<p>a &amp; b</p>");
+    assert_eq!(html! { br; }.to_html().into_maud().into_string(), "<br>");
+}
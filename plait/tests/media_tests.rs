@@ -0,0 +1,160 @@
+use plait::{
+    ToHtml, context, html,
+    media::{Audio, ImgSpec, LazyIframe, Picture, SourceSpec, Track, TrackKind, Video, YouTube},
+    url_policy::{HttpUrls, UrlRules},
+};
+
+#[test]
+fn test_picture_orders_and_validates_sources() {
+    let sources = [
+        SourceSpec::new("/a.jpg 1x, /a@2x.jpg 2x"),
+        SourceSpec::new("/b.jpg"),
+        SourceSpec::new(""),
+        SourceSpec::new("/c.webp").mime_type("webp"),
+        SourceSpec::new("/d.webp").mime_type("image/webp; codecs=vp8"),
+        SourceSpec::new("/e.jpg 800w")
+            .media("(min-width: 800px)")
+            .sizes("100vw")
+            .size(800, 400),
+    ];
+
+    let img = ImgSpec::new("/a.jpg", "").srcset("/a.jpg 1x").lazy();
+
+    let html = html! {
+        @Picture(sources: &sources, img: img; class: "hero") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<picture class=\"hero\">\
+        <source srcset=\"/d.webp\" type=\"image/webp; codecs=vp8\">\
+        <source srcset=\"/e.jpg 800w\" media=\"(min-width: 800px)\" sizes=\"100vw\" width=\"800\" height=\"400\">\
+        <source srcset=\"/a.jpg 1x, /a@2x.jpg 2x\">\
+        <img src=\"/a.jpg\" alt=\"\" srcset=\"/a.jpg 1x\" loading=\"lazy\">\
+        </picture>"
+    );
+}
+
+#[test]
+fn test_picture_checks_urls() {
+    let sources = [SourceSpec::new("http://cdn.example/a.avif").mime_type("image/avif")];
+
+    let html = html! {
+        @Picture(sources: &sources, img: ImgSpec::new("http://cdn.example/a.jpg", "A")) {}
+    };
+
+    assert_eq!(
+        context::provide(UrlRules::new().http(HttpUrls::Upgrade), || html.to_html()),
+        "<picture><source srcset=\"https://cdn.example/a.avif\" type=\"image/avif\">\
+        <img src=\"https://cdn.example/a.jpg\" alt=\"A\"></picture>"
+    );
+}
+
+#[test]
+fn test_video_and_audio() {
+    let tracks = [
+        Track::captions("/v.en.vtt", "en", "English").as_default(),
+        Track {
+            srclang: Some("de"),
+            ..Track::new("/v.de.vtt", TrackKind::Subtitles)
+        },
+        Track::new("http://cdn.example/v.chapters.vtt", TrackKind::Chapters),
+    ];
+
+    let html = html! {
+        @Video(src: "http://cdn.example/v.mp4", captions: &tracks; muted, playsinline) {
+            "Your browser can't play this video."
+        }
+        @Audio(src: "/a.mp3", captions: &[]) {
+            a(href: "/a.mp3") { "Download" }
+        }
+    };
+
+    assert_eq!(
+        context::provide(UrlRules::new().http(HttpUrls::Block), || html.to_html()),
+        "<video controls preload=\"metadata\" muted playsinline>\
+        <track src=\"/v.en.vtt\" kind=\"captions\" srclang=\"en\" label=\"English\" default>\
+        <track src=\"/v.de.vtt\" kind=\"subtitles\" srclang=\"de\">\
+        <track kind=\"chapters\">\
+        Your browser can&#39;t play this video.</video>\
+        <audio src=\"/a.mp3\" controls preload=\"metadata\"><a href=\"/a.mp3\">Download</a></audio>"
+    );
+}
+
+#[test]
+fn test_lazy_iframe() {
+    let html = html! {
+        @LazyIframe(src: "http://maps.example/embed?q=a&b", title: "Map", sandbox: Some("")) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<iframe src=\"http://maps.example/embed?q=a&amp;b\" \
+        srcdoc=\"&lt;style&gt;*{margin:0;padding:0}html,body{height:100%;overflow:hidden}\
+        a{position:relative;display:flex;align-items:center;justify-content:center;height:100%;\
+        background:#000;color:#fff;font:16px/1.5 sans-serif;text-decoration:none}\
+        img{position:absolute;inset:0;width:100%;height:100%;object-fit:cover}\
+        span{position:relative;padding:.5em 1em;border-radius:.25em;background:rgba(0,0,0,.75)}\
+        a:hover span,a:focus span{background:#c00}&lt;/style&gt;\
+        &lt;a href=&quot;http://maps.example/embed?q=a&amp;amp;b&quot;&gt;&lt;span&gt;Load content&lt;/span&gt;&lt;/a&gt;\" \
+        title=\"Map\" sandbox=\"\" loading=\"lazy\" referrerpolicy=\"strict-origin-when-cross-origin\"></iframe>"
+    );
+
+    let blocked = context::provide(UrlRules::new().http(HttpUrls::Block), || html.to_html());
+
+    assert!(!blocked.contains("maps.example"));
+    assert!(blocked.starts_with("<iframe srcdoc="));
+}
+
+#[test]
+fn test_lazy_iframe_requires_http_src() {
+    for src in [
+        "javascript:alert(1)",
+        " JavaScript:alert(1)",
+        "data:text/html,x",
+        "/embed",
+    ] {
+        let html = html! {
+            @LazyIframe(src: src, title: "Embed") {}
+        }
+        .to_html();
+
+        assert!(html.starts_with("<iframe srcdoc="), "{src}");
+        assert!(html.contains("&lt;a&gt;&lt;span&gt;Load content"), "{src}");
+        assert!(
+            !html.contains("alert") && !html.contains("data:") && !html.contains("/embed"),
+            "{src}"
+        );
+    }
+
+    let html = html! {
+        @LazyIframe(src: "HTTPS://maps.example/embed", title: "Map") {}
+    }
+    .to_html();
+
+    assert!(html.starts_with("<iframe src=\"HTTPS://maps.example/embed\" srcdoc="));
+    assert!(html.contains("sandbox=\"allow-scripts allow-popups allow-popups-to-escape-sandbox\""));
+}
+
+#[test]
+fn test_youtube() {
+    let html = html! {
+        @YouTube(id: "a b", title: "Music video"; width: 560) {}
+    }
+    .to_html();
+
+    assert!(html.starts_with(
+        "<iframe src=\"https://www.youtube-nocookie.com/embed/a%20b?autoplay=1\" srcdoc="
+    ));
+    assert!(html.contains(
+        "&lt;a href=&quot;https://www.youtube-nocookie.com/embed/a%20b?autoplay=1&quot;&gt;\
+        &lt;img src=&quot;https://i.ytimg.com/vi/a%20b/hqdefault.jpg&quot; alt=&quot;&quot;&gt;\
+        &lt;span&gt;Play video&lt;/span&gt;&lt;/a&gt;"
+    ));
+    assert!(html.ends_with(
+        "title=\"Music video\" \
+        allow=\"accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture; web-share\" \
+        sandbox=\"allow-scripts allow-popups allow-popups-to-escape-sandbox\" loading=\"lazy\" \
+        referrerpolicy=\"strict-origin-when-cross-origin\" allowfullscreen width=\"560\"></iframe>"
+    ));
+}
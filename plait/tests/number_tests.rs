@@ -0,0 +1,91 @@
+use plait::{
+    ToHtml, context, html,
+    locale::Locale,
+    number::{Money, NumberFormat, Percent},
+};
+
+#[test]
+fn test_money() {
+    let html = html! {
+        p { (Money(123450, "USD")) }
+        p { (Money(-5, "usd")) }
+        p { (Money(500, "JPY")) }
+        p { (Money(1234567, "KWD")) }
+        p { (Money(99, "XYZ")) }
+        p { (Money(i64::MAX, "GBP")) }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<p>$1,234.50</p><p>-$0.05</p><p>¥500</p><p>KWD\u{a0}1,234.567</p><p>XYZ\u{a0}0.99</p>\
+         <p>£92,233,720,368,547,758.07</p>"
+    );
+}
+
+#[test]
+fn test_percent() {
+    let html = html! {
+        span { (Percent(0.123, 1)) }
+        span { (Percent(0.5, 0)) }
+        span { (Percent(-0.0125, 2)) }
+        span { (Percent(0.00125, 1)) }
+        span { (Percent(1.0, 3)) }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<span>12.3%</span><span>50%</span><span>-1.25%</span><span>0.1%</span><span>100.000%</span>"
+    );
+}
+
+#[test]
+fn test_number_format_from_locale() {
+    let page = html! {
+        p { (Money(123450, "EUR")) " " (Percent(0.125, 1)) }
+    };
+
+    assert_eq!(
+        context::provide(Locale::new("de-AT"), || page.to_html()),
+        "<p>1.234,50\u{a0}€ 12,5%</p>"
+    );
+    assert_eq!(
+        context::provide(Locale::new("fr"), || page.to_html()),
+        "<p>1\u{202f}234,50\u{a0}€ 12,5%</p>"
+    );
+    assert_eq!(
+        context::provide(Locale::new("pt-BR"), || {
+            html! { p { (Money(-123450, "BRL")) } }.to_html()
+        }),
+        "<p>-R$1.234,50</p>"
+    );
+    assert_eq!(
+        context::provide(Locale::new("ja"), || page.to_html()),
+        "<p>€1,234.50 12.5%</p>"
+    );
+}
+
+#[test]
+fn test_number_format_in_context() {
+    let format = NumberFormat::new()
+        .with_decimal_separator(',')
+        .with_group_separator(None)
+        .with_currency_after(true);
+
+    let page = html! {
+        p(data_price: (Money(1234500, "USD"))) { (Money(1234500, "USD")) }
+    };
+
+    // A provided format takes precedence over the locale
+    assert_eq!(
+        context::provide(Locale::new("en"), || context::provide(format, || page
+            .to_html())),
+        "<p data-price=\"12345,00\u{a0}$\">12345,00\u{a0}$</p>"
+    );
+}
+
+#[test]
+fn test_number_display() {
+    assert_eq!(Money(1999, "USD").to_string(), "$19.99");
+    assert_eq!(Percent(0.25, 0).to_string(), "25%");
+    assert_eq!(Percent(f64::NAN, 1).to_string(), "NaN%");
+}
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use plait::{
+    ToHtml, context, html,
+    permission::{self, Permissions},
+};
+
+#[test]
+fn test_can_without_permissions() {
+    let html = html! {
+        @can("admin") { "admin" } @else { "guest" }
+    };
+
+    assert_eq!(html.to_html(), "guest");
+    assert!(!permission::can("admin"));
+}
+
+#[test]
+fn test_can_with_permissions() {
+    let granted: HashSet<&'static str> = ["posts.edit"].into_iter().collect();
+    let edit = String::from("posts.edit");
+
+    let html = html! {
+        ul {
+            @can(edit) { li { "Edit" } }
+            @can("posts.delete") { li { "Delete" } }
+        }
+    };
+
+    let html = context::provide(Permissions::new(granted), || {
+        assert!(permission::can("posts.edit"));
+        html.to_html()
+    });
+
+    assert_eq!(html, "<ul><li>Edit</li></ul>");
+}
+
+#[test]
+fn test_can_else_chain() {
+    let html = html! {
+        @can("a") { "a" } @else @can("b") { "b" } @else { "none" }
+    };
+
+    let render = |granted: &'static str| {
+        context::provide(
+            Permissions::new(move |permission: &str| permission == granted),
+            || html.to_html(),
+        )
+    };
+
+    assert_eq!(render("a"), "a");
+    assert_eq!(render("b"), "b");
+    assert_eq!(render("c"), "none");
+}
+
+#[test]
+fn test_can_nested_provide() {
+    let html = html! {
+        @can("x") { "yes" } @else { "no" }
+    };
+
+    let allow = Permissions::new(|_: &str| true);
+    let deny = Permissions::new(|_: &str| false);
+
+    context::provide(allow, || {
+        assert_eq!(html.to_html(), "yes");
+        assert_eq!(context::provide(deny, || html.to_html()), "no");
+    });
+}
@@ -0,0 +1,226 @@
+use plait::{
+    ToHtml, html,
+    pipeline::{
+        AttributeQuotes, Minify, PostProcessor, QuoteStyle, RenderPipeline, Token, Tokens,
+        VoidElementSlash,
+    },
+};
+
+struct Uppercase;
+
+impl PostProcessor for Uppercase {
+    fn process(&self, tokens: Tokens<'_>, out: &mut String) {
+        for token in tokens {
+            match token {
+                Token::Text(text) => out.push_str(&text.to_uppercase()),
+                token => out.push_str(token.source()),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_empty_pipeline() {
+    let page = html! { p(class: "a") { "Fish & chips" } };
+
+    assert_eq!(RenderPipeline::new().render(&page), page.to_html());
+}
+
+#[test]
+fn test_pipeline_runs_processors_in_order() {
+    let pipeline =
+        RenderPipeline::new()
+            .then(Uppercase)
+            .then(|tokens: Tokens<'_>, out: &mut String| {
+                for token in tokens {
+                    match token {
+                        Token::StartTag { name: "b", .. } | Token::EndTag { name: "b", .. } => {}
+                        token => out.push_str(token.source()),
+                    }
+                }
+            });
+
+    assert_eq!(
+        pipeline.process("<p>hello <b>world</b></p><script>let x = 'a';</script>"),
+        "<p>HELLO WORLD</p><script>let x = 'a';</script>"
+    );
+}
+
+#[test]
+fn test_tokens() {
+    let tokens: Vec<Token<'_>> =
+        Tokens::new("<!-- c --><a href=\"/x\">&lt;<br/></a><style>p{}</style>").collect();
+
+    assert_eq!(
+        tokens,
+        [
+            Token::Other("<!-- c -->"),
+            Token::StartTag {
+                name: "a",
+                source: "<a href=\"/x\">",
+                self_closing: false,
+            },
+            Token::Text("&lt;"),
+            Token::StartTag {
+                name: "br",
+                source: "<br/>",
+                self_closing: true,
+            },
+            Token::EndTag {
+                name: "a",
+                source: "</a>",
+            },
+            Token::StartTag {
+                name: "style",
+                source: "<style>",
+                self_closing: false,
+            },
+            Token::RawText("p{}"),
+            Token::EndTag {
+                name: "style",
+                source: "</style>",
+            },
+        ]
+    );
+
+    assert_eq!(tokens[1].attribute("HREF"), Some("/x"));
+    assert_eq!(tokens[2].attribute("href"), None);
+}
+
+#[test]
+fn test_pipeline_is_shareable() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<RenderPipeline>();
+}
+
+#[test]
+fn test_minify_whitespace() {
+    let pipeline = RenderPipeline::new().then(Minify);
+
+    assert_eq!(
+        pipeline.process(
+            "<!DOCTYPE html>\n<html>\n  <head>\n    <title> Home </title>\n  </head>\n  <body>\n    \
+            <p>\n      Hello,\n      <a href=\"/\">world</a> !\n    </p>\n  </body>\n</html>\n"
+        ),
+        "<!DOCTYPE html><html><head><title>Home</title></head><body><p>Hello, <a href=\"/\">world</a> !</p></body></html>"
+    );
+}
+
+#[test]
+fn test_minify_keeps_preformatted_content() {
+    let pipeline = RenderPipeline::new().then(Minify);
+
+    assert_eq!(
+        pipeline.process(
+            "<div>\n  <pre>\n  a  <b> b </b>\n</pre>\n  <textarea>  x\n  y</textarea>\n  \
+            <script>\n  let  a = 1;\n</script>\n</div>"
+        ),
+        "<div><pre>\n  a  <b> b </b>\n</pre><textarea>  x\n  y</textarea><script>\n  let  a = 1;\n</script></div>"
+    );
+}
+
+#[test]
+fn test_minify_comments() {
+    let pipeline = RenderPipeline::new().then(Minify);
+
+    assert_eq!(
+        pipeline.process(
+            "<p>a <!-- note --> b</p><!--googleoff: index--><nav>x</nav><!--googleon: index-->\
+            <!--[if lt IE 9]><script src=\"shim.js\"></script><![endif]-->\
+            <!--[if !IE]><!--><p>modern</p><!--<![endif]--><!--! MIT license -->"
+        ),
+        "<p>a b</p><!--googleoff: index--><nav>x</nav><!--googleon: index-->\
+        <!--[if lt IE 9]><script src=\"shim.js\"></script><![endif]-->\
+        <!--[if !IE]><!--><p>modern</p><!--<![endif]--><!--! MIT license -->"
+    );
+}
+
+#[test]
+fn test_minify_rendered_fragment() {
+    let name = "  Ada   Lovelace ";
+    let page = html! {
+        ul {
+            li { (name) }
+            li { span { "x" } " " span { "y" } }
+        }
+    };
+
+    assert_eq!(
+        RenderPipeline::new().then(Minify).render(&page),
+        "<ul><li>Ada Lovelace</li><li><span>x</span> <span>y</span></li></ul>"
+    );
+}
+
+#[test]
+fn test_attribute_quotes() {
+    let html = r#"<input type="text" value='say "hi"' disabled data-x="" title="it&#39;s"><br/><a href="/a/">x</a><img src="a.png" /><script>let a = "<b>";</script>"#;
+
+    let process = |style| {
+        RenderPipeline::new()
+            .then(AttributeQuotes(style))
+            .process(html)
+    };
+
+    assert_eq!(
+        process(QuoteStyle::Double),
+        r#"<input type="text" value="say &quot;hi&quot;" disabled data-x="" title="it&#39;s"><br/><a href="/a/">x</a><img src="a.png"/><script>let a = "<b>";</script>"#
+    );
+    assert_eq!(
+        process(QuoteStyle::Single),
+        r#"<input type='text' value='say "hi"' disabled data-x='' title='it&#39;s'><br/><a href='/a/'>x</a><img src='a.png'/><script>let a = "<b>";</script>"#
+    );
+    assert_eq!(
+        process(QuoteStyle::Minimal),
+        r#"<input type=text value='say "hi"' disabled data-x title=it&#39;s><br/><a href="/a/">x</a><img src=a.png /><script>let a = "<b>";</script>"#
+    );
+}
+
+#[test]
+fn test_attribute_quotes_rendered_fragment() {
+    let label = "Tom's \"page\"";
+    let page = html! {
+        a(href: "/users?id=1&sort=name", title: (label), class: #("it's")) { (label) }
+    };
+
+    assert_eq!(
+        RenderPipeline::new()
+            .then(AttributeQuotes(QuoteStyle::Single))
+            .render(&page),
+        "<a href='/users?id=1&amp;sort=name' title='Tom&#39;s &quot;page&quot;' class='it&#39;s'>Tom&#39;s &quot;page&quot;</a>"
+    );
+}
+
+#[test]
+fn test_void_element_slash() {
+    let value = "hello";
+    let disabled = true;
+
+    let page = html! {
+        br;
+        img(src: "/logo.png", alt: "Logo");
+        input(value: (value), disabled?: disabled);
+        p { "text" }
+        svg { circle(r: 1); }
+    };
+
+    assert_eq!(
+        page.to_html(),
+        "<br><img src=\"/logo.png\" alt=\"Logo\"><input value=\"hello\" disabled><p>text</p>\
+        <svg xmlns=\"http://www.w3.org/2000/svg\"><circle r=\"1\"/></svg>"
+    );
+    assert_eq!(
+        RenderPipeline::new().then(VoidElementSlash).render(&page),
+        "<br /><img src=\"/logo.png\" alt=\"Logo\" /><input value=\"hello\" disabled /><p>text</p>\
+        <svg xmlns=\"http://www.w3.org/2000/svg\"><circle r=\"1\"/></svg>"
+    );
+}
+
+#[test]
+fn test_void_element_slash_keeps_other_tags() {
+    assert_eq!(
+        RenderPipeline::new().then(VoidElementSlash).process(
+            "<BR><hr/><img src=a.png ><br class=x/><script>let t = \"<br>\";</script><div></div>"
+        ),
+        "<BR /><hr/><img src=a.png /><br class=x/><script>let t = \"<br>\";</script><div></div>"
+    );
+}
@@ -0,0 +1,99 @@
+use plait::{
+    ToHtml, html,
+    pool::{self, BufferPool, PoolStats},
+};
+
+#[test]
+fn test_pool_reuses_buffers() {
+    let pool = BufferPool::new();
+
+    let first = pool.render(&html! { p { "Hello, " ("Alice") } });
+    let second = pool.render(&html! { p { "Hello, " ("Bob") } });
+
+    assert_eq!(first, "<p>Hello, Alice</p>");
+    assert_eq!(second, "<p>Hello, Bob</p>");
+    assert_eq!(
+        pool.stats(),
+        PoolStats {
+            hits: 1,
+            misses: 1,
+            discarded: 0,
+            idle: 1,
+        }
+    );
+    assert_eq!(pool.stats().hit_rate(), 0.5);
+}
+
+#[test]
+fn test_pool_render_with() {
+    let pool = BufferPool::new();
+    let page = html! { div { "content" } };
+
+    let length = pool.render_with(&page, |html| {
+        assert_eq!(html, &*page.to_html());
+        html.len()
+    });
+
+    assert_eq!(length, 18);
+    assert_eq!(pool.stats().idle, 1);
+}
+
+#[test]
+fn test_pool_take_returns_empty_buffer() {
+    let pool = BufferPool::new();
+
+    {
+        let mut buffer = pool.take();
+        buffer.push_str("leftover");
+    }
+
+    let buffer = pool.take();
+    assert!(buffer.is_empty());
+    assert!(buffer.capacity() >= 8);
+
+    assert_eq!(buffer.into_string(), "");
+    assert_eq!(pool.stats().idle, 0);
+    assert_eq!(pool.stats().discarded, 0);
+}
+
+#[test]
+fn test_pool_limits() {
+    let pool = BufferPool::with_limits(1, 16);
+
+    let small = pool.take();
+    let mut large = pool.take();
+    let mut extra = pool.take();
+
+    large.push_str(&"x".repeat(32));
+    extra.push('x');
+
+    drop(large);
+    drop(extra);
+    drop(small);
+
+    let stats = pool.stats();
+    assert_eq!(stats.misses, 3);
+    assert_eq!(stats.discarded, 1);
+    assert_eq!(stats.idle, 1);
+
+    pool.clear();
+    assert_eq!(pool.stats(), PoolStats::default());
+}
+
+#[test]
+fn test_pool_render_is_separate_render() {
+    let pool = BufferPool::new();
+    let page = html! { p(id: (plait::ids::next("item"))) {} };
+
+    assert_eq!(pool.render(&page), &*page.to_html());
+    assert_eq!(pool.render(&page), &*page.to_html());
+}
+
+#[test]
+fn test_pool_local() {
+    let html = pool::with_local(|pool| pool.render(&html! { br; }));
+    assert_eq!(html, "<br>");
+
+    let stats = pool::with_local(|pool| pool.stats());
+    assert_eq!(stats.idle, 1);
+}
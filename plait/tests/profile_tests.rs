@@ -0,0 +1,65 @@
+#![cfg(feature = "profile")]
+
+use plait::{ToHtml, component, html, profile};
+
+component! {
+    pub fn Inner(label: &str) {
+        span { (label) }
+    }
+}
+
+component! {
+    pub fn Outer() {
+        div {
+            @Inner(label: "a") {}
+            @Inner(label: "b") {}
+        }
+    }
+}
+
+#[test]
+fn test_profile_records_nested_components() {
+    profile::take_records();
+
+    let html = html! {
+        @Outer() {}
+    };
+
+    assert_eq!(html.to_html(), "<div><span>a</span><span>b</span></div>");
+
+    let records = profile::take_records();
+
+    assert_eq!(records.len(), 3);
+
+    assert_eq!(records[0].component, "Outer");
+    assert_eq!(records[0].depth, 0);
+    assert_eq!(records[0].bytes, 39);
+
+    assert_eq!(records[1].component, "Inner");
+    assert_eq!(records[1].depth, 1);
+    assert_eq!(records[1].bytes, 14);
+
+    assert_eq!(records[2].component, "Inner");
+    assert_eq!(records[2].depth, 1);
+
+    assert!(profile::take_records().is_empty());
+}
+
+#[test]
+fn test_profile_overlay() {
+    profile::take_records();
+
+    let html = html! {
+        body {
+            @Inner(label: "a") {}
+            (profile::overlay())
+        }
+    };
+
+    let html = html.to_html();
+
+    assert!(html.starts_with("<body><span>a</span><details id=\"plait-profile\""));
+    assert!(html.contains("<summary>plait: 1 component renders in "));
+    assert!(html.contains("<td style=\"padding-left:0em\">Inner</td>"));
+    assert!(profile::take_records().is_empty());
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use plait::{Href, RouteHref, ToHtml, component, html, url};
+
+enum Route {
+    Home,
+    Post { slug: &'static str },
+    Search { query: &'static str },
+}
+
+impl RouteHref for Route {
+    fn render_href(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        match self {
+            Route::Home => f.write_str("/"),
+            Route::Post { slug } => f.write_str(&url!("/posts/{slug}")),
+            Route::Search { query } => f.write_str(&url!("/search?q={query}&page=1")),
+        }
+    }
+}
+
+#[test]
+fn test_route_href() {
+    let html = html! {
+        a(href: (Href(Route::Home))) { "Home" }
+        a(href: (Href(Route::Post { slug: "hello world" }))) { "Post" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<a href=\"/\">Home</a><a href=\"/posts/hello%20world\">Post</a>"
+    );
+}
+
+#[test]
+fn test_route_href_is_escaped() {
+    let html = html! {
+        form(action: (Href(Route::Search { query: "a b" }))) {}
+        a(href: (Href("/?a=1&b=\"2\""))) {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<form action=\"/search?q=a%20b&amp;page=1\"></form><a href=\"/?a=1&amp;b=&quot;2&quot;\"></a>"
+    );
+}
+
+#[test]
+fn test_route_href_display() {
+    assert_eq!(Href(Route::Post { slug: "x" }).to_string(), "/posts/x");
+}
+
+component! {
+    pub fn NavLink<R>(route: R) where R: RouteHref {
+        a(href: (Href(route)), #attrs) { #children }
+    }
+}
+
+#[test]
+fn test_route_href_component_prop() {
+    let html = html! {
+        @NavLink(route: Route::Home; class: "active") { "Home" }
+    };
+
+    assert_eq!(html.to_html(), "<a href=\"/\" class=\"active\">Home</a>");
+}
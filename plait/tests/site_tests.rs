@@ -0,0 +1,262 @@
+use std::{fs, path::PathBuf};
+
+use plait::{
+    Asset, asset, html,
+    site::{BrokenLink, MANIFEST_FILE, SITEMAP_FILE, StaticSite},
+};
+
+const SMALL_CSS: Asset = asset!("tests/assets/small.css");
+
+fn out_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("plait-site-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_site_writes_pages_and_manifest() {
+    let dir = out_dir("pages");
+    let title = "About";
+
+    let manifest = StaticSite::new(&dir)
+        .page("/", html! { h1 { "Home" } })
+        .page("/about", html! { h1 { (title) } })
+        .page("/blog/hello/", html! { p { "Hello" } })
+        .page("/404.html", html! { p { "Not found" } })
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dir.join("index.html")).unwrap(),
+        "<h1>Home</h1>"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("about/index.html")).unwrap(),
+        "<h1>About</h1>"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("blog/hello/index.html")).unwrap(),
+        "<p>Hello</p>"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("404.html")).unwrap(),
+        "<p>Not found</p>"
+    );
+
+    let paths: Vec<&str> = manifest
+        .pages()
+        .iter()
+        .map(|page| page.path.as_str())
+        .collect();
+    assert_eq!(paths, ["/", "/404.html", "/about", "/blog/hello/"]);
+
+    let about = manifest.page("/about").unwrap();
+    assert_eq!(about.file, PathBuf::from("about/index.html"));
+    assert_eq!(about.hash.len(), 16);
+    assert!(about.changed);
+
+    let json = fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap();
+    assert_eq!(json, manifest.to_json());
+    assert!(json.contains(&format!(
+        "  \"/about\": {{ \"file\": \"about/index.html\", \"hash\": \"{}\" }}",
+        about.hash
+    )));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_site_only_writes_changed_pages() {
+    let dir = out_dir("incremental");
+
+    let build = |about: &str| {
+        StaticSite::new(&dir)
+            .threads(1)
+            .page("/", html! { h1 { "Home" } })
+            .page("/about", html! { h1 { (about) } })
+            .build()
+            .unwrap()
+    };
+
+    let first = build("About");
+    assert_eq!(first.changed().count(), 2);
+
+    let second = build("About us");
+    let changed: Vec<&str> = second.changed().map(|page| page.path.as_str()).collect();
+    assert_eq!(changed, ["/about"]);
+    assert_eq!(
+        first.page("/").unwrap().hash,
+        second.page("/").unwrap().hash
+    );
+    assert_ne!(
+        first.page("/about").unwrap().hash,
+        second.page("/about").unwrap().hash
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_site_renders_in_parallel() {
+    let dir = out_dir("parallel");
+
+    let mut site = StaticSite::new(&dir).threads(4);
+    for index in 0..20 {
+        site = site.page(&format!("/pages/{index}"), html! { p { (index) } });
+    }
+
+    let manifest = site.build().unwrap();
+    assert_eq!(manifest.pages().len(), 20);
+
+    for index in 0..20 {
+        assert_eq!(
+            fs::read_to_string(dir.join(format!("pages/{index}/index.html"))).unwrap(),
+            format!("<p>{index}</p>")
+        );
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "invalid page path")]
+fn test_site_rejects_parent_segments() {
+    let _ = StaticSite::new("dist").page("/../secret", html! {});
+}
+
+#[test]
+#[should_panic(expected = "is added more than once")]
+fn test_site_rejects_duplicate_pages() {
+    let _ = StaticSite::new("dist")
+        .page("/about", html! {})
+        .page("/about", html! {});
+}
+
+#[test]
+fn test_site_copies_static_files() {
+    let dir = out_dir("copy");
+
+    let manifest = StaticSite::new(&dir)
+        .page(
+            "/",
+            html! { link(rel: "stylesheet", href: "/tests/assets/small.css"); },
+        )
+        .copy("/static", "tests/assets")
+        .copy("/app.js", "tests/assets/app.js")
+        .asset(SMALL_CSS)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        fs::read(dir.join("static/app.js")).unwrap(),
+        fs::read("tests/assets/app.js").unwrap()
+    );
+    assert_eq!(
+        fs::read(dir.join("static/small.css")).unwrap(),
+        fs::read("tests/assets/small.css").unwrap()
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("tests/assets/small.css")).unwrap(),
+        SMALL_CSS.content()
+    );
+
+    let assets: Vec<&str> = manifest
+        .assets()
+        .iter()
+        .map(|asset| asset.path.as_str())
+        .collect();
+    assert_eq!(
+        assets,
+        [
+            "/app.js",
+            "/static/app.js",
+            "/static/small.css",
+            "/tests/assets/small.css"
+        ]
+    );
+    assert!(manifest.broken_links().is_empty());
+
+    let json = fs::read_to_string(dir.join(MANIFEST_FILE)).unwrap();
+    assert!(json.contains("\"/static/small.css\": { \"file\": \"static/small.css\""));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_site_reports_broken_links() {
+    let dir = out_dir("links");
+
+    let site = || {
+        StaticSite::new(&dir)
+            .page(
+                "/",
+                html! {
+                    a(href: "/docs/intro?x=1#top") { "Intro" }
+                    a(href: "/missing") { "Missing" }
+                    a(href: "https://example.com/missing") { "External" }
+                    a(href: "mailto:ada@example.com") { "Mail" }
+                    a(href: "#top") { "Top" }
+                },
+            )
+            .page(
+                "/docs/intro",
+                html! {
+                    a(href: "setup") { "Setup" }
+                    a(href: "../caf%C3%A9/") { "Café" }
+                    img(src: "./logo.png");
+                },
+            )
+            .page("/docs/setup", html! {})
+            .page("/café", html! {})
+    };
+
+    let manifest = site().build().unwrap();
+
+    assert_eq!(
+        manifest.broken_links(),
+        [
+            BrokenLink {
+                page: "/".to_owned(),
+                href: "/missing".to_owned(),
+            },
+            BrokenLink {
+                page: "/docs/intro".to_owned(),
+                href: "./logo.png".to_owned(),
+            },
+        ]
+    );
+
+    let error = site().check_links().build().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(
+        error.to_string(),
+        "broken links: / links to /missing, /docs/intro links to ./logo.png"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_site_writes_sitemap() {
+    let dir = out_dir("sitemap");
+
+    StaticSite::new(&dir)
+        .page("/", html! {})
+        .page("/blog/hello world", html! {})
+        .page("/feed.xml", html! {})
+        .page("/404.html", html! {})
+        .sitemap("https://example.com/")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dir.join(SITEMAP_FILE)).unwrap(),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  \
+        <url><loc>https://example.com/</loc></url>\n  \
+        <url><loc>https://example.com/blog/hello%20world</loc></url>\n\
+        </urlset>\n"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,95 @@
+use plait::{RenderEscaped, ToHtml, component, html, slugify};
+
+#[test]
+fn test_slugify() {
+    assert_eq!(slugify("Hello World"), "hello-world");
+    assert_eq!(slugify("--Already-slugged--"), "already-slugged");
+    assert_eq!(slugify("C++ & Rust: a comparison"), "c-rust-a-comparison");
+    assert_eq!(slugify("snake_case_name"), "snake-case-name");
+    assert_eq!(slugify("Version 2.0"), "version-20");
+    assert_eq!(slugify("Grüße"), "grüße");
+    assert_eq!(slugify(""), "section");
+}
+
+#[test]
+fn test_auto_id() {
+    let title = "Install <now>";
+
+    let html = html! {
+        h2(auto_id) { (title) }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<h2 id=\"install-now\">Install &lt;now&gt;</h2>"
+    );
+}
+
+#[test]
+fn test_auto_id_collisions_are_per_render() {
+    let html = html! {
+        for _ in 0..3 {
+            h2(auto_id, class: "title") { "FAQ" }
+        }
+    };
+
+    let expected = "<h2 id=\"faq\" class=\"title\">FAQ</h2>\
+        <h2 id=\"faq-1\" class=\"title\">FAQ</h2>\
+        <h2 id=\"faq-2\" class=\"title\">FAQ</h2>";
+
+    assert_eq!(html.to_html(), expected);
+    assert_eq!(html.to_html(), expected);
+}
+
+#[test]
+fn test_auto_id_nested() {
+    let html = html! {
+        section(auto_id) {
+            h2(auto_id) { "Inner" }
+            p { "body" }
+        }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<section id=\"inner-body\"><h2 id=\"inner\">Inner</h2><p>body</p></section>"
+    );
+}
+
+component! {
+    pub fn Section(title: &str) {
+        section {
+            h2(auto_id) { (title) }
+            #children
+        }
+    }
+}
+
+#[test]
+fn test_auto_id_across_components() {
+    let html = html! {
+        @Section(title: "Usage") {
+            h3(auto_id) { "Usage" }
+        }
+        @Section(title: "Usage") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<section><h2 id=\"usage\">Usage</h2><h3 id=\"usage-1\">Usage</h3></section>\
+        <section><h2 id=\"usage-2\">Usage</h2></section>"
+    );
+}
+
+#[test]
+fn test_auto_id_outside_to_html() {
+    let html = html! {
+        h2(auto_id) { "Same" }
+        h2(auto_id) { "Same" }
+    };
+
+    let mut output = String::new();
+    html.render_escaped(&mut output).unwrap();
+
+    assert_eq!(output, "<h2 id=\"same\">Same</h2><h2 id=\"same\">Same</h2>");
+}
@@ -0,0 +1,106 @@
+use plait::{ToHtml, html, spec};
+
+#[test]
+fn test_lookups_are_case_insensitive() {
+    assert!(spec::is_void_element("IMG"));
+    assert!(spec::is_raw_text_element("Script"));
+    assert!(spec::is_rcdata_element("title"));
+    assert!(spec::is_url_attribute("SRC"));
+    assert!(spec::is_boolean_attribute("ReadOnly"));
+}
+
+#[test]
+fn test_lookups_reject_other_names() {
+    assert!(!spec::is_void_element("div"));
+    assert!(!spec::is_raw_text_element("textarea"));
+    assert!(!spec::is_rcdata_element("script"));
+    assert!(!spec::is_url_attribute("class"));
+    assert!(!spec::is_boolean_attribute("value"));
+}
+
+#[test]
+fn test_void_elements_match_html_macro() {
+    let html = html! {
+        area; base; br; col; embed; hr; img; input; link; meta; param; source; track; wbr;
+    };
+
+    let expected: String = spec::VOID_ELEMENTS
+        .iter()
+        .map(|tag| format!("<{tag}>"))
+        .collect();
+
+    assert_eq!(html.to_html(), expected.as_str());
+}
+
+#[test]
+fn test_tables_are_sorted_and_lowercase() {
+    for table in [
+        spec::VOID_ELEMENTS,
+        spec::RAW_TEXT_ELEMENTS,
+        spec::RCDATA_ELEMENTS,
+        spec::URL_ATTRIBUTES,
+        spec::WHITESPACE_SENSITIVE_ATTRIBUTES,
+        spec::BOOLEAN_ATTRIBUTES,
+    ] {
+        assert!(table.is_sorted(), "{table:?} is not sorted");
+        assert!(
+            table.iter().all(|name| *name == name.to_ascii_lowercase()),
+            "{table:?} is not lower-case"
+        );
+    }
+}
+
+#[test]
+fn test_every_table_entry_is_found() {
+    assert!(
+        spec::VOID_ELEMENTS
+            .iter()
+            .all(|tag| spec::is_void_element(tag))
+    );
+    assert!(
+        spec::URL_ATTRIBUTES
+            .iter()
+            .all(|name| spec::is_url_attribute(name))
+    );
+    assert!(
+        spec::WHITESPACE_SENSITIVE_ATTRIBUTES
+            .iter()
+            .all(|name| spec::is_whitespace_sensitive_attribute(name))
+    );
+    assert!(
+        spec::BOOLEAN_ATTRIBUTES
+            .iter()
+            .all(|name| spec::is_boolean_attribute(&name.to_ascii_uppercase()))
+    );
+}
+
+#[test]
+fn test_valid_element_names() {
+    for name in ["div", "H1", "my-element", "svg:rect", "x-😀"] {
+        assert!(spec::is_valid_element_name(name), "{name:?}");
+    }
+
+    for name in [
+        "", "1div", "-x", "div x", "div/", "div>", "a=b", "a\"", "a'", "a<b", "a\0",
+    ] {
+        assert!(!spec::is_valid_element_name(name), "{name:?}");
+    }
+}
+
+#[test]
+fn test_valid_attribute_names() {
+    for name in [
+        "class",
+        "data-id",
+        "@click",
+        ":value",
+        "x-on:click.prevent",
+        "_",
+    ] {
+        assert!(spec::is_valid_attribute_name(name), "{name:?}");
+    }
+
+    for name in ["", "a b", "a\nb", "/", "a>", "a=b", "\"", "'", "<a"] {
+        assert!(!spec::is_valid_attribute_name(name), "{name:?}");
+    }
+}
@@ -0,0 +1,108 @@
+use plait::{
+    ToHtml, html,
+    stream::{Multipart, SseEvent, WsEnvelope},
+};
+
+#[test]
+fn test_sse_event_data_only() {
+    let frag = html! { div(id: "clock") { "12:00" } };
+
+    assert_eq!(
+        SseEvent::new(&frag.to_html()).to_string(),
+        "data: <div id=\"clock\">12:00</div>\n\n"
+    );
+}
+
+#[test]
+fn test_sse_event_all_fields() {
+    let event = SseEvent::new("<p>hi</p>")
+        .event("greeting")
+        .id("7")
+        .retry(5000);
+
+    assert_eq!(
+        event.to_string(),
+        "event: greeting\nid: 7\nretry: 5000\ndata: <p>hi</p>\n\n"
+    );
+}
+
+#[test]
+fn test_sse_event_splits_lines() {
+    let frag = html! {
+        pre { "one\ntwo\r\nthree\rfour" }
+    };
+
+    assert_eq!(
+        SseEvent::new(&frag.to_html()).to_string(),
+        "data: <pre>one\ndata: two\ndata: three\ndata: four</pre>\n\n"
+    );
+}
+
+#[test]
+fn test_sse_event_trailing_newline() {
+    assert_eq!(SseEvent::new("a\n").to_string(), "data: a\ndata: \n\n");
+    assert_eq!(SseEvent::new("").to_string(), "data: \n\n");
+}
+
+#[test]
+fn test_sse_event_single_line_fields() {
+    let event = SseEvent::new("x").event("up\ndate").id("1\r\n2");
+
+    assert_eq!(event.to_string(), "event: update\nid: 12\ndata: x\n\n");
+}
+
+#[test]
+fn test_multipart() {
+    let multipart = Multipart::new("b0und4ry");
+
+    assert_eq!(multipart.boundary(), "b0und4ry");
+    assert_eq!(
+        multipart.content_type(),
+        "multipart/mixed; boundary=\"b0und4ry\""
+    );
+    assert_eq!(
+        multipart.part("<p>x</p>"),
+        "--b0und4ry\r\nContent-Type: text/html; charset=utf-8\r\n\r\n<p>x</p>\r\n"
+    );
+    assert_eq!(multipart.end(), "--b0und4ry--\r\n");
+}
+
+#[test]
+#[should_panic(expected = "invalid multipart boundary")]
+fn test_multipart_invalid_boundary() {
+    Multipart::new("has space");
+}
+
+#[test]
+fn test_ws_envelope_message() {
+    let frag = html! {
+        p(class: "note") { "Line one\nline \"two\" \\ \t" }
+    };
+
+    assert_eq!(
+        WsEnvelope::new().message("#notes > p", &frag.to_html()),
+        r##"{"target":"#notes > p","html":"<p class=\"note\">Line one\nline &quot;two&quot; \\ \t</p>"}"##
+    );
+}
+
+#[test]
+fn test_ws_envelope_control_characters() {
+    assert_eq!(
+        WsEnvelope::new().message("#a", "\u{1}\u{1f}\u{7f}"),
+        "{\"target\":\"#a\",\"html\":\"\\u0001\\u001f\u{7f}\"}"
+    );
+}
+
+#[test]
+fn test_ws_envelope_batch() {
+    let envelope = WsEnvelope::new()
+        .target_field("id")
+        .html_field("markup")
+        .batch_field("updates");
+
+    assert_eq!(
+        envelope.batch([("#a", "<b>1</b>"), ("#b", "<i>2</i>")]),
+        r##"{"updates":[{"id":"#a","markup":"<b>1</b>"},{"id":"#b","markup":"<i>2</i>"}]}"##
+    );
+    assert_eq!(envelope.batch([]), r#"{"updates":[]}"#);
+}
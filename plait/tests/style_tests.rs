@@ -0,0 +1,90 @@
+use std::borrow::Cow;
+
+use plait::{
+    ToHtml, html, style,
+    style::{Em, Pct, Px, Rem, Style},
+};
+
+#[test]
+fn test_style_values() {
+    let style = Style::new()
+        .prop("margin", Px(-4))
+        .prop("line-height", 1.5)
+        .prop("z-index", 10)
+        .prop("font-size", Em(1.25))
+        .prop("padding", Rem(2))
+        .prop("width", Pct(50))
+        .prop("color", String::from("red"))
+        .prop("--accent", Cow::Borrowed("#fff"));
+
+    assert_eq!(
+        style.as_str(),
+        "margin: -4px; line-height: 1.5; z-index: 10; font-size: 1.25em; padding: 2rem; width: 50%; color: red; \
+        --accent: #fff"
+    );
+}
+
+#[test]
+fn test_style_skips_missing_values() {
+    let none: Option<&str> = None;
+    let style = Style::new()
+        .prop("color", none)
+        .prop("background", "")
+        .prop("width", Some(Px(10)));
+
+    assert_eq!(style.to_string(), "width: 10px");
+    assert!(Style::new().prop("color", none).is_empty());
+}
+
+#[test]
+fn test_style_escapes_values() {
+    let color = "red; background: url(\"https://evil.example/\") } <b>";
+    let style = Style::new().prop("color", color);
+
+    assert_eq!(
+        style.as_str(),
+        r#"color: red\; background: url("https://evil.example/") \} <b>"#
+    );
+    assert_eq!(
+        html! { p(style: (style)) {} }.to_html(),
+        r#"<p style="color: red\; background: url(&quot;https://evil.example/&quot;) \} &lt;b&gt;"></p>"#
+    );
+}
+
+#[test]
+fn test_style_optional_attribute() {
+    let empty = Style::new();
+    let full = Style::new().prop("display", "none");
+
+    assert_eq!(
+        html! { div(style?: empty) {} span(style?: full) {} }.to_html(),
+        "<div></div><span style=\"display: none\"></span>"
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid CSS property name")]
+fn test_style_invalid_property_name() {
+    let _ = Style::new().prop("color: red; x", "blue");
+}
+
+#[test]
+fn test_style_macro() {
+    let color = "red; x: y";
+    let width: Option<Px<u32>> = None;
+    let hidden = true;
+
+    let frag = html! {
+        div(style: style!(
+            "color": color,
+            "width": width,
+            "display": if hidden { Some("none") } else { None },
+            "--gap": Rem(1.5),
+        )) {}
+    };
+
+    assert_eq!(
+        frag.to_html(),
+        r#"<div style="color: red\; x: y; display: none; --gap: 1.5rem"></div>"#
+    );
+}
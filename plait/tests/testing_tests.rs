@@ -0,0 +1,219 @@
+use plait::{ToHtml, component, component_test, html, testing};
+
+component! {
+    pub fn Field(id: &str, label: &str) {
+        label(for: id) { (label) }
+        input(id: id, type: "email", #attrs);
+    }
+}
+
+component! {
+    pub fn Card(title: &str) {
+        article {
+            header { h2 { (title) } }
+            #children
+            footer { a(href: "/more") { "Read " span(class: "sr-only") { (title) } } }
+        }
+    }
+}
+
+#[component_test(
+    r#"
+    textbox "Email"
+    "#
+)]
+fn field_email() -> impl ToHtml {
+    html! { @Field(id: "email", label: "Email"; required) {} }
+}
+
+#[component_test(
+    r#"
+    heading "Release notes"
+    link "Read Release notes"
+    "#
+)]
+fn card_release_notes() -> impl ToHtml {
+    html! { @Card(title: "Release notes") { p { "Version 2 is out." } } }
+}
+
+#[component_test]
+fn card_is_valid_html() -> impl ToHtml {
+    html! { @Card(title: "Empty") {} }
+}
+
+#[test]
+fn test_html_errors() {
+    assert!(
+        testing::html_errors("<div><br><img src=\"a.png\"><svg><path d=\"M0\"/></svg></div>")
+            .is_empty()
+    );
+
+    assert_eq!(
+        testing::html_errors("<div><span></div></p><br></br><div/>"),
+        [
+            "`<span>` is closed by `</div>`",
+            "`</p>` has no matching start tag",
+            "`<br>` is a void element and has no end tag",
+            "`<div/>` is not a void element and can't be self-closing",
+            "`<div>` is never closed",
+        ]
+    );
+
+    assert_eq!(
+        testing::html_errors("<a id=\"x\" id=\"y\"></a><b id=\"x\"></b>"),
+        ["`<a>` has a duplicate `id` attribute", "duplicate id `x`",]
+    );
+}
+
+#[test]
+fn test_accessible_names() {
+    let html = html! {
+        header { a(href: "/") { img(src: "/logo.png", alt: "Home"); } }
+        main {
+            h1 { "Dashboard" }
+            img(src: "/divider.png", alt: "");
+            button(type: "button", title: "Settings") { svg(aria_hidden: "true") {} }
+            button(type: "button", aria_labelledby: "close-label") { "×" }
+            span(id: "close-label", hidden) { "Close dialog" }
+            label { "Search " input(type: "search"); }
+            input(type: "submit", value: "Go");
+            input(type: "hidden", name: "token", value: "1");
+            div(role: "tab navigation") { "Overview" }
+            section(aria_label: "Stats") { footer { "Updated daily" } }
+        }
+        footer { a(href: "#top", aria_label: "Back to top") { "↑" } }
+    }
+    .to_html();
+
+    assert_eq!(
+        testing::accessible_names(&html),
+        [
+            "banner",
+            "link \"Home\"",
+            "img \"Home\"",
+            "main",
+            "heading \"Dashboard\"",
+            "button \"Settings\"",
+            "button \"Close dialog\"",
+            "searchbox \"Search\"",
+            "button \"Go\"",
+            "tab \"Overview\"",
+            "region \"Stats\"",
+            "contentinfo",
+            "link \"Back to top\"",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+#[should_panic(expected = "accessible names don't match the snapshot")]
+fn test_assert_accessible_names_mismatch() {
+    testing::assert_accessible_names("<button></button>", "button \"Save\"");
+}
+
+#[test]
+#[should_panic(expected = "`<p>` is never closed")]
+fn test_assert_valid_html_panics() {
+    testing::assert_valid_html("<p>Unclosed");
+}
+
+#[test]
+fn test_inline_scripts() {
+    let html = html! {
+        body(onload: "init()") {
+            a(href: "/safe") { "Safe" }
+            a(href: "\tjava\nscript:alert(1)") { "Unsafe" }
+            form(action: "javascript:submit()") {
+                button(ONCLICK: "save()") { "Save" }
+            }
+            script(type: "application/ld+json") { #("{}") }
+            script(type: "module") { #("import './app.js';") }
+            script(src: "/app.js") {}
+            script(nonce: "abc") { #("init();") }
+        }
+    };
+
+    assert_eq!(
+        testing::inline_scripts(&html.to_html()),
+        [
+            "`onload` attribute on `<body>`",
+            "`javascript:` URL in the `href` attribute of `<a>`",
+            "`javascript:` URL in the `action` attribute of `<form>`",
+            "`onclick` attribute on `<button>`",
+            "inline `<script>`",
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "`onclick` attribute on `<button>`")]
+fn test_assert_no_inline_scripts_panics() {
+    testing::assert_no_inline_scripts(r#"<button onclick="save()">Save</button>"#);
+}
+
+#[test]
+fn test_outline_issues() {
+    use plait::testing::OutlineIssue;
+
+    let valid = html! {
+        header { nav(aria_label: "Primary") {} }
+        main {
+            h1 { "Title" }
+            h2 { "Section" }
+            h3 { "Subsection" }
+            h2 { "Another section" }
+            div(role: "heading", aria_level: "3") { "Styled heading" }
+        }
+        nav(aria_label: "Footer") {}
+        footer {}
+    };
+
+    assert!(testing::outline_issues(&valid.to_html()).is_empty());
+    testing::assert_valid_outline(&valid.to_html());
+
+    let invalid = html! {
+        aside(aria_label: "Related") {}
+        main { h2 { "No title" } h4 { "Deep" } }
+        main(hidden) { h1 { "Hidden" } }
+        main {}
+        aside(aria_label: "Related") {}
+        section(aria_label: "Stats") {}
+        section(aria_label: "Stats") {}
+        nav {}
+        nav {}
+    };
+
+    assert_eq!(
+        testing::outline_issues(&invalid.to_html()),
+        [
+            OutlineIssue::MultipleMain { count: 2 },
+            OutlineIssue::MissingH1,
+            OutlineIssue::SkippedHeadingLevel {
+                previous: 2,
+                level: 4,
+                text: "Deep".to_owned()
+            },
+            OutlineIssue::DuplicateLandmarkLabel {
+                role: "complementary".to_owned(),
+                label: "Related".to_owned()
+            },
+            OutlineIssue::DuplicateLandmarkLabel {
+                role: "region".to_owned(),
+                label: "Stats".to_owned()
+            },
+            OutlineIssue::UnlabeledLandmark {
+                role: "navigation".to_owned()
+            },
+            OutlineIssue::UnlabeledLandmark {
+                role: "navigation".to_owned()
+            },
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "the page has no `main` landmark")]
+fn test_assert_valid_outline_panics() {
+    testing::assert_valid_outline("<h1>Title</h1>");
+}
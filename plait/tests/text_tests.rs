@@ -0,0 +1,128 @@
+use plait::{Html, ToHtml, html, html_to_text, truncate_html};
+
+fn article() -> Html {
+    html! {
+        article {
+            h2 { "Tom & Jerry" }
+            p { "A cat " em { "chases" } " a mouse." br; "Again." }
+        }
+    }
+    .to_html()
+}
+
+#[test]
+fn test_truncate_html_fits() {
+    let article = article();
+
+    assert_eq!(truncate_html(&article, 100), article.to_string().as_str());
+    assert_eq!(truncate_html(&article, 38), article.to_string().as_str());
+}
+
+#[test]
+fn test_truncate_html_closes_open_elements() {
+    assert_eq!(
+        truncate_html(&article(), 8),
+        "<article><h2>Tom &amp;…</h2></article>"
+    );
+    assert_eq!(
+        truncate_html(&article(), 22),
+        "<article><h2>Tom &amp; Jerry</h2><p>A cat <em>…</em></p></article>"
+    );
+    assert_eq!(
+        truncate_html(&article(), 23),
+        "<article><h2>Tom &amp; Jerry</h2><p>A cat <em>chases</em>…</p></article>"
+    );
+}
+
+#[test]
+fn test_truncate_html_word_boundary() {
+    let html = html! { p { "Hello wonderful world" } }.to_html();
+
+    assert_eq!(truncate_html(&html, 12), "<p>Hello…</p>");
+    assert_eq!(truncate_html(&html, 15), "<p>Hello wonderful…</p>");
+    assert_eq!(truncate_html(&html, 3), "<p>Hel…</p>");
+    assert_eq!(truncate_html(&html, 0), "<p>…</p>");
+}
+
+#[test]
+fn test_truncate_html_entities_and_whitespace() {
+    let html = html! { p { "a &   b <c> d" } }.to_html();
+
+    assert_eq!(html, "<p>a &amp;   b &lt;c&gt; d</p>");
+    assert_eq!(truncate_html(&html, 9), "<p>a &amp;   b &lt;c&gt;…</p>");
+}
+
+#[test]
+fn test_truncate_html_ignores_scripts_and_comments() {
+    let html = html! {
+        div {
+            script { #("let a = '<b>long long long';") }
+            #("<!-- comment -->")
+            "short text"
+        }
+    }
+    .to_html();
+
+    assert_eq!(
+        truncate_html(&html, 5),
+        "<div><script>let a = '<b>long long long';</script><!-- comment -->short…</div>"
+    );
+}
+
+#[test]
+fn test_html_to_text() {
+    assert_eq!(
+        html_to_text(&article()),
+        "Tom & Jerry A cat chases a mouse. Again."
+    );
+}
+
+#[test]
+fn test_html_to_text_inline_elements_do_not_separate() {
+    let html = html! { p { "un" strong { "believ" } "able" } }.to_html();
+
+    assert_eq!(html_to_text(&html), "unbelievable");
+}
+
+#[test]
+fn test_html_to_text_skips_scripts_styles_and_comments() {
+    let html = html! {
+        head { style { #("p { color: red; }") } }
+        body {
+            #("<!-- hidden -->")
+            ul { li { "one" } li { "two" } }
+            script { #("alert('x')") }
+        }
+    }
+    .to_html();
+
+    assert_eq!(html_to_text(&html), "one two");
+}
+
+#[test]
+fn test_html_to_text_non_ascii_after_end_tag_opener_in_script() {
+    let html = html! {
+        script { #("x = \"</aééé\"; y = \"</scripté\";") }
+        p { "after" }
+    }
+    .to_html();
+
+    assert_eq!(html_to_text(&html), "after");
+    assert_eq!(
+        truncate_html(&html, 3),
+        "<script>x = \"</aééé\"; y = \"</scripté\";</script><p>aft…</p>"
+    );
+}
+
+#[test]
+fn test_html_to_text_decodes_references() {
+    let html = html! {
+        p { #("&lt;tag&gt; &quot;quoted&quot; &#39;single&#39; &#x263A; &#9731; &unknown; &") }
+    }
+    .to_html();
+
+    assert_eq!(
+        html_to_text(&html),
+        "<tag> \"quoted\" 'single' ☺ ☃ &unknown; &"
+    );
+}
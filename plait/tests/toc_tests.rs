@@ -0,0 +1,136 @@
+use plait::{
+    ToHtml, html,
+    toc::{self, Toc, TocEntry},
+};
+
+fn entry(level: u8, id: &str, text: &str, children: Vec<TocEntry>) -> TocEntry {
+    TocEntry {
+        level,
+        id: id.to_owned(),
+        text: text.to_owned(),
+        children,
+    }
+}
+
+#[test]
+fn test_toc_nesting() {
+    let content = html! {
+        h2 { "One" }
+        h3 { "One A" }
+        h4 { "One A i" }
+        h3 { "One B" }
+        h2 { "Two" }
+        h4 { "Two deep" }
+        h1 { "Top" }
+    };
+
+    let (_, toc) = toc::generate(&content.to_html());
+
+    assert_eq!(
+        toc.entries(),
+        &[
+            entry(
+                2,
+                "one",
+                "One",
+                vec![
+                    entry(
+                        3,
+                        "one-a",
+                        "One A",
+                        vec![entry(4, "one-a-i", "One A i", vec![])]
+                    ),
+                    entry(3, "one-b", "One B", vec![]),
+                ]
+            ),
+            entry(
+                2,
+                "two",
+                "Two",
+                vec![entry(4, "two-deep", "Two deep", vec![])]
+            ),
+            entry(1, "top", "Top", vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_toc_ids_are_unique() {
+    let content = html! {
+        section(id: "intro") {
+            h2 { "Intro" }
+            h2 { "Intro" }
+        }
+        h2(class: "title") { "Intro" }
+    };
+
+    let (content, toc) = toc::generate(&content.to_html());
+
+    assert_eq!(
+        content,
+        "<section id=\"intro\"><h2 id=\"intro-1\">Intro</h2><h2 id=\"intro-2\">Intro</h2></section>\
+        <h2 id=\"intro-3\" class=\"title\">Intro</h2>"
+    );
+    assert_eq!(toc.entries().len(), 3);
+}
+
+#[test]
+fn test_toc_text_content() {
+    let content = html! {
+        h2 { "Using " code { "<T>" } " &  more" }
+        h2(id: "a&amp;b") { "Kept id" }
+        header { "not a heading" }
+        h2 { "?!" }
+    };
+
+    let (content, toc) = toc::generate(&content.to_html());
+
+    assert_eq!(
+        content,
+        "<h2 id=\"using-t-more\">Using <code>&lt;T&gt;</code> &amp;  more</h2>\
+        <h2 id=\"a&amp;amp;b\">Kept id</h2><header>not a heading</header>\
+        <h2 id=\"section\">?!</h2>"
+    );
+    assert_eq!(toc.entries()[0].text, "Using <T> & more");
+    assert_eq!(toc.entries()[1].id, "a&amp;b");
+}
+
+#[test]
+fn test_toc_skips_comments_and_scripts() {
+    let html = "<!-- <h2>Hidden</h2> --><script>let s = '<h2 id=\"x\">x</h2>';</script><H2 class=\"x\">Real</H2 >\
+        <h3>Unclosed";
+
+    let (content, toc) = toc::generate(html);
+
+    assert_eq!(
+        content,
+        "<!-- <h2>Hidden</h2> --><script>let s = '<h2 id=\"x\">x</h2>';</script>\
+        <H2 id=\"real\" class=\"x\">Real</H2 ><h3>Unclosed"
+    );
+    assert_eq!(toc.entries(), &[entry(2, "real", "Real", vec![])]);
+}
+
+#[test]
+fn test_toc_component() {
+    let content = html! {
+        h1 { "A <b>" }
+        h2 { "B" }
+    };
+
+    let (_, toc) = toc::generate(&content.to_html());
+
+    let html = html! {
+        @Toc(toc: &toc; aria_label: "Contents") {}
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<nav class=\"toc\" aria-label=\"Contents\"><ol><li><a href=\"#a-b\">A &lt;b&gt;</a>\
+        <ol><li><a href=\"#b\">B</a></li></ol></li></ol></nav>"
+    );
+
+    let (_, empty) = toc::generate("<p>No headings</p>");
+
+    assert!(empty.is_empty());
+    assert_eq!(html! { @Toc(toc: &empty) {} }.to_html(), "");
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use plait::{ToHtml, component, html};
+use tracing::{
+    Event, Id, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Record},
+};
+
+/// Records the name field of every `component` span.
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<Vec<String>>>);
+
+struct NameVisitor<'a>(&'a mut Option<String>);
+
+impl Visit for NameVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            *self.0 = Some(value.to_owned());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "name" {
+            *self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        if span.metadata().name() == "component" {
+            let mut name = None;
+            span.record(&mut NameVisitor(&mut name));
+            self.0.lock().unwrap().extend(name);
+        }
+
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+component! {
+    fn Item<'a>(label: &'a str) {
+        li { (label) }
+    }
+
+    fn List() {
+        ul {
+            @Item(label: "a") {}
+            @Item(label: "b") {}
+        }
+    }
+}
+
+#[test]
+fn test_component_spans() {
+    let spans = Spans::default();
+
+    let html = tracing::subscriber::with_default(spans.clone(), || html! { @List() {} }.to_html());
+
+    assert_eq!(html, "<ul><li>a</li><li>b</li></ul>");
+    assert_eq!(*spans.0.lock().unwrap(), ["List", "Item", "Item"]);
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+
+use plait::{
+    Href, RouteHref, ToHtml, component, context, html,
+    url_policy::{HttpUrls, ProtocolRelativeUrls, SafeUrl, UrlPolicy, UrlRules},
+};
+
+component! {
+    fn Link(href: &str) {
+        a(href: (href), #attrs) { #children }
+    }
+}
+
+fn strict() -> UrlRules {
+    UrlRules::new()
+        .http(HttpUrls::Block)
+        .protocol_relative(ProtocolRelativeUrls::Block)
+}
+
+#[test]
+fn test_without_rules() {
+    let url = "http://example.com";
+    let html = html! { a(href: (url)) {} img(src?: Some("//cdn.example.com/x.png")); };
+
+    assert_eq!(
+        html.to_html(),
+        "<a href=\"http://example.com\"></a><img src=\"//cdn.example.com/x.png\">"
+    );
+}
+
+#[test]
+fn test_upgrade() {
+    let rules = UrlRules::new()
+        .http(HttpUrls::Upgrade)
+        .protocol_relative(ProtocolRelativeUrls::Upgrade);
+
+    let urls = [
+        "http://a.example/x?y=1&z=2",
+        " HTTP://b.example",
+        "//c.example",
+        "/d",
+        "https://e.example",
+    ];
+
+    let html = html! {
+        for url in urls {
+            a(href: (url)) {}
+        }
+    };
+
+    assert_eq!(
+        context::provide(rules, || html.to_html()),
+        "<a href=\"https://a.example/x?y=1&amp;z=2\"></a><a href=\"https://b.example\"></a>\
+        <a href=\"https://c.example\"></a><a href=\"/d\"></a><a href=\"https://e.example\"></a>"
+    );
+}
+
+#[test]
+fn test_block_drops_attribute() {
+    let urls = [
+        "http://a.example",
+        "//b.example",
+        "\\\\c.example",
+        "ht\ttp://d.example",
+        "/ok",
+    ];
+
+    let html = html! {
+        for url in urls {
+            a(href: (url), class: "link") {}
+        }
+    };
+
+    assert_eq!(
+        context::provide(strict(), || html.to_html()),
+        "<a class=\"link\"></a><a class=\"link\"></a><a class=\"link\"></a><a class=\"link\"></a>\
+        <a href=\"/ok\" class=\"link\"></a>"
+    );
+}
+
+#[test]
+fn test_maybe_attribute() {
+    let insecure = Some("http://example.com/x.png");
+    let missing: Option<&str> = None;
+
+    let html = html! {
+        img(src?: insecure, poster?: missing, data?: true);
+    };
+
+    assert_eq!(context::provide(strict(), || html.to_html()), "<img data>");
+}
+
+#[test]
+fn test_srcset_and_ping() {
+    let srcset = "/a.png 1x, http://cdn.example/b.png 2x";
+    let ping = "http://track.example /ping";
+    let all_blocked = "http://x.example 1x";
+
+    let html = html! {
+        img(srcset: (srcset));
+        a(ping: (ping)) {}
+        img(srcset: (all_blocked));
+    };
+
+    assert_eq!(
+        context::provide(strict(), || html.to_html()),
+        "<img srcset=\"/a.png 1x\"><a ping=\"/ping\"></a><img>"
+    );
+}
+
+#[test]
+fn test_attribute_overrides() {
+    let rules = strict().attribute("HREF", UrlRules::new().http(HttpUrls::Upgrade));
+
+    assert_eq!(
+        rules.for_attribute("href").apply("http://x").as_deref(),
+        Some("https://x")
+    );
+    assert_eq!(rules.for_attribute("src").apply("http://x"), None);
+
+    let url = "http://example.com";
+    let html = html! { a(href: (url)) {} iframe(src: (url)) {} };
+
+    assert_eq!(
+        context::provide(rules, || html.to_html()),
+        "<a href=\"https://example.com\"></a><iframe></iframe>"
+    );
+}
+
+#[test]
+fn test_literal_and_raw_values_are_trusted() {
+    let url = "http://example.com";
+
+    let html = html! {
+        a(href: "http://literal.example") {}
+        a(href: #(url)) {}
+        a(href: (url)) {}
+    };
+
+    assert_eq!(
+        context::provide(strict(), || html.to_html()),
+        "<a href=\"http://literal.example\"></a><a href=\"http://example.com\"></a><a></a>"
+    );
+}
+
+#[test]
+fn test_component_props_and_forwarded_attributes() {
+    let url = "http://example.com";
+
+    let html = html! {
+        @Link(href: url; ping: (url)) { "x" }
+    };
+
+    assert_eq!(context::provide(strict(), || html.to_html()), "<a>x</a>");
+}
+
+struct Legacy(u32);
+
+impl RouteHref for Legacy {
+    fn render_href(&self, f: &mut (dyn fmt::Write + '_)) -> fmt::Result {
+        write!(f, "http://legacy.example/{}?a=1&b=2", self.0)
+    }
+}
+
+#[test]
+fn test_safe_urls_are_trusted() {
+    let checked = "http://a.example";
+    let safe = SafeUrl("http://a.example");
+    let route = SafeUrl(Href(Legacy(3)));
+    let maybe = Some(SafeUrl("//cdn.example/x.png".to_owned()));
+    let missing: Option<SafeUrl<&str>> = None;
+
+    assert_eq!(route.to_string(), "http://legacy.example/3?a=1&b=2");
+
+    let html = html! {
+        a(href: (checked)) {}
+        a(href: safe) {}
+        a(href: &route) {}
+        img(src?: &maybe, srcset?: missing);
+        div(#attrs([("data", SafeUrl("//cdn.example/y"))])) {}
+    };
+
+    assert_eq!(
+        context::provide(strict(), || html.to_html()),
+        "<a></a><a href=\"http://a.example\"></a>\
+        <a href=\"http://legacy.example/3?a=1&amp;b=2\"></a>\
+        <img src=\"//cdn.example/x.png\"><div data=\"//cdn.example/y\"></div>"
+    );
+}
+
+#[test]
+fn test_on_blocked_fallback() {
+    let url = "http://example.com";
+    let srcset = "//a.example/1.png 1x, /2.png 2x";
+    let html = html! { a(href: (url)) {} img(srcset: (srcset)); };
+
+    let fallback = strict().on_blocked(UrlPolicy::Fallback("#".into()));
+    assert_eq!(
+        context::provide(fallback, || html.to_html()),
+        "<a href=\"#\"></a><img srcset=\"#, /2.png 2x\">"
+    );
+}
+
+#[test]
+#[cfg(feature = "tracing")]
+fn test_on_blocked_log() {
+    let url = "http://example.com";
+    let srcset = "//a.example/1.png 1x, /2.png 2x";
+    let html = html! { a(href: (url)) {} img(srcset: (srcset)); };
+
+    let log = strict()
+        .on_blocked(UrlPolicy::Log)
+        .attribute("href", UrlRules::new().http(HttpUrls::Block));
+    assert_eq!(
+        context::provide(log, || html.to_html()),
+        "<a></a><img srcset=\" /2.png 2x\">"
+    );
+}
+
+#[test]
+#[should_panic(expected = "blocked URL `http://example.com` in the `href` attribute")]
+fn test_on_blocked_panic() {
+    let url = "http://example.com";
+    let html = html! { a(href: (url)) {} };
+
+    context::provide(strict().on_blocked(UrlPolicy::Panic), || html.to_html());
+}
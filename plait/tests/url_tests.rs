@@ -0,0 +1,78 @@
+use plait::{ToHtml, html, url};
+
+#[test]
+fn test_url_macro_static() {
+    assert_eq!(url!("/about"), "/about");
+}
+
+#[test]
+fn test_url_macro_path_segments() {
+    let id = 42;
+    let name = "john doe/../admin";
+
+    assert_eq!(
+        url!("/users/{id}/{name}"),
+        "/users/42/john%20doe%2F..%2Fadmin"
+    );
+}
+
+#[test]
+fn test_url_macro_dot_segments() {
+    let id = "..";
+    let current = ".";
+    let version = "v1.2";
+
+    assert_eq!(url!("/users/{id}/edit"), "/users/%2E%2E/edit");
+    assert_eq!(url!("/users/{current}/edit"), "/users/%2E/edit");
+    assert_eq!(url!("/files/{id}.txt"), "/files/%2E%2E.txt");
+    assert_eq!(url!("/files/{version}"), "/files/v1.2");
+}
+
+#[test]
+fn test_url_macro_query_components() {
+    let page = 2;
+    let q = "a&b=c+d#e";
+
+    assert_eq!(
+        url!("/search?q={q}&page={page}"),
+        "/search?q=a%26b%3Dc%2Bd%23e&page=2"
+    );
+}
+
+#[test]
+fn test_url_macro_field_access() {
+    struct User {
+        id: u32,
+    }
+
+    let user = User { id: 7 };
+
+    assert_eq!(url!("/users/{user.id}#{user.id}"), "/users/7#7");
+}
+
+#[test]
+fn test_url_macro_unicode() {
+    let name = "café";
+
+    assert_eq!(url!("/{name}"), "/caf%C3%A9");
+}
+
+#[test]
+fn test_url_macro_escaped_braces() {
+    assert_eq!(url!("/{{literal}}"), "/{literal}");
+}
+
+#[test]
+fn test_url_macro_in_attribute() {
+    let id = 1;
+    let tab = "a&b";
+
+    let html = html! {
+        a(href: (url!("/users/{id}?tab={tab}&x=1"))) { "User" }
+    };
+
+    assert_eq!(
+        html.to_html(),
+        "<a href=\"/users/1?tab=a%26b&amp;x=1\">User</a>"
+    );
+}